@@ -0,0 +1,51 @@
+use libexodus::highscores::highscores_database::HighscoresDatabase;
+use libexodus::world::hash::hash_to_str;
+use libexodus::world::metadata::format_timestamp;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Print all highscores of the given database, grouped by map and player, best highscore first
+pub fn dump_highscores(path: &Path) -> Result<ExitCode, Box<dyn Error>> {
+    let database = HighscoresDatabase::load_from_file(path)?;
+    let mut records: Vec<_> = database.iter().collect();
+    records.sort_by_key(|records| *records.get_hash());
+    for records in records {
+        println!("Map {}", hash_to_str(records.get_hash()));
+        let mut players: Vec<_> = records.iter().collect();
+        players.sort_by_key(|player_highscores| player_highscores.player());
+        for player_highscores in players {
+            println!("  Player \"{}\"", player_highscores.player());
+            for (timestamp, highscore, replay) in player_highscores.iter().rev() {
+                println!(
+                    "    {}  moves: {}  coins: {}  deaths: {}{}",
+                    format_timestamp(timestamp).unwrap_or_else(|| timestamp.to_string()),
+                    highscore.moves(),
+                    highscore.coins(),
+                    highscore.deaths(),
+                    if replay.is_some() { "  (replay)" } else { "" }
+                );
+            }
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Merge all input databases into the output database, which is created if it does not exist
+pub fn merge_highscores(output: &Path, inputs: &[PathBuf]) -> Result<ExitCode, Box<dyn Error>> {
+    let mut database = if output.exists() {
+        HighscoresDatabase::load_from_file(output)?
+    } else {
+        HighscoresDatabase::new()
+    };
+    for input in inputs {
+        database.merge(HighscoresDatabase::load_from_file(input)?);
+    }
+    database.save_to_file(output)?;
+    println!(
+        "Saved highscores of {} maps to {}",
+        database.len(),
+        output.to_string_lossy()
+    );
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,85 @@
+//! A command-line tool to inspect, convert and validate Exodus maps and highscore databases
+//! without launching the game.
+use crate::highscore_commands::{dump_highscores, merge_highscores};
+use crate::map_commands::{convert_map, fix_hash, print_map_info, render_map, validate_maps};
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+mod highscore_commands;
+mod map_commands;
+
+#[derive(Parser)]
+#[command(name = "exodus-cli", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the name, author, hash, size and a tile histogram of a map
+    Info { map: PathBuf },
+    /// Render a map as ASCII art
+    Render { map: PathBuf },
+    /// Convert a map into the current binary format (.exm) or the text format (.exmt),
+    /// depending on the extension of the output file
+    Convert { input: PathBuf, output: PathBuf },
+    /// Recompute the hash of a map and save the map with the correct hash
+    FixHash {
+        map: PathBuf,
+        /// Only print if the hash is correct, without changing the file
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Validate maps and print all errors and warnings.
+    /// Exits with a non-zero exit code if any map contains errors.
+    Validate {
+        #[arg(required = true)]
+        maps: Vec<PathBuf>,
+        /// Do not check if the exit can be reached, which might take a while for large maps
+        #[arg(long)]
+        structure_only: bool,
+    },
+    /// Inspect and combine highscore databases
+    #[command(subcommand)]
+    Highscores(HighscoresCommand),
+}
+
+#[derive(Subcommand)]
+enum HighscoresCommand {
+    /// Print all highscores that are stored in a highscore database
+    Dump { database: PathBuf },
+    /// Merge highscore databases into one database.
+    /// If the output database exists, the highscores are merged into it.
+    Merge {
+        output: PathBuf,
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Info { map } => print_map_info(&map),
+        Command::Render { map } => render_map(&map),
+        Command::Convert { input, output } => convert_map(&input, &output),
+        Command::FixHash { map, dry_run } => fix_hash(&map, dry_run),
+        Command::Validate {
+            maps,
+            structure_only,
+        } => validate_maps(&maps, structure_only),
+        Command::Highscores(HighscoresCommand::Dump { database }) => dump_highscores(&database),
+        Command::Highscores(HighscoresCommand::Merge { output, inputs }) => {
+            merge_highscores(&output, &inputs)
+        },
+    };
+    match result {
+        Ok(exit_code) => exit_code,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            ExitCode::FAILURE
+        },
+    }
+}
@@ -0,0 +1,155 @@
+use libexodus::world::GameWorld;
+use libexodus::world::hash::{RecomputeHashResult, hash_to_str};
+use libexodus::world::io_error::GameWorldParseError;
+use libexodus::world::metadata::format_timestamp;
+use libexodus::world::validation::{ValidationReport, validate, validate_structure};
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Print the name, author, hash, size, metadata and tile histogram of the given map
+pub fn print_map_info(path: &Path) -> Result<ExitCode, Box<dyn Error>> {
+    let map = GameWorld::load_from_file(path)?;
+    let format = match GameWorld::file_format_version(path)? {
+        Some(version) => format!("binary (version {})", version),
+        None => "text".to_string(),
+    };
+    println!("Name:        {}", map.get_name());
+    println!("Author:      {}", map.get_author());
+    println!("Hash:        {}", map.hash_str());
+    println!("Format:      {}", format);
+    println!("Size:        {}x{}", map.width(), map.height());
+    println!(
+        "Spawn:       {},{}",
+        map.player_spawn().0,
+        map.player_spawn().1
+    );
+    println!("Lives:       {}", map.lives());
+    if !map.enemies().is_empty() {
+        println!("Enemies:     {}", map.enemies().len());
+    }
+    let switches = map.active_links().count();
+    if switches > 0 {
+        println!("Switches:    {}", switches);
+    }
+    if let Some(tileset) = map.forced_tileset() {
+        println!("Tile Set:    {}", tileset);
+    }
+    let metadata = map.metadata();
+    if !metadata.description.is_empty() {
+        println!("Description: {}", metadata.description);
+    }
+    if let Some(difficulty) = &metadata.difficulty {
+        println!("Difficulty:  {}", difficulty);
+    }
+    if let Some(par_moves) = metadata.par_moves {
+        println!("Par Moves:   {}", par_moves);
+    }
+    if !metadata.tags.is_empty() {
+        println!("Tags:        {}", metadata.tags.join(", "));
+    }
+    for (label, timestamp) in [
+        ("Created", metadata.created),
+        ("Modified", metadata.modified),
+    ] {
+        if let Some(timestamp) = timestamp.and_then(format_timestamp) {
+            println!("{:<12} {}", format!("{}:", label), timestamp);
+        }
+    }
+
+    let mut histogram: HashMap<String, usize> = HashMap::new();
+    for x in 0..map.width() {
+        for y in 0..map.height() {
+            if let Some(tile) = map.get(x as i32, y as i32) {
+                *histogram.entry(tile.to_string()).or_default() += 1;
+            }
+        }
+    }
+    let mut histogram: Vec<(String, usize)> = histogram.into_iter().collect();
+    histogram.sort_by(|(name_a, count_a), (name_b, count_b)| {
+        count_b.cmp(count_a).then_with(|| name_a.cmp(name_b))
+    });
+    println!();
+    println!("Tiles:");
+    for (name, count) in histogram {
+        println!("{:>8}  {}", count, name);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Print the given map as ASCII art
+pub fn render_map(path: &Path) -> Result<ExitCode, Box<dyn Error>> {
+    let map = GameWorld::load_from_file(path)?;
+    print!("{}", map.to_ascii());
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Load the given map in any supported format and version and save it to the output path.
+/// The format is chosen by the extension of the output path.
+pub fn convert_map(input: &Path, output: &Path) -> Result<ExitCode, Box<dyn Error>> {
+    let mut map = GameWorld::load_from_file(input)?;
+    if let RecomputeHashResult::ERROR { error } = map.recompute_hash() {
+        return Err(error.into());
+    }
+    map.save_to_file(output)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Recompute the hash of the given map and save the map again if the stored hash was wrong
+pub fn fix_hash(path: &Path, dry_run: bool) -> Result<ExitCode, Box<dyn Error>> {
+    match GameWorld::load_from_file(path) {
+        Ok(map) => {
+            println!("The hash is correct: {}", map.hash_str());
+            Ok(ExitCode::SUCCESS)
+        },
+        Err(GameWorldParseError::HashMismatch { expected, actual }) => {
+            println!("Stored hash:   {}", hash_to_str(&actual));
+            println!("Computed hash: {}", hash_to_str(&expected));
+            if dry_run {
+                return Ok(ExitCode::FAILURE);
+            }
+            let map = GameWorld::load_from_file_unverified(path)?;
+            map.save_to_file(path)?;
+            Ok(ExitCode::SUCCESS)
+        },
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Validate all given maps and print the results.
+/// Fails if any of the maps cannot be loaded or contains errors.
+pub fn validate_maps(paths: &[PathBuf], structure_only: bool) -> Result<ExitCode, Box<dyn Error>> {
+    let mut success = true;
+    for path in paths {
+        let path_str = path.to_string_lossy();
+        let map = match GameWorld::load_from_file(path) {
+            Ok(map) => map,
+            Err(err) => {
+                println!("{}: Could not load map: {}", path_str, err);
+                success = false;
+                continue;
+            },
+        };
+        let report: ValidationReport = if structure_only {
+            validate_structure(&map)
+        } else {
+            validate(&map)
+        };
+        if report.is_empty() {
+            println!("{}: OK", path_str);
+        }
+        for error in report.errors() {
+            println!("{}: Error: {}", path_str, error);
+        }
+        for warning in report.warnings() {
+            println!("{}: Warning: {}", path_str, warning);
+        }
+        success &= !report.has_errors();
+    }
+    Ok(if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
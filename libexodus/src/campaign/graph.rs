@@ -1,7 +1,7 @@
 use crate::exodus_serializable::ExodusSerializable;
 use regex::Regex;
 use std::cmp::{max, min};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::{Display, Formatter};
 use std::io::{BufReader, Error, prelude::*};
 use std::num::ParseIntError;
@@ -35,12 +35,20 @@ impl Node {
 
 #[derive(Debug)]
 pub struct Graph {
+    /// The title of the campaign trail
+    title: String,
+    /// A description of the campaign trail, which may span multiple lines
+    description: String,
     /// All nodes, mapped from their ID
     nodes: HashMap<NodeID, Node>,
     /// All edges
     edges: HashMap<NodeID, Vec<NodeID>>,
     /// All edge labels for labeled edges
     edge_labels: HashMap<(NodeID, NodeID), String>,
+    /// The line in the parsed file each node has been declared in
+    node_lines: HashMap<NodeID, usize>,
+    /// All edges in the order they have been declared in the parsed file, with their line
+    edge_lines: Vec<(usize, NodeID, NodeID)>,
     /// The start node at (0,0)
     start_node: Option<NodeID>,
     /// The smallest X coordinate of all nodes of this graph
@@ -53,6 +61,14 @@ pub struct Graph {
     max_y: Coord,
 }
 impl Graph {
+    /// Get the title of the campaign trail, or an empty string if the trail does not have a title
+    pub fn title(&self) -> &str {
+        self.title.as_str()
+    }
+    /// Get the description of the campaign trail, or an empty string if the trail does not have a description
+    pub fn description(&self) -> &str {
+        self.description.as_str()
+    }
     /// Get the start node of this graph
     pub fn start_node(&self) -> Option<&Node> {
         self.start_node.map(|id| self.nodes.get(&id).unwrap())
@@ -83,13 +99,267 @@ impl Graph {
     pub fn get_node(&self, node_id: &NodeID) -> Option<&Node> {
         self.nodes.get(node_id)
     }
+    /// Get the node at the given graph coordinates, if there is any
+    pub fn get_node_at(&self, coord: (Coord, Coord)) -> Option<&Node> {
+        self.nodes.values().find(|node| node.coord == coord)
+    }
+    /// Get the position of the player on the trail, given the last position of the player.
+    /// If there is no node at the last position, e.g., because the trail has been edited since,
+    /// the player is placed on the start node instead.
+    pub fn player_position(&self, last_position: (Coord, Coord)) -> Option<(Coord, Coord)> {
+        self.get_node_at(last_position)
+            .or_else(|| self.start_node())
+            .map(|node| node.coord)
+    }
+    /// Get the IDs of all map nodes that are unlocked, i.e., that the player may play.
+    /// A map is unlocked if it has been won before, according to the given function, or if it can
+    /// be reached from the start node without passing any map that has not been won yet.
+    pub fn unlocked_map_nodes<F: Fn(&Node) -> bool>(&self, is_won: F) -> HashSet<NodeID> {
+        let is_locked =
+            |node: &Node| matches!(node.kind, NodeKind::MapFilename { .. }) && !is_won(node);
+        let mut unlocked: HashSet<NodeID> = self
+            .nodes
+            .values()
+            .filter(|node| matches!(node.kind, NodeKind::MapFilename { .. }) && is_won(node))
+            .map(|node| node.id)
+            .collect();
+        let Some(start_node) = self.start_node() else {
+            return unlocked;
+        };
+        // Traverse the graph using a breadth-first search, starting at the start node.
+        // Maps that have not been won yet are unlocked, but block the way to all nodes behind them.
+        let mut visited: HashSet<NodeID> = HashSet::from([start_node.id]);
+        let mut queue: VecDeque<&Node> = VecDeque::new();
+        if is_locked(start_node) {
+            unlocked.insert(start_node.id);
+        } else {
+            queue.push_back(start_node);
+        }
+        while let Some(cur) = queue.pop_front() {
+            for node in self
+                .edges
+                .get(&cur.id)
+                .into_iter()
+                .flatten()
+                .filter_map(|id| self.nodes.get(id))
+            {
+                if !visited.insert(node.id) {
+                    continue;
+                }
+                if is_locked(node) {
+                    unlocked.insert(node.id);
+                } else {
+                    queue.push_back(node);
+                }
+            }
+        }
+        unlocked
+    }
+    /// Get the label of the edge between the two given nodes, if the edge has a label
+    pub fn edge_label(&self, node_a: &NodeID, node_b: &NodeID) -> Option<&str> {
+        self.edge_labels
+            .get(&(*node_a, *node_b))
+            .map(|label| label.as_str())
+    }
+    /// Check if the two given nodes are connected through an edge
+    pub fn is_connected(&self, node_a: &NodeID, node_b: &NodeID) -> bool {
+        self.edges
+            .get(node_a)
+            .is_some_and(|edges| edges.contains(node_b))
+    }
+    /// Set the title of the campaign trail
+    pub fn set_title(&mut self, title: &str) {
+        self.title = title.trim().to_string();
+        self.update_lines();
+    }
+    /// Set the description of the campaign trail, which may span multiple lines
+    pub fn set_description(&mut self, description: &str) {
+        self.description = description
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<&str>>()
+            .join("\n");
+        self.update_lines();
+    }
+    /// Add a new node of the given kind at the given coordinates and return its ID.
+    /// Returns None, if there already is a node at the given coordinates or all IDs are in use.
+    pub fn add_node(&mut self, kind: NodeKind, coord: (Coord, Coord)) -> Option<NodeID> {
+        if self.get_node_at(coord).is_some() {
+            return None;
+        }
+        let id = match self.nodes.keys().max() {
+            None => 0,
+            Some(max_id) => max_id.checked_add(1)?,
+        };
+        self.nodes.insert(id, Node { id, kind, coord });
+        self.update_bounds();
+        self.update_lines();
+        Some(id)
+    }
+    /// Remove the node with the given ID together with all edges that are connected to it
+    pub fn remove_node(&mut self, node_id: &NodeID) -> Option<Node> {
+        let node = self.nodes.remove(node_id)?;
+        for other in self.edges.remove(node_id).unwrap_or_default() {
+            if let Some(edges) = self.edges.get_mut(&other) {
+                edges.retain(|id| id != node_id);
+            }
+            self.edge_labels.remove(&(*node_id, other));
+            self.edge_labels.remove(&(other, *node_id));
+        }
+        self.update_bounds();
+        self.update_lines();
+        Some(node)
+    }
+    /// Move the node with the given ID to the given coordinates.
+    /// Returns false, if the node does not exist or there already is another node at the given coordinates.
+    pub fn move_node(&mut self, node_id: &NodeID, coord: (Coord, Coord)) -> bool {
+        if self
+            .get_node_at(coord)
+            .is_some_and(|node| node.id != *node_id)
+        {
+            return false;
+        }
+        let Some(node) = self.nodes.get_mut(node_id) else {
+            return false;
+        };
+        node.coord = coord;
+        self.update_bounds();
+        true
+    }
+    /// Change the kind of the node with the given ID
+    pub fn set_node_kind(&mut self, node_id: &NodeID, kind: NodeKind) {
+        if let Some(node) = self.nodes.get_mut(node_id) {
+            node.kind = kind;
+        }
+    }
+    /// Connect the two given nodes through an edge.
+    /// Returns false, if the nodes do not exist or are already connected.
+    pub fn connect(&mut self, node_a: &NodeID, node_b: &NodeID) -> bool {
+        if node_a == node_b
+            || !self.nodes.contains_key(node_a)
+            || !self.nodes.contains_key(node_b)
+            || self.is_connected(node_a, node_b)
+        {
+            return false;
+        }
+        self.edges.entry(*node_a).or_default().push(*node_b);
+        self.edges.entry(*node_b).or_default().push(*node_a);
+        self.update_lines();
+        true
+    }
+    /// Remove the edge between the two given nodes, including its label.
+    /// Returns false, if the nodes are not connected.
+    pub fn disconnect(&mut self, node_a: &NodeID, node_b: &NodeID) -> bool {
+        if !self.is_connected(node_a, node_b) {
+            return false;
+        }
+        for (from, to) in [(node_a, node_b), (node_b, node_a)] {
+            if let Some(edges) = self.edges.get_mut(from) {
+                edges.retain(|id| id != to);
+                if edges.is_empty() {
+                    self.edges.remove(from);
+                }
+            }
+            self.edge_labels.remove(&(*from, *to));
+        }
+        self.update_lines();
+        true
+    }
+    /// Set the label of the edge between the two given nodes.
+    /// An empty label removes the label from the edge.
+    /// Line breaks are replaced by spaces, since each edge is stored in a single line.
+    pub fn set_edge_label(&mut self, node_a: &NodeID, node_b: &NodeID, label: &str) {
+        if !self.is_connected(node_a, node_b) {
+            return;
+        }
+        let label = label
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<&str>>()
+            .join(" ");
+        if label.is_empty() {
+            self.edge_labels.remove(&(*node_a, *node_b));
+            self.edge_labels.remove(&(*node_b, *node_a));
+        } else {
+            self.edge_labels
+                .insert((*node_a, *node_b), label.to_string());
+            self.edge_labels
+                .insert((*node_b, *node_a), label.to_string());
+        }
+    }
+    /// Get all edges of this graph, each edge only once in the direction from the smaller to the
+    /// greater node ID, sorted by node IDs
+    pub fn unique_edges(&self) -> Vec<(NodeID, NodeID)> {
+        let mut edges: Vec<(NodeID, NodeID)> = self
+            .edges
+            .iter()
+            .flat_map(|(node_a, edges)| {
+                edges
+                    .iter()
+                    .filter(move |node_b| node_a <= *node_b)
+                    .map(move |node_b| (*node_a, *node_b))
+            })
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+        edges
+    }
+    /// Get all nodes of this graph, sorted by their ID
+    fn sorted_nodes(&self) -> Vec<&Node> {
+        let mut nodes: Vec<&Node> = self.nodes.values().collect();
+        nodes.sort_unstable_by_key(|node| node.id);
+        nodes
+    }
+    /// Recompute the bounds and the start node of this graph
+    fn update_bounds(&mut self) {
+        (self.min_x, self.max_x, self.min_y, self.max_y) = self.nodes.values().fold(
+            (Coord::MAX, Coord::MIN, Coord::MAX, Coord::MIN),
+            |(min_x1, max_x1, min_y1, max_y1), node| {
+                (
+                    min(min_x1, node.coord.0),
+                    max(max_x1, node.coord.0),
+                    min(min_y1, node.coord.1),
+                    max(max_y1, node.coord.1),
+                )
+            },
+        );
+        self.start_node = self.get_node_at((0, 0)).map(|node| node.id);
+    }
+    /// Recompute the line numbers of all nodes and edges after the graph has been edited,
+    /// such that they match the lines the nodes and edges are written to when serializing the graph
+    fn update_lines(&mut self) {
+        let mut lineno = usize::from(!self.title.is_empty()) + self.description.lines().count();
+        self.node_lines = self
+            .sorted_nodes()
+            .into_iter()
+            .map(|node| {
+                lineno += 1;
+                (node.id, lineno)
+            })
+            .collect();
+        lineno += 1; // The hash line
+        self.edge_lines = self
+            .unique_edges()
+            .into_iter()
+            .map(|(node_a, node_b)| {
+                lineno += 1;
+                (lineno, node_a, node_b)
+            })
+            .collect();
+    }
 }
 impl Default for Graph {
     fn default() -> Self {
         Graph {
+            title: Default::default(),
+            description: Default::default(),
             nodes: Default::default(),
             edges: Default::default(),
             edge_labels: Default::default(),
+            node_lines: Default::default(),
+            edge_lines: Default::default(),
             start_node: None,
             min_x: Coord::MAX,
             max_x: Coord::MIN,
@@ -125,6 +395,25 @@ pub enum GraphValidationError {
         node2_x: Coord,
         node2_y: Coord,
     },
+    /// There is no node at (0,0)
+    MissingStartNode,
+    /// The node with the given ID cannot be reached from the start node
+    UnreachableNode {
+        line: usize,
+        node_id: NodeID,
+        x: Coord,
+        y: Coord,
+    },
+    /// The edge connects two nodes that are neither in the same row nor in the same column
+    DiagonalEdge {
+        line: usize,
+        node1_id: NodeID,
+        node2_id: NodeID,
+    },
+    /// The two edges share more than a single node
+    OverlappingEdges { line: usize, other_line: usize },
+    /// The edge references a node ID that does not exist
+    DanglingEdgeReference { line: usize, node_id: NodeID },
 }
 
 impl GraphParseError {
@@ -146,6 +435,11 @@ impl GraphValidationError {
     pub fn numeric_error(&self) -> u8 {
         match *self {
             GraphValidationError::AdjacentNodesAreNotConnected { .. } => 0,
+            GraphValidationError::MissingStartNode => 1,
+            GraphValidationError::UnreachableNode { .. } => 2,
+            GraphValidationError::DiagonalEdge { .. } => 3,
+            GraphValidationError::OverlappingEdges { .. } => 4,
+            GraphValidationError::DanglingEdgeReference { .. } => 5,
         }
     }
 }
@@ -207,12 +501,84 @@ impl Display for GraphValidationError {
                 "The adjacent nodes {} (at {},{}) and {} (at {},{}) must be connected through an edge explicitly!",
                 node1_id, node1_x, node1_y, node2_id, node2_x, node2_y
             ),
+            GraphValidationError::MissingStartNode => {
+                write!(f, "There must be a start node at 0,0!")
+            },
+            GraphValidationError::UnreachableNode {
+                line,
+                node_id,
+                x,
+                y,
+            } => write!(
+                f,
+                "The node {} (at {},{}) in line {} cannot be reached from the start node!",
+                node_id, x, y, line
+            ),
+            GraphValidationError::DiagonalEdge {
+                line,
+                node1_id,
+                node2_id,
+            } => write!(
+                f,
+                "The edge between the nodes {} and {} in line {} must be horizontal or vertical!",
+                node1_id, node2_id, line
+            ),
+            GraphValidationError::OverlappingEdges { line, other_line } => write!(
+                f,
+                "The edges in line {} and line {} overlap!",
+                other_line, line
+            ),
+            GraphValidationError::DanglingEdgeReference { line, node_id } => write!(
+                f,
+                "The edge in line {} references the unknown node {}!",
+                line, node_id
+            ),
         }
     }
 }
 
 impl Graph {
-    fn validate(&self) -> Result<(), GraphValidationError> {
+    /// Check if this graph is a valid campaign trail that can be displayed and played
+    pub fn validate(&self) -> Result<(), GraphValidationError> {
+        let start_node = self
+            .start_node()
+            .ok_or(GraphValidationError::MissingStartNode)?;
+        // Check if all edges reference existing nodes
+        for (line, node_a, node_b) in &self.edge_lines {
+            for node_id in [node_a, node_b] {
+                if !self.nodes.contains_key(node_id) {
+                    return Err(GraphValidationError::DanglingEdgeReference {
+                        line: *line,
+                        node_id: *node_id,
+                    });
+                }
+            }
+        }
+        // Check if all edges are straight horizontal or vertical lines
+        for (line, node_a, node_b) in &self.edge_lines {
+            let (a, b) = (self.nodes[node_a].coord, self.nodes[node_b].coord);
+            if a.0 != b.0 && a.1 != b.1 {
+                return Err(GraphValidationError::DiagonalEdge {
+                    line: *line,
+                    node1_id: *node_a,
+                    node2_id: *node_b,
+                });
+            }
+        }
+        // Check if any two edges overlap, i.e., share more than a single node
+        for (i, (line, node_a, node_b)) in self.edge_lines.iter().enumerate() {
+            for (other_line, other_a, other_b) in &self.edge_lines[..i] {
+                if edges_overlap(
+                    (self.nodes[node_a].coord, self.nodes[node_b].coord),
+                    (self.nodes[other_a].coord, self.nodes[other_b].coord),
+                ) {
+                    return Err(GraphValidationError::OverlappingEdges {
+                        line: *line,
+                        other_line: *other_line,
+                    });
+                }
+            }
+        }
         // Check if there are adjacent nodes that are not connected
         for (node_id, node) in self.nodes.iter() {
             for (inner_node_id, inner_node) in self.nodes.iter() {
@@ -238,16 +604,63 @@ impl Graph {
                 }
             }
         }
+        // Check if all nodes can be reached from the start node
+        let mut visited: HashSet<NodeID> = HashSet::from([start_node.id]);
+        let mut queue: VecDeque<NodeID> = VecDeque::from([start_node.id]);
+        while let Some(node_id) = queue.pop_front() {
+            for next in self.edges.get(&node_id).into_iter().flatten() {
+                if visited.insert(*next) {
+                    queue.push_back(*next);
+                }
+            }
+        }
+        if let Some(node) = self
+            .nodes
+            .values()
+            .filter(|node| !visited.contains(&node.id))
+            .min_by_key(|node| self.node_lines.get(&node.id))
+        {
+            return Err(GraphValidationError::UnreachableNode {
+                line: self.node_lines.get(&node.id).copied().unwrap_or_default(),
+                node_id: node.id,
+                x: node.coord.0,
+                y: node.coord.1,
+            });
+        }
         Ok(())
     }
 }
 
+/// Check if the two given axis-aligned edges lie on the same line and share more than a single point
+fn edges_overlap(
+    edge: ((Coord, Coord), (Coord, Coord)),
+    other: ((Coord, Coord), (Coord, Coord)),
+) -> bool {
+    let ((a, b), (c, d)) = (edge, other);
+    let overlap = |fixed: fn(&(Coord, Coord)) -> Coord, free: fn(&(Coord, Coord)) -> Coord| {
+        fixed(&a) == fixed(&b)
+            && fixed(&c) == fixed(&d)
+            && fixed(&a) == fixed(&c)
+            && max(min(free(&a), free(&b)), min(free(&c), free(&d)))
+                < min(max(free(&a), free(&b)), max(free(&c), free(&d)))
+    };
+    overlap(|p| p.0, |p| p.1) || overlap(|p| p.1, |p| p.0)
+}
+
 impl ExodusSerializable for Graph {
     const CURRENT_VERSION: u8 = 0;
     type ParseError = GraphParseError;
 
     fn serialize<T: Write>(&self, file: &mut T) -> Result<(), Self::ParseError> {
-        for node in self.nodes.values() {
+        if !self.title.is_empty() {
+            writeln!(file, "title: {}", self.title)?;
+        }
+        if !self.description.is_empty() {
+            for line in self.description.lines() {
+                writeln!(file, "description: {}", line)?;
+            }
+        }
+        for node in self.sorted_nodes() {
             match &node.kind {
                 NodeKind::Empty => writeln!(file, "{} {} {}", node.id, node.coord.0, node.coord.1)?,
                 NodeKind::MapFilename { map } => writeln!(
@@ -258,13 +671,13 @@ impl ExodusSerializable for Graph {
             };
         }
         writeln!(file, "#")?;
-        for (edge_a, edges) in &self.edges {
-            for edge_b in edges {
-                match self.edge_labels.get(&(*edge_a, *edge_b)) {
-                    None => writeln!(file, "{} {}", edge_a, edge_b)?,
-                    Some(label) => writeln!(file, "{} {} {}", edge_a, edge_b, label)?,
-                };
-            }
+        // All edges are stored in both directions, but must only be written once.
+        // Nodes and edges are sorted by ID, such that saving an unchanged graph yields the same file.
+        for (edge_a, edge_b) in self.unique_edges() {
+            match self.edge_labels.get(&(edge_a, edge_b)) {
+                None => writeln!(file, "{} {}", edge_a, edge_b)?,
+                Some(label) => writeln!(file, "{} {} {}", edge_a, edge_b, label)?,
+            };
         }
         Ok(())
     }
@@ -285,28 +698,46 @@ impl ExodusSerializable for Graph {
         }
         let reader = BufReader::new(file);
         let mut state = ReadState::Nodes;
-        for (lineno, line) in reader.lines().enumerate() {
+        for (index, line) in reader.lines().enumerate() {
             let line = line?;
+            let lineno = index + 1;
             match state {
                 ReadState::Nodes => match parse_node_line(line.as_str()) {
-                    NodeParseResult::UnnamedNode { id, x, y } => self.nodes.insert(
-                        str::parse::<NodeID>(id)?,
-                        Node {
-                            id: str::parse::<NodeID>(id)?,
-                            kind: NodeKind::Empty,
-                            coord: (str::parse::<Coord>(x)?, str::parse::<Coord>(y)?),
-                        },
-                    ),
-                    NodeParseResult::NamedNode { id, map_file, x, y } => self.nodes.insert(
-                        str::parse::<NodeID>(id)?,
-                        Node {
-                            id: str::parse::<NodeID>(id)?,
-                            kind: NodeKind::MapFilename {
-                                map: map_file.to_string(),
+                    NodeParseResult::UnnamedNode { id, x, y } => {
+                        self.node_lines.insert(str::parse::<NodeID>(id)?, lineno);
+                        self.nodes.insert(
+                            str::parse::<NodeID>(id)?,
+                            Node {
+                                id: str::parse::<NodeID>(id)?,
+                                kind: NodeKind::Empty,
+                                coord: (str::parse::<Coord>(x)?, str::parse::<Coord>(y)?),
                             },
-                            coord: (str::parse::<Coord>(x)?, str::parse::<Coord>(y)?),
-                        },
-                    ),
+                        )
+                    },
+                    NodeParseResult::NamedNode { id, map_file, x, y } => {
+                        self.node_lines.insert(str::parse::<NodeID>(id)?, lineno);
+                        self.nodes.insert(
+                            str::parse::<NodeID>(id)?,
+                            Node {
+                                id: str::parse::<NodeID>(id)?,
+                                kind: NodeKind::MapFilename {
+                                    map: map_file.to_string(),
+                                },
+                                coord: (str::parse::<Coord>(x)?, str::parse::<Coord>(y)?),
+                            },
+                        )
+                    },
+                    NodeParseResult::Title { title } => {
+                        self.title = title.to_string();
+                        continue;
+                    },
+                    NodeParseResult::Description { description } => {
+                        if !self.description.is_empty() {
+                            self.description.push('\n');
+                        }
+                        self.description.push_str(description);
+                        continue;
+                    },
                     NodeParseResult::Hash => {
                         state = ReadState::Edges;
                         continue;
@@ -322,6 +753,11 @@ impl ExodusSerializable for Graph {
                 })?,
                 ReadState::Edges => match parse_edge_line(line.as_str()) {
                     EdgeParseResult::UnnamedEdge { id_a, id_b } => {
+                        self.edge_lines.push((
+                            lineno,
+                            str::parse::<NodeID>(id_a)?,
+                            str::parse::<NodeID>(id_b)?,
+                        ));
                         self.edges
                             .entry(str::parse::<NodeID>(id_a)?)
                             .or_default()
@@ -337,6 +773,11 @@ impl ExodusSerializable for Graph {
                         id_b,
                         edge_label,
                     } => {
+                        self.edge_lines.push((
+                            lineno,
+                            str::parse::<NodeID>(id_a)?,
+                            str::parse::<NodeID>(id_b)?,
+                        ));
                         self.edges
                             .entry(str::parse::<NodeID>(id_a)?)
                             .or_default()
@@ -362,18 +803,7 @@ impl ExodusSerializable for Graph {
                 })?,
             }
         }
-        (self.min_x, self.max_x, self.min_y, self.max_y) = self.nodes.values().fold(
-            (Coord::MAX, Coord::MIN, Coord::MAX, Coord::MIN),
-            |(min_x1, max_x1, min_y1, max_y1), node| {
-                (
-                    min(min_x1, node.coord.0),
-                    max(max_x1, node.coord.0),
-                    min(min_y1, node.coord.1),
-                    max(max_y1, node.coord.1),
-                )
-            },
-        );
-        self.start_node = Some(0);
+        self.update_bounds();
         Ok(())
     }
 }
@@ -391,6 +821,12 @@ enum NodeParseResult<'s> {
         x: &'s str,
         y: &'s str,
     },
+    Title {
+        title: &'s str,
+    },
+    Description {
+        description: &'s str,
+    },
     Hash,
     Empty,
 }
@@ -399,8 +835,16 @@ fn parse_node_line(line: &str) -> NodeParseResult<'_> {
         NodeParseResult::Empty
     } else if matches!(line.trim(), "#") {
         NodeParseResult::Hash
+    } else if let Some(title) = line.trim().strip_prefix("title:") {
+        NodeParseResult::Title {
+            title: title.trim(),
+        }
+    } else if let Some(description) = line.trim().strip_prefix("description:") {
+        NodeParseResult::Description {
+            description: description.trim(),
+        }
     } else {
-        let re_filename = Regex::new(r"^\s*(\S+)\s+(\S.*\S)\s+(\S+)\s+(\S+)\s*$").unwrap();
+        let re_filename = Regex::new(r"^\s*(\S+)\s+(\S(?:.*\S)?)\s+(\S+)\s+(\S+)\s*$").unwrap();
         let re_no_filename = Regex::new(r"^\s*(\S+)\s+(\S+)\s+(\S+)\s*$").unwrap();
         if let Some(captures) = re_filename.captures(line) {
             NodeParseResult::NamedNode {
@@ -436,7 +880,7 @@ enum EdgeParseResult<'s> {
 }
 
 fn parse_edge_line(line: &str) -> EdgeParseResult<'_> {
-    let re_named = Regex::new(r"^\s*(\S+)\s+(\S+)\s+(\S.*\S)\s*$").unwrap();
+    let re_named = Regex::new(r"^\s*(\S+)\s+(\S+)\s+(\S(?:.*\S)?)\s*$").unwrap();
     let re_unnamed = Regex::new(r"^\s*(\S+)\s+(\S+)\s*$").unwrap();
     if line.trim().is_empty() {
         EdgeParseResult::Empty
@@ -459,6 +903,8 @@ fn parse_edge_line(line: &str) -> EdgeParseResult<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
     #[test]
     fn test_simple_in_memory_deserialization() {
         let graph_file: String = r#"
@@ -475,8 +921,8 @@ mod tests {
         .to_string();
         let mut graph = Graph::default();
         let result = graph.parse(&mut graph_file.as_bytes());
-        if result.is_err() {
-            panic!("{}", result.unwrap_err());
+        if let Err(e) = result {
+            panic!("{}", e);
         }
         // Check Nodes
         assert_eq!(graph.nodes.len(), 4);
@@ -653,6 +1099,64 @@ mod tests {
         assert!(matches!(result, NodeParseResult::Empty), "Got {:?}", result);
     }
     #[test]
+    fn test_parse_node_line_title() {
+        let line = "  title:  The Main Campaign ".to_string();
+        let result = parse_node_line(line.as_str());
+        assert!(
+            matches!(result, NodeParseResult::Title { title } if title == "The Main Campaign"),
+            "Got {:?}",
+            result
+        );
+    }
+    #[test]
+    fn test_parse_node_line_description() {
+        let line = "description: Ten maps: jump and run!".to_string();
+        let result = parse_node_line(line.as_str());
+        assert!(
+            matches!(result, NodeParseResult::Description { description } if description == "Ten maps: jump and run!"),
+            "Got {:?}",
+            result
+        );
+    }
+    #[test]
+    fn test_deserialization_with_title_and_description() {
+        let graph_file: String = r#"
+        title: Test Campaign
+        description: The first line
+        description: The second line
+        0 0 0
+        #
+        "#
+        .to_string();
+        let mut graph = Graph::default();
+        graph.parse(&mut graph_file.as_bytes()).unwrap();
+        assert_eq!("Test Campaign", graph.title());
+        assert_eq!("The first line\nThe second line", graph.description());
+        assert_eq!(1, graph.nodes.len());
+    }
+    #[test]
+    fn test_serialize_title_and_description() {
+        let graph_file: String = r#"
+        title: Test Campaign
+        description: The first line
+        description: The second line
+        0 0 0
+        1 map.exm 0 2
+        #
+        0 1
+        "#
+        .to_string();
+        let mut graph = Graph::default();
+        graph.parse(&mut graph_file.as_bytes()).unwrap();
+        let mut buf: Vec<u8> = vec![];
+        graph.serialize(&mut buf).unwrap();
+        let mut result = Graph::default();
+        result.parse(&mut buf.as_slice()).unwrap();
+        assert_eq!(graph.title(), result.title());
+        assert_eq!(graph.description(), result.description());
+        assert_eq!(graph.nodes, result.nodes);
+    }
+    #[test]
     fn test_parse_node_line_err_1() {
         let line = "0 1".to_string();
         let result = parse_node_line(line.as_str());
@@ -741,6 +1245,23 @@ mod tests {
         );
     }
     #[test]
+    fn test_parse_edge_line_single_character_label() {
+        let line = "0 1 A ".to_string();
+        let result = parse_edge_line(line.as_str());
+        assert!(
+            matches!(
+                result,
+                EdgeParseResult::NamedEdge {
+                    id_a: "0",
+                    id_b: "1",
+                    edge_label: "A",
+                }
+            ),
+            "Got {:?}",
+            result
+        );
+    }
+    #[test]
     fn test_parse_edge_line_5() {
         let line = "\t   \t\t  \t\t".to_string();
         let result = parse_edge_line(line.as_str());
@@ -757,6 +1278,26 @@ mod tests {
         );
     }
     #[test]
+    fn test_err_syntax_error_reports_one_based_line() {
+        let graph_file: String = "0 0 0\n0 1\n#\n".to_string();
+        let mut graph = Graph::default();
+        let result = graph.parse(&mut graph_file.as_bytes());
+        assert!(matches!(
+            result.expect_err("Expected an invalid node line to return a Syntax Error"),
+            GraphParseError::SyntaxError { line: 2 }
+        ));
+    }
+    #[test]
+    fn test_err_duplicate_node_id_reports_one_based_line() {
+        let graph_file: String = "0 0 0\n1 0 2\n1 2 2\n#\n".to_string();
+        let mut graph = Graph::default();
+        let result = graph.parse(&mut graph_file.as_bytes());
+        assert!(matches!(
+            result.expect_err("Expected a duplicate node ID to return an error"),
+            GraphParseError::DuplicateNodeId { line: 3, id: 1 }
+        ));
+    }
+    #[test]
     fn test_simple_in_memory_deserialization_with_filenames() {
         let graph_file: String = r#"
         0 0 0
@@ -810,16 +1351,13 @@ mod tests {
     fn test_simple_in_memory_deserialization_with_empty_edges() {
         let graph_file: String = r#"
         0 0 0
-        1 0 2
-        2 2 2
-        3 2 0
         #
         "#
         .to_string();
         let mut graph = Graph::default();
         let result = graph.parse(&mut graph_file.as_bytes());
         assert!(result.is_ok());
-        assert_eq!(graph.nodes.len(), 4);
+        assert_eq!(graph.nodes.len(), 1);
         // Check Edges
         assert_edges_are_connected(&graph, &[]);
     }
@@ -832,6 +1370,8 @@ mod tests {
         3 2 0
         #
         0 3 lululu
+        3 2
+        2 1
         "#
         .to_string();
         let mut graph = Graph::default();
@@ -863,6 +1403,494 @@ mod tests {
         ));
     }
     #[test]
+    fn test_err_graph_missing_start_node() {
+        let graph_file: String = r#"
+        0 0 1
+        1 0 3
+        #
+        0 1
+        "#
+        .to_string();
+        let mut graph = Graph::default();
+        let result = graph.parse(&mut graph_file.as_bytes());
+        assert!(matches!(
+            result.expect_err("Expected graphs without a node at 0,0 to return a Validation Error"),
+            GraphParseError::ValidationError {
+                error: GraphValidationError::MissingStartNode
+            }
+        ));
+    }
+    #[test]
+    fn test_start_node_is_at_origin() {
+        let graph_file: String = r#"
+        1 0 2
+        0 2 0
+        5 0 0
+        #
+        5 1
+        5 0
+        "#
+        .to_string();
+        let mut graph = Graph::default();
+        graph.parse(&mut graph_file.as_bytes()).unwrap();
+        assert_eq!(graph.start_node().map(|node| node.id), Some(5));
+        assert_eq!(graph.get_node_at((0, 2)).map(|node| node.id), Some(1));
+        assert!(graph.get_node_at((1, 0)).is_none());
+    }
+    #[test]
+    fn test_player_position_falls_back_to_start_node() {
+        let graph_file: String = r#"
+        1 0 2
+        0 2 0
+        5 0 0
+        #
+        5 1
+        5 0
+        "#
+        .to_string();
+        let mut graph = Graph::default();
+        graph.parse(&mut graph_file.as_bytes()).unwrap();
+        assert_eq!(Some((0, 2)), graph.player_position((0, 2)));
+        // Positions that are not on a node, outside of the trail or left of and below the trail
+        for position in [(1, 0), (0, 1), (7, 7), (-1, 0), (0, -3)] {
+            assert_eq!(
+                Some((0, 0)),
+                graph.player_position(position),
+                "{:?}",
+                position
+            );
+        }
+    }
+    #[test]
+    fn test_err_graph_unreachable_node() {
+        let graph_file: String = r#"
+        0 0 0
+        1 0 2
+        2 4 4
+        3 4 6
+        #
+        0 1
+        2 3
+        "#
+        .to_string();
+        let mut graph = Graph::default();
+        let result = graph.parse(&mut graph_file.as_bytes());
+        assert!(matches!(
+            result.expect_err("Expected orphaned subgraphs to return a Validation Error"),
+            GraphParseError::ValidationError {
+                error: GraphValidationError::UnreachableNode {
+                    line: 4,
+                    node_id: 2,
+                    x: 4,
+                    y: 4
+                }
+            }
+        ));
+    }
+    #[test]
+    fn test_err_graph_diagonal_edge() {
+        let graph_file: String = r#"
+        0 0 0
+        1 2 2
+        #
+        0 1
+        "#
+        .to_string();
+        let mut graph = Graph::default();
+        let result = graph.parse(&mut graph_file.as_bytes());
+        assert!(matches!(
+            result.expect_err("Expected diagonal edges to return a Validation Error"),
+            GraphParseError::ValidationError {
+                error: GraphValidationError::DiagonalEdge {
+                    line: 5,
+                    node1_id: 0,
+                    node2_id: 1
+                }
+            }
+        ));
+    }
+    #[test]
+    fn test_err_graph_overlapping_edges() {
+        let graph_file: String = r#"
+        0 0 0
+        1 0 4
+        2 0 2
+        #
+        0 1
+        0 2
+        "#
+        .to_string();
+        let mut graph = Graph::default();
+        let result = graph.parse(&mut graph_file.as_bytes());
+        assert!(matches!(
+            result.expect_err("Expected overlapping edges to return a Validation Error"),
+            GraphParseError::ValidationError {
+                error: GraphValidationError::OverlappingEdges {
+                    line: 7,
+                    other_line: 6
+                }
+            }
+        ));
+    }
+    #[test]
+    fn test_graph_edges_sharing_a_node_do_not_overlap() {
+        let graph_file: String = r#"
+        0 0 0
+        1 0 2
+        2 0 4
+        3 -2 0
+        4 2 0
+        #
+        0 1
+        1 2
+        3 0
+        0 4
+        "#
+        .to_string();
+        let mut graph = Graph::default();
+        assert!(graph.parse(&mut graph_file.as_bytes()).is_ok());
+    }
+    #[test]
+    fn test_err_graph_dangling_edge_reference() {
+        let graph_file: String = r#"
+        0 0 0
+        1 0 2
+        #
+        0 1
+        1 7
+        "#
+        .to_string();
+        let mut graph = Graph::default();
+        let result = graph.parse(&mut graph_file.as_bytes());
+        assert!(matches!(
+            result.expect_err("Expected edges to unknown nodes to return a Validation Error"),
+            GraphParseError::ValidationError {
+                error: GraphValidationError::DanglingEdgeReference {
+                    line: 6,
+                    node_id: 7
+                }
+            }
+        ));
+    }
+    /// Parse the trail S-1-2-3 with a branch 1-4-5, where S and 4 are empty and all other nodes are maps.
+    fn unlock_test_graph() -> Graph {
+        let graph_file: String = r#"
+        0 0 0
+        1 map1 2 0
+        2 map2 4 0
+        3 map3 6 0
+        4 2 2
+        5 map5 2 4
+        #
+        0 1
+        1 2
+        2 3
+        1 4
+        4 5
+        "#
+        .to_string();
+        let mut graph = Graph::default();
+        graph.parse(&mut graph_file.as_bytes()).unwrap();
+        graph
+    }
+    #[test]
+    fn test_unlocked_map_nodes_nothing_won() {
+        let graph = unlock_test_graph();
+        assert_eq!(HashSet::from([1]), graph.unlocked_map_nodes(|_| false));
+    }
+    #[test]
+    fn test_unlocked_map_nodes_first_map_won() {
+        let graph = unlock_test_graph();
+        assert_eq!(
+            HashSet::from([1, 2, 5]),
+            graph.unlocked_map_nodes(|node| node.id == 1)
+        );
+    }
+    #[test]
+    fn test_unlocked_map_nodes_all_won() {
+        let graph = unlock_test_graph();
+        assert_eq!(
+            HashSet::from([1, 2, 3, 5]),
+            graph.unlocked_map_nodes(|_| true)
+        );
+    }
+    #[test]
+    fn test_unlocked_map_nodes_won_map_behind_locked_map() {
+        let graph = unlock_test_graph();
+        assert_eq!(
+            HashSet::from([1, 3]),
+            graph.unlocked_map_nodes(|node| node.id == 3)
+        );
+    }
+    #[test]
+    fn test_unlocked_map_nodes_start_node_is_map() {
+        let graph_file: String = r#"
+        0 map0 0 0
+        1 map1 0 2
+        #
+        0 1
+        "#
+        .to_string();
+        let mut graph = Graph::default();
+        graph.parse(&mut graph_file.as_bytes()).unwrap();
+        assert_eq!(HashSet::from([0]), graph.unlocked_map_nodes(|_| false));
+        assert_eq!(
+            HashSet::from([0, 1]),
+            graph.unlocked_map_nodes(|node| node.id == 0)
+        );
+    }
+    #[test]
+    fn test_edit_add_and_connect_nodes() {
+        let mut graph = Graph::default();
+        assert!(matches!(
+            graph.validate(),
+            Err(GraphValidationError::MissingStartNode)
+        ));
+        let start = graph.add_node(NodeKind::Empty, (0, 0)).unwrap();
+        let map = graph
+            .add_node(
+                NodeKind::MapFilename {
+                    map: "map.exm".to_string(),
+                },
+                (0, 2),
+            )
+            .unwrap();
+        assert_ne!(start, map);
+        assert!(graph.add_node(NodeKind::Empty, (0, 2)).is_none());
+        assert_eq!(start, graph.start_node().unwrap().id);
+        assert_eq!(3, graph.height());
+        assert!(matches!(
+            graph.validate(),
+            Err(GraphValidationError::UnreachableNode { .. })
+        ));
+        assert!(graph.connect(&start, &map));
+        assert!(!graph.connect(&map, &start));
+        assert!(graph.is_connected(&map, &start));
+        graph.validate().unwrap();
+    }
+    #[test]
+    fn test_edit_move_and_remove_nodes() {
+        let mut graph = unlock_test_graph();
+        assert!(!graph.move_node(&5, (2, 2)));
+        assert!(graph.move_node(&5, (2, 6)));
+        assert_eq!((2, 6), graph.get_node(&5).unwrap().coord);
+        assert_eq!(7, graph.height());
+        graph.validate().unwrap();
+        assert!(graph.move_node(&5, (4, 6)));
+        assert!(matches!(
+            graph.validate(),
+            Err(GraphValidationError::DiagonalEdge { .. })
+        ));
+        let removed = graph.remove_node(&4).unwrap();
+        assert_eq!((2, 2), removed.coord);
+        assert!(!graph.is_connected(&1, &4));
+        assert!(matches!(
+            graph.validate(),
+            Err(GraphValidationError::UnreachableNode { node_id: 5, .. })
+        ));
+        graph.remove_node(&5).unwrap();
+        graph.validate().unwrap();
+    }
+    #[test]
+    fn test_edit_edge_labels() {
+        let mut graph = unlock_test_graph();
+        graph.set_edge_label(&2, &1, " Bridge ");
+        assert_eq!(Some("Bridge"), graph.edge_label(&1, &2));
+        assert_eq!(Some("Bridge"), graph.edge_label(&2, &1));
+        graph.set_edge_label(&0, &5, "Not connected");
+        assert_eq!(None, graph.edge_label(&0, &5));
+        let mut buf: Vec<u8> = vec![];
+        graph.serialize(&mut buf).unwrap();
+        let mut result = Graph::default();
+        result.parse(&mut buf.as_slice()).unwrap();
+        assert_eq!(Some("Bridge"), result.edge_label(&1, &2));
+        graph.set_edge_label(&1, &2, "");
+        assert_eq!(None, graph.edge_label(&1, &2));
+        assert!(graph.disconnect(&1, &2));
+        assert!(!graph.disconnect(&1, &2));
+        assert!(matches!(
+            graph.validate(),
+            Err(GraphValidationError::UnreachableNode { .. })
+        ));
+    }
+    #[test]
+    fn test_edit_serialize_roundtrip() {
+        let mut graph = Graph::default();
+        graph.set_title("Edited Campaign");
+        graph.set_description("First line\n\n  Second line ");
+        let start = graph.add_node(NodeKind::Empty, (0, 0)).unwrap();
+        let left = graph.add_node(NodeKind::Empty, (-2, 0)).unwrap();
+        let map = graph
+            .add_node(
+                NodeKind::MapFilename {
+                    map: "maps/my map.exm".to_string(),
+                },
+                (-2, -3),
+            )
+            .unwrap();
+        graph.connect(&start, &left);
+        graph.connect(&map, &left);
+        graph.set_edge_label(&left, &map, "Long Way");
+        graph.validate().unwrap();
+        let mut buf: Vec<u8> = vec![];
+        graph.serialize(&mut buf).unwrap();
+        let mut result = Graph::default();
+        result.parse(&mut buf.as_slice()).unwrap();
+        assert_eq!("Edited Campaign", result.title());
+        assert_eq!("First line\nSecond line", result.description());
+        assert_eq!(graph.nodes, result.nodes);
+        assert_eq!(Some("Long Way"), result.edge_label(&map, &left));
+        assert_eq!((-2, -3), (result.min_x(), result.min_y()));
+    }
+    #[test]
+    fn test_edit_edge_labels_roundtrip() {
+        let mut graph = Graph::default();
+        let start = graph.add_node(NodeKind::Empty, (0, 0)).unwrap();
+        let mut previous = start;
+        for (index, (label, expected)) in [
+            ("A", Some("A")),
+            ("  padded  ", Some("padded")),
+            ("two\nlines", Some("two lines")),
+            ("windows\r\nline\r\n", Some("windows line")),
+            (" \n ", None),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let node = graph
+                .add_node(NodeKind::Empty, (2 * (index as Coord + 1), 0))
+                .unwrap();
+            graph.connect(&previous, &node);
+            graph.set_edge_label(&previous, &node, label);
+            assert_eq!(expected, graph.edge_label(&previous, &node), "{:?}", label);
+            previous = node;
+        }
+        graph.validate().unwrap();
+        let mut buf: Vec<u8> = vec![];
+        graph.serialize(&mut buf).unwrap();
+        let mut result = Graph::default();
+        result.parse(&mut buf.as_slice()).unwrap();
+        assert_eq!(graph.edge_labels, result.edge_labels);
+    }
+    #[test]
+    fn test_edit_validation_lines_match_serialized_lines() {
+        let mut graph = Graph::default();
+        graph.set_title("Lines");
+        let start = graph.add_node(NodeKind::Empty, (0, 0)).unwrap();
+        let other = graph.add_node(NodeKind::Empty, (3, 3)).unwrap();
+        graph.connect(&start, &other);
+        let Err(GraphValidationError::DiagonalEdge { line, .. }) = graph.validate() else {
+            panic!("Expected a diagonal edge");
+        };
+        let mut buf: Vec<u8> = vec![];
+        graph.serialize(&mut buf).unwrap();
+        let serialized = String::from_utf8(buf).unwrap();
+        let edge_line = serialized.lines().nth(line - 1).unwrap();
+        assert_eq!(format!("{} {}", start, other), edge_line);
+    }
+    #[test]
+    fn test_serialize_is_sorted_and_writes_edges_once() {
+        let graph_file: String = r#"
+        5 map5 2 4
+        2 map2 4 0
+        0 0 0
+        4 2 2
+        1 map1 2 0
+        #
+        4 5
+        2 1 Second Edge
+        4 1
+        1 0 First Edge
+        "#
+        .to_string();
+        let mut graph = Graph::default();
+        graph.parse(&mut graph_file.as_bytes()).unwrap();
+        let mut buf: Vec<u8> = vec![];
+        graph.serialize(&mut buf).unwrap();
+        assert_eq!(
+            "0 0 0\n1 map1 2 0\n2 map2 4 0\n4 2 2\n5 map5 2 4\n#\n0 1 First Edge\n1 2 Second Edge\n1 4\n4 5\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+    /// Generate a random valid campaign trail from the given seed.
+    /// All nodes are placed on even coordinates, such that no two nodes are adjacent.
+    fn random_graph(seed: u64) -> Graph {
+        let mut rng = ChaCha20Rng::seed_from_u64(seed);
+        let mut graph = Graph::default();
+        graph.set_title(&format!("Random Trail {}", seed));
+        graph.set_description("A random trail\nfor round-trip tests");
+        graph.add_node(NodeKind::Empty, (0, 0)).unwrap();
+        for _ in 0..rng.random_range(1..30) {
+            let nodes: Vec<NodeID> = graph.nodes().map(|node| node.id).collect();
+            let from = nodes[rng.random_range(0..nodes.len())];
+            let (x, y) = graph.get_node(&from).unwrap().coord;
+            let distance: Coord = 2 * rng.random_range(1..4);
+            let coord = match rng.random_range(0..4) {
+                0 => (x + distance, y),
+                1 => (x - distance, y),
+                2 => (x, y + distance),
+                _ => (x, y - distance),
+            };
+            let kind = if rng.random_bool(0.5) {
+                NodeKind::MapFilename {
+                    map: format!("maps/map {}.exm", rng.random_range(0..100)),
+                }
+            } else {
+                NodeKind::Empty
+            };
+            let Some(to) = graph.add_node(kind, coord) else {
+                continue;
+            };
+            graph.connect(&from, &to);
+            if rng.random_bool(0.3) {
+                let label = if rng.random_bool(0.5) {
+                    format!("Edge {}", to)
+                } else {
+                    // Labels that consist of a single character
+                    char::from(rng.random_range(b'A'..=b'Z')).to_string()
+                };
+                graph.set_edge_label(&from, &to, &label);
+            }
+            // Drop edges that overlap other edges
+            if graph.validate().is_err() {
+                graph.remove_node(&to);
+            }
+        }
+        graph.validate().unwrap();
+        graph
+    }
+    #[test]
+    fn test_serialize_roundtrip_random_graphs() {
+        for seed in 0..200 {
+            let graph = random_graph(seed);
+            let mut buf: Vec<u8> = vec![];
+            graph.serialize(&mut buf).unwrap();
+            let mut result = Graph::default();
+            result
+                .parse(&mut buf.as_slice())
+                .unwrap_or_else(|e| panic!("Seed {}: {}", seed, e));
+            assert_eq!(graph.title(), result.title(), "Seed {}", seed);
+            assert_eq!(graph.description(), result.description(), "Seed {}", seed);
+            assert_eq!(graph.nodes, result.nodes, "Seed {}", seed);
+            assert_eq!(graph.unique_edges(), result.unique_edges(), "Seed {}", seed);
+            assert_eq!(graph.edge_labels, result.edge_labels, "Seed {}", seed);
+            assert_eq!(
+                (graph.min_x(), graph.min_y(), graph.width(), graph.height()),
+                (
+                    result.min_x(),
+                    result.min_y(),
+                    result.width(),
+                    result.height()
+                ),
+                "Seed {}",
+                seed
+            );
+            let mut buf_again: Vec<u8> = vec![];
+            result.serialize(&mut buf_again).unwrap();
+            assert_eq!(buf, buf_again, "Seed {}", seed);
+        }
+    }
+    #[test]
     fn test_err_graph_not_a_number() {
         let graph_file: String = r#"
         0 0 0
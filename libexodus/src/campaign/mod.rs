@@ -1 +1,2 @@
 pub mod graph;
+pub mod progress;
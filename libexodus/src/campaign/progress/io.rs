@@ -0,0 +1,227 @@
+use crate::campaign::graph::{Coord, NodeID};
+use crate::campaign::progress::CampaignProgress;
+use crate::campaign::progress::io_error::CampaignProgressParseError;
+use crate::exodus_serializable::ExodusSerializable;
+use std::collections::BTreeSet;
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+//00000000: 4578 6f64 7573 5072 6f67 7265 7373       ExodusProgress
+pub(crate) const MAGICBYTES: [u8; 14] = [
+    0x45, 0x78, 0x6f, 0x64, 0x75, 0x73, 0x50, 0x72, 0x6f, 0x67, 0x72, 0x65, 0x73, 0x73,
+];
+
+///
+/// Definition of the binary Campaign Progress File Format:
+///
+/// 1. Magic Bytes 0x 45 78 6f 64 75 73 50 72 6f 67 72 65 73 73
+///
+/// 2. Campaign Progress Format Version (current version: 0x01)
+///
+/// 3. Name of the campaign trail, encoded with bincode crate
+///
+/// 4. Name of the player, encoded with bincode crate
+///
+/// 5. Last player position as x and y graph coordinates, encoded with bincode crate
+///
+/// 6. Number of completed nodes, followed by the IDs of all completed nodes, encoded with bincode crate
+///
+/// 7. Number of collected rewards, followed by the node IDs of all collected rewards, encoded with bincode crate
+impl CampaignProgress {
+    /// Load a campaign progress from the given file
+    pub fn load_from_file(path: &Path) -> Result<Self, CampaignProgressParseError> {
+        let file = OpenOptions::new().read(true).open(path)?;
+        let mut buf = BufReader::new(file);
+        let mut ret = CampaignProgress::default();
+        ret.parse(&mut buf)?;
+        Ok(ret)
+    }
+    /// Save the campaign progress to the given file
+    pub fn save_to_file(&self, path: &Path) -> Result<(), CampaignProgressParseError> {
+        let file: File = OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .write(true)
+            .open(path)?;
+        let mut buf = BufWriter::new(file);
+        self.serialize(&mut buf)?;
+        Ok(())
+    }
+}
+
+/// Write the number of the given node IDs followed by all node IDs
+fn serialize_nodes<T: Write>(
+    nodes: &BTreeSet<NodeID>,
+    file: &mut T,
+) -> Result<(), CampaignProgressParseError> {
+    file.write_all(&bincode::serialize(&nodes.len())?)?;
+    for node in nodes {
+        file.write_all(&bincode::serialize(node)?)?;
+    }
+    Ok(())
+}
+
+/// Parse a list of node IDs that has been written with `serialize_nodes`
+fn parse_nodes<T: Read>(file: &mut T) -> Result<BTreeSet<NodeID>, CampaignProgressParseError> {
+    let length = bincode::deserialize_from::<&mut T, usize>(file)?;
+    let mut nodes = BTreeSet::new();
+    for _ in 0..length {
+        let node_id = bincode::deserialize_from::<&mut T, NodeID>(file)?;
+        if !nodes.insert(node_id) {
+            return Err(CampaignProgressParseError::DuplicateNode { node_id });
+        }
+    }
+    Ok(nodes)
+}
+
+/// Implementation for Serializer
+impl ExodusSerializable for CampaignProgress {
+    const CURRENT_VERSION: u8 = 0x01;
+    type ParseError = CampaignProgressParseError;
+
+    fn serialize<T: Write>(&self, file: &mut T) -> Result<(), Self::ParseError> {
+        // Write magic bytes
+        file.write_all(&MAGICBYTES)?;
+
+        // Write Campaign Progress Version
+        file.write_all(&[Self::CURRENT_VERSION])?;
+
+        // Write Trail and Player Name
+        file.write_all(&bincode::serialize(&self.trail)?)?;
+        file.write_all(&bincode::serialize(&self.player)?)?;
+
+        // Write Player Position
+        file.write_all(&bincode::serialize(&self.position)?)?;
+
+        // Write Completed Nodes and Collected Rewards
+        serialize_nodes(&self.completed_nodes, file)?;
+        serialize_nodes(&self.collected_rewards, file)?;
+
+        Ok(())
+    }
+
+    fn parse<T: Read>(&mut self, file: &mut T) -> Result<(), Self::ParseError> {
+        // Parse Magic Bytes
+        let mut buf: [u8; MAGICBYTES.len()] = [0; MAGICBYTES.len()];
+        file.read_exact(&mut buf)?;
+        if buf != MAGICBYTES {
+            return Err(Self::ParseError::InvalidMagicBytes {
+                expected: MAGICBYTES,
+                actual: buf,
+            });
+        }
+
+        // Parse Campaign Progress Format
+        let mut buf: [u8; 1] = [0; 1];
+        file.read_exact(&mut buf)?;
+        match buf[0] {
+            Self::CURRENT_VERSION => self.parse_current_version(file),
+            // Add older versions here
+            _ => {
+                return Err(Self::ParseError::InvalidVersion {
+                    invalid_version: buf[0],
+                });
+            },
+        }?;
+
+        Ok(())
+    }
+
+    fn parse_current_version<T: Read>(&mut self, file: &mut T) -> Result<(), Self::ParseError> {
+        // Parse Trail and Player Name
+        self.trail = bincode::deserialize_from::<&mut T, String>(file)?;
+        self.player = bincode::deserialize_from::<&mut T, String>(file)?;
+
+        // Parse Player Position
+        self.position = bincode::deserialize_from::<&mut T, (Coord, Coord)>(file)?;
+
+        // Parse Completed Nodes and Collected Rewards
+        self.completed_nodes = parse_nodes(file)?;
+        self.collected_rewards = parse_nodes(file)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::campaign::progress::CampaignProgress;
+    use crate::campaign::progress::io::MAGICBYTES;
+    use crate::campaign::progress::io_error::CampaignProgressParseError;
+    use crate::exodus_serializable::ExodusSerializable;
+    use bytebuffer::ByteBuffer;
+
+    #[test]
+    fn test_write_and_read_empty_progress() {
+        let progress = CampaignProgress::new("campaign".to_string(), "".to_string());
+        let mut buf = ByteBuffer::new();
+        progress.serialize(&mut buf).unwrap();
+        buf.set_rpos(0);
+        let mut result = CampaignProgress::default();
+        result.parse(&mut buf).unwrap();
+        assert_eq!(progress, result);
+    }
+
+    #[test]
+    fn test_write_and_read_progress() {
+        let mut progress = CampaignProgress::new("campaign".to_string(), "Thorsten".to_string());
+        progress.set_position((-3, 12));
+        progress.complete(1);
+        progress.complete(12);
+        progress.complete(4);
+        progress.collect_reward(12);
+        let mut buf = ByteBuffer::new();
+        progress.serialize(&mut buf).unwrap();
+        buf.set_rpos(0);
+        let mut result = CampaignProgress::new("other".to_string(), "Max".to_string());
+        result.complete(2);
+        result.parse(&mut buf).unwrap();
+        assert_eq!(progress, result);
+    }
+
+    #[test]
+    fn test_invalid_magic_bytes() {
+        let mut buf = ByteBuffer::from_bytes(&[0x00; 50]);
+        let result = CampaignProgress::default().parse(&mut buf);
+        assert_eq!(
+            CampaignProgressParseError::InvalidMagicBytes {
+                expected: MAGICBYTES,
+                actual: [0x00; MAGICBYTES.len()],
+            }
+            .numeric_error(),
+            result.unwrap_err().numeric_error()
+        );
+    }
+
+    #[test]
+    fn test_invalid_version() {
+        let mut data = MAGICBYTES.to_vec();
+        data.push(0xff);
+        let mut buf = ByteBuffer::from_bytes(&data);
+        let result = CampaignProgress::default().parse(&mut buf);
+        assert!(matches!(
+            result.unwrap_err(),
+            CampaignProgressParseError::InvalidVersion {
+                invalid_version: 0xff
+            }
+        ));
+    }
+
+    #[test]
+    fn test_duplicate_completed_node() {
+        let mut data = MAGICBYTES.to_vec();
+        data.push(CampaignProgress::CURRENT_VERSION);
+        data.extend_from_slice(&bincode::serialize("campaign").unwrap());
+        data.extend_from_slice(&bincode::serialize("Thorsten").unwrap());
+        data.extend_from_slice(&bincode::serialize(&(0i16, 0i16)).unwrap());
+        data.extend_from_slice(&bincode::serialize(&2usize).unwrap());
+        data.extend_from_slice(&bincode::serialize(&5u16).unwrap());
+        data.extend_from_slice(&bincode::serialize(&5u16).unwrap());
+        let mut buf = ByteBuffer::from_bytes(&data);
+        let result = CampaignProgress::default().parse(&mut buf);
+        assert!(matches!(
+            result.unwrap_err(),
+            CampaignProgressParseError::DuplicateNode { node_id: 5 }
+        ));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::campaign::graph::NodeID;
+use crate::campaign::progress::io::MAGICBYTES;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug)]
+#[repr(u8)]
+/// An error that might be thrown in a Campaign Progress Parser
+pub enum CampaignProgressParseError {
+    InvalidMagicBytes {
+        expected: [u8; MAGICBYTES.len()],
+        actual: [u8; MAGICBYTES.len()],
+    },
+    InvalidVersion {
+        invalid_version: u8,
+    },
+    IOError {
+        io_error: std::io::Error,
+    },
+    BincodeError {
+        bincode_error: Box<bincode::ErrorKind>,
+    },
+    DuplicateNode {
+        node_id: NodeID,
+    },
+}
+
+impl Display for CampaignProgressParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CampaignProgressParseError::InvalidMagicBytes { expected, actual } => write!(
+                f,
+                "Invalid Magic Bytes in Campaign Progress File! Expected: {:02x?} Got: {:02x?}",
+                expected, actual
+            ),
+            CampaignProgressParseError::InvalidVersion { invalid_version } => write!(
+                f,
+                "Invalid Campaign Progress Version: 0x{:02x}",
+                invalid_version
+            ),
+            CampaignProgressParseError::IOError { io_error } => {
+                std::fmt::Display::fmt(&io_error, f)
+            },
+            CampaignProgressParseError::BincodeError { bincode_error } => {
+                std::fmt::Display::fmt(&bincode_error, f)
+            },
+            CampaignProgressParseError::DuplicateNode { node_id } => write!(
+                f,
+                "Unexpected duplicate node {} found in campaign progress file!",
+                node_id
+            ),
+        }
+    }
+}
+
+impl Error for CampaignProgressParseError {}
+
+impl From<std::io::Error> for CampaignProgressParseError {
+    fn from(io_error: std::io::Error) -> Self {
+        CampaignProgressParseError::IOError { io_error }
+    }
+}
+
+impl From<Box<bincode::ErrorKind>> for CampaignProgressParseError {
+    fn from(bincode_error: Box<bincode::ErrorKind>) -> Self {
+        CampaignProgressParseError::BincodeError { bincode_error }
+    }
+}
+
+impl CampaignProgressParseError {
+    /// Get the numeric error to compare the error kind. Discards all data that is carried by this error
+    pub fn numeric_error(&self) -> u8 {
+        match self {
+            CampaignProgressParseError::InvalidMagicBytes { .. } => 0,
+            CampaignProgressParseError::InvalidVersion { .. } => 1,
+            CampaignProgressParseError::IOError { .. } => 2,
+            CampaignProgressParseError::BincodeError { .. } => 3,
+            CampaignProgressParseError::DuplicateNode { .. } => 4,
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::campaign::graph::{Coord, NodeID};
+use std::collections::BTreeSet;
+
+pub mod io;
+pub mod io_error;
+
+/// The progress of a single player in a single campaign trail.
+/// Contains the last position of the player on the trail, all nodes whose maps have been
+/// completed and all trail rewards that have been collected.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CampaignProgress {
+    trail: String,
+    player: String,
+    /// The last player position in the campaign trail, as graph coordinates
+    position: (Coord, Coord),
+    completed_nodes: BTreeSet<NodeID>,
+    collected_rewards: BTreeSet<NodeID>,
+}
+
+impl CampaignProgress {
+    /// Create a new empty progress for the given player in the campaign trail with the given name.
+    /// The player starts at the start node at (0,0).
+    pub fn new(trail: String, player: String) -> Self {
+        CampaignProgress {
+            trail,
+            player,
+            ..Default::default()
+        }
+    }
+    /// Get the name of the campaign trail this progress belongs to
+    pub fn trail(&self) -> &str {
+        self.trail.as_str()
+    }
+    /// Get the name of the player this progress belongs to
+    pub fn player(&self) -> &str {
+        self.player.as_str()
+    }
+    /// Get the last position of the player in the campaign trail, as graph coordinates
+    pub fn position(&self) -> (Coord, Coord) {
+        self.position
+    }
+    /// Set the position of the player in the campaign trail, as graph coordinates
+    pub fn set_position(&mut self, position: (Coord, Coord)) -> &mut Self {
+        self.position = position;
+        self
+    }
+    /// Mark the map at the node with the given ID as completed.
+    /// Returns true if the node has not been completed before.
+    pub fn complete(&mut self, node: NodeID) -> bool {
+        self.completed_nodes.insert(node)
+    }
+    /// Check if the map at the node with the given ID has been completed
+    pub fn is_completed(&self, node: &NodeID) -> bool {
+        self.completed_nodes.contains(node)
+    }
+    /// Iterate over the IDs of all completed nodes in ascending order
+    pub fn completed_nodes(&self) -> impl Iterator<Item = &NodeID> {
+        self.completed_nodes.iter()
+    }
+    /// Mark the reward at the node with the given ID as collected.
+    /// Returns true if the reward has not been collected before.
+    pub fn collect_reward(&mut self, node: NodeID) -> bool {
+        self.collected_rewards.insert(node)
+    }
+    /// Check if the reward at the node with the given ID has been collected
+    pub fn has_collected_reward(&self, node: &NodeID) -> bool {
+        self.collected_rewards.contains(node)
+    }
+    /// Iterate over the IDs of all nodes whose rewards have been collected in ascending order
+    pub fn collected_rewards(&self) -> impl Iterator<Item = &NodeID> {
+        self.collected_rewards.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::campaign::progress::CampaignProgress;
+
+    #[test]
+    fn test_new_progress_starts_at_origin() {
+        let progress = CampaignProgress::new("campaign".to_string(), "Thorsten".to_string());
+        assert_eq!("campaign", progress.trail());
+        assert_eq!("Thorsten", progress.player());
+        assert_eq!((0, 0), progress.position());
+        assert_eq!(0, progress.completed_nodes().count());
+        assert_eq!(0, progress.collected_rewards().count());
+    }
+
+    #[test]
+    fn test_complete_node() {
+        let mut progress = CampaignProgress::new("campaign".to_string(), "Thorsten".to_string());
+        assert!(!progress.is_completed(&3));
+        assert!(progress.complete(3));
+        assert!(!progress.complete(3));
+        assert!(progress.complete(1));
+        assert!(progress.is_completed(&3));
+        assert_eq!(vec![&1, &3], progress.completed_nodes().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_collect_reward() {
+        let mut progress = CampaignProgress::new("campaign".to_string(), "Thorsten".to_string());
+        assert!(progress.collect_reward(7));
+        assert!(!progress.collect_reward(7));
+        assert!(progress.has_collected_reward(&7));
+        assert!(!progress.has_collected_reward(&8));
+        assert!(!progress.is_completed(&7));
+    }
+}
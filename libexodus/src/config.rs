@@ -201,7 +201,7 @@ mod tests {
             assert!(
                 actual.is_some(),
                 "Deserializing Language {} (0x{:02X}) resulted in an error: Language not found in {}",
-                reference.to_string(),
+                reference,
                 reference.to_bytes(),
                 stringify!(Language::from_bytes())
             );
@@ -210,9 +210,9 @@ mod tests {
                 *reference,
                 actual,
                 "The Language {} (0x{:02X}) deserialized into Language {} (0x{:02X}) !",
-                reference.to_string(),
+                reference,
                 reference.to_bytes(),
-                actual.to_string(),
+                actual,
                 actual.to_bytes(),
             );
         }
@@ -226,7 +226,7 @@ mod tests {
             assert!(
                 actual.is_some(),
                 "Deserializing Tile Set {} (0x{:02X}) resulted in an error: Tile Set not found in {}",
-                reference.to_string(),
+                reference,
                 reference.to_bytes(),
                 stringify!(Tileset::from_bytes())
             );
@@ -235,9 +235,9 @@ mod tests {
                 *reference,
                 actual,
                 "The Tile Set {} (0x{:02X}) deserialized into Tile Set {} (0x{:02X}) !",
-                reference.to_string(),
+                reference,
                 reference.to_bytes(),
-                actual.to_string(),
+                actual,
                 actual.to_bytes(),
             );
         }
@@ -249,7 +249,7 @@ mod tests {
         assert!(
             result.is_ok(),
             "Config failed to serialize with error: {}",
-            result.unwrap_err().to_string()
+            result.unwrap_err()
         );
         let mut result_config = Config::default();
         buf.set_rpos(0);
@@ -257,7 +257,7 @@ mod tests {
         assert!(
             result.is_ok(),
             "Config failed to parse with error: {}",
-            result.unwrap_err().to_string()
+            result.unwrap_err()
         );
         assert_eq!(config.game_language, result_config.game_language);
         assert_eq!(config.tile_set, result_config.tile_set);
@@ -272,18 +272,21 @@ mod tests {
 
     #[test]
     fn test_write_and_read_german_config() {
-        let mut config = Config::default();
-        config.game_language = Language::GERMAN;
-        config.player_id = "Stefan".to_string();
+        let mut config = Config {
+            game_language: Language::GERMAN,
+            player_id: "Stefan".to_string(),
+            ..Default::default()
+        };
         test_write_and_read_config(&mut config);
     }
 
     #[test]
     fn test_write_and_read_german_config_classic() {
-        let mut config = Config::default();
-        config.game_language = Language::GERMAN;
-        config.tile_set = Tileset::Classic;
-        config.player_id = "Eberhardt".to_string();
+        let mut config = Config {
+            game_language: Language::GERMAN,
+            tile_set: Tileset::Classic,
+            player_id: "Eberhardt".to_string(),
+        };
         test_write_and_read_config(&mut config);
     }
 }
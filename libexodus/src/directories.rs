@@ -1,4 +1,6 @@
 use crate::directories::InvalidMapNameError::{InvalidPath, NotASubpath};
+use crate::world::hash::hash_to_str;
+use crate::world::text_io::TEXT_MAP_FILE_EXTENSION;
 use directories::ProjectDirs;
 ///
 /// This file contains code used to build and query file system directories
@@ -68,10 +70,17 @@ pub struct GameDirectories {
     _base_dir: PathBuf,
     pub maps_dir: PathBuf,
     pub config_dir: PathBuf,
+    pub replays_dir: PathBuf,
+    pub progress_dir: PathBuf,
+    pub campaigns_dir: PathBuf,
 }
 
 impl GameDirectories {
     const MAP_FILE_SUFFIX: &'static str = "exm";
+    const TEXT_MAP_FILE_SUFFIX: &'static str = TEXT_MAP_FILE_EXTENSION;
+    const REPLAY_FILE_SUFFIX: &'static str = "exr";
+    const PROGRESS_FILE_SUFFIX: &'static str = "exp";
+    const CAMPAIGN_TRAIL_FILE_SUFFIX: &'static str = "tgf";
 
     /// Automatically get the game directories from the system directories.
     /// Returns an `InvalidSystemConfigurationError` if the system variables are set up incorrectly.
@@ -84,15 +93,33 @@ impl GameDirectories {
         let game_base_dir = basedir_struct.data_dir();
         let game_maps_dir = game_base_dir.join("maps");
         let game_config_dir = game_base_dir.join("config");
+        let game_replays_dir = game_base_dir.join("replays");
+        let game_progress_dir = game_base_dir.join("progress");
+        let game_campaigns_dir = game_base_dir.join("campaigns");
         Ok(GameDirectories {
             _base_dir: PathBuf::from(game_base_dir),
             maps_dir: game_maps_dir,
             config_dir: game_config_dir,
+            replays_dir: game_replays_dir,
+            progress_dir: game_progress_dir,
+            campaigns_dir: game_campaigns_dir,
         })
     }
 
     pub fn relative_map_dir_from_path(&self, path: &Path) -> Result<String, InvalidMapNameError> {
-        let resolved_path = path.strip_prefix(&self.maps_dir)?;
+        Self::relative_path_in(&self.maps_dir, path)
+    }
+
+    /// Get the path of the given campaign map relative to the campaigns folder
+    pub fn relative_campaign_map_dir_from_path(
+        &self,
+        path: &Path,
+    ) -> Result<String, InvalidMapNameError> {
+        Self::relative_path_in(&self.campaigns_dir, path)
+    }
+
+    fn relative_path_in(folder: &Path, path: &Path) -> Result<String, InvalidMapNameError> {
+        let resolved_path = path.strip_prefix(folder)?;
         resolved_path
             .as_os_str()
             .to_str()
@@ -120,14 +147,17 @@ impl GameDirectories {
                 meta.is_ok() && meta.unwrap().is_file()
             })
             .filter(|file| {
-                let ret = file
+                let extension = file
                     .path()
                     .extension()
                     .map(|s| s.to_ascii_lowercase())
-                    .unwrap_or_else(|| "".into())
-                    == GameDirectories::MAP_FILE_SUFFIX
-                        .to_ascii_lowercase()
-                        .as_str();
+                    .unwrap_or_else(|| "".into());
+                let ret = [
+                    GameDirectories::MAP_FILE_SUFFIX,
+                    GameDirectories::TEXT_MAP_FILE_SUFFIX,
+                ]
+                .iter()
+                .any(|suffix| extension == suffix.to_ascii_lowercase().as_str());
                 if cfg!(debug_assertions) && !ret {
                     println!(
                         "Skipped {} because its file extension did not match.",
@@ -138,10 +168,44 @@ impl GameDirectories {
             })
     }
 
+    /// Iterate over all campaign trail files that are found inside the user campaigns folder and
+    /// all subfolders, such that community campaigns can be installed together with their maps.
+    /// Follow symlinks on the way.
+    pub fn iter_campaign_trails(&self) -> impl Iterator<Item = PathBuf> + '_ {
+        WalkDir::new(&self.campaigns_dir)
+            .follow_links(true)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|file| file.file_type().is_file())
+            .map(|file| file.into_path())
+            .filter(|path| {
+                path.extension().is_some_and(|extension| {
+                    extension.eq_ignore_ascii_case(Self::CAMPAIGN_TRAIL_FILE_SUFFIX)
+                })
+            })
+    }
+
     /// Get the path of a map with the given user input path.
     /// The user input is sanitized and resolved as subdirectory relative to the maps folder.
     /// If the file name that results from converting the name is invalid, an error is returned.
     pub fn path_from_userinput(&self, user_input: &str) -> Result<PathBuf, InvalidMapNameError> {
+        Self::path_from_userinput_in(&self.maps_dir, user_input)
+    }
+
+    /// Get the path of a campaign map with the given user input path.
+    /// Campaign maps are stored next to their campaign trails, hence the user input is resolved
+    /// relative to the campaigns folder, like in [GameDirectories::path_from_userinput].
+    pub fn campaign_map_path_from_userinput(
+        &self,
+        user_input: &str,
+    ) -> Result<PathBuf, InvalidMapNameError> {
+        Self::path_from_userinput_in(&self.campaigns_dir, user_input)
+    }
+
+    fn path_from_userinput_in(
+        folder: &Path,
+        user_input: &str,
+    ) -> Result<PathBuf, InvalidMapNameError> {
         let user_input_t = user_input.trim();
         let map_subdir_name: Result<String, InvalidMapNameError> = user_input_t
             .chars()
@@ -180,18 +244,22 @@ impl GameDirectories {
         match &map_folder {
             None => unreachable!(),
             Some(path) => {
-                let mut ret: PathBuf = self.maps_dir.clone();
+                let mut ret: PathBuf = folder.to_path_buf();
                 ret.push(path);
                 Ok(ret)
             },
         }
     }
 
-    /// Make sure the given path has the .exm extension
+    /// Make sure the given path has the .exm extension.
+    /// Paths with the .exmt extension are kept, such that maps can be saved in the text format.
     fn assure_map_extension(map_subdir_name: String) -> String {
         if map_subdir_name
             .as_str()
             .ends_with(format!(".{}", GameDirectories::MAP_FILE_SUFFIX).as_str())
+            || map_subdir_name
+                .as_str()
+                .ends_with(format!(".{}", GameDirectories::TEXT_MAP_FILE_SUFFIX).as_str())
         {
             map_subdir_name
         } else {
@@ -199,17 +267,44 @@ impl GameDirectories {
         }
     }
 
+    /// Check if the given map path lies inside the campaigns folder or any of its subfolders
+    pub fn is_campaign_map(&self, path: &Path) -> bool {
+        path.starts_with(&self.campaigns_dir)
+    }
+
     pub fn config_file(&self) -> PathBuf {
         self.config_dir.as_path().join("config.exc")
     }
     pub fn highscores_file(&self) -> PathBuf {
         self.config_dir.as_path().join("score.exh")
     }
+    /// Get the path of the replay file for the map with the given hash
+    pub fn replay_file(&self, map_hash: &[u8; 32]) -> PathBuf {
+        let hash_str = hash_to_str(map_hash);
+        self.replays_dir
+            .as_path()
+            .join(format!("{}.{}", hash_str, Self::REPLAY_FILE_SUFFIX))
+    }
+    /// Get the path of the campaign progress file of the given player in the campaign trail
+    /// with the given name.
+    /// The player name is hex-encoded, such that arbitrary player names result in valid file names.
+    pub fn campaign_progress_file(&self, trail: &str, player: &str) -> PathBuf {
+        let player_str: String = player.bytes().map(|b| format!("{:02x}", b)).collect();
+        self.progress_dir.as_path().join(format!(
+            "{}_{}.{}",
+            trail,
+            player_str,
+            Self::PROGRESS_FILE_SUFFIX
+        ))
+    }
 }
 #[cfg(test)]
 impl GameDirectories {
     pub fn mock(_base_dir: PathBuf, maps_dir: PathBuf, config_dir: PathBuf) -> Self {
         GameDirectories {
+            replays_dir: _base_dir.join("replays"),
+            progress_dir: _base_dir.join("progress"),
+            campaigns_dir: _base_dir.join("campaigns"),
             _base_dir,
             maps_dir,
             config_dir,
@@ -267,6 +362,7 @@ mod tests {
     assert_map_path_resolves_to!(map_from_uppercase: "/var/maps", "TestMap 1234 NEW", "/var/maps/testmap_1234_new.exm");
     assert_map_path_resolves_to_and_back!(map_from_in_root: "/", "testmap.exm", "/testmap.exm");
     assert_map_path_resolves_to_and_back!(map_from_map_folder: "/home/user/.local/share/libexodus/maps", "testmap.exm", "/home/user/.local/share/libexodus/maps/testmap.exm");
+    assert_map_path_resolves_to_and_back!(map_from_text_format: "/var/maps", "campaign/testmap.exmt", "/var/maps/campaign/testmap.exmt");
     assert_map_path_resolves_to_and_back!(map_from_map_subfolder: "/home/user/.local/share/libexodus/maps", "campaign/testmap.exm", "/home/user/.local/share/libexodus/maps/campaign/testmap.exm");
 
     assert_map_errors!(map_with_lt: "/home", "test<map.exm", '<');
@@ -278,6 +374,17 @@ mod tests {
     assert_map_errors!(map_with_question: "/home", "testmap?.exm", '?');
     assert_map_errors!(map_with_asterisk: "/home", "testmap*.exm", '*');
 
+    #[test]
+    fn test_replay_file() {
+        let directories = GameDirectories::mock("/base".into(), "/maps".into(), "".into());
+        let mut hash = [0u8; 32];
+        hash[0] = 0xab;
+        assert_eq!(
+            PathBuf::from(format!("/base/replays/AB{}.exr", "00".repeat(31))),
+            directories.replay_file(&hash)
+        );
+    }
+
     #[test]
     fn test_empty_map() {
         let directories = GameDirectories::mock("".into(), "/maps".into(), "".into());
@@ -288,4 +395,53 @@ mod tests {
             InvalidMapNameError::EmptyName
         ))
     }
+
+    #[test]
+    fn test_is_campaign_map() {
+        let directories = GameDirectories::mock("/base".into(), "/base/maps".into(), "".into());
+        for input in ["testmap.exm", "community/testmap.exm"] {
+            let path = directories.campaign_map_path_from_userinput(input).unwrap();
+            assert!(directories.is_campaign_map(&path), "{}", input);
+            assert_eq!(
+                input,
+                directories
+                    .relative_campaign_map_dir_from_path(&path)
+                    .unwrap()
+            );
+            let path = directories.path_from_userinput(input).unwrap();
+            assert!(!directories.is_campaign_map(&path), "{}", input);
+        }
+    }
+    #[test]
+    fn test_iter_campaign_trails() {
+        let base_dir =
+            std::env::temp_dir().join(format!("exodus_test_campaigns_{}", std::process::id()));
+        let directories = GameDirectories::mock(base_dir.clone(), "".into(), "".into());
+        std::fs::create_dir_all(directories.campaigns_dir.join("community")).unwrap();
+        for file in ["main.tgf", "community/community.TGF", "community/map.exm"] {
+            std::fs::write(directories.campaigns_dir.join(file), "").unwrap();
+        }
+        let mut trails: Vec<PathBuf> = directories.iter_campaign_trails().collect();
+        trails.sort();
+        assert_eq!(
+            vec![
+                directories.campaigns_dir.join("community/community.TGF"),
+                directories.campaigns_dir.join("main.tgf"),
+            ],
+            trails
+        );
+        std::fs::remove_dir_all(&base_dir).unwrap();
+    }
+    #[test]
+    fn test_campaign_progress_file() {
+        let directories = GameDirectories::mock("/base".into(), "/maps".into(), "".into());
+        assert_eq!(
+            PathBuf::from("/base/progress/campaign_4d61782f4d.exp"),
+            directories.campaign_progress_file("campaign", "Max/M")
+        );
+        assert_ne!(
+            directories.campaign_progress_file("campaign", "Max"),
+            directories.campaign_progress_file("campaign", "max")
+        );
+    }
 }
@@ -0,0 +1,398 @@
+//! Patrolling enemies that are defined in the map file next to the tile grid.
+//! Enemies move exactly one tile per player move, after the player has come to rest.
+use crate::directions::FromDirection::{FROMEAST, FROMNORTH, FROMSOUTH, FROMWEST};
+use crate::directions::{Directions, FromDirection};
+use crate::world::GameWorld;
+
+/// The atlas index of a walking enemy
+pub const WALKER_SPRITE: usize = 252;
+/// The atlas index of a flying enemy
+pub const FLYER_SPRITE: usize = 253;
+
+/// The way an enemy patrols the map
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum EnemyKind {
+    /// An enemy that walks back and forth along the platform it stands on. It turns around
+    /// whenever it would walk into a solid tile, off the edge of its platform or out of the map.
+    WALKER { facing_right: bool },
+    /// An enemy that flies from its spawn along the given waypoints, ignoring all tiles.
+    /// After reaching the last waypoint, it flies back along the same path.
+    /// Between two waypoints, the enemy first flies horizontally, then vertically.
+    FLYER { waypoints: Vec<(usize, usize)> },
+}
+
+/// An enemy, as it is stored in a map
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Enemy {
+    kind: EnemyKind,
+    spawn: (usize, usize),
+    /// The directions from which touching this enemy is deadly for the player.
+    /// Touching the enemy from any other direction defeats the enemy.
+    deadly_from: Vec<FromDirection>,
+}
+
+/// The state of an enemy while a map is being played
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct EnemyState {
+    position: (usize, usize),
+    facing_right: bool,
+    /// The index of the next position on the path of a flying enemy
+    next_waypoint: usize,
+    /// True, if a flying enemy is on its way back to its spawn
+    returning: bool,
+    defeated: bool,
+}
+
+impl EnemyState {
+    /// Get the current position of the enemy
+    pub fn position(&self) -> (usize, usize) {
+        self.position
+    }
+    /// Check if the enemy is facing right, i.e., if it has moved to the right most recently
+    pub fn is_facing_right(&self) -> bool {
+        self.facing_right
+    }
+    /// Check if the enemy has been defeated by the player
+    pub fn is_defeated(&self) -> bool {
+        self.defeated
+    }
+    /// Check if the enemy is still alive and occupies the given tile
+    pub fn is_at(&self, position: (usize, usize)) -> bool {
+        !self.defeated && self.position == position
+    }
+    /// Defeat the enemy, such that it neither moves nor interacts with the player anymore
+    pub fn defeat(&mut self) {
+        self.defeated = true;
+    }
+}
+
+impl Enemy {
+    /// Create a new walking enemy that can be defeated by jumping onto it from above
+    pub fn walker(spawn: (usize, usize), facing_right: bool) -> Self {
+        Enemy {
+            kind: EnemyKind::WALKER { facing_right },
+            spawn,
+            deadly_from: vec![FROMSOUTH, FROMEAST, FROMWEST],
+        }
+    }
+    /// Create a new flying enemy that is deadly from all directions
+    pub fn flyer(spawn: (usize, usize), waypoints: Vec<(usize, usize)>) -> Self {
+        Enemy {
+            kind: EnemyKind::FLYER { waypoints },
+            spawn,
+            deadly_from: vec![FROMNORTH, FROMSOUTH, FROMEAST, FROMWEST],
+        }
+    }
+    /// Set the directions from which touching this enemy is deadly for the player.
+    /// The directions are stored in a fixed order, such that equal enemies always compare equal.
+    pub fn with_deadly_from(mut self, deadly_from: Vec<FromDirection>) -> Self {
+        self.deadly_from = [FROMNORTH, FROMSOUTH, FROMEAST, FROMWEST]
+            .into_iter()
+            .filter(|direction| deadly_from.contains(direction))
+            .collect();
+        self
+    }
+    /// Get the kind of this enemy
+    pub fn kind(&self) -> &EnemyKind {
+        &self.kind
+    }
+    /// Get the position at which this enemy is spawned
+    pub fn spawn(&self) -> (usize, usize) {
+        self.spawn
+    }
+    /// Get the directions from which touching this enemy is deadly for the player
+    pub fn deadly_from(&self) -> &[FromDirection] {
+        &self.deadly_from
+    }
+    /// Check if touching this enemy from the given direction is deadly for the player
+    pub fn is_deadly_from(&self, from_direction: &FromDirection) -> bool {
+        self.deadly_from.contains(from_direction)
+    }
+    /// Get a unique string id, describing the kind of this enemy.
+    /// Consists only of underscores and lower-case characters. Suitable for i18n keys.
+    pub fn str_id(&self) -> &str {
+        match self.kind {
+            EnemyKind::WALKER { .. } => "walker",
+            EnemyKind::FLYER { .. } => "flyer",
+        }
+    }
+    /// Get the atlas index of this enemy
+    pub fn atlas_index(&self) -> usize {
+        match self.kind {
+            EnemyKind::WALKER { .. } => WALKER_SPRITE,
+            EnemyKind::FLYER { .. } => FLYER_SPRITE,
+        }
+    }
+    /// Get all map positions this enemy refers to, i.e., its spawn and all waypoints
+    pub fn positions(&self) -> Vec<(usize, usize)> {
+        let mut ret = vec![self.spawn];
+        if let EnemyKind::FLYER { waypoints } = &self.kind {
+            ret.extend(waypoints.iter().copied());
+        }
+        ret
+    }
+    /// Move all positions of this enemy using the given function.
+    /// Returns None, if the function returns None for any of the positions.
+    pub(crate) fn moved(
+        &self,
+        f: impl Fn((usize, usize)) -> Option<(usize, usize)>,
+    ) -> Option<Enemy> {
+        let kind = match &self.kind {
+            EnemyKind::WALKER { facing_right } => EnemyKind::WALKER {
+                facing_right: *facing_right,
+            },
+            EnemyKind::FLYER { waypoints } => EnemyKind::FLYER {
+                waypoints: waypoints
+                    .iter()
+                    .map(|waypoint| f(*waypoint))
+                    .collect::<Option<Vec<_>>>()?,
+            },
+        };
+        Some(Enemy {
+            kind,
+            spawn: f(self.spawn)?,
+            deadly_from: self.deadly_from.clone(),
+        })
+    }
+    /// Get the state of this enemy at the start of a game
+    pub fn initial_state(&self) -> EnemyState {
+        EnemyState {
+            position: self.spawn,
+            facing_right: match &self.kind {
+                EnemyKind::WALKER { facing_right } => *facing_right,
+                EnemyKind::FLYER { waypoints } => waypoints
+                    .first()
+                    .is_none_or(|waypoint| waypoint.0 >= self.spawn.0),
+            },
+            next_waypoint: 0,
+            returning: false,
+            defeated: false,
+        }
+    }
+    /// Move the given enemy by one tile.
+    /// Returns the direction the enemy has moved in, or None if the enemy did not move.
+    ///
+    /// ```rust
+    /// use libexodus::enemies::Enemy;
+    /// use libexodus::world::presets;
+    /// let world = presets::map_with_border(5, 3);
+    /// let enemy = Enemy::walker((2, 1), true);
+    /// let mut state = enemy.initial_state();
+    /// enemy.step(&mut state, &world);
+    /// assert_eq!((3, 1), state.position());
+    /// // The enemy turns around at the wall
+    /// enemy.step(&mut state, &world);
+    /// assert_eq!((2, 1), state.position());
+    /// assert!(!state.is_facing_right());
+    /// ```
+    pub fn step(&self, state: &mut EnemyState, world: &GameWorld) -> Option<Directions> {
+        if state.defeated {
+            return None;
+        }
+        let direction = match &self.kind {
+            EnemyKind::WALKER { .. } => Self::step_walker(state, world)?,
+            EnemyKind::FLYER { waypoints } => self.step_flyer(state, waypoints)?,
+        };
+        let (x, y) = state.position;
+        state.position = match direction {
+            Directions::NORTH => (x, y + 1),
+            Directions::SOUTH => (x, y - 1),
+            Directions::EAST => (x + 1, y),
+            Directions::WEST => (x - 1, y),
+        };
+        match direction {
+            Directions::EAST => state.facing_right = true,
+            Directions::WEST => state.facing_right = false,
+            Directions::NORTH | Directions::SOUTH => {},
+        }
+        Some(direction)
+    }
+    /// Get the direction a walking enemy moves in, turning it around if necessary
+    fn step_walker(state: &mut EnemyState, world: &GameWorld) -> Option<Directions> {
+        let (x, y) = (state.position.0 as i32, state.position.1 as i32);
+        for _ in 0..2 {
+            let (direction, target_x) = if state.facing_right {
+                (Directions::EAST, x + 1)
+            } else {
+                (Directions::WEST, x - 1)
+            };
+            let walkable = world
+                .get(target_x, y)
+                .is_some_and(|tile| !tile.can_collide_from(&direction.into()))
+                && world
+                    .get(target_x, y - 1)
+                    .is_none_or(|tile| tile.can_collide_from(&FROMNORTH));
+            if walkable {
+                return Some(direction);
+            }
+            state.facing_right = !state.facing_right;
+        }
+        None
+    }
+    /// Get the direction a flying enemy moves in, advancing to the next waypoint if necessary
+    fn step_flyer(
+        &self,
+        state: &mut EnemyState,
+        waypoints: &[(usize, usize)],
+    ) -> Option<Directions> {
+        // The path consists of the spawn, followed by all waypoints
+        let path_len = waypoints.len() + 1;
+        let path = |index: usize| match index {
+            0 => self.spawn,
+            i => waypoints[i - 1],
+        };
+        for _ in 0..path_len {
+            let target = path(state.next_waypoint);
+            if target != state.position {
+                let (x, y) = state.position;
+                return Some(if target.0 > x {
+                    Directions::EAST
+                } else if target.0 < x {
+                    Directions::WEST
+                } else if target.1 > y {
+                    Directions::NORTH
+                } else {
+                    Directions::SOUTH
+                });
+            }
+            if path_len < 2 {
+                return None;
+            }
+            if state.returning && state.next_waypoint == 0 {
+                state.returning = false;
+            } else if !state.returning && state.next_waypoint + 1 == path_len {
+                state.returning = true;
+            }
+            if state.returning {
+                state.next_waypoint -= 1;
+            } else {
+                state.next_waypoint += 1;
+            }
+        }
+        None
+    }
+}
+
+/// Get the side of an enemy that leads when the enemy moves in the given direction,
+/// i.e., the direction from which the enemy touches a player it moves into
+pub fn leading_side(direction: Directions) -> FromDirection {
+    match direction {
+        Directions::NORTH => FROMNORTH,
+        Directions::SOUTH => FROMSOUTH,
+        Directions::EAST => FROMEAST,
+        Directions::WEST => FROMWEST,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::directions::Directions;
+    use crate::directions::FromDirection::{FROMEAST, FROMNORTH, FROMSOUTH, FROMWEST};
+    use crate::enemies::{Enemy, leading_side};
+    use crate::tiles::Tile;
+    use crate::world::presets;
+
+    #[test]
+    fn test_walker_turns_around_at_platform_edge() {
+        let mut world = presets::map_with_border(7, 5);
+        world.set(2, 2, Tile::WALL);
+        world.set(3, 2, Tile::WALL);
+        world.set(4, 2, Tile::WALL);
+        let enemy = Enemy::walker((3, 3), true);
+        let mut state = enemy.initial_state();
+        let positions: Vec<(usize, usize)> = (0..5)
+            .map(|_| {
+                enemy.step(&mut state, &world);
+                state.position()
+            })
+            .collect();
+        assert_eq!(vec![(4, 3), (3, 3), (2, 3), (3, 3), (4, 3)], positions);
+    }
+
+    #[test]
+    fn test_walker_without_space_stays_in_place() {
+        let mut world = presets::map_with_border(3, 3);
+        world.set(1, 1, Tile::AIR);
+        let enemy = Enemy::walker((1, 1), false);
+        let mut state = enemy.initial_state();
+        assert_eq!(None, enemy.step(&mut state, &world));
+        assert_eq!((1, 1), state.position());
+    }
+
+    #[test]
+    fn test_flyer_follows_waypoints_back_and_forth() {
+        let world = presets::map_with_border(6, 6);
+        let enemy = Enemy::flyer((1, 1), vec![(3, 1), (3, 3)]);
+        let mut state = enemy.initial_state();
+        let mut positions: Vec<(usize, usize)> = vec![];
+        for _ in 0..10 {
+            enemy.step(&mut state, &world);
+            positions.push(state.position());
+        }
+        assert_eq!(
+            vec![
+                (2, 1),
+                (3, 1),
+                (3, 2),
+                (3, 3),
+                (3, 2),
+                (3, 1),
+                (2, 1),
+                (1, 1),
+                (2, 1),
+                (3, 1)
+            ],
+            positions
+        );
+    }
+
+    #[test]
+    fn test_flyer_ignores_tiles() {
+        let mut world = presets::map_with_border(5, 3);
+        world.set(2, 1, Tile::WALL);
+        let enemy = Enemy::flyer((1, 1), vec![(3, 1)]);
+        let mut state = enemy.initial_state();
+        assert_eq!(Some(Directions::EAST), enemy.step(&mut state, &world));
+        assert_eq!((2, 1), state.position());
+    }
+
+    #[test]
+    fn test_flyer_without_waypoints_stays_in_place() {
+        let world = presets::map_with_border(3, 3);
+        let enemy = Enemy::flyer((1, 1), vec![]);
+        let mut state = enemy.initial_state();
+        assert_eq!(None, enemy.step(&mut state, &world));
+        assert_eq!((1, 1), state.position());
+    }
+
+    #[test]
+    fn test_defeated_enemies_do_not_move() {
+        let world = presets::map_with_border(5, 3);
+        let enemy = Enemy::walker((1, 1), true);
+        let mut state = enemy.initial_state();
+        state.defeat();
+        assert_eq!(None, enemy.step(&mut state, &world));
+        assert!(!state.is_at((1, 1)));
+    }
+
+    #[test]
+    fn test_deadly_directions() {
+        let walker = Enemy::walker((1, 1), true);
+        assert!(!walker.is_deadly_from(&FROMNORTH));
+        assert!(walker.is_deadly_from(&FROMEAST));
+        let walker = walker.with_deadly_from(vec![FROMNORTH]);
+        assert!(walker.is_deadly_from(&FROMNORTH));
+        assert!(!walker.is_deadly_from(&FROMWEST));
+        assert!(Enemy::flyer((1, 1), vec![]).is_deadly_from(&FROMNORTH));
+        assert_eq!(FROMEAST, leading_side(Directions::EAST));
+        assert_eq!(FROMSOUTH, leading_side(Directions::SOUTH));
+    }
+
+    #[test]
+    fn test_moved_drops_enemies_outside_of_the_map() {
+        let enemy = Enemy::flyer((1, 1), vec![(3, 1)]);
+        let moved = enemy.moved(|(x, y)| Some((x + 1, y))).unwrap();
+        assert_eq!(vec![(2, 1), (4, 1)], moved.positions());
+        assert!(enemy.moved(|(x, y)| (x < 3).then_some((x, y))).is_none());
+    }
+}
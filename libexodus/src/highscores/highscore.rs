@@ -7,6 +7,7 @@ use std::io::{Read, Write};
 pub struct Highscore {
     num_moves: u32,
     num_coins: u32,
+    num_deaths: u32,
 }
 
 impl Highscore {
@@ -14,19 +15,28 @@ impl Highscore {
         Highscore {
             num_moves,
             num_coins,
+            num_deaths: 0,
         }
     }
+    /// Set the number of times the player died before reaching the exit
+    pub fn with_deaths(mut self, num_deaths: u32) -> Self {
+        self.num_deaths = num_deaths;
+        self
+    }
     pub fn moves(&self) -> u32 {
         self.num_moves
     }
     pub fn coins(&self) -> u32 {
         self.num_coins
     }
+    pub fn deaths(&self) -> u32 {
+        self.num_deaths
+    }
 }
 
 /// Implementation for Serializer
 impl ExodusSerializable for Highscore {
-    const CURRENT_VERSION: u8 = 0x01;
+    const CURRENT_VERSION: u8 = 0x02;
     type ParseError = HighscoreParseError;
     fn serialize<T: Write>(&self, file: &mut T) -> Result<(), HighscoreParseError> {
         // Write Highscore Version
@@ -40,6 +50,10 @@ impl ExodusSerializable for Highscore {
         let coins_b = bincode::serialize(&self.num_coins)?;
         file.write_all(&coins_b)?;
 
+        // Write number of deaths
+        let deaths_b = bincode::serialize(&self.num_deaths)?;
+        file.write_all(&deaths_b)?;
+
         Ok(())
     }
     fn parse<T: Read>(&mut self, file: &mut T) -> Result<(), HighscoreParseError> {
@@ -47,6 +61,7 @@ impl ExodusSerializable for Highscore {
         let mut buf: [u8; 1] = [0; 1];
         file.read_exact(&mut buf)?;
         match buf[0] {
+            0x01 => self.parse_v1(file),
             Self::CURRENT_VERSION => self.parse_current_version(file),
             // Add older versions here
             _ => {
@@ -70,6 +85,27 @@ impl ExodusSerializable for Highscore {
         let coins: u32 = bincode::deserialize_from::<&mut T, u32>(file)?;
         self.num_coins = coins;
 
+        // Parse deaths
+        let deaths: u32 = bincode::deserialize_from::<&mut T, u32>(file)?;
+        self.num_deaths = deaths;
+
+        Ok(())
+    }
+}
+
+/// Implementations for parsing v0x01 highscores, which do not contain the number of deaths.
+impl Highscore {
+    /// Parse a highscore with version 0x01.
+    fn parse_v1<T: Read>(&mut self, file: &mut T) -> Result<(), HighscoreParseError> {
+        // Parse moves
+        let moves: u32 = bincode::deserialize_from::<&mut T, u32>(file)?;
+        self.num_moves = moves;
+
+        // Parse coins
+        let coins: u32 = bincode::deserialize_from::<&mut T, u32>(file)?;
+        self.num_coins = coins;
+
+        self.num_deaths = 0;
         Ok(())
     }
 }
@@ -139,9 +175,33 @@ mod tests {
         let highscore2 = Highscore {
             num_moves: 0,
             num_coins: 111,
+            num_deaths: 0,
         };
         assert_eq!(highscore, highscore2);
     }
+
+    #[test]
+    fn test_highscore_with_deaths() {
+        let highscore = Highscore::new(10, 3).with_deaths(2);
+        let mut buf = ByteBuffer::new();
+        highscore.serialize(&mut buf).unwrap();
+        buf.set_rpos(0);
+        let mut result = Highscore::default();
+        result.parse(&mut buf).unwrap();
+        assert_eq!(highscore, result);
+        assert_eq!(2, result.deaths());
+    }
+
+    #[test]
+    fn test_parse_v1_highscore() {
+        let mut data = vec![0x01];
+        data.extend_from_slice(&bincode::serialize(&12u32).unwrap());
+        data.extend_from_slice(&bincode::serialize(&5u32).unwrap());
+        let mut buf = ByteBuffer::from_bytes(&data);
+        let mut result = Highscore::new(1, 1).with_deaths(1);
+        result.parse(&mut buf).unwrap();
+        assert_eq!(Highscore::new(12, 5), result);
+    }
     macro_rules! serialize_tests {
     ($($name:ident: $value:expr,)*) => {
     $(
@@ -2,6 +2,7 @@ use crate::exodus_serializable::ExodusSerializable;
 use crate::highscores::highscore::Highscore;
 use crate::highscores::io_error::HighscoreParseError;
 use crate::highscores::player_highscores::PlayerHighscores;
+use crate::replay::Replay;
 use std::collections::HashMap;
 use std::io::{Read, Write};
 
@@ -35,6 +36,35 @@ impl HighscoreRecords {
             .store_with_current_time(highscore);
         self
     }
+    /// Put the given highscore together with the replay that proves it into the record,
+    /// creating all necessary data structures automatically
+    pub fn put_with_replay(
+        &mut self,
+        player: String,
+        timestamp: i64,
+        highscore: Highscore,
+        replay: Replay,
+    ) -> &mut Self {
+        self.player_records
+            .entry(player.clone())
+            .or_insert_with(|| PlayerHighscores::new(player))
+            .store_with_replay(timestamp, highscore, replay);
+        self
+    }
+    /// Put the given highscore together with the replay that proves it with the current time
+    /// into the record, creating all necessary data structures automatically
+    pub fn put_with_current_time_and_replay(
+        &mut self,
+        player: String,
+        highscore: Highscore,
+        replay: Replay,
+    ) -> &mut Self {
+        self.player_records
+            .entry(player.clone())
+            .or_insert_with(|| PlayerHighscores::new(player))
+            .store_with_current_time_and_replay(highscore, replay);
+        self
+    }
     /// Get the (best) highscore for the given player
     pub fn get_best(&self, player: &String) -> Option<(i64, &Highscore)> {
         self.player_records
@@ -49,6 +79,19 @@ impl HighscoreRecords {
     pub fn get_mut(&mut self, playername: &String) -> Option<&mut PlayerHighscores> {
         self.player_records.get_mut(playername)
     }
+    /// Iterate over the high scores of all players
+    pub fn iter(&self) -> impl Iterator<Item = &PlayerHighscores> {
+        self.player_records.values()
+    }
+    /// Move all high scores of the given records into these records
+    pub fn merge(&mut self, other: HighscoreRecords) {
+        for (player, player_highscores) in other.player_records {
+            self.player_records
+                .entry(player.clone())
+                .or_insert_with(|| PlayerHighscores::new(player))
+                .merge(player_highscores);
+        }
+    }
     /// Get the number of players stored in these highscore records
     pub fn len(&self) -> usize {
         self.player_records.len()
@@ -57,7 +100,8 @@ impl HighscoreRecords {
     pub fn is_empty(&self) -> bool {
         self.player_records.is_empty()
     }
-    pub(crate) fn get_hash(&self) -> &[u8; 32] {
+    /// Get the hash of the map these highscore records belong to
+    pub fn get_hash(&self) -> &[u8; 32] {
         &self.map_hash
     }
 }
@@ -2,6 +2,9 @@ use crate::exodus_serializable::ExodusSerializable;
 use crate::highscores::highscore::Highscore;
 use crate::highscores::highscore_records::HighscoreRecords;
 use crate::highscores::io_error::HighscoreParseError;
+use crate::highscores::verification::HighscoreVerification;
+use crate::replay::Replay;
+use crate::world::GameWorld;
 use std::collections::HashMap;
 use std::fs::{File, OpenOptions};
 use std::io::{BufReader, BufWriter, Read, Write};
@@ -59,12 +62,53 @@ impl HighscoresDatabase {
             .put_with_current_time(player, highscore);
         self
     }
+    /// Put the given highscore together with the replay that proves it into the database,
+    /// creating all necessary data structures automatically
+    pub fn put_with_replay(
+        &mut self,
+        map: [u8; 32],
+        player: String,
+        timestamp: i64,
+        highscore: Highscore,
+        replay: Replay,
+    ) -> &mut Self {
+        self.records
+            .entry(map)
+            .or_insert_with(|| HighscoreRecords::new(map))
+            .put_with_replay(player, timestamp, highscore, replay);
+        self
+    }
+    /// Put the given highscore together with the replay that proves it with the current time
+    /// into the database, creating all necessary data structures automatically
+    pub fn put_with_current_time_and_replay(
+        &mut self,
+        map: [u8; 32],
+        player: String,
+        highscore: Highscore,
+        replay: Replay,
+    ) -> &mut Self {
+        self.records
+            .entry(map)
+            .or_insert_with(|| HighscoreRecords::new(map))
+            .put_with_current_time_and_replay(player, highscore, replay);
+        self
+    }
     /// Get the (best) highscore for the given map and player
     pub fn get_best(&self, map: &[u8; 32], player: &String) -> Option<(i64, &Highscore)> {
         self.records
             .get(map)
             .and_then(|highscore_records| highscore_records.get_best(player))
     }
+    /// Verify the best highscore of the given player in the given map by playing back the replay
+    /// that has been stored with it.
+    /// The highscore is looked up by the hash of the given map.
+    /// Returns None, if the player does not have a highscore in the given map.
+    pub fn verify_best(&self, map: &GameWorld, player: &String) -> Option<HighscoreVerification> {
+        self.records
+            .get(map.hash())
+            .and_then(|highscore_records| highscore_records.get(player))
+            .and_then(|player_highscores| player_highscores.verify_best(map))
+    }
     /// Get the size, i.e., the number of maps stored in this database
     pub fn len(&self) -> usize {
         self.records.len()
@@ -81,6 +125,21 @@ impl HighscoresDatabase {
     pub fn get_mut(&mut self, map: &[u8; 32]) -> Option<&mut HighscoreRecords> {
         self.records.get_mut(map)
     }
+    /// Iterate over the highscore records of all maps
+    pub fn iter(&self) -> impl Iterator<Item = &HighscoreRecords> {
+        self.records.values()
+    }
+    /// Move all highscores of the given database into this database.
+    /// Highscores that are stored in both databases are kept only once.
+    pub fn merge(&mut self, other: HighscoresDatabase) -> &mut Self {
+        for (map, records) in other.records {
+            self.records
+                .entry(map)
+                .or_insert_with(|| HighscoreRecords::new(map))
+                .merge(records);
+        }
+        self
+    }
     /// Load a HighscoreDatabase from the given file
     pub fn load_from_file(path: &Path) -> Result<Self, HighscoreParseError> {
         let file = OpenOptions::new().read(true).open(path)?;
@@ -187,6 +246,11 @@ mod tests {
     use crate::exodus_serializable::ExodusSerializable;
     use crate::highscores::highscore::Highscore;
     use crate::highscores::highscores_database::HighscoresDatabase;
+    use crate::highscores::verification::HighscoreVerification;
+    use crate::replay::Replay;
+    use crate::simulation::PlayerAction;
+    use crate::tiles::Tile;
+    use crate::world::presets;
     use bytebuffer::ByteBuffer;
     use std::time::Duration;
 
@@ -335,6 +399,49 @@ mod tests {
         database
     }
 
+    #[test]
+    fn test_merge_databases() {
+        let mut database = create_complex_database();
+        let mut other = HighscoresDatabase::new();
+        // Already contained in the database
+        other.put([0u8; 32], "Frank".to_string(), 1337, Highscore::new(10, 0));
+        // A better highscore for an existing player
+        other.put([0u8; 32], "Frank".to_string(), 2000, Highscore::new(5, 0));
+        // A new map
+        other.put([2u8; 32], "Anna".to_string(), 42, Highscore::new(3, 1));
+        database.merge(other);
+        assert_eq!(3, database.len());
+        assert_eq!(
+            2,
+            database
+                .get(&[0u8; 32])
+                .unwrap()
+                .get(&"Frank".to_string())
+                .unwrap()
+                .len()
+        );
+        assert_eq!(
+            Some(2000),
+            database
+                .get_best(&[0u8; 32], &"Frank".to_string())
+                .map(|(timestamp, _)| timestamp)
+        );
+        assert_eq!(
+            Some(42),
+            database
+                .get_best(&[2u8; 32], &"Anna".to_string())
+                .map(|(timestamp, _)| timestamp)
+        );
+        assert_eq!(
+            6,
+            database
+                .iter()
+                .flat_map(|records| records.iter())
+                .map(|player_highscores| player_highscores.iter().count())
+                .sum::<usize>()
+        );
+    }
+
     #[test]
     fn test_serialize_complex_database() {
         let db = create_complex_database();
@@ -343,7 +450,7 @@ mod tests {
         assert!(
             result.is_ok(),
             "Database failed to serialize with error: {}",
-            result.unwrap_err().to_string()
+            result.unwrap_err()
         );
         buf.set_rpos(0);
         let mut db2 = HighscoresDatabase::default();
@@ -351,11 +458,11 @@ mod tests {
         assert!(
             result.is_ok(),
             "Highscore Database failed to parse with error: {}",
-            result.unwrap_err().to_string()
+            result.unwrap_err()
         );
         assert_eq!(db.len(), db2.len());
         assert_eq!(db.records.len(), db2.records.len());
-        for (r_hash, _r_data) in &db.records {
+        for r_hash in db.records.keys() {
             assert!(db2.records.contains_key(r_hash));
         }
     }
@@ -382,4 +489,74 @@ mod tests {
         assert_eq!(3, best.moves());
         assert_eq!(2, best.coins());
     }
+
+    #[test]
+    fn test_verify_best() {
+        let mut world = presets::map_with_border(5, 3);
+        world.set(3, 1, Tile::EXIT);
+        world.recompute_hash();
+        let mut replay = Replay::new(*world.hash());
+        replay.push(PlayerAction::Right).push(PlayerAction::Right);
+        let mut database = HighscoresDatabase::new();
+        let player = "Leo".to_string();
+        assert!(database.verify_best(&world, &player).is_none());
+        database.put(*world.hash(), player.clone(), 0, Highscore::new(3, 0));
+        assert_eq!(
+            Some(HighscoreVerification::Unverified),
+            database.verify_best(&world, &player)
+        );
+        database.put_with_replay(
+            *world.hash(),
+            player.clone(),
+            1,
+            Highscore::new(2, 0),
+            replay,
+        );
+        assert_eq!(
+            Some(HighscoreVerification::Verified),
+            database.verify_best(&world, &player)
+        );
+        // A tampered highscore must not be verified, even if it comes with a valid replay
+        let mut tampered = Replay::new(*world.hash());
+        tampered.push(PlayerAction::Right).push(PlayerAction::Right);
+        database.put_with_replay(
+            *world.hash(),
+            player.clone(),
+            2,
+            Highscore::new(1, 0),
+            tampered,
+        );
+        assert_eq!(
+            Some(HighscoreVerification::Invalid),
+            database.verify_best(&world, &player)
+        );
+    }
+
+    #[test]
+    fn test_serialize_database_with_replay() {
+        let mut replay = Replay::new([0u8; 32]);
+        replay.push(PlayerAction::Left);
+        let mut database = HighscoresDatabase::new();
+        database.put_with_replay(
+            [0u8; 32],
+            "Leo".to_string(),
+            1337,
+            Highscore::new(1, 0),
+            replay.clone(),
+        );
+        let mut buf = ByteBuffer::new();
+        database.serialize(&mut buf).unwrap();
+        buf.set_rpos(0);
+        let mut result = HighscoresDatabase::default();
+        result.parse(&mut buf).unwrap();
+        assert_eq!(
+            Some(&replay),
+            result
+                .get(&[0u8; 32])
+                .unwrap()
+                .get(&"Leo".to_string())
+                .unwrap()
+                .best_replay()
+        );
+    }
 }
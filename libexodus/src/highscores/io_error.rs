@@ -1,4 +1,5 @@
 use crate::highscores::highscores_database::MAGICBYTES;
+use crate::replay::io_error::ReplayParseError;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 
@@ -31,6 +32,9 @@ pub enum HighscoreParseError {
         expected: [u8; 32],
         actual: [u8; 32],
     },
+    ReplayError {
+        replay_error: ReplayParseError,
+    },
 }
 
 impl Display for HighscoreParseError {
@@ -69,6 +73,9 @@ impl Display for HighscoreParseError {
                 "Invalid Map Hash in Map File! Expected: {:02x?} Got: {:02x?}",
                 expected, actual
             ),
+            HighscoreParseError::ReplayError { replay_error } => {
+                std::fmt::Display::fmt(&replay_error, f)
+            },
         }
     }
 }
@@ -87,6 +94,12 @@ impl From<Box<bincode::ErrorKind>> for HighscoreParseError {
     }
 }
 
+impl From<ReplayParseError> for HighscoreParseError {
+    fn from(replay_error: ReplayParseError) -> Self {
+        HighscoreParseError::ReplayError { replay_error }
+    }
+}
+
 impl HighscoreParseError {
     /// Get the numeric error to compare the error kind. Discards all data that is carried by this error
     pub fn numeric_error(&self) -> u8 {
@@ -101,6 +114,7 @@ impl HighscoreParseError {
             HighscoreParseError::DuplicateDatabaseEntry => 9,
             HighscoreParseError::DuplicatePlayerEntry => 10,
             HighscoreParseError::HashMismatch { .. } => 11,
+            HighscoreParseError::ReplayError { .. } => 12,
         }
     }
 }
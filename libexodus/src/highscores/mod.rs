@@ -3,3 +3,4 @@ pub mod highscore_records;
 pub mod highscores_database;
 pub mod io_error;
 pub mod player_highscores;
+pub mod verification;
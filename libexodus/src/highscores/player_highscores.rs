@@ -1,6 +1,9 @@
 use crate::exodus_serializable::ExodusSerializable;
 use crate::highscores::highscore::Highscore;
 use crate::highscores::io_error::HighscoreParseError;
+use crate::highscores::verification::HighscoreVerification;
+use crate::replay::Replay;
+use crate::world::GameWorld;
 use std::cmp::Ordering;
 use std::collections::BTreeSet;
 use std::io::{Read, Write};
@@ -29,6 +32,22 @@ impl PlayerHighscores {
             scores: BTreeSet::new(),
         }
     }
+    /// Get the name of the player these highscores belong to
+    pub fn player(&self) -> &str {
+        self.player.as_str()
+    }
+    /// Iterate over all stored highscores together with their timestamp and replay,
+    /// from the worst to the best highscore
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (i64, &Highscore, Option<&Replay>)> {
+        self.scores
+            .iter()
+            .map(|phw| (phw.timestamp, &phw.highscore, phw.replay.as_ref()))
+    }
+    /// Move all highscores of the given player highscores into these highscores.
+    /// Highscores that are stored in both are kept only once.
+    pub fn merge(&mut self, other: PlayerHighscores) {
+        self.scores.extend(other.scores);
+    }
     /// Get the number of stored highscores
     pub fn len(&self) -> usize {
         self.scores.len()
@@ -43,16 +62,43 @@ impl PlayerHighscores {
             .last()
             .map(|phw| (phw.timestamp, &phw.highscore))
     }
+    /// Get the replay that has been stored with the best highscore, if any
+    pub fn best_replay(&self) -> Option<&Replay> {
+        self.scores.last().and_then(|phw| phw.replay.as_ref())
+    }
+    /// Verify the best highscore against the given world, using the replay that has been stored with it.
+    /// Returns None, if there is no highscore.
+    pub fn verify_best(&self, world: &GameWorld) -> Option<HighscoreVerification> {
+        self.scores
+            .last()
+            .map(|phw| phw.highscore.verify(phw.replay.as_ref(), world))
+    }
     /// Store the given highscore with the current time
     pub fn store_with_current_time(&mut self, highscore: Highscore) {
         let timestamp = chrono::offset::Local::now().timestamp_millis();
         self.store(timestamp, highscore);
     }
+    /// Store the given highscore with the current time, together with the replay that proves it
+    pub fn store_with_current_time_and_replay(&mut self, highscore: Highscore, replay: Replay) {
+        let timestamp = chrono::offset::Local::now().timestamp_millis();
+        self.store_with_replay(timestamp, highscore, replay);
+    }
     /// Store the given highscore with the given timestamp (ms from UNIX Epoch)
     pub fn store(&mut self, timestamp: i64, highscore: Highscore) {
         let wrapper = PlayerHighscoresWrapper {
             timestamp,
             highscore,
+            replay: None,
+        };
+        self.scores.insert(wrapper);
+    }
+    /// Store the given highscore with the given timestamp (ms from UNIX Epoch), together with the
+    /// replay that proves it
+    pub fn store_with_replay(&mut self, timestamp: i64, highscore: Highscore, replay: Replay) {
+        let wrapper = PlayerHighscoresWrapper {
+            timestamp,
+            highscore,
+            replay: Some(replay),
         };
         self.scores.insert(wrapper);
     }
@@ -122,11 +168,13 @@ impl ExodusSerializable for PlayerHighscores {
 struct PlayerHighscoresWrapper {
     pub timestamp: i64,
     pub highscore: Highscore,
+    /// The replay of the game that led to the highscore, used to verify the highscore
+    pub replay: Option<Replay>,
 }
 
 /// Implementation for Serializer
 impl ExodusSerializable for PlayerHighscoresWrapper {
-    const CURRENT_VERSION: u8 = 0x01;
+    const CURRENT_VERSION: u8 = 0x02;
     type ParseError = HighscoreParseError;
 
     fn serialize<T: Write>(&self, file: &mut T) -> Result<(), HighscoreParseError> {
@@ -139,6 +187,14 @@ impl ExodusSerializable for PlayerHighscoresWrapper {
 
         // Store Highscore
         self.highscore.serialize(file)?;
+
+        // Store Replay
+        if let Some(replay) = &self.replay {
+            file.write_all(&[0x01u8])?;
+            replay.serialize(file)?;
+        } else {
+            file.write_all(&[0x00u8])?;
+        }
         Ok(())
     }
 
@@ -148,6 +204,7 @@ impl ExodusSerializable for PlayerHighscoresWrapper {
         file.read_exact(&mut buf)?;
         match buf[0] {
             Self::CURRENT_VERSION => self.parse_current_version(file),
+            0x01 => self.parse_v1(file),
             // Add older versions here
             _ => {
                 return Err(Self::ParseError::InvalidVersion {
@@ -166,6 +223,32 @@ impl ExodusSerializable for PlayerHighscoresWrapper {
         highscore.parse(file)?;
         self.highscore = highscore;
 
+        let mut opt = [0u8; 1];
+        file.read_exact(&mut opt)?;
+        if opt[0] == 0x00 {
+            self.replay = None;
+        } else {
+            let mut replay = Replay::default();
+            replay.parse(file)?;
+            self.replay = Some(replay);
+        }
+
+        Ok(())
+    }
+}
+
+/// Implementations for parsing v0x01 highscore entries, which do not contain a replay.
+impl PlayerHighscoresWrapper {
+    /// Parse a highscore entry with version 0x01.
+    fn parse_v1<T: Read>(&mut self, file: &mut T) -> Result<(), HighscoreParseError> {
+        let timestamp = bincode::deserialize_from::<&mut T, i64>(file)?;
+        self.timestamp = timestamp;
+
+        let mut highscore = Highscore::default();
+        highscore.parse(file)?;
+        self.highscore = highscore;
+        self.replay = None;
+
         Ok(())
     }
 }
@@ -181,7 +264,11 @@ impl Ord for PlayerHighscoresWrapper {
         match (self.highscore.coins() as i32 - self.highscore.moves() as i32)
             .cmp(&(other.highscore.coins() as i32 - other.highscore.moves() as i32))
         {
-            Ordering::Equal => self.timestamp.cmp(&other.timestamp),
+            // Fewer deaths are better
+            Ordering::Equal => match other.highscore.deaths().cmp(&self.highscore.deaths()) {
+                Ordering::Equal => self.timestamp.cmp(&other.timestamp),
+                x => x,
+            },
             x => x,
         }
     }
@@ -189,8 +276,15 @@ impl Ord for PlayerHighscoresWrapper {
 
 #[cfg(test)]
 mod tests {
+    use crate::exodus_serializable::ExodusSerializable;
     use crate::highscores::highscore::Highscore;
     use crate::highscores::player_highscores::{PlayerHighscores, PlayerHighscoresWrapper};
+    use crate::highscores::verification::HighscoreVerification;
+    use crate::replay::Replay;
+    use crate::simulation::PlayerAction;
+    use crate::tiles::Tile;
+    use crate::world::presets;
+    use bytebuffer::ByteBuffer;
     use std::cmp::Ordering;
     use std::time::Duration;
 
@@ -199,10 +293,12 @@ mod tests {
         let good_score = PlayerHighscoresWrapper {
             timestamp: 0,
             highscore: Highscore::new(5, 4),
+            replay: None,
         };
         let bad_score = PlayerHighscoresWrapper {
             timestamp: 0,
             highscore: Highscore::new(6, 4),
+            replay: None,
         };
         assert_eq!(Ordering::Greater, good_score.cmp(&bad_score));
         assert!(good_score > bad_score);
@@ -213,24 +309,43 @@ mod tests {
         let good_score = PlayerHighscoresWrapper {
             timestamp: 1,
             highscore: Highscore::new(6, 4),
+            replay: None,
         };
         let bad_score = PlayerHighscoresWrapper {
             timestamp: 0,
             highscore: Highscore::new(6, 4),
+            replay: None,
         };
         assert_eq!(Ordering::Greater, good_score.cmp(&bad_score));
         assert!(good_score > bad_score);
     }
 
+    #[test]
+    fn test_total_ordering_fewer_deaths() {
+        let good_score = PlayerHighscoresWrapper {
+            timestamp: 0,
+            highscore: Highscore::new(6, 4).with_deaths(1),
+            replay: None,
+        };
+        let bad_score = PlayerHighscoresWrapper {
+            timestamp: 1,
+            highscore: Highscore::new(6, 4).with_deaths(2),
+            replay: None,
+        };
+        assert_eq!(Ordering::Greater, good_score.cmp(&bad_score));
+    }
+
     #[test]
     fn test_total_ordering_equal() {
         let good_score = PlayerHighscoresWrapper {
             timestamp: 0,
             highscore: Highscore::new(5, 0),
+            replay: None,
         };
         let equal_score = PlayerHighscoresWrapper {
             timestamp: 0,
             highscore: Highscore::new(5, 0),
+            replay: None,
         };
         assert_eq!(Ordering::Equal, good_score.cmp(&equal_score));
     }
@@ -308,4 +423,77 @@ mod tests {
         let player_highscores = PlayerHighscores::new("Thorsten".to_string());
         assert!(player_highscores.best().is_none());
     }
+
+    #[test]
+    fn test_parse_v1_wrapper() {
+        let mut buf = ByteBuffer::new();
+        buf.write_u8(0x01);
+        buf.write_bytes(&bincode::serialize(&1337i64).unwrap());
+        Highscore::new(5, 2).serialize(&mut buf).unwrap();
+        buf.set_rpos(0);
+        let mut wrapper = PlayerHighscoresWrapper::default();
+        wrapper.parse(&mut buf).unwrap();
+        assert_eq!(1337, wrapper.timestamp);
+        assert_eq!(Highscore::new(5, 2), wrapper.highscore);
+        assert!(wrapper.replay.is_none());
+    }
+
+    #[test]
+    fn test_serialize_wrapper_with_replay() {
+        let mut replay = Replay::new([0x42; 32]);
+        replay.push(PlayerAction::JumpRight).push(PlayerAction::Up);
+        let wrapper = PlayerHighscoresWrapper {
+            timestamp: 42,
+            highscore: Highscore::new(2, 0),
+            replay: Some(replay),
+        };
+        let mut buf = ByteBuffer::new();
+        wrapper.serialize(&mut buf).unwrap();
+        buf.set_rpos(0);
+        let mut result = PlayerHighscoresWrapper::default();
+        result.parse(&mut buf).unwrap();
+        assert_eq!(wrapper, result);
+    }
+
+    #[test]
+    fn test_serialize_wrapper_without_replay() {
+        let wrapper = PlayerHighscoresWrapper {
+            timestamp: 42,
+            highscore: Highscore::new(2, 0),
+            replay: None,
+        };
+        let mut buf = ByteBuffer::new();
+        wrapper.serialize(&mut buf).unwrap();
+        buf.set_rpos(0);
+        let mut result = PlayerHighscoresWrapper {
+            timestamp: 0,
+            highscore: Highscore::default(),
+            replay: Some(Replay::default()),
+        };
+        result.parse(&mut buf).unwrap();
+        assert_eq!(wrapper, result);
+    }
+
+    #[test]
+    fn test_player_highscores_verify_best() {
+        let mut world = presets::map_with_border(5, 3);
+        world.set(3, 1, Tile::EXIT);
+        world.recompute_hash();
+        let mut replay = Replay::new(*world.hash());
+        replay.push(PlayerAction::Right).push(PlayerAction::Right);
+        let mut player_highscores = PlayerHighscores::new("Thorsten".to_string());
+        assert!(player_highscores.verify_best(&world).is_none());
+        player_highscores.store_with_replay(0, Highscore::new(2, 0), replay.clone());
+        assert_eq!(Some(&replay), player_highscores.best_replay());
+        assert_eq!(
+            Some(HighscoreVerification::Verified),
+            player_highscores.verify_best(&world)
+        );
+        player_highscores.store(1, Highscore::new(1, 0));
+        assert!(player_highscores.best_replay().is_none());
+        assert_eq!(
+            Some(HighscoreVerification::Unverified),
+            player_highscores.verify_best(&world)
+        );
+    }
 }
@@ -0,0 +1,235 @@
+use crate::highscores::highscore::Highscore;
+use crate::replay::Replay;
+use crate::simulation::SimulationState;
+use crate::world::GameWorld;
+
+/// The result of verifying a highscore against the replay that has been stored with it
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum HighscoreVerification {
+    /// The replay beats the map and reproduces the claimed moves, deaths and coins
+    Verified,
+    /// No replay has been stored with the highscore, i.e., the highscore cannot be verified
+    Unverified,
+    /// The replay has been recorded in a different map or does not reproduce the claimed highscore
+    Invalid,
+}
+
+impl HighscoreVerification {
+    /// Check if the highscore has been verified successfully
+    pub fn is_verified(&self) -> bool {
+        matches!(self, HighscoreVerification::Verified)
+    }
+}
+
+impl Highscore {
+    /// Verify this highscore by playing back the given replay in the given world.
+    /// The highscore is verified if the replay reaches the exit with exactly the claimed number of
+    /// moves, deaths and coins, including the coins purchased at vending machines.
+    ///
+    /// ```rust
+    /// use libexodus::highscores::highscore::Highscore;
+    /// use libexodus::highscores::verification::HighscoreVerification;
+    /// use libexodus::replay::Replay;
+    /// use libexodus::simulation::PlayerAction;
+    /// use libexodus::tiles::Tile;
+    /// use libexodus::world::presets;
+    /// let mut world = presets::map_with_border(5, 3);
+    /// world.set(3, 1, Tile::EXIT);
+    /// world.recompute_hash();
+    /// let mut replay = Replay::new(*world.hash());
+    /// replay.push(PlayerAction::Right).push(PlayerAction::Right);
+    /// let highscore = Highscore::new(2, 0);
+    /// assert_eq!(HighscoreVerification::Verified, highscore.verify(Some(&replay), &world));
+    /// assert_eq!(HighscoreVerification::Unverified, highscore.verify(None, &world));
+    /// let cheated = Highscore::new(1, 0);
+    /// assert_eq!(HighscoreVerification::Invalid, cheated.verify(Some(&replay), &world));
+    /// ```
+    pub fn verify(&self, replay: Option<&Replay>, world: &GameWorld) -> HighscoreVerification {
+        let Some(replay) = replay else {
+            return HighscoreVerification::Unverified;
+        };
+        let Some(simulation) = replay.simulate(world) else {
+            return HighscoreVerification::Invalid;
+        };
+        if simulation.state() == SimulationState::Won
+            && simulation.moves() == self.moves() as usize
+            && simulation.deaths() == self.deaths() as usize
+            && simulation.coins() == self.coins() as usize
+        {
+            HighscoreVerification::Verified
+        } else {
+            HighscoreVerification::Invalid
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::highscores::highscore::Highscore;
+    use crate::highscores::verification::HighscoreVerification;
+    use crate::replay::Replay;
+    use crate::simulation::{PlayerAction, Purchase};
+    use crate::tiles::Tile;
+    use crate::world::{GameWorld, presets};
+
+    fn test_world() -> GameWorld {
+        let mut world = presets::map_with_border(8, 3);
+        world.set(1, 1, Tile::PLAYERSPAWN);
+        world.set(2, 1, Tile::COIN);
+        world.set(3, 1, Tile::STARCRYSTAL);
+        world.set(5, 1, Tile::EXIT);
+        world.recompute_hash();
+        world
+    }
+
+    fn test_replay(world: &GameWorld) -> Replay {
+        let mut replay = Replay::new(*world.hash());
+        for _ in 0..4 {
+            replay.push(PlayerAction::Right);
+        }
+        replay
+    }
+
+    #[test]
+    fn test_verify_valid_highscore() {
+        let world = test_world();
+        let replay = test_replay(&world);
+        assert!(
+            Highscore::new(4, 1)
+                .verify(Some(&replay), &world)
+                .is_verified()
+        );
+    }
+
+    #[test]
+    fn test_verify_highscore_with_purchased_coins() {
+        let mut world = presets::map_with_border(8, 3);
+        world.set(2, 1, Tile::PLAYERSPAWN);
+        world.set(1, 1, Tile::VENDINGMACHINER);
+        world.set(3, 1, Tile::STARCRYSTAL);
+        world.set(5, 1, Tile::EXIT);
+        world.recompute_hash();
+        let mut replay = Replay::new(*world.hash());
+        // Collect the crystal and walk into the vending machine
+        replay
+            .push(PlayerAction::Right)
+            .push(PlayerAction::Left)
+            .push(PlayerAction::Left);
+        let mut replay_without_purchase = replay.clone();
+        replay.push_purchase(Purchase::FiveCoins);
+        for replay in [&mut replay, &mut replay_without_purchase] {
+            replay
+                .push(PlayerAction::Right)
+                .push(PlayerAction::Right)
+                .push(PlayerAction::Right);
+        }
+        assert!(
+            Highscore::new(6, 5)
+                .verify(Some(&replay), &world)
+                .is_verified()
+        );
+        assert_eq!(
+            HighscoreVerification::Invalid,
+            Highscore::new(6, 6).verify(Some(&replay), &world)
+        );
+        // Coins that have not been purchased in the replay are rejected
+        assert_eq!(
+            HighscoreVerification::Invalid,
+            Highscore::new(6, 5).verify(Some(&replay_without_purchase), &world)
+        );
+        assert!(
+            Highscore::new(6, 0)
+                .verify(Some(&replay_without_purchase), &world)
+                .is_verified()
+        );
+    }
+
+    #[test]
+    fn test_verify_wrong_coins() {
+        let world = test_world();
+        let replay = test_replay(&world);
+        assert_eq!(
+            HighscoreVerification::Invalid,
+            Highscore::new(4, 0).verify(Some(&replay), &world)
+        );
+    }
+
+    #[test]
+    fn test_verify_wrong_moves() {
+        let world = test_world();
+        let replay = test_replay(&world);
+        assert_eq!(
+            HighscoreVerification::Invalid,
+            Highscore::new(3, 1).verify(Some(&replay), &world)
+        );
+        assert_eq!(
+            HighscoreVerification::Invalid,
+            Highscore::new(5, 1).verify(Some(&replay), &world)
+        );
+    }
+
+    #[test]
+    fn test_verify_deaths() {
+        let mut world = presets::map_with_border(8, 5);
+        world.set(1, 1, Tile::PLAYERSPAWN);
+        world.set(3, 1, Tile::SPIKES);
+        world.set(6, 1, Tile::EXIT);
+        world.set_lives(2);
+        world.recompute_hash();
+        let mut replay = Replay::new(*world.hash());
+        // Die once on the spikes, then jump over them after respawning
+        for action in [
+            PlayerAction::Right,
+            PlayerAction::Right,
+            PlayerAction::Right,
+            PlayerAction::JumpRight,
+            PlayerAction::Right,
+            PlayerAction::Right,
+        ] {
+            replay.push(action);
+        }
+        assert!(
+            Highscore::new(6, 0)
+                .with_deaths(1)
+                .verify(Some(&replay), &world)
+                .is_verified()
+        );
+        assert_eq!(
+            HighscoreVerification::Invalid,
+            Highscore::new(6, 0).verify(Some(&replay), &world)
+        );
+    }
+
+    #[test]
+    fn test_verify_replay_does_not_win() {
+        let world = test_world();
+        let mut replay = Replay::new(*world.hash());
+        replay.push(PlayerAction::Right);
+        assert_eq!(
+            HighscoreVerification::Invalid,
+            Highscore::new(1, 1).verify(Some(&replay), &world)
+        );
+    }
+
+    #[test]
+    fn test_verify_replay_of_other_map() {
+        let world = test_world();
+        let mut other_world = test_world();
+        other_world.set(6, 1, Tile::COIN);
+        other_world.recompute_hash();
+        let replay = test_replay(&other_world);
+        assert_eq!(
+            HighscoreVerification::Invalid,
+            Highscore::new(4, 1).verify(Some(&replay), &world)
+        );
+    }
+
+    #[test]
+    fn test_verify_without_replay() {
+        let world = test_world();
+        assert_eq!(
+            HighscoreVerification::Unverified,
+            Highscore::new(4, 1).verify(None, &world)
+        );
+    }
+}
@@ -2,10 +2,13 @@ pub mod campaign;
 pub mod config;
 pub mod directions;
 pub mod directories;
+pub mod enemies;
 pub mod exodus_serializable;
 pub mod highscores;
 pub mod movement;
 pub mod player;
+pub mod replay;
+pub mod simulation;
 pub mod tiles;
 pub mod tilesets;
 pub mod world;
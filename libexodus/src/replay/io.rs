@@ -0,0 +1,366 @@
+use crate::exodus_serializable::ExodusSerializable;
+use crate::replay::Replay;
+use crate::replay::io_error::ReplayParseError;
+use crate::simulation::{PlayerAction, Purchase};
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+//00000000: 4578 6f64 7573 5265 706c 6179            ExodusReplay
+pub(crate) const MAGICBYTES: [u8; 12] = [
+    0x45, 0x78, 0x6f, 0x64, 0x75, 0x73, 0x52, 0x65, 0x70, 0x6c, 0x61, 0x79,
+];
+
+///
+/// Definition of the binary Replay File Format:
+///
+/// 1. Magic Bytes 0x 45 78 6f 64 75 73 52 65 70 6c 61 79
+///
+/// 2. Replay Format Version (current version: 0x02)
+///
+/// 3. Hash of the map the replay was recorded in, as 32-byte octet
+///
+/// 4. Number of actions, encoded with bincode crate
+///
+/// 5. All actions in the order they were executed, one byte per action
+///
+/// 6. Number of purchases, encoded with bincode crate
+///
+/// 7. All purchases in the order they were made, each consisting of the number of actions
+///    executed before the purchase, encoded with bincode crate, and one byte for the item
+///
+/// Version 0x01 only contains the parts 1-5.
+impl Replay {
+    /// Load a replay from the given file
+    pub fn load_from_file(path: &Path) -> Result<Self, ReplayParseError> {
+        let file = OpenOptions::new().read(true).open(path)?;
+        let mut buf = BufReader::new(file);
+        let mut ret = Replay::default();
+        ret.parse(&mut buf)?;
+        Ok(ret)
+    }
+    /// Save the replay to the given file
+    pub fn save_to_file(&self, path: &Path) -> Result<(), ReplayParseError> {
+        let file: File = OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .write(true)
+            .open(path)?;
+        let mut buf = BufWriter::new(file);
+        self.serialize(&mut buf)?;
+        Ok(())
+    }
+}
+
+/// Implementation for Serializer
+impl ExodusSerializable for Replay {
+    const CURRENT_VERSION: u8 = 0x02;
+    type ParseError = ReplayParseError;
+
+    fn serialize<T: Write>(&self, file: &mut T) -> Result<(), Self::ParseError> {
+        // Write magic bytes
+        file.write_all(&MAGICBYTES)?;
+
+        // Write Replay Version
+        file.write_all(&[Self::CURRENT_VERSION])?;
+
+        // Write Map Hash
+        file.write_all(&self.map_hash)?;
+
+        // Write Actions
+        let b_length = bincode::serialize(&self.actions.len())?;
+        file.write_all(&b_length)?;
+        let actions: Vec<u8> = self.actions.iter().map(|a| a.to_bytes()).collect();
+        file.write_all(&actions)?;
+
+        // Write Purchases
+        let b_length = bincode::serialize(&self.purchases.len())?;
+        file.write_all(&b_length)?;
+        for (index, item) in &self.purchases {
+            let b_index = bincode::serialize(index)?;
+            file.write_all(&b_index)?;
+            file.write_all(&[item.to_bytes()])?;
+        }
+
+        Ok(())
+    }
+
+    fn parse<T: Read>(&mut self, file: &mut T) -> Result<(), Self::ParseError> {
+        // Parse Magic Bytes
+        let mut buf: [u8; MAGICBYTES.len()] = [0; MAGICBYTES.len()];
+        file.read_exact(&mut buf)?;
+        if buf != MAGICBYTES {
+            return Err(Self::ParseError::InvalidMagicBytes {
+                expected: MAGICBYTES,
+                actual: buf,
+            });
+        }
+
+        // Parse Replay Format
+        let mut buf: [u8; 1] = [0; 1];
+        file.read_exact(&mut buf)?;
+        match buf[0] {
+            0x01 => self.parse_v1(file),
+            Self::CURRENT_VERSION => self.parse_current_version(file),
+            // Add older versions here
+            _ => {
+                return Err(Self::ParseError::InvalidVersion {
+                    invalid_version: buf[0],
+                });
+            },
+        }?;
+
+        Ok(())
+    }
+
+    fn parse_current_version<T: Read>(&mut self, file: &mut T) -> Result<(), Self::ParseError> {
+        // The current version extends version 1 by the purchases
+        self.parse_v1(file)?;
+
+        // Parse Purchases
+        let length = bincode::deserialize_from::<&mut T, usize>(file)?;
+        let mut buf: [u8; 1] = [0; 1];
+        for _ in 0..length {
+            let index = bincode::deserialize_from::<&mut T, usize>(file)?;
+            file.read_exact(&mut buf)?;
+            let item = Purchase::from_bytes(buf[0]).ok_or(ReplayParseError::InvalidPurchase {
+                purchase_bytes: buf[0],
+            })?;
+            self.purchases.push((index, item));
+        }
+        Ok(())
+    }
+}
+
+impl Replay {
+    /// Parse a replay with version 0x01, which does not contain any purchases.
+    /// The file read position must be already behind the version byte
+    fn parse_v1<T: Read>(&mut self, file: &mut T) -> Result<(), ReplayParseError> {
+        // Parse Map Hash
+        file.read_exact(&mut self.map_hash)?;
+
+        // Parse Actions
+        let length = bincode::deserialize_from::<&mut T, usize>(file)?;
+        self.actions.clear();
+        self.purchases.clear();
+        let mut buf: [u8; 1] = [0; 1];
+        for _ in 0..length {
+            file.read_exact(&mut buf)?;
+            let action =
+                PlayerAction::from_bytes(buf[0]).ok_or(ReplayParseError::InvalidAction {
+                    action_bytes: buf[0],
+                })?;
+            self.actions.push(action);
+        }
+        Ok(())
+    }
+}
+
+impl PlayerAction {
+    /// Convert this player action into its byte representation for the replay file format
+    pub const fn to_bytes(&self) -> u8 {
+        match self {
+            PlayerAction::Left => 0x00,
+            PlayerAction::Right => 0x01,
+            PlayerAction::Up => 0x02,
+            PlayerAction::Down => 0x03,
+            PlayerAction::JumpLeft => 0x04,
+            PlayerAction::JumpRight => 0x05,
+        }
+    }
+    /// Convert the given byte into a player action, or return None if the byte is invalid
+    pub const fn from_bytes(byte: u8) -> Option<Self> {
+        match byte {
+            0x00 => Some(PlayerAction::Left),
+            0x01 => Some(PlayerAction::Right),
+            0x02 => Some(PlayerAction::Up),
+            0x03 => Some(PlayerAction::Down),
+            0x04 => Some(PlayerAction::JumpLeft),
+            0x05 => Some(PlayerAction::JumpRight),
+            _ => None,
+        }
+    }
+}
+
+impl Purchase {
+    /// Convert this purchase into its byte representation for the replay file format
+    pub const fn to_bytes(&self) -> u8 {
+        match self {
+            Purchase::FiveCoins => 0x00,
+            Purchase::Key => 0x01,
+        }
+    }
+    /// Convert the given byte into a purchase, or return None if the byte is invalid
+    pub const fn from_bytes(byte: u8) -> Option<Self> {
+        match byte {
+            0x00 => Some(Purchase::FiveCoins),
+            0x01 => Some(Purchase::Key),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::exodus_serializable::ExodusSerializable;
+    use crate::replay::Replay;
+    use crate::replay::io::MAGICBYTES;
+    use crate::replay::io_error::ReplayParseError;
+    use crate::simulation::{PlayerAction, Purchase};
+    use bytebuffer::ByteBuffer;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_bidirectional_serialization_for_actions() {
+        for action in PlayerAction::iter() {
+            assert_eq!(Some(action), PlayerAction::from_bytes(action.to_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_bidirectional_serialization_for_purchases() {
+        for item in Purchase::iter() {
+            assert_eq!(Some(item), Purchase::from_bytes(item.to_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_write_and_read_empty_replay() {
+        let replay = Replay::new([0xab; 32]);
+        let mut buf = ByteBuffer::new();
+        replay.serialize(&mut buf).unwrap();
+        buf.set_rpos(0);
+        let mut result = Replay::default();
+        result.parse(&mut buf).unwrap();
+        assert_eq!(replay, result);
+    }
+
+    #[test]
+    fn test_write_and_read_replay() {
+        let mut replay = Replay::new([0x13; 32]);
+        for action in PlayerAction::iter() {
+            replay.push(action);
+        }
+        replay.push(PlayerAction::Right);
+        let mut buf = ByteBuffer::new();
+        replay.serialize(&mut buf).unwrap();
+        buf.set_rpos(0);
+        let mut result = Replay::new([0x00; 32]);
+        result.push(PlayerAction::Left);
+        result.parse(&mut buf).unwrap();
+        assert_eq!(replay, result);
+    }
+
+    #[test]
+    fn test_write_and_read_replay_with_purchases() {
+        let mut replay = Replay::new([0x13; 32]);
+        replay
+            .push_purchase(Purchase::Key)
+            .push(PlayerAction::Right)
+            .push(PlayerAction::Left)
+            .push_purchase(Purchase::FiveCoins);
+        let mut buf = ByteBuffer::new();
+        replay.serialize(&mut buf).unwrap();
+        buf.set_rpos(0);
+        let mut result = Replay::default();
+        result.parse(&mut buf).unwrap();
+        assert_eq!(replay, result);
+        assert_eq!(
+            &[(0, Purchase::Key), (2, Purchase::FiveCoins)],
+            result.purchases()
+        );
+    }
+
+    #[test]
+    fn test_read_v1_replay() {
+        let mut data = MAGICBYTES.to_vec();
+        data.push(0x01);
+        data.extend_from_slice(&[0x13; 32]);
+        data.extend_from_slice(&bincode::serialize(&2usize).unwrap());
+        data.extend_from_slice(&[PlayerAction::Up.to_bytes(), PlayerAction::Left.to_bytes()]);
+        let mut buf = ByteBuffer::from_bytes(&data);
+        let mut result = Replay::default();
+        result.push_purchase(Purchase::Key);
+        result.parse(&mut buf).unwrap();
+        let mut expected = Replay::new([0x13; 32]);
+        expected.push(PlayerAction::Up).push(PlayerAction::Left);
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_invalid_purchase() {
+        let mut data = MAGICBYTES.to_vec();
+        data.push(Replay::CURRENT_VERSION);
+        data.extend_from_slice(&[0x00; 32]);
+        data.extend_from_slice(&bincode::serialize(&0usize).unwrap());
+        data.extend_from_slice(&bincode::serialize(&1usize).unwrap());
+        data.extend_from_slice(&bincode::serialize(&0usize).unwrap());
+        data.push(0xee);
+        let mut buf = ByteBuffer::from_bytes(&data);
+        let result = Replay::default().parse(&mut buf);
+        assert!(matches!(
+            result.unwrap_err(),
+            ReplayParseError::InvalidPurchase {
+                purchase_bytes: 0xee
+            }
+        ));
+    }
+
+    #[test]
+    fn test_invalid_magic_bytes() {
+        let mut buf = ByteBuffer::from_bytes(&[0x00; 50]);
+        let result = Replay::default().parse(&mut buf);
+        assert_eq!(
+            ReplayParseError::InvalidMagicBytes {
+                expected: MAGICBYTES,
+                actual: [0x00; MAGICBYTES.len()],
+            }
+            .numeric_error(),
+            result.unwrap_err().numeric_error()
+        );
+    }
+
+    #[test]
+    fn test_invalid_version() {
+        let mut data = MAGICBYTES.to_vec();
+        data.push(0xff);
+        let mut buf = ByteBuffer::from_bytes(&data);
+        let result = Replay::default().parse(&mut buf);
+        assert!(matches!(
+            result.unwrap_err(),
+            ReplayParseError::InvalidVersion {
+                invalid_version: 0xff
+            }
+        ));
+    }
+
+    #[test]
+    fn test_invalid_action() {
+        let mut data = MAGICBYTES.to_vec();
+        data.push(Replay::CURRENT_VERSION);
+        data.extend_from_slice(&[0x00; 32]);
+        data.extend_from_slice(&bincode::serialize(&2usize).unwrap());
+        data.extend_from_slice(&[PlayerAction::Up.to_bytes(), 0xee]);
+        let mut buf = ByteBuffer::from_bytes(&data);
+        let result = Replay::default().parse(&mut buf);
+        assert!(matches!(
+            result.unwrap_err(),
+            ReplayParseError::InvalidAction { action_bytes: 0xee }
+        ));
+    }
+
+    #[test]
+    fn test_missing_actions() {
+        let mut data = MAGICBYTES.to_vec();
+        data.push(Replay::CURRENT_VERSION);
+        data.extend_from_slice(&[0x00; 32]);
+        data.extend_from_slice(&bincode::serialize(&3usize).unwrap());
+        data.push(PlayerAction::Up.to_bytes());
+        let mut buf = ByteBuffer::from_bytes(&data);
+        let result = Replay::default().parse(&mut buf);
+        assert!(matches!(
+            result.unwrap_err(),
+            ReplayParseError::IOError { .. }
+        ));
+    }
+}
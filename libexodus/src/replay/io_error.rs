@@ -0,0 +1,85 @@
+use crate::replay::io::MAGICBYTES;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug)]
+#[repr(u8)]
+/// An error that might be thrown in a Replay Parser
+pub enum ReplayParseError {
+    InvalidMagicBytes {
+        expected: [u8; MAGICBYTES.len()],
+        actual: [u8; MAGICBYTES.len()],
+    },
+    InvalidVersion {
+        invalid_version: u8,
+    },
+    IOError {
+        io_error: std::io::Error,
+    },
+    BincodeError {
+        bincode_error: Box<bincode::ErrorKind>,
+    },
+    InvalidAction {
+        action_bytes: u8,
+    },
+    InvalidPurchase {
+        purchase_bytes: u8,
+    },
+}
+
+impl Display for ReplayParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayParseError::InvalidMagicBytes { expected, actual } => write!(
+                f,
+                "Invalid Magic Bytes in Replay File! Expected: {:02x?} Got: {:02x?}",
+                expected, actual
+            ),
+            ReplayParseError::InvalidVersion { invalid_version } => {
+                write!(f, "Invalid Replay Version: 0x{:02x}", invalid_version)
+            },
+            ReplayParseError::IOError { io_error } => std::fmt::Display::fmt(&io_error, f),
+            ReplayParseError::BincodeError { bincode_error } => {
+                std::fmt::Display::fmt(&bincode_error, f)
+            },
+            ReplayParseError::InvalidAction { action_bytes } => write!(
+                f,
+                "Action Byte not recognized as valid player action: 0x{:02x}",
+                action_bytes
+            ),
+            ReplayParseError::InvalidPurchase { purchase_bytes } => write!(
+                f,
+                "Purchase Byte not recognized as valid vending machine item: 0x{:02x}",
+                purchase_bytes
+            ),
+        }
+    }
+}
+
+impl Error for ReplayParseError {}
+
+impl From<std::io::Error> for ReplayParseError {
+    fn from(io_error: std::io::Error) -> Self {
+        ReplayParseError::IOError { io_error }
+    }
+}
+
+impl From<Box<bincode::ErrorKind>> for ReplayParseError {
+    fn from(bincode_error: Box<bincode::ErrorKind>) -> Self {
+        ReplayParseError::BincodeError { bincode_error }
+    }
+}
+
+impl ReplayParseError {
+    /// Get the numeric error to compare the error kind. Discards all data that is carried by this error
+    pub fn numeric_error(&self) -> u8 {
+        match self {
+            ReplayParseError::InvalidMagicBytes { .. } => 0,
+            ReplayParseError::InvalidVersion { .. } => 1,
+            ReplayParseError::IOError { .. } => 2,
+            ReplayParseError::BincodeError { .. } => 3,
+            ReplayParseError::InvalidAction { .. } => 4,
+            ReplayParseError::InvalidPurchase { .. } => 5,
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use crate::player::Player;
+use crate::simulation::{PlayerAction, Purchase, Simulation};
+use crate::world::GameWorld;
+
+pub mod io;
+pub mod io_error;
+
+/// A recording of all actions a player made while playing a map.
+/// Since the game rules are deterministic, playing back the actions in the same map always
+/// leads to the same result.
+/// Purchases at vending machines are recorded together with the number of actions that have been
+/// executed before the purchase.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Replay {
+    map_hash: [u8; 32],
+    actions: Vec<PlayerAction>,
+    purchases: Vec<(usize, Purchase)>,
+}
+
+impl Replay {
+    /// Create a new empty replay for the map with the given hash
+    pub fn new(map_hash: [u8; 32]) -> Self {
+        Replay {
+            map_hash,
+            actions: vec![],
+            purchases: vec![],
+        }
+    }
+    /// Get the hash of the map this replay was recorded in
+    pub fn map_hash(&self) -> &[u8; 32] {
+        &self.map_hash
+    }
+    /// Get all recorded actions in the order they were executed
+    pub fn actions(&self) -> &[PlayerAction] {
+        &self.actions
+    }
+    /// Record the given action
+    pub fn push(&mut self, action: PlayerAction) -> &mut Self {
+        self.actions.push(action);
+        self
+    }
+    /// Get all recorded purchases in the order they were made, each together with the number of
+    /// actions that have been executed before the purchase
+    pub fn purchases(&self) -> &[(usize, Purchase)] {
+        &self.purchases
+    }
+    /// Record the purchase of the given item after the most recently recorded action
+    pub fn push_purchase(&mut self, item: Purchase) -> &mut Self {
+        self.purchases.push((self.actions.len(), item));
+        self
+    }
+    /// Get the number of recorded actions, i.e., the number of moves
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+    /// Check if this replay does not contain any actions
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+    /// Play back this replay in the given world and return the simulation after all actions
+    /// and purchases have been executed.
+    /// Returns None, if the hash of the world does not match the hash this replay was recorded in,
+    /// or if one of the recorded purchases cannot be made.
+    ///
+    /// ```rust
+    /// use libexodus::replay::Replay;
+    /// use libexodus::simulation::{PlayerAction, SimulationState};
+    /// use libexodus::tiles::Tile;
+    /// use libexodus::world::presets;
+    /// let mut world = presets::map_with_border(5, 3);
+    /// world.set(3, 1, Tile::EXIT);
+    /// world.recompute_hash();
+    /// let mut replay = Replay::new(*world.hash());
+    /// replay.push(PlayerAction::Right).push(PlayerAction::Right);
+    /// let simulation = replay.simulate(&world).unwrap();
+    /// assert_eq!(SimulationState::Won, simulation.state());
+    /// assert_eq!(2, simulation.moves());
+    /// ```
+    pub fn simulate(&self, world: &GameWorld) -> Option<Simulation> {
+        if world.hash() != &self.map_hash {
+            return None;
+        }
+        let mut simulation = Simulation::new(world.clone(), Player::new());
+        let mut purchases = self.purchases.iter().peekable();
+        for executed_actions in 0..=self.actions.len() {
+            while let Some((_, item)) = purchases.next_if(|(index, _)| *index == executed_actions) {
+                if !simulation.purchase(*item) {
+                    return None;
+                }
+            }
+            if let Some(action) = self.actions.get(executed_actions) {
+                simulation.step(*action);
+            }
+        }
+        // Purchases that have been recorded out of order are never made
+        purchases.next().is_none().then_some(simulation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::replay::Replay;
+    use crate::simulation::{PlayerAction, Purchase, SimulationState};
+    use crate::tiles::Tile;
+    use crate::world::GameWorld;
+    use crate::world::presets;
+
+    fn test_world() -> GameWorld {
+        let mut world = presets::map_with_border(8, 6);
+        world.set(1, 1, Tile::PLAYERSPAWN);
+        world.set(3, 1, Tile::COIN);
+        world.set(6, 1, Tile::EXIT);
+        world.recompute_hash();
+        world
+    }
+
+    #[test]
+    fn test_new_replay_is_empty() {
+        let replay = Replay::new([0x42; 32]);
+        assert!(replay.is_empty());
+        assert_eq!(0, replay.len());
+        assert_eq!(&[0x42; 32], replay.map_hash());
+    }
+
+    #[test]
+    fn test_simulate_replay() {
+        let world = test_world();
+        let mut replay = Replay::new(*world.hash());
+        for _ in 0..5 {
+            replay.push(PlayerAction::Right);
+        }
+        let simulation = replay.simulate(&world).unwrap();
+        assert_eq!(SimulationState::Won, simulation.state());
+        assert_eq!(5, simulation.moves());
+        assert_eq!(1, simulation.coins());
+    }
+
+    #[test]
+    fn test_simulate_replay_is_deterministic() {
+        let world = test_world();
+        let mut replay = Replay::new(*world.hash());
+        replay
+            .push(PlayerAction::JumpRight)
+            .push(PlayerAction::Left)
+            .push(PlayerAction::Up)
+            .push(PlayerAction::JumpRight);
+        let first = replay.simulate(&world).unwrap();
+        let second = replay.simulate(&world).unwrap();
+        assert_eq!(first.position(), second.position());
+        assert_eq!(first.state(), second.state());
+        assert_eq!(first.coins(), second.coins());
+    }
+
+    #[test]
+    fn test_simulate_replay_with_purchase() {
+        let mut world = test_world();
+        world.set(2, 1, Tile::STARCRYSTAL);
+        world.set(4, 1, Tile::VENDINGMACHINEL);
+        world.recompute_hash();
+        let mut replay = Replay::new(*world.hash());
+        replay
+            .push(PlayerAction::Right)
+            .push(PlayerAction::Right)
+            .push(PlayerAction::Right)
+            .push_purchase(Purchase::FiveCoins);
+        let simulation = replay.simulate(&world).unwrap();
+        assert_eq!(6, simulation.coins());
+        assert_eq!(0, simulation.crystals());
+        assert_eq!(&[(3, Purchase::FiveCoins)], replay.purchases());
+        // The player cannot afford a second purchase
+        replay.push_purchase(Purchase::FiveCoins);
+        assert!(replay.simulate(&world).is_none());
+    }
+
+    #[test]
+    fn test_simulate_replay_with_purchase_without_vending_machine() {
+        let world = test_world();
+        let mut replay = Replay::new(*world.hash());
+        replay
+            .push(PlayerAction::Right)
+            .push_purchase(Purchase::Key);
+        assert!(replay.simulate(&world).is_none());
+    }
+
+    #[test]
+    fn test_simulate_replay_in_wrong_map() {
+        let world = test_world();
+        let replay = Replay::new([0u8; 32]);
+        assert!(replay.simulate(&world).is_none());
+    }
+}
@@ -0,0 +1,96 @@
+use crate::movement::Movement;
+use crate::tiles::TileKind;
+use crate::world::GameWorld;
+use strum_macros::{EnumCount as EnumCountMacro, EnumIter};
+
+/// An action the player can take while playing a map, i.e., a single key press.
+/// Each action counts as exactly one move.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, EnumIter, EnumCountMacro)]
+pub enum PlayerAction {
+    /// Walk one tile to the left
+    Left,
+    /// Walk one tile to the right
+    Right,
+    /// Jump three tiles high, or climb up one tile if the player is on a ladder
+    Up,
+    /// Move one tile down, e.g., on a ladder
+    Down,
+    /// Jump two tiles high and two tiles to the left
+    JumpLeft,
+    /// Jump two tiles high and two tiles to the right
+    JumpRight,
+}
+
+impl PlayerAction {
+    /// Get the direction the player faces after executing this action.
+    /// Returns `Some(true)` for right, `Some(false)` for left and `None`, if the player does not turn.
+    pub fn faces_right(&self) -> Option<bool> {
+        match self {
+            PlayerAction::Left | PlayerAction::JumpLeft => Some(false),
+            PlayerAction::Right | PlayerAction::JumpRight => Some(true),
+            PlayerAction::Up | PlayerAction::Down => None,
+        }
+    }
+
+    /// Get the movements that need to be enqueued to the player's movement queue to execute this action.
+    /// The position is the current position of the player in world coordinates, and the speed is the
+    /// velocity of each movement in units per second.
+    ///
+    /// # Examples
+    /// ```
+    /// use libexodus::simulation::PlayerAction;
+    /// use libexodus::tiles::Tile;
+    /// use libexodus::world::GameWorld;
+    /// let mut world = GameWorld::new(5, 5);
+    /// assert_eq!(3, PlayerAction::Up.movements((1, 1), &world, 1.0).len());
+    /// world.set(1, 1, Tile::LADDER);
+    /// assert_eq!(1, PlayerAction::Up.movements((1, 1), &world, 1.0).len());
+    /// let moves = PlayerAction::JumpRight.movements((1, 1), &world, 1.0);
+    /// assert_eq!((3, 3), moves.last().unwrap().target);
+    /// ```
+    pub fn movements(
+        &self,
+        position: (usize, usize),
+        world: &GameWorld,
+        speed: f32,
+    ) -> Vec<Movement> {
+        let (x, y) = (position.0 as i32, position.1 as i32);
+        let mv = |velocity: (f32, f32), target: (i32, i32)| Movement {
+            velocity,
+            target,
+            is_manual: true,
+        };
+        match self {
+            PlayerAction::Left => vec![mv((-speed, 0.), (x - 1, y))],
+            PlayerAction::Right => vec![mv((speed, 0.), (x + 1, y))],
+            PlayerAction::Down => vec![mv((0., -speed), (x, y - 1))],
+            PlayerAction::Up => {
+                // We need to differentiate between ladder and NOT ladder here, this allows us to jump only 1 high on the end of a ladder
+                let on_ladder = world
+                    .get(x, y)
+                    .is_some_and(|tile| tile.kind() == TileKind::LADDER);
+                if on_ladder {
+                    vec![mv((0., speed), (x, y + 1))]
+                } else {
+                    vec![
+                        mv((0., speed), (x, y + 1)),
+                        mv((0., speed), (x, y + 2)),
+                        mv((0., speed), (x, y + 3)),
+                    ]
+                }
+            },
+            PlayerAction::JumpLeft => vec![
+                mv((0., speed), (x, y + 1)),
+                mv((0., speed), (x, y + 2)),
+                mv((-speed, 0.), (x - 1, y + 2)),
+                mv((-speed, 0.), (x - 2, y + 2)),
+            ],
+            PlayerAction::JumpRight => vec![
+                mv((0., speed), (x, y + 1)),
+                mv((0., speed), (x, y + 2)),
+                mv((speed, 0.), (x + 1, y + 2)),
+                mv((speed, 0.), (x + 2, y + 2)),
+            ],
+        }
+    }
+}
@@ -0,0 +1,1221 @@
+//! A headless implementation of the game rules.
+//! The simulation steps discrete turns, where each turn consists of exactly one [PlayerAction]
+//! that is executed until the player comes to rest, dies or exits the map.
+//! Every movement is executed as a single tile step, without any animations.
+//! After the player has come to rest, all enemies move by one tile.
+use crate::directions::Directions;
+use crate::directions::FromDirection;
+use crate::enemies::{EnemyState, leading_side};
+use crate::movement::Movement;
+use crate::player::Player;
+use crate::tiles::{CollectibleKind, InteractionKind, KeyColor, Tile, TileKind};
+use crate::world::GameWorld;
+use strum::EnumCount;
+
+mod action;
+mod purchase;
+pub mod solver;
+
+pub use action::PlayerAction;
+pub use purchase::Purchase;
+
+/// The maximum number of single-tile movements that are executed within one turn.
+/// This limit only applies to degenerate maps, e.g., where a teleport leads into itself.
+pub const MAX_MOVEMENTS_PER_TURN: usize = 1 << 16;
+
+/// The velocity used for all movements inside the simulation.
+/// Only the sign of the velocity matters, since every movement is executed as a single tile step.
+const SIMULATION_SPEED: f32 = 1.0;
+
+/// An event that occurred while simulating a turn
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SimulationEvent {
+    /// The player collected the collectible at the given position
+    Collected {
+        kind: CollectibleKind,
+        position: (usize, usize),
+    },
+    /// The player opened the door at the given position, using up one key
+    DoorOpened { position: (usize, usize) },
+    /// The player pushed the crate at `from`, which has come to rest at `to`
+    CratePushed {
+        from: (usize, usize),
+        to: (usize, usize),
+    },
+    /// The player entered the switch at the given position, toggling it and all linked blocks
+    SwitchToggled { position: (usize, usize) },
+    /// The crumbling platform at the given position has cracked, since the player stands on it
+    PlatformCracked { position: (usize, usize) },
+    /// The crumbling platform at the given position has broken after the player has left it
+    PlatformBroken { position: (usize, usize) },
+    /// The falling block at `from` has fallen down to `to`
+    BlockFell {
+        from: (usize, usize),
+        to: (usize, usize),
+    },
+    /// The player walked into the vending machine at the given position
+    VendingMachineTriggered { position: (usize, usize) },
+    /// The player stepped onto the teleport entry at `from` and was teleported to `to`
+    Teleported {
+        from: (usize, usize),
+        to: (usize, usize),
+    },
+    /// The player defeated the enemy at the given position by touching it from a harmless direction
+    EnemyDefeated { position: (usize, usize) },
+    /// The player died at the given position
+    Died { position: (usize, usize) },
+    /// The player lost a life and has been respawned at the given position
+    Respawned { position: (usize, usize) },
+    /// The player reached a new checkpoint at the given position
+    CheckpointReached { position: (usize, usize) },
+    /// The player reached the exit at the given position
+    Exited { position: (usize, usize) },
+}
+
+/// An entry of the timeline of a turn
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TimelineEntry {
+    /// The player moved by a single tile from `from` to `to`
+    PlayerMoved {
+        from: (usize, usize),
+        to: (usize, usize),
+    },
+    /// All enemies moved by one tile and are now in the given states,
+    /// in the same order as the enemies of the world
+    EnemiesMoved { states: Vec<EnemyState> },
+    /// The given event occurred
+    Event(SimulationEvent),
+}
+
+impl From<SimulationEvent> for TimelineEntry {
+    fn from(event: SimulationEvent) -> Self {
+        TimelineEntry::Event(event)
+    }
+}
+
+/// The state of a simulated game
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum SimulationState {
+    /// The game is still running and accepts new actions
+    Running,
+    /// The player has reached the exit
+    Won,
+    /// The player has lost all lives
+    Lost,
+}
+
+/// A headless game, consisting of a game world and the player inside it.
+#[derive(Clone)]
+pub struct Simulation {
+    world: GameWorld,
+    player: Player,
+    position: (usize, usize),
+    coins: usize,
+    keys: usize,
+    colored_keys: [usize; KeyColor::COUNT],
+    crystals: usize,
+    moves: usize,
+    deaths: usize,
+    respawn_point: (usize, usize),
+    state: SimulationState,
+    /// The states of all enemies, in the same order as the enemies of the world
+    enemies: Vec<EnemyState>,
+    /// True, if the player has walked into a vending machine during the last turn and has not
+    /// purchased anything yet
+    vending_machine_open: bool,
+}
+
+impl Simulation {
+    /// Start a new game in the given world.
+    /// The game state of the world is reset and the player is spawned at the player spawn.
+    /// Like in the game, the player starts falling down right away, which is executed
+    /// as part of the first call to [Simulation::step] or [Simulation::settle].
+    pub fn new(mut world: GameWorld, mut player: Player) -> Self {
+        world.reset_game_state();
+        let position = world.player_spawn();
+        Self::spawn(&mut player, position);
+        let enemies = world
+            .enemies()
+            .iter()
+            .map(|enemy| enemy.initial_state())
+            .collect();
+        Simulation {
+            world,
+            player,
+            position,
+            coins: 0,
+            keys: 0,
+            colored_keys: [0; KeyColor::COUNT],
+            crystals: 0,
+            moves: 0,
+            deaths: 0,
+            respawn_point: position,
+            state: SimulationState::Running,
+            enemies,
+            vending_machine_open: false,
+        }
+    }
+    /// Prepare the given player to be spawned at the given position, i.e., let them fall down
+    fn spawn(player: &mut Player, position: (usize, usize)) {
+        player.clear_movement_queue();
+        player.push_movement_queue(Movement {
+            velocity: (0., -SIMULATION_SPEED),
+            target: (position.0 as i32, position.1 as i32 - 1),
+            is_manual: false,
+        });
+    }
+    /// Get the simulated world, including all changes made by the player, e.g., opened doors and pushed crates
+    pub fn world(&self) -> &GameWorld {
+        &self.world
+    }
+    /// Get the simulated player
+    pub fn player(&self) -> &Player {
+        &self.player
+    }
+    /// Get the current position of the player
+    pub fn position(&self) -> (usize, usize) {
+        self.position
+    }
+    /// Get the number of coins collected so far
+    pub fn coins(&self) -> usize {
+        self.coins
+    }
+    /// Get the number of uncolored keys the player currently holds
+    pub fn keys(&self) -> usize {
+        self.keys
+    }
+    /// Get the number of keys of the given color the player currently holds
+    pub fn colored_keys(&self, color: KeyColor) -> usize {
+        self.colored_keys[color.const_to_u8() as usize]
+    }
+    /// Get the number of keys of all colors the player currently holds, indexed by color
+    pub fn all_colored_keys(&self) -> [usize; KeyColor::COUNT] {
+        self.colored_keys
+    }
+    /// Get the counter of the keys of the given color, or of the uncolored keys
+    fn keys_mut(&mut self, color: Option<KeyColor>) -> &mut usize {
+        match color {
+            None => &mut self.keys,
+            Some(color) => &mut self.colored_keys[color.const_to_u8() as usize],
+        }
+    }
+    /// Get the number of star crystals collected so far
+    pub fn crystals(&self) -> usize {
+        self.crystals
+    }
+    /// Get the number of moves (i.e., actions) the player has made
+    pub fn moves(&self) -> usize {
+        self.moves
+    }
+    /// Get the number of lives the player has lost so far
+    pub fn deaths(&self) -> usize {
+        self.deaths
+    }
+    /// Get the position at which the player is respawned after losing a life,
+    /// i.e., the last checkpoint reached or the player spawn
+    pub fn respawn_point(&self) -> (usize, usize) {
+        self.respawn_point
+    }
+    /// Get the current states of all enemies, in the same order as the enemies of the world
+    pub fn enemies(&self) -> &[EnemyState] {
+        &self.enemies
+    }
+    /// Get the state of the game
+    pub fn state(&self) -> SimulationState {
+        self.state
+    }
+    /// Check if the game is over, i.e., the player has either won or lost
+    pub fn is_over(&self) -> bool {
+        self.state != SimulationState::Running
+    }
+    /// Check if the player can purchase items, i.e., if the player has walked into a vending
+    /// machine during the last turn and has not purchased anything since
+    pub fn is_vending_machine_open(&self) -> bool {
+        self.vending_machine_open && !self.is_over()
+    }
+
+    /// Purchase the given item at the vending machine the player has walked into during the last
+    /// turn, paying with star crystals. Only a single item can be purchased per visit.
+    /// Returns false and leaves the game unchanged, if there is no open vending machine or the
+    /// player cannot afford the item.
+    ///
+    /// # Examples
+    /// ```
+    /// use libexodus::player::Player;
+    /// use libexodus::simulation::{PlayerAction, Purchase, Simulation};
+    /// use libexodus::tiles::Tile;
+    /// use libexodus::world::presets;
+    /// let mut world = presets::map_with_border(5, 3);
+    /// world.set(2, 1, Tile::STARCRYSTAL);
+    /// world.set(3, 1, Tile::VENDINGMACHINEL);
+    /// let mut simulation = Simulation::new(world, Player::new());
+    /// assert!(!simulation.purchase(Purchase::FiveCoins));
+    /// simulation.step(PlayerAction::Right);
+    /// simulation.step(PlayerAction::Right);
+    /// assert!(simulation.purchase(Purchase::FiveCoins));
+    /// assert_eq!(5, simulation.coins());
+    /// assert_eq!(0, simulation.crystals());
+    /// assert!(!simulation.purchase(Purchase::FiveCoins));
+    /// ```
+    pub fn purchase(&mut self, item: Purchase) -> bool {
+        if !self.is_vending_machine_open() || self.crystals < item.cost() {
+            return false;
+        }
+        self.vending_machine_open = false;
+        self.crystals -= item.cost();
+        self.coins += item.coins();
+        self.keys += item.keys();
+        true
+    }
+
+    /// Execute the given action as a single turn and return all events that occurred.
+    /// Pending movements (e.g., the initial fall after spawning) are executed before the action.
+    /// The enemies move only after the player has come to rest, i.e., a player that respawns
+    /// during the turn falls down before the enemies move.
+    /// If the game is already over, the action is discarded and no move is counted.
+    ///
+    /// # Examples
+    /// ```
+    /// use libexodus::player::Player;
+    /// use libexodus::simulation::{PlayerAction, Simulation, SimulationEvent, SimulationState};
+    /// use libexodus::tiles::Tile;
+    /// use libexodus::world::presets;
+    /// let mut world = presets::map_with_border(5, 3);
+    /// world.set(3, 1, Tile::EXIT);
+    /// let mut simulation = Simulation::new(world, Player::new());
+    /// simulation.step(PlayerAction::Right);
+    /// let events = simulation.step(PlayerAction::Right);
+    /// assert_eq!(vec![SimulationEvent::Exited { position: (3, 1) }], events);
+    /// assert_eq!(SimulationState::Won, simulation.state());
+    /// assert_eq!(2, simulation.moves());
+    /// ```
+    pub fn step(&mut self, action: PlayerAction) -> Vec<SimulationEvent> {
+        Self::events_of(self.step_with_timeline(action))
+    }
+
+    /// Execute the given action as a single turn, like [Simulation::step], and return the
+    /// timeline of the turn, i.e., all single-tile movements of the player and the enemies,
+    /// interleaved with the events in the order in which they occurred.
+    /// This is used to animate a turn.
+    ///
+    /// # Examples
+    /// ```
+    /// use libexodus::player::Player;
+    /// use libexodus::simulation::{PlayerAction, Simulation, SimulationEvent, TimelineEntry};
+    /// use libexodus::tiles::Tile;
+    /// use libexodus::world::presets;
+    /// let mut world = presets::map_with_border(5, 3);
+    /// world.set(2, 1, Tile::COIN);
+    /// let mut simulation = Simulation::new(world, Player::new());
+    /// let timeline = simulation.step_with_timeline(PlayerAction::Right);
+    /// assert_eq!(2, timeline.len());
+    /// assert_eq!(TimelineEntry::PlayerMoved { from: (1, 1), to: (2, 1) }, timeline[0]);
+    /// assert!(matches!(timeline[1], TimelineEntry::Event(SimulationEvent::Collected { .. })));
+    /// ```
+    pub fn step_with_timeline(&mut self, action: PlayerAction) -> Vec<TimelineEntry> {
+        let mut timeline = self.settle_with_timeline();
+        if self.is_over() {
+            return timeline;
+        }
+        self.moves += 1;
+        self.vending_machine_open = false;
+        if let Some(right) = action.faces_right() {
+            self.player.set_face_right(right);
+        }
+        for movement in action.movements(self.position, &self.world, SIMULATION_SPEED) {
+            self.player.push_movement_queue(movement);
+        }
+        // Settle the movements, including the fall after respawning, before the enemies move
+        timeline.append(&mut self.settle_with_timeline());
+        if !self.is_over() {
+            self.step_enemies(&mut timeline);
+            // Let the player fall down after being respawned
+            timeline.append(&mut self.settle_with_timeline());
+        }
+        timeline
+    }
+
+    /// Get all events contained in the given timeline
+    fn events_of(timeline: Vec<TimelineEntry>) -> Vec<SimulationEvent> {
+        timeline
+            .into_iter()
+            .filter_map(|entry| match entry {
+                TimelineEntry::Event(event) => Some(event),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Move all enemies by one tile and handle the enemies that move into the player
+    fn step_enemies(&mut self, timeline: &mut Vec<TimelineEntry>) {
+        let mut contacts: Vec<(usize, FromDirection)> = vec![];
+        for (index, (enemy, state)) in self
+            .world
+            .enemies()
+            .iter()
+            .zip(self.enemies.iter_mut())
+            .enumerate()
+        {
+            if let Some(direction) = enemy.step(state, &self.world)
+                && state.is_at(self.position)
+            {
+                contacts.push((index, leading_side(direction)));
+            }
+        }
+        if !self.enemies.is_empty() {
+            timeline.push(TimelineEntry::EnemiesMoved {
+                states: self.enemies.clone(),
+            });
+        }
+        for (index, from_direction) in contacts {
+            if self.touch_enemy(index, &from_direction, timeline) {
+                break;
+            }
+        }
+    }
+
+    /// Handle the player touching the enemy with the given index from the given direction.
+    /// Return true if the player died.
+    fn touch_enemy(
+        &mut self,
+        index: usize,
+        from_direction: &FromDirection,
+        timeline: &mut Vec<TimelineEntry>,
+    ) -> bool {
+        if self.world.enemies()[index].is_deadly_from(from_direction) {
+            self.die(self.position, timeline);
+            return true;
+        }
+        self.enemies[index].defeat();
+        timeline.push(
+            SimulationEvent::EnemyDefeated {
+                position: self.enemies[index].position(),
+            }
+            .into(),
+        );
+        false
+    }
+
+    /// Lose a life at the given position. If the player has lives left, respawn the player
+    /// at the respawn point, otherwise the game is lost.
+    fn die(&mut self, position: (usize, usize), timeline: &mut Vec<TimelineEntry>) {
+        self.player.clear_movement_queue();
+        self.deaths += 1;
+        timeline.push(SimulationEvent::Died { position }.into());
+        if self.deaths >= self.world.lives() {
+            self.state = SimulationState::Lost;
+        } else {
+            self.position = self.respawn_point;
+            Self::spawn(&mut self.player, self.respawn_point);
+            timeline.push(
+                SimulationEvent::Respawned {
+                    position: self.respawn_point,
+                }
+                .into(),
+            );
+        }
+    }
+
+    /// Execute all pending movements and apply gravity, until the player comes to rest.
+    /// Return all events that occurred.
+    pub fn settle(&mut self) -> Vec<SimulationEvent> {
+        Self::events_of(self.settle_with_timeline())
+    }
+
+    /// Execute all pending movements and apply gravity, until the player comes to rest.
+    /// Return the timeline of all movements and events, like [Simulation::step_with_timeline].
+    pub fn settle_with_timeline(&mut self) -> Vec<TimelineEntry> {
+        let mut timeline = vec![];
+        for _ in 0..MAX_MOVEMENTS_PER_TURN {
+            if self.is_over() {
+                break;
+            }
+            if self.player.movement_queue_is_empty() {
+                self.apply_gravity();
+                if self.player.movement_queue_is_empty() {
+                    break;
+                }
+            }
+            self.advance(&mut timeline);
+        }
+        timeline
+    }
+
+    /// Gravity: If the tile below the player is non-solid and the player does not stand on
+    /// or above a ladder, add a downward movement
+    fn apply_gravity(&mut self) {
+        let (x, y) = (self.position.0 as i32, self.position.1 as i32);
+        if let Some(block) = self.world.get(x, y - 1)
+            && !block.can_collide_from(&FromDirection::FROMNORTH)
+        {
+            self.player.push_movement_queue(Movement {
+                velocity: (0., -SIMULATION_SPEED),
+                target: (x, y - 1),
+                is_manual: false,
+            });
+        }
+        let is_ladder = |block: Option<&Tile>| block.is_some_and(|b| b.kind() == TileKind::LADDER);
+        if is_ladder(self.world.get(x, y)) || is_ladder(self.world.get(x, y - 1)) {
+            self.player.clear_movement_queue();
+        }
+    }
+
+    /// Handle the interaction with a tile the player would normally collide with.
+    /// Return true if the player does not collide with the tile, e.g., because a door has been opened.
+    fn handle_collision_interaction(
+        &mut self,
+        target: (usize, usize),
+        movement: &Movement,
+        timeline: &mut Vec<TimelineEntry>,
+    ) -> bool {
+        let Some(tile) = self.world.get(target.0 as i32, target.1 as i32).cloned() else {
+            return false;
+        };
+        match tile.kind() {
+            TileKind::SOLIDINTERACTABLE { from, kind } => {
+                if movement.is_manual
+                    && kind == InteractionKind::VendingMachine
+                    && from.contains(&FromDirection::from(movement.direction()))
+                {
+                    self.vending_machine_open = true;
+                    timeline
+                        .push(SimulationEvent::VendingMachineTriggered { position: target }.into());
+                }
+                false
+            },
+            TileKind::DOOR { color } => {
+                let (keys, Some(opened_door)) = (self.keys_mut(color), tile.opened_door()) else {
+                    return false;
+                };
+                if *keys == 0 {
+                    return false;
+                }
+                *keys -= 1;
+                self.world.set(target.0, target.1, opened_door);
+                timeline.push(SimulationEvent::DoorOpened { position: target }.into());
+                true
+            },
+            TileKind::CRATE => {
+                let Some(to) = self.world.push_crate(target, movement.direction()) else {
+                    return false;
+                };
+                timeline.push(SimulationEvent::CratePushed { from: target, to }.into());
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// Execute the next movement in the player's movement queue.
+    /// Movements that are blocked by a collision or by the map boundaries are discarded.
+    fn advance(&mut self, timeline: &mut Vec<TimelineEntry>) {
+        let target = loop {
+            let Some(movement) = self.player.peek_movement_queue().copied() else {
+                return;
+            };
+            let (target_x, target_y) =
+                movement.int_target_from_direction(self.position.0 as f32, self.position.1 as f32);
+            if target_x < 0
+                || target_y < 0
+                || target_x >= self.world.width() as i32
+                || target_y >= self.world.height() as i32
+            {
+                self.player.pop_movement_queue();
+                continue;
+            }
+            let target = (target_x as usize, target_y as usize);
+            let collision = self
+                .world
+                .get(target_x, target_y)
+                .is_some_and(|block| block.can_collide_from(&movement.direction().into()));
+            if collision && !self.handle_collision_interaction(target, &movement, timeline) {
+                self.player.pop_movement_queue();
+                continue;
+            }
+            break target;
+        };
+        let movement = self.player.pop_movement_queue().unwrap();
+        match movement.direction() {
+            Directions::EAST => self.player.set_face_right(true),
+            Directions::WEST => self.player.set_face_right(false),
+            Directions::NORTH | Directions::SOUTH => {},
+        }
+        timeline.push(TimelineEntry::PlayerMoved {
+            from: self.position,
+            to: target,
+        });
+        self.position = target;
+        for index in 0..self.enemies.len() {
+            if self.enemies[index].is_at(target)
+                && self.touch_enemy(index, &movement.direction().into(), timeline)
+            {
+                return;
+            }
+        }
+        let block = self
+            .world
+            .get(target.0 as i32, target.1 as i32)
+            .unwrap()
+            .clone();
+        match block.kind() {
+            TileKind::DEADLY { .. } if block.is_deadly_from(&movement.direction().into()) => {
+                self.die(target, timeline);
+            },
+            TileKind::CHECKPOINT if self.respawn_point != target => {
+                self.respawn_point = target;
+                timeline.push(SimulationEvent::CheckpointReached { position: target }.into());
+            },
+            TileKind::SPECIAL {
+                interaction: InteractionKind::TeleportTo { teleport_id },
+            } => {
+                if let Some(location) = self.world.get_teleport_location(teleport_id) {
+                    self.player.clear_movement_queue();
+                    self.position = *location;
+                    timeline.push(
+                        SimulationEvent::Teleported {
+                            from: target,
+                            to: *location,
+                        }
+                        .into(),
+                    );
+                }
+            },
+            TileKind::LADDER => {
+                // On a ladder, the movement queue is cleared after every step!
+                // This way, the player is unable to jump on a double ladder and ascends instead of jumping.
+                self.player.clear_movement_queue();
+            },
+            TileKind::COLLECTIBLE { kind } => {
+                match kind {
+                    CollectibleKind::Decorative => {},
+                    CollectibleKind::Coins { amount } => self.coins += amount,
+                    CollectibleKind::Keys { amount, color } => *self.keys_mut(color) += amount,
+                    CollectibleKind::StarCrystals { amount } => self.crystals += amount,
+                }
+                self.world.set(target.0, target.1, Tile::AIR);
+                timeline.push(
+                    SimulationEvent::Collected {
+                        kind,
+                        position: target,
+                    }
+                    .into(),
+                );
+            },
+            TileKind::SWITCH => {
+                self.world.toggle_switch(target);
+                timeline.push(SimulationEvent::SwitchToggled { position: target }.into());
+            },
+            TileKind::EXIT => {
+                self.player.clear_movement_queue();
+                self.state = SimulationState::Won;
+                timeline.push(SimulationEvent::Exited { position: target }.into());
+            },
+            _ => {},
+        }
+        let changes = self.world.update_platforms(target);
+        timeline.extend(
+            changes
+                .broken
+                .into_iter()
+                .map(|position| SimulationEvent::PlatformBroken { position }.into()),
+        );
+        timeline.extend(
+            changes
+                .cracked
+                .map(|position| SimulationEvent::PlatformCracked { position }.into()),
+        );
+        timeline.extend(
+            changes
+                .fallen
+                .into_iter()
+                .map(|(from, to)| SimulationEvent::BlockFell { from, to }.into()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::enemies::Enemy;
+    use crate::player::Player;
+    use crate::simulation::{
+        PlayerAction, Purchase, Simulation, SimulationEvent, SimulationState, TimelineEntry,
+    };
+    use crate::tiles::{CollectibleKind, KeyColor, TeleportId, Tile};
+    use crate::world::presets::map_with_border_and_spawn;
+
+    #[test]
+    fn test_player_falls_after_spawning() {
+        let mut world = map_with_border_and_spawn(5, 6);
+        world.set(2, 4, Tile::PLAYERSPAWN);
+        let mut simulation = Simulation::new(world, Player::new());
+        assert_eq!((2, 4), simulation.position());
+        assert!(simulation.settle().is_empty());
+        assert_eq!((2, 1), simulation.position());
+        assert_eq!(0, simulation.moves());
+    }
+
+    #[test]
+    fn test_walk_against_wall() {
+        let mut simulation = Simulation::new(map_with_border_and_spawn(4, 3), Player::new());
+        simulation.step(PlayerAction::Right);
+        assert_eq!((2, 1), simulation.position());
+        assert!(simulation.player().is_facing_right());
+        simulation.step(PlayerAction::Right);
+        assert_eq!((2, 1), simulation.position());
+        simulation.step(PlayerAction::Left);
+        assert_eq!((1, 1), simulation.position());
+        assert!(!simulation.player().is_facing_right());
+        assert_eq!(3, simulation.moves());
+    }
+
+    #[test]
+    fn test_jump_onto_platform() {
+        let mut world = map_with_border_and_spawn(6, 7);
+        world.set(3, 2, Tile::WALL);
+        world.set(4, 2, Tile::WALL);
+        let mut simulation = Simulation::new(world, Player::new());
+        simulation.step(PlayerAction::JumpRight);
+        assert_eq!((3, 3), simulation.position());
+        simulation.step(PlayerAction::Up);
+        assert_eq!((3, 3), simulation.position());
+    }
+
+    #[test]
+    fn test_jump_blocked_by_ceiling() {
+        let mut world = map_with_border_and_spawn(5, 7);
+        world.set(1, 3, Tile::WALL);
+        let mut simulation = Simulation::new(world, Player::new());
+        simulation.step(PlayerAction::JumpRight);
+        // The second upward movement is blocked, the player moves sideways one tile above the ground and falls down
+        assert_eq!((3, 1), simulation.position());
+    }
+
+    #[test]
+    fn test_collect_coins_and_crystals() {
+        let mut world = map_with_border_and_spawn(6, 3);
+        world.set(2, 1, Tile::COIN);
+        world.set(3, 1, Tile::STARCRYSTAL);
+        let mut simulation = Simulation::new(world, Player::new());
+        let events = simulation.step(PlayerAction::Right);
+        assert_eq!(
+            vec![SimulationEvent::Collected {
+                kind: CollectibleKind::Coins { amount: 1 },
+                position: (2, 1)
+            }],
+            events
+        );
+        simulation.step(PlayerAction::Right);
+        assert_eq!(1, simulation.coins());
+        assert_eq!(1, simulation.crystals());
+        assert_eq!(Tile::AIR, *simulation.world().get(2, 1).unwrap());
+        // Collectibles can only be collected once
+        simulation.step(PlayerAction::Left);
+        assert_eq!(1, simulation.coins());
+    }
+
+    #[test]
+    fn test_open_door_with_key() {
+        let mut world = map_with_border_and_spawn(6, 3);
+        world.set(2, 1, Tile::KEY);
+        world.set(3, 1, Tile::DOOR);
+        world.set(4, 1, Tile::DOOR);
+        let mut simulation = Simulation::new(world, Player::new());
+        simulation.step(PlayerAction::Right);
+        assert_eq!(1, simulation.keys());
+        let events = simulation.step(PlayerAction::Right);
+        assert_eq!(
+            vec![SimulationEvent::DoorOpened { position: (3, 1) }],
+            events
+        );
+        assert_eq!((3, 1), simulation.position());
+        assert_eq!(0, simulation.keys());
+        assert_eq!(Tile::OPENDOOR, *simulation.world().get(3, 1).unwrap());
+        // No keys left for the second door
+        assert!(simulation.step(PlayerAction::Right).is_empty());
+        assert_eq!((3, 1), simulation.position());
+    }
+
+    #[test]
+    fn test_open_colored_doors() {
+        let mut world = map_with_border_and_spawn(7, 3);
+        world.set(2, 1, Tile::KEY);
+        world.set(
+            3,
+            1,
+            Tile::COLOREDKEY {
+                color: KeyColor::RED,
+            },
+        );
+        world.set(
+            4,
+            1,
+            Tile::COLOREDDOOR {
+                color: KeyColor::BLUE,
+            },
+        );
+        let mut simulation = Simulation::new(world.clone(), Player::new());
+        simulation.step(PlayerAction::Right);
+        simulation.step(PlayerAction::Right);
+        assert_eq!(1, simulation.keys());
+        assert_eq!(1, simulation.colored_keys(KeyColor::RED));
+        assert_eq!(0, simulation.colored_keys(KeyColor::BLUE));
+        // Neither the uncolored nor the red key opens the blue door
+        assert!(simulation.step(PlayerAction::Right).is_empty());
+        assert_eq!((3, 1), simulation.position());
+
+        world.set(
+            4,
+            1,
+            Tile::COLOREDDOOR {
+                color: KeyColor::RED,
+            },
+        );
+        let mut simulation = Simulation::new(world, Player::new());
+        simulation.step(PlayerAction::Right);
+        simulation.step(PlayerAction::Right);
+        let events = simulation.step(PlayerAction::Right);
+        assert_eq!(
+            vec![SimulationEvent::DoorOpened { position: (4, 1) }],
+            events
+        );
+        assert_eq!(1, simulation.keys());
+        assert_eq!(0, simulation.colored_keys(KeyColor::RED));
+        assert_eq!(
+            Tile::OPENCOLOREDDOOR {
+                color: KeyColor::RED
+            },
+            *simulation.world().get(4, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_push_crate() {
+        let mut world = map_with_border_and_spawn(6, 3);
+        world.set(2, 1, Tile::CRATE);
+        world.set(4, 1, Tile::WALL);
+        let mut simulation = Simulation::new(world, Player::new());
+        let events = simulation.step(PlayerAction::Right);
+        assert_eq!(
+            vec![SimulationEvent::CratePushed {
+                from: (2, 1),
+                to: (3, 1)
+            }],
+            events
+        );
+        assert_eq!((2, 1), simulation.position());
+        assert_eq!(Tile::CRATE, *simulation.world().get(3, 1).unwrap());
+        // The crate is blocked by the wall
+        assert!(simulation.step(PlayerAction::Right).is_empty());
+        assert_eq!((2, 1), simulation.position());
+    }
+
+    #[test]
+    fn test_lever_toggles_linked_blocks() {
+        let mut world = map_with_border_and_spawn(7, 3);
+        world.set(2, 1, Tile::LEVER);
+        world.set(3, 1, Tile::TOGGLEBLOCK);
+        world.set(5, 1, Tile::TOGGLEBLOCKOFF);
+        world.link((2, 1), (3, 1)).link((2, 1), (5, 1));
+        let mut simulation = Simulation::new(world, Player::new());
+        assert_eq!(
+            vec![SimulationEvent::SwitchToggled { position: (2, 1) }],
+            simulation.step(PlayerAction::Right)
+        );
+        assert_eq!(Tile::LEVERPULLED, *simulation.world().get(2, 1).unwrap());
+        assert_eq!(Tile::TOGGLEBLOCKOFF, *simulation.world().get(3, 1).unwrap());
+        assert_eq!(Tile::TOGGLEBLOCK, *simulation.world().get(5, 1).unwrap());
+        simulation.step(PlayerAction::Right);
+        simulation.step(PlayerAction::Right);
+        assert_eq!((4, 1), simulation.position());
+        // The formerly open toggle block is solid now
+        assert!(simulation.step(PlayerAction::Right).is_empty());
+        assert_eq!((4, 1), simulation.position());
+    }
+
+    #[test]
+    fn test_crumbling_platform_breaks_after_one_move() {
+        let mut world = map_with_border_and_spawn(7, 6);
+        world.set(1, 3, Tile::PLAYERSPAWN);
+        world.set(1, 2, Tile::WALL);
+        world.set(2, 2, Tile::CRUMBLINGPLATFORM);
+        world.set(3, 2, Tile::WALL);
+        let mut simulation = Simulation::new(world, Player::new());
+        assert_eq!(
+            vec![SimulationEvent::PlatformCracked { position: (2, 2) }],
+            simulation.step(PlayerAction::Right)
+        );
+        assert_eq!(
+            vec![SimulationEvent::PlatformBroken { position: (2, 2) }],
+            simulation.step(PlayerAction::Right)
+        );
+        assert_eq!((3, 3), simulation.position());
+        assert_eq!(Tile::AIR, *simulation.world().get(2, 2).unwrap());
+        // The way back is gone
+        simulation.step(PlayerAction::Left);
+        assert_eq!((2, 1), simulation.position());
+    }
+
+    #[test]
+    fn test_falling_block_seals_passage() {
+        let mut world = map_with_border_and_spawn(6, 5);
+        world.set(2, 2, Tile::FALLINGBLOCK);
+        world.set(2, 3, Tile::FALLINGBLOCK);
+        let mut simulation = Simulation::new(world, Player::new());
+        // The blocks rest on the player's head while the player walks below them
+        simulation.step(PlayerAction::Right);
+        assert_eq!(
+            vec![
+                SimulationEvent::BlockFell {
+                    from: (2, 2),
+                    to: (2, 1)
+                },
+                SimulationEvent::BlockFell {
+                    from: (2, 3),
+                    to: (2, 2)
+                }
+            ],
+            simulation.step(PlayerAction::Right)
+        );
+        assert!(simulation.step(PlayerAction::Left).is_empty());
+        assert_eq!((3, 1), simulation.position());
+        assert_eq!(Tile::FALLINGBLOCK, *simulation.world().get(2, 1).unwrap());
+    }
+
+    #[test]
+    fn test_crate_covers_spikes_in_gap() {
+        let mut world = map_with_border_and_spawn(7, 4);
+        for x in [1, 2, 4, 5] {
+            world.set(x, 1, Tile::WALL);
+        }
+        world.set(3, 1, Tile::SPIKES);
+        world.set(1, 2, Tile::PLAYERSPAWN);
+        world.set(2, 2, Tile::CRATE);
+        let mut simulation = Simulation::new(world, Player::new());
+        simulation.step(PlayerAction::Right);
+        assert_eq!(Tile::CRATE, *simulation.world().get(3, 1).unwrap());
+        assert!(simulation.step(PlayerAction::Right).is_empty());
+        assert_eq!((3, 2), simulation.position());
+        assert!(simulation.step(PlayerAction::Right).is_empty());
+        assert_eq!((4, 2), simulation.position());
+        assert_eq!(SimulationState::Running, simulation.state());
+    }
+
+    #[test]
+    fn test_die_on_spikes() {
+        let mut world = map_with_border_and_spawn(5, 3);
+        world.set(2, 1, Tile::SPIKES);
+        let mut simulation = Simulation::new(world, Player::new());
+        let events = simulation.step(PlayerAction::Right);
+        assert_eq!(vec![SimulationEvent::Died { position: (2, 1) }], events);
+        assert_eq!(SimulationState::Lost, simulation.state());
+        // Actions after the game is over are discarded
+        assert!(simulation.step(PlayerAction::Left).is_empty());
+        assert_eq!(1, simulation.moves());
+    }
+
+    #[test]
+    fn test_fall_onto_spikes() {
+        let mut world = map_with_border_and_spawn(5, 6);
+        world.set(2, 4, Tile::PLAYERSPAWN);
+        world.set(2, 1, Tile::SPIKES);
+        let mut simulation = Simulation::new(world, Player::new());
+        assert_eq!(
+            vec![SimulationEvent::Died { position: (2, 1) }],
+            simulation.settle()
+        );
+        assert!(simulation.is_over());
+    }
+
+    #[test]
+    fn test_respawn_at_player_spawn() {
+        let mut world = map_with_border_and_spawn(5, 3);
+        world.set(2, 1, Tile::SPIKES);
+        world.set_lives(2);
+        let mut simulation = Simulation::new(world, Player::new());
+        let events = simulation.step(PlayerAction::Right);
+        assert_eq!(
+            vec![
+                SimulationEvent::Died { position: (2, 1) },
+                SimulationEvent::Respawned { position: (1, 1) },
+            ],
+            events
+        );
+        assert_eq!(SimulationState::Running, simulation.state());
+        assert_eq!((1, 1), simulation.position());
+        assert_eq!(1, simulation.deaths());
+        simulation.step(PlayerAction::Right);
+        assert_eq!(SimulationState::Lost, simulation.state());
+        assert_eq!(2, simulation.deaths());
+    }
+
+    #[test]
+    fn test_respawn_at_checkpoint() {
+        let mut world = map_with_border_and_spawn(6, 3);
+        world.set(2, 1, Tile::CHECKPOINT);
+        world.set(4, 1, Tile::SPIKES);
+        world.set_lives(3);
+        let mut simulation = Simulation::new(world, Player::new());
+        assert_eq!(
+            vec![SimulationEvent::CheckpointReached { position: (2, 1) }],
+            simulation.step(PlayerAction::Right)
+        );
+        assert_eq!((2, 1), simulation.respawn_point());
+        // Stepping onto the same checkpoint again does not trigger a new event
+        simulation.step(PlayerAction::Left);
+        assert!(simulation.step(PlayerAction::Right).is_empty());
+        simulation.step(PlayerAction::Right);
+        let events = simulation.step(PlayerAction::Right);
+        assert_eq!(
+            vec![
+                SimulationEvent::Died { position: (4, 1) },
+                SimulationEvent::Respawned { position: (2, 1) },
+            ],
+            events
+        );
+        assert_eq!((2, 1), simulation.position());
+        // The checkpoint is not removed when the player passes it
+        assert_eq!(Tile::CHECKPOINT, *simulation.world().get(2, 1).unwrap());
+    }
+
+    #[test]
+    fn test_climb_ladder() {
+        let mut world = map_with_border_and_spawn(5, 7);
+        for y in 1..5 {
+            world.set(2, y, Tile::LADDER);
+        }
+        let mut simulation = Simulation::new(world, Player::new());
+        simulation.step(PlayerAction::Right);
+        assert_eq!((2, 1), simulation.position());
+        // On a ladder, the player climbs one tile per action and does not fall down
+        simulation.step(PlayerAction::Up);
+        assert_eq!((2, 2), simulation.position());
+        simulation.step(PlayerAction::Up);
+        assert_eq!((2, 3), simulation.position());
+        simulation.step(PlayerAction::Down);
+        assert_eq!((2, 2), simulation.position());
+    }
+
+    #[test]
+    fn test_teleport() {
+        let mut world = map_with_border_and_spawn(8, 3);
+        world.set(
+            2,
+            1,
+            Tile::TELEPORTENTRY {
+                teleport_id: TeleportId::ONE,
+            },
+        );
+        world.set(
+            6,
+            1,
+            Tile::TELEPORTEXIT {
+                teleport_id: TeleportId::ONE,
+            },
+        );
+        let mut simulation = Simulation::new(world, Player::new());
+        let events = simulation.step(PlayerAction::Right);
+        assert_eq!(
+            vec![SimulationEvent::Teleported {
+                from: (2, 1),
+                to: (6, 1)
+            }],
+            events
+        );
+        assert_eq!((6, 1), simulation.position());
+    }
+
+    #[test]
+    fn test_reach_exit() {
+        let mut world = map_with_border_and_spawn(5, 3);
+        world.set(2, 1, Tile::EXIT);
+        let mut simulation = Simulation::new(world, Player::new());
+        let events = simulation.step(PlayerAction::Right);
+        assert_eq!(vec![SimulationEvent::Exited { position: (2, 1) }], events);
+        assert_eq!(SimulationState::Won, simulation.state());
+    }
+
+    #[test]
+    fn test_new_resets_game_state() {
+        let mut world = map_with_border_and_spawn(5, 3);
+        world.set(2, 1, Tile::OPENDOOR);
+        let simulation = Simulation::new(world, Player::new());
+        assert_eq!(Tile::DOOR, *simulation.world().get(2, 1).unwrap());
+    }
+
+    #[test]
+    fn test_vending_machine() {
+        let mut world = map_with_border_and_spawn(5, 3);
+        world.set(2, 1, Tile::VENDINGMACHINEL);
+        world.set(3, 1, Tile::VENDINGMACHINER);
+        let mut simulation = Simulation::new(world, Player::new());
+        let events = simulation.step(PlayerAction::Right);
+        assert_eq!(
+            vec![SimulationEvent::VendingMachineTriggered { position: (2, 1) }],
+            events
+        );
+        assert_eq!((1, 1), simulation.position());
+    }
+
+    #[test]
+    fn test_purchase_key_opens_door() {
+        let mut world = map_with_border_and_spawn(10, 5);
+        world.set(1, 1, Tile::AIR);
+        world.set(5, 1, Tile::PLAYERSPAWN);
+        for x in 6..9 {
+            world.set(x, 1, Tile::STARCRYSTAL);
+        }
+        world.set(4, 1, Tile::VENDINGMACHINER);
+        world.set(2, 1, Tile::DOOR);
+        let mut simulation = Simulation::new(world, Player::new());
+        for _ in 0..3 {
+            simulation.step(PlayerAction::Right);
+        }
+        assert_eq!(3, simulation.crystals());
+        // Walking into the vending machine from the right opens it
+        for _ in 0..4 {
+            simulation.step(PlayerAction::Left);
+        }
+        assert!(simulation.is_vending_machine_open());
+        assert!(simulation.purchase(Purchase::Key));
+        assert_eq!((0, 1), (simulation.crystals(), simulation.keys()));
+        assert!(!simulation.is_vending_machine_open());
+        simulation.step(PlayerAction::JumpLeft);
+        assert_eq!((3, 1), simulation.position());
+        simulation.step(PlayerAction::Left);
+        assert_eq!(Tile::OPENDOOR, *simulation.world().get(2, 1).unwrap());
+        assert_eq!(0, simulation.keys());
+    }
+
+    #[test]
+    fn test_purchase_requires_open_vending_machine() {
+        let mut world = map_with_border_and_spawn(5, 3);
+        world.set(2, 1, Tile::STARCRYSTAL);
+        world.set(4, 1, Tile::VENDINGMACHINEL);
+        let mut simulation = Simulation::new(world, Player::new());
+        simulation.step(PlayerAction::Right);
+        assert!(!simulation.purchase(Purchase::FiveCoins));
+        simulation.step(PlayerAction::Right);
+        simulation.step(PlayerAction::Right);
+        assert!(simulation.is_vending_machine_open());
+        // The vending machine is closed as soon as the player moves on
+        simulation.step(PlayerAction::Left);
+        assert!(!simulation.purchase(Purchase::FiveCoins));
+        assert_eq!((0, 1), (simulation.coins(), simulation.crystals()));
+    }
+
+    #[test]
+    fn test_walk_into_enemy() {
+        let mut world = map_with_border_and_spawn(7, 3);
+        world.add_enemy(Enemy::walker((4, 1), false));
+        let mut simulation = Simulation::new(world, Player::new());
+        assert!(simulation.step(PlayerAction::Right).is_empty());
+        assert_eq!((3, 1), simulation.enemies()[0].position());
+        let events = simulation.step(PlayerAction::Right);
+        assert_eq!(vec![SimulationEvent::Died { position: (3, 1) }], events);
+        assert_eq!(SimulationState::Lost, simulation.state());
+    }
+
+    #[test]
+    fn test_enemy_moves_into_player() {
+        let mut world = map_with_border_and_spawn(6, 3);
+        world.set_lives(2);
+        world.add_enemy(Enemy::walker((3, 1), false));
+        let mut simulation = Simulation::new(world, Player::new());
+        // The player is blocked by the wall, but the enemy keeps moving
+        simulation.step(PlayerAction::Left);
+        assert_eq!((2, 1), simulation.enemies()[0].position());
+        let events = simulation.step(PlayerAction::Left);
+        assert_eq!(
+            vec![
+                SimulationEvent::Died { position: (1, 1) },
+                SimulationEvent::Respawned { position: (1, 1) }
+            ],
+            events
+        );
+        assert_eq!(SimulationState::Running, simulation.state());
+        assert_eq!(1, simulation.deaths());
+    }
+
+    #[test]
+    fn test_respawned_player_falls_before_enemies_move() {
+        let mut world = map_with_border_and_spawn(7, 5);
+        world.set(1, 1, Tile::SPIKES);
+        world.set(2, 3, Tile::PLAYERSPAWN);
+        world.set_lives(3);
+        world.add_enemy(Enemy::walker((3, 1), false));
+        let mut simulation = Simulation::new(world, Player::new());
+        simulation.settle();
+        assert_eq!((2, 1), simulation.position());
+        // The respawned player lands next to the enemy before it moves, so the enemy walks into
+        // the player. The player respawned after that lands on top of the enemy.
+        let events = simulation.step(PlayerAction::Left);
+        assert_eq!(
+            vec![
+                SimulationEvent::Died { position: (1, 1) },
+                SimulationEvent::Respawned { position: (2, 3) },
+                SimulationEvent::Died { position: (2, 1) },
+                SimulationEvent::Respawned { position: (2, 3) },
+                SimulationEvent::EnemyDefeated { position: (2, 1) },
+            ],
+            events
+        );
+        assert_eq!((2, 1), simulation.position());
+        assert_eq!(2, simulation.deaths());
+    }
+
+    #[test]
+    fn test_timeline_of_turn() {
+        let mut world = map_with_border_and_spawn(7, 4);
+        world.set(1, 2, Tile::PLAYERSPAWN);
+        world.set(2, 1, Tile::COIN);
+        world.add_enemy(Enemy::walker((5, 1), false));
+        let mut simulation = Simulation::new(world, Player::new());
+        assert_eq!(
+            vec![TimelineEntry::PlayerMoved {
+                from: (1, 2),
+                to: (1, 1)
+            }],
+            simulation.settle_with_timeline()
+        );
+        let timeline = simulation.step_with_timeline(PlayerAction::Right);
+        assert_eq!(
+            vec![
+                TimelineEntry::PlayerMoved {
+                    from: (1, 1),
+                    to: (2, 1)
+                },
+                TimelineEntry::Event(SimulationEvent::Collected {
+                    kind: CollectibleKind::Coins { amount: 1 },
+                    position: (2, 1)
+                }),
+                TimelineEntry::EnemiesMoved {
+                    states: simulation.enemies().to_vec()
+                },
+            ],
+            timeline
+        );
+        assert_eq!((4, 1), simulation.enemies()[0].position());
+    }
+
+    #[test]
+    fn test_jump_onto_enemy() {
+        let mut world = map_with_border_and_spawn(7, 5);
+        world.add_enemy(Enemy::walker((3, 1), true));
+        world.add_enemy(Enemy::flyer((5, 1), vec![]));
+        let mut simulation = Simulation::new(world, Player::new());
+        let events = simulation.step(PlayerAction::JumpRight);
+        assert_eq!(
+            vec![SimulationEvent::EnemyDefeated { position: (3, 1) }],
+            events
+        );
+        assert!(simulation.enemies()[0].is_defeated());
+        assert_eq!((3, 1), simulation.position());
+        // Defeated enemies do not move and cannot be touched anymore
+        simulation.step(PlayerAction::Left);
+        assert_eq!((3, 1), simulation.enemies()[0].position());
+        simulation.step(PlayerAction::Right);
+        // Flying enemies are deadly from above
+        let events = simulation.step(PlayerAction::JumpRight);
+        assert_eq!(vec![SimulationEvent::Died { position: (5, 1) }], events);
+    }
+}
@@ -0,0 +1,34 @@
+use strum_macros::{EnumCount as EnumCountMacro, EnumIter};
+
+/// An item that can be purchased at a vending machine in exchange for star crystals
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, EnumIter, EnumCountMacro)]
+pub enum Purchase {
+    /// Five coins
+    FiveCoins,
+    /// A single uncolored key
+    Key,
+}
+
+impl Purchase {
+    /// Get the number of star crystals this item costs
+    pub const fn cost(&self) -> usize {
+        match self {
+            Purchase::FiveCoins => 1,
+            Purchase::Key => 3,
+        }
+    }
+    /// Get the number of coins the player receives when purchasing this item
+    pub const fn coins(&self) -> usize {
+        match self {
+            Purchase::FiveCoins => 5,
+            Purchase::Key => 0,
+        }
+    }
+    /// Get the number of uncolored keys the player receives when purchasing this item
+    pub const fn keys(&self) -> usize {
+        match self {
+            Purchase::FiveCoins => 0,
+            Purchase::Key => 1,
+        }
+    }
+}
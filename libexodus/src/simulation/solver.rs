@@ -0,0 +1,482 @@
+//! A breadth-first solver that finds the minimum number of moves needed to beat a map.
+//! The solver uses the [Simulation] to execute all possible [PlayerAction]s, hence it follows
+//! exactly the same movement rules as the game.
+use crate::enemies::EnemyState;
+use crate::player::Player;
+use crate::simulation::{PlayerAction, Purchase, Simulation, SimulationEvent, SimulationState};
+use crate::tiles::{CollectibleKind, KeyColor, Tile};
+use crate::world::GameWorld;
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use strum::{EnumCount, IntoEnumIterator};
+
+/// The default maximum number of distinct states the solver explores before giving up
+pub const DEFAULT_MAX_STATES: usize = 1_000_000;
+
+/// An optimal solution for a map
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Solution {
+    actions: Vec<PlayerAction>,
+    purchases: Vec<(usize, Purchase)>,
+    coins: usize,
+    crystals: usize,
+}
+
+impl Solution {
+    /// Get the actions that need to be executed to reach the exit
+    pub fn actions(&self) -> &[PlayerAction] {
+        &self.actions
+    }
+    /// Get the purchases that need to be made at vending machines, each together with the number
+    /// of actions that have been executed before the purchase, like in a replay
+    pub fn purchases(&self) -> &[(usize, Purchase)] {
+        &self.purchases
+    }
+    /// Get the number of moves of this solution, i.e., the par of the map
+    pub fn moves(&self) -> usize {
+        self.actions.len()
+    }
+    /// Get the number of coins that are collected along the way
+    pub fn coins(&self) -> usize {
+        self.coins
+    }
+    /// Get the number of star crystals that are collected along the way
+    pub fn crystals(&self) -> usize {
+        self.crystals
+    }
+}
+
+/// The result of solving a map
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SolverResult {
+    /// The map can be beaten with the given minimum-move solution
+    Solved(Solution),
+    /// All reachable states have been explored, and none of them reaches the exit
+    Unsolvable,
+    /// The search has been aborted, because too many states would have to be explored
+    SearchLimitExceeded { explored_states: usize },
+}
+
+/// The part of the game state that influences the movement of the player.
+/// Collected coins do not change what the player is able to reach, hence only collected keys of
+/// all colors, opened doors, pushed crates, switches that have been toggled an odd number of
+/// times, crumbled platforms, falling blocks, the respawn point, the enemies and the number of
+/// lost lives are considered.
+/// On maps with vending machines, the star crystals and whether a vending machine is open are
+/// considered as well, since the player can buy keys with star crystals.
+#[derive(Clone, Eq, PartialEq, Hash)]
+struct SearchState {
+    position: (usize, usize),
+    keys: usize,
+    crystals: usize,
+    vending_machine_open: bool,
+    colored_keys: [usize; KeyColor::COUNT],
+    changed_tiles: BTreeSet<(usize, usize)>,
+    crates: BTreeSet<(usize, usize)>,
+    toggled_switches: BTreeSet<(usize, usize)>,
+    cracked_platform: Option<(usize, usize)>,
+    falling_blocks: BTreeSet<(usize, usize)>,
+    respawn_point: (usize, usize),
+    deaths: usize,
+    enemies: Vec<EnemyState>,
+}
+
+/// A node in the search tree
+#[derive(Clone)]
+struct SearchNode {
+    simulation: Simulation,
+    changed_tiles: BTreeSet<(usize, usize)>,
+    crates: BTreeSet<(usize, usize)>,
+    toggled_switches: BTreeSet<(usize, usize)>,
+    cracked_platform: Option<(usize, usize)>,
+    falling_blocks: BTreeSet<(usize, usize)>,
+    /// The index of this node in the list of parents
+    index: usize,
+}
+
+/// Find the minimum-move solution for the given world, exploring at most [DEFAULT_MAX_STATES] states.
+///
+/// # Examples
+/// ```
+/// use libexodus::simulation::solver::{solve, SolverResult};
+/// use libexodus::tiles::Tile;
+/// use libexodus::world::presets;
+/// let mut world = presets::map_with_border(6, 3);
+/// world.set(4, 1, Tile::EXIT);
+/// match solve(&world) {
+///     SolverResult::Solved(solution) => assert_eq!(2, solution.moves()),
+///     _ => panic!("The map should be solvable"),
+/// }
+/// world.set(3, 1, Tile::WALL);
+/// assert_eq!(SolverResult::Unsolvable, solve(&world));
+/// ```
+pub fn solve(world: &GameWorld) -> SolverResult {
+    solve_with_limit(world, DEFAULT_MAX_STATES)
+}
+
+/// Find the minimum-move solution for the given world, exploring at most `max_states` states.
+/// Whenever a vending machine is open, every affordable purchase is tried as well.
+/// Purchases do not count as moves.
+pub fn solve_with_limit(world: &GameWorld, max_states: usize) -> SolverResult {
+    let mut simulation = Simulation::new(world.clone(), Player::new());
+    simulation.settle();
+    match simulation.state() {
+        SimulationState::Running => {},
+        SimulationState::Won => {
+            return SolverResult::Solved(Solution {
+                actions: vec![],
+                purchases: vec![],
+                coins: simulation.coins(),
+                crystals: simulation.crystals(),
+            });
+        },
+        SimulationState::Lost => return SolverResult::Unsolvable,
+    }
+    let positions_of = |tile: Tile| -> BTreeSet<(usize, usize)> {
+        (0..world.width())
+            .flat_map(|x| (0..world.height()).map(move |y| (x, y)))
+            .filter(|(x, y)| simulation.world().get(*x as i32, *y as i32) == Some(&tile))
+            .collect()
+    };
+    let crates = positions_of(Tile::CRATE);
+    let falling_blocks = positions_of(Tile::FALLINGBLOCK);
+    let cracked_platform = positions_of(Tile::CRUMBLINGPLATFORMCRACKED).pop_first();
+    // Star crystals only matter if they can be spent
+    let has_vending_machines = !positions_of(Tile::VENDINGMACHINEL).is_empty()
+        || !positions_of(Tile::VENDINGMACHINER).is_empty();
+    let search_state = |node: &SearchNode| SearchState {
+        position: node.simulation.position(),
+        keys: node.simulation.keys(),
+        crystals: if has_vending_machines {
+            node.simulation.crystals()
+        } else {
+            0
+        },
+        vending_machine_open: node.simulation.is_vending_machine_open(),
+        colored_keys: node.simulation.all_colored_keys(),
+        changed_tiles: node.changed_tiles.clone(),
+        crates: node.crates.clone(),
+        toggled_switches: node.toggled_switches.clone(),
+        cracked_platform: node.cracked_platform,
+        falling_blocks: node.falling_blocks.clone(),
+        respawn_point: node.simulation.respawn_point(),
+        deaths: node.simulation.deaths(),
+        enemies: node.simulation.enemies().to_vec(),
+    };
+    // The parent index, the action and the purchase made after the action that led to each
+    // explored node, used to reconstruct the solution
+    let mut parents: Vec<Option<(usize, PlayerAction, Option<Purchase>)>> = vec![None];
+    let root = SearchNode {
+        simulation,
+        changed_tiles: BTreeSet::new(),
+        crates,
+        toggled_switches: BTreeSet::new(),
+        cracked_platform,
+        falling_blocks,
+        index: 0,
+    };
+    let mut visited: HashSet<SearchState> = HashSet::new();
+    visited.insert(search_state(&root));
+    let mut queue: VecDeque<SearchNode> = VecDeque::from([root]);
+    while let Some(node) = queue.pop_front() {
+        for action in PlayerAction::iter() {
+            let mut simulation = node.simulation.clone();
+            let mut changed_tiles = node.changed_tiles.clone();
+            let mut crates = node.crates.clone();
+            let mut toggled_switches = node.toggled_switches.clone();
+            let mut cracked_platform = node.cracked_platform;
+            let mut falling_blocks = node.falling_blocks.clone();
+            for event in simulation.step(action) {
+                match event {
+                    SimulationEvent::Collected {
+                        kind: CollectibleKind::Keys { .. },
+                        position,
+                    }
+                    | SimulationEvent::DoorOpened { position } => {
+                        changed_tiles.insert(position);
+                    },
+                    SimulationEvent::Collected {
+                        kind: CollectibleKind::StarCrystals { .. },
+                        position,
+                    } if has_vending_machines => {
+                        changed_tiles.insert(position);
+                    },
+                    SimulationEvent::CratePushed { from, to } => {
+                        crates.remove(&from);
+                        crates.insert(to);
+                    },
+                    SimulationEvent::SwitchToggled { position } => {
+                        // Toggling a switch twice restores the original state
+                        toggled_switches = &toggled_switches ^ &BTreeSet::from([position]);
+                    },
+                    SimulationEvent::PlatformCracked { position } => {
+                        cracked_platform = Some(position);
+                    },
+                    SimulationEvent::PlatformBroken { position } => {
+                        cracked_platform = None;
+                        changed_tiles.insert(position);
+                    },
+                    SimulationEvent::BlockFell { from, to } => {
+                        falling_blocks.remove(&from);
+                        falling_blocks.insert(to);
+                    },
+                    _ => {},
+                }
+            }
+            match simulation.state() {
+                SimulationState::Running => {},
+                SimulationState::Won => {
+                    let mut steps = vec![(action, None)];
+                    let mut index = node.index;
+                    while let Some((parent, action, purchase)) = parents[index] {
+                        steps.push((action, purchase));
+                        index = parent;
+                    }
+                    steps.reverse();
+                    // Each purchase is made right after the action that opened the vending machine
+                    let purchases = steps
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(index, (_, purchase))| purchase.map(|item| (index + 1, item)))
+                        .collect();
+                    return SolverResult::Solved(Solution {
+                        actions: steps.into_iter().map(|(action, _)| action).collect(),
+                        purchases,
+                        coins: simulation.coins(),
+                        crystals: simulation.crystals(),
+                    });
+                },
+                SimulationState::Lost => continue,
+            }
+            let child = SearchNode {
+                simulation,
+                changed_tiles,
+                crates,
+                toggled_switches,
+                cracked_platform,
+                falling_blocks,
+                index: 0,
+            };
+            // Purchases do not count as moves, hence all children are at the same depth
+            let mut children: Vec<(SearchNode, Option<Purchase>)> = vec![];
+            if child.simulation.is_vending_machine_open() {
+                for item in Purchase::iter() {
+                    let mut bought = child.clone();
+                    if bought.simulation.purchase(item) {
+                        children.push((bought, Some(item)));
+                    }
+                }
+            }
+            children.insert(0, (child, None));
+            for (mut child, purchase) in children {
+                let state = search_state(&child);
+                if visited.contains(&state) {
+                    continue;
+                }
+                if visited.len() >= max_states {
+                    return SolverResult::SearchLimitExceeded {
+                        explored_states: visited.len(),
+                    };
+                }
+                visited.insert(state);
+                parents.push(Some((node.index, action, purchase)));
+                child.index = parents.len() - 1;
+                queue.push_back(child);
+            }
+        }
+    }
+    SolverResult::Unsolvable
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::enemies::Enemy;
+    use crate::player::Player;
+    use crate::simulation::solver::{SolverResult, solve, solve_with_limit};
+    use crate::simulation::{PlayerAction, Purchase, Simulation, SimulationState};
+    use crate::tiles::Tile;
+    use crate::world::GameWorld;
+    use crate::world::presets::map_with_border_and_spawn;
+
+    /// Solve the given world and make sure the solution actually reaches the exit
+    fn assert_solution(world: &GameWorld, expected_moves: usize) -> Vec<PlayerAction> {
+        let SolverResult::Solved(solution) = solve(world) else {
+            panic!("The world should be solvable");
+        };
+        assert_eq!(expected_moves, solution.moves());
+        let mut simulation = Simulation::new(world.clone(), Player::new());
+        let mut purchases = solution.purchases().iter().peekable();
+        for (index, action) in solution.actions().iter().enumerate() {
+            while let Some((_, item)) = purchases.next_if(|(i, _)| *i == index) {
+                assert!(simulation.purchase(*item));
+            }
+            simulation.step(*action);
+        }
+        assert!(purchases.next().is_none());
+        assert_eq!(SimulationState::Won, simulation.state());
+        assert_eq!(solution.coins(), simulation.coins());
+        solution.actions().to_vec()
+    }
+
+    #[test]
+    fn test_walk_to_exit() {
+        let mut world = map_with_border_and_spawn(8, 3);
+        world.set(6, 1, Tile::EXIT);
+        // The ceiling blocks the upward movements, so the player can jump two tiles to the right
+        assert_solution(&world, 3);
+    }
+
+    #[test]
+    fn test_jump_over_wall() {
+        let mut world = map_with_border_and_spawn(8, 6);
+        world.set(3, 1, Tile::WALL);
+        world.set(5, 1, Tile::EXIT);
+        assert_solution(&world, 2);
+    }
+
+    #[test]
+    fn test_jump_over_spikes() {
+        let mut world = map_with_border_and_spawn(8, 6);
+        world.set(3, 1, Tile::SPIKES);
+        world.set(6, 1, Tile::EXIT);
+        let actions = assert_solution(&world, 3);
+        assert!(actions.contains(&PlayerAction::JumpRight));
+    }
+
+    #[test]
+    fn test_enemy_blocks_corridor() {
+        let mut world = map_with_border_and_spawn(8, 3);
+        world.set(6, 1, Tile::EXIT);
+        world.add_enemy(Enemy::flyer((3, 1), vec![(5, 1)]));
+        // The player cannot jump over the enemy, which is deadly from all directions
+        assert_eq!(SolverResult::Unsolvable, solve(&world));
+    }
+
+    #[test]
+    fn test_climb_wall_using_crate() {
+        let mut world = map_with_border_and_spawn(8, 6);
+        for y in 1..4 {
+            world.set(5, y, Tile::WALL);
+        }
+        world.set(6, 1, Tile::EXIT);
+        assert_eq!(SolverResult::Unsolvable, solve(&world));
+        world.set(3, 1, Tile::CRATE);
+        assert!(matches!(solve(&world), SolverResult::Solved(_)));
+    }
+
+    #[test]
+    fn test_exit_behind_wall_is_unsolvable() {
+        let mut world = map_with_border_and_spawn(8, 8);
+        for y in 1..7 {
+            world.set(4, y, Tile::WALL);
+        }
+        world.set(6, 1, Tile::EXIT);
+        assert_eq!(SolverResult::Unsolvable, solve(&world));
+    }
+
+    #[test]
+    fn test_no_exit_is_unsolvable() {
+        assert_eq!(
+            SolverResult::Unsolvable,
+            solve(&map_with_border_and_spawn(8, 8))
+        );
+    }
+
+    #[test]
+    fn test_door_needs_key() {
+        let mut world = map_with_border_and_spawn(8, 3);
+        world.set(4, 1, Tile::DOOR);
+        world.set(6, 1, Tile::EXIT);
+        assert_eq!(SolverResult::Unsolvable, solve(&world));
+        // The key lies behind the player, so the player has to walk back first
+        world.set(2, 1, Tile::PLAYERSPAWN);
+        world.set(1, 1, Tile::KEY);
+        assert_solution(&world, 4);
+    }
+
+    #[test]
+    fn test_buy_key_at_vending_machine() {
+        let mut world = map_with_border_and_spawn(11, 3);
+        for x in 2..5 {
+            world.set(x, 1, Tile::STARCRYSTAL);
+        }
+        world.set(5, 0, Tile::VENDINGMACHINEL);
+        world.set(7, 1, Tile::DOOR);
+        world.set(9, 1, Tile::EXIT);
+        let SolverResult::Solved(solution) = solve(&world) else {
+            panic!("The key can be bought at the vending machine");
+        };
+        assert!(matches!(solution.purchases(), [(_, Purchase::Key)]));
+        assert_solution(&world, solution.moves());
+        // Without enough star crystals, the key cannot be bought
+        world.set(4, 1, Tile::AIR);
+        assert_eq!(SolverResult::Unsolvable, solve(&world));
+    }
+
+    #[test]
+    fn test_lever_opens_wall() {
+        let mut world = map_with_border_and_spawn(8, 3);
+        world.set(4, 1, Tile::TOGGLEBLOCK);
+        world.set(6, 1, Tile::EXIT);
+        assert_eq!(SolverResult::Unsolvable, solve(&world));
+        // The lever lies behind the player and must only be pulled once
+        world.set(2, 1, Tile::PLAYERSPAWN);
+        world.set(1, 1, Tile::LEVER);
+        world.link((1, 1), (4, 1));
+        assert_solution(&world, 4);
+    }
+
+    #[test]
+    fn test_crumbling_platform_cannot_be_crossed_twice() {
+        // The ceiling prevents the player from jumping higher, and the gap is too wide to jump over
+        let mut world = map_with_border_and_spawn(10, 5);
+        for x in [1, 5, 6, 7, 8] {
+            world.set(x, 2, Tile::WALL);
+        }
+        for x in 2..5 {
+            world.set(x, 2, Tile::CRUMBLINGPLATFORM);
+            world.set(x, 1, Tile::SPIKES);
+        }
+        world.set(1, 3, Tile::KEY);
+        world.set(5, 3, Tile::PLAYERSPAWN);
+        world.set(7, 3, Tile::DOOR);
+        world.set(8, 3, Tile::EXIT);
+        world.set_lives(1);
+        assert_eq!(SolverResult::Unsolvable, solve(&world));
+        for x in 2..5 {
+            world.set(x, 2, Tile::WALL);
+        }
+        assert!(matches!(solve(&world), SolverResult::Solved(_)));
+    }
+
+    #[test]
+    fn test_climb_ladder() {
+        let mut world = map_with_border_and_spawn(5, 10);
+        for y in 1..8 {
+            world.set(2, y, Tile::LADDER);
+        }
+        for x in 1..4 {
+            if x != 2 {
+                world.set(x, 5, Tile::WALL);
+            }
+        }
+        world.set(3, 6, Tile::EXIT);
+        // Jump onto the ladder, climb three tiles and step onto the exit
+        assert_solution(&world, 5);
+    }
+
+    #[test]
+    fn test_example_worlds_are_solvable() {
+        assert_solution(&GameWorld::exampleworld(), 4);
+        assert_solution(&GameWorld::showcaseworld(), 11);
+    }
+
+    #[test]
+    fn test_search_limit() {
+        let mut world = map_with_border_and_spawn(30, 3);
+        world.set(28, 1, Tile::EXIT);
+        assert_eq!(
+            SolverResult::SearchLimitExceeded { explored_states: 3 },
+            solve_with_limit(&world, 3)
+        );
+    }
+}
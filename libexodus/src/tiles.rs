@@ -55,21 +55,38 @@ pub enum TileKind {
     /// A collectible the player may collect by stepping onto it
     COLLECTIBLE { kind: CollectibleKind },
     ///
-    /// A door that can be opened (removed) using a key
-    DOOR,
+    /// A door that can be opened (removed) using a key of the given color, or an uncolored key
+    /// if no color is given
+    DOOR { color: Option<KeyColor> },
     ///
     /// A ladder
     LADDER,
     ///
     /// The map exit
     EXIT,
+    ///
+    /// A checkpoint that sets the respawn point of the player
+    CHECKPOINT,
+    ///
+    /// A solid block that can be pushed by the player and falls down
+    CRATE,
+    ///
+    /// A switch that toggles all blocks that are linked to it when the player enters it
+    SWITCH,
 }
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum CollectibleKind {
     Decorative,
-    Coins { amount: usize },
-    Keys { amount: usize },
-    StarCrystals { amount: usize },
+    Coins {
+        amount: usize,
+    },
+    Keys {
+        amount: usize,
+        color: Option<KeyColor>,
+    },
+    StarCrystals {
+        amount: usize,
+    },
 }
 
 pub type AtlasIndex = usize;
@@ -117,6 +134,53 @@ impl TeleportId {
     }
 }
 
+/// The color of a colored key, which only opens doors of the same color
+#[derive(
+    Default, Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, EnumIter, EnumCountMacro, Hash,
+)]
+pub enum KeyColor {
+    #[default]
+    RED,
+    GREEN,
+    BLUE,
+}
+
+impl KeyColor {
+    pub const fn const_to_u8(&self) -> u8 {
+        match self {
+            KeyColor::RED => 0,
+            KeyColor::GREEN => 1,
+            KeyColor::BLUE => 2,
+        }
+    }
+    pub const fn const_from_u8(value: u8) -> Self {
+        match value {
+            0 => KeyColor::RED,
+            1 => KeyColor::GREEN,
+            2 => KeyColor::BLUE,
+            _ => panic!("Invalid Key Color"),
+        }
+    }
+    /// Get a unique string id, describing this color. Suitable for i18n keys.
+    pub fn str_id(&self) -> &str {
+        match self {
+            KeyColor::RED => "red",
+            KeyColor::GREEN => "green",
+            KeyColor::BLUE => "blue",
+        }
+    }
+}
+
+impl fmt::Display for KeyColor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyColor::RED => write!(f, "Red"),
+            KeyColor::GREEN => write!(f, "Green"),
+            KeyColor::BLUE => write!(f, "Blue"),
+        }
+    }
+}
+
 // Tiles Definitions
 #[derive(Clone, Debug, Eq, PartialEq, EnumIter, EnumCountMacro, Default)]
 pub enum Tile {
@@ -223,6 +287,30 @@ pub enum Tile {
     VENDINGMACHINEL,
     /// A vending machine facing to the right which the player can interact with from the right or top
     VENDINGMACHINER,
+    /// A checkpoint. If the player loses a life after passing it, they respawn at this checkpoint.
+    CHECKPOINT,
+    /// A crate that the player can push horizontally. Crates fall down and can cover gaps and spikes.
+    CRATE,
+    /// A door that can only be opened with a key of the same color
+    COLOREDDOOR { color: KeyColor },
+    /// An opened colored door
+    OPENCOLOREDDOOR { color: KeyColor },
+    /// A collectible colored key
+    COLOREDKEY { color: KeyColor },
+    /// A lever that toggles all linked blocks whenever the player enters it
+    LEVER,
+    /// A lever that has been pulled by the player
+    LEVERPULLED,
+    /// A solid block that appears and disappears when a linked switch is toggled
+    TOGGLEBLOCK,
+    /// A toggle block that has disappeared, i.e., the outline of a toggle block
+    TOGGLEBLOCKOFF,
+    /// A platform that cracks when the player stands on it and breaks after the player's next move
+    CRUMBLINGPLATFORM,
+    /// A crumbling platform the player is standing on. It breaks after the player's next move.
+    CRUMBLINGPLATFORMCRACKED,
+    /// A solid block that falls down as soon as the tile below it is vacated
+    FALLINGBLOCK,
 }
 
 impl Tile {
@@ -298,9 +386,12 @@ impl Tile {
             Tile::WALLSPIKESRLT => TileKind::DEADLY {
                 from: vec![FROMNORTH, FROMEAST, FROMWEST],
             },
-            Tile::DOOR => TileKind::DOOR,
+            Tile::DOOR => TileKind::DOOR { color: None },
             Tile::KEY => TileKind::COLLECTIBLE {
-                kind: CollectibleKind::Keys { amount: 1 },
+                kind: CollectibleKind::Keys {
+                    amount: 1,
+                    color: None,
+                },
             },
             Tile::OPENDOOR => TileKind::AIR,
             Tile::ARROWRIGHT => TileKind::COLLECTIBLE {
@@ -344,6 +435,25 @@ impl Tile {
             Tile::STARCRYSTAL => TileKind::COLLECTIBLE {
                 kind: CollectibleKind::StarCrystals { amount: 1 },
             },
+            Tile::CHECKPOINT => TileKind::CHECKPOINT,
+            Tile::CRATE => TileKind::CRATE,
+            Tile::COLOREDDOOR { color } => TileKind::DOOR {
+                color: Some(*color),
+            },
+            Tile::OPENCOLOREDDOOR { .. } => TileKind::AIR,
+            Tile::COLOREDKEY { color } => TileKind::COLLECTIBLE {
+                kind: CollectibleKind::Keys {
+                    amount: 1,
+                    color: Some(*color),
+                },
+            },
+            Tile::LEVER => TileKind::SWITCH,
+            Tile::LEVERPULLED => TileKind::SWITCH,
+            Tile::TOGGLEBLOCK => TileKind::SOLID,
+            Tile::TOGGLEBLOCKOFF => TileKind::AIR,
+            Tile::CRUMBLINGPLATFORM => TileKind::SOLID,
+            Tile::CRUMBLINGPLATFORMCRACKED => TileKind::SOLID,
+            Tile::FALLINGBLOCK => TileKind::SOLID,
         }
     }
     pub fn atlas_index(&self) -> Option<AtlasIndex> {
@@ -403,6 +513,18 @@ impl Tile {
             Tile::VENDINGMACHINEL => Some(74),
             Tile::VENDINGMACHINER => Some(75),
             Tile::STARCRYSTAL => Some(202),
+            Tile::CHECKPOINT => Some(ANGEL_SPRITE),
+            Tile::CRATE => Some(203),
+            Tile::COLOREDDOOR { color } => Some(204 + color.const_to_u8() as AtlasIndex),
+            Tile::OPENCOLOREDDOOR { color } => Some(197 + color.const_to_u8() as AtlasIndex),
+            Tile::COLOREDKEY { color } => Some(208 + color.const_to_u8() as AtlasIndex),
+            Tile::LEVER => Some(211),
+            Tile::LEVERPULLED => Some(212),
+            Tile::TOGGLEBLOCK => Some(213),
+            Tile::TOGGLEBLOCKOFF => Some(214),
+            Tile::CRUMBLINGPLATFORM => Some(215),
+            Tile::CRUMBLINGPLATFORMCRACKED => Some(218),
+            Tile::FALLINGBLOCK => Some(219),
         }
     }
     pub fn can_collide_from(&self, from_direction: &FromDirection) -> bool {
@@ -415,8 +537,11 @@ impl Tile {
             TileKind::PLAYERSPAWN => false,
             TileKind::COLLECTIBLE { .. } => false,
             TileKind::LADDER => false,
-            TileKind::DOOR => true,
+            TileKind::DOOR { .. } => true,
             TileKind::EXIT => false,
+            TileKind::CHECKPOINT => false,
+            TileKind::CRATE => true,
+            TileKind::SWITCH => false,
         }
     }
     pub fn is_deadly_from(&self, from_direction: &FromDirection) -> bool {
@@ -428,9 +553,96 @@ impl Tile {
             TileKind::SPECIAL { .. } => false,
             TileKind::PLAYERSPAWN => false,
             TileKind::LADDER => false,
-            TileKind::DOOR => false,
+            TileKind::DOOR { .. } => false,
             TileKind::COLLECTIBLE { .. } => false,
             TileKind::EXIT => false,
+            TileKind::CHECKPOINT => false,
+            TileKind::CRATE => false,
+            TileKind::SWITCH => false,
+        }
+    }
+    /// Get the key of the given color, or the uncolored key if no color is given
+    pub fn key_of_color(color: Option<KeyColor>) -> Tile {
+        match color {
+            None => Tile::KEY,
+            Some(color) => Tile::COLOREDKEY { color },
+        }
+    }
+    /// Get the door of the given color, or the uncolored door if no color is given
+    pub fn door_of_color(color: Option<KeyColor>) -> Tile {
+        match color {
+            None => Tile::DOOR,
+            Some(color) => Tile::COLOREDDOOR { color },
+        }
+    }
+    /// Get the opened door that replaces this door when it is opened with a key,
+    /// or None if this tile is no door.
+    ///
+    /// ```rust
+    /// use libexodus::tiles::{KeyColor, Tile};
+    /// assert_eq!(Some(Tile::OPENDOOR), Tile::DOOR.opened_door());
+    /// assert_eq!(
+    ///     Some(Tile::OPENCOLOREDDOOR { color: KeyColor::BLUE }),
+    ///     Tile::COLOREDDOOR { color: KeyColor::BLUE }.opened_door()
+    /// );
+    /// assert_eq!(None, Tile::WALL.opened_door());
+    /// ```
+    pub fn opened_door(&self) -> Option<Tile> {
+        match self {
+            Tile::DOOR => Some(Tile::OPENDOOR),
+            Tile::COLOREDDOOR { color } => Some(Tile::OPENCOLOREDDOOR { color: *color }),
+            _ => None,
+        }
+    }
+    /// Get the closed door that replaces this opened door when the game state is reset,
+    /// or None if this tile is no opened door.
+    pub fn closed_door(&self) -> Option<Tile> {
+        match self {
+            Tile::OPENDOOR => Some(Tile::DOOR),
+            Tile::OPENCOLOREDDOOR { color } => Some(Tile::COLOREDDOOR { color: *color }),
+            _ => None,
+        }
+    }
+    /// Get the tile that replaces this tile when a switch that is linked to it is toggled,
+    /// or None if this tile cannot be toggled.
+    /// Levers are pulled and released, toggle blocks appear and disappear and doors are opened and closed.
+    ///
+    /// ```rust
+    /// use libexodus::tiles::Tile;
+    /// assert_eq!(Some(Tile::LEVERPULLED), Tile::LEVER.toggled());
+    /// assert_eq!(Some(Tile::TOGGLEBLOCK), Tile::TOGGLEBLOCKOFF.toggled());
+    /// assert_eq!(Some(Tile::OPENDOOR), Tile::DOOR.toggled());
+    /// assert_eq!(Some(Tile::DOOR), Tile::OPENDOOR.toggled());
+    /// assert_eq!(None, Tile::WALL.toggled());
+    /// ```
+    pub fn toggled(&self) -> Option<Tile> {
+        match self {
+            Tile::LEVER => Some(Tile::LEVERPULLED),
+            Tile::LEVERPULLED => Some(Tile::LEVER),
+            Tile::TOGGLEBLOCK => Some(Tile::TOGGLEBLOCKOFF),
+            Tile::TOGGLEBLOCKOFF => Some(Tile::TOGGLEBLOCK),
+            tile => tile.opened_door().or_else(|| tile.closed_door()),
+        }
+    }
+    /// Check if a crate that is moved in from the given direction may enter this tile.
+    /// Crates can be moved through air and onto spikes that sit on the ground, which are covered by the crate.
+    ///
+    /// ```rust
+    /// use libexodus::directions::FromDirection;
+    /// use libexodus::tiles::Tile;
+    /// assert!(Tile::AIR.can_be_entered_by_crate(&FromDirection::FROMWEST));
+    /// assert!(Tile::SPIKESSLOPED.can_be_entered_by_crate(&FromDirection::FROMNORTH));
+    /// assert!(!Tile::SPIKESSLOPED.can_be_entered_by_crate(&FromDirection::FROMWEST));
+    /// assert!(!Tile::WALLSPIKESL.can_be_entered_by_crate(&FromDirection::FROMWEST));
+    /// assert!(!Tile::COIN.can_be_entered_by_crate(&FromDirection::FROMNORTH));
+    /// ```
+    pub fn can_be_entered_by_crate(&self, from_direction: &FromDirection) -> bool {
+        match self.kind() {
+            TileKind::AIR => true,
+            TileKind::DEADLY { from } => {
+                self.wall_spike_directions().is_none() && from.contains(from_direction)
+            },
+            _ => false,
         }
     }
     /// Get a unique string id, describing this tile. Suitable for i18n keys.
@@ -488,6 +700,152 @@ impl Tile {
             Tile::VENDINGMACHINEL => "vending_machine_l",
             Tile::VENDINGMACHINER => "vending_machine_r",
             Tile::STARCRYSTAL => "star_crystal",
+            Tile::CHECKPOINT => "checkpoint",
+            Tile::CRATE => "crate",
+            Tile::COLOREDDOOR { color } => match color {
+                KeyColor::RED => "door_red",
+                KeyColor::GREEN => "door_green",
+                KeyColor::BLUE => "door_blue",
+            },
+            Tile::OPENCOLOREDDOOR { color } => match color {
+                KeyColor::RED => "opendoor_red",
+                KeyColor::GREEN => "opendoor_green",
+                KeyColor::BLUE => "opendoor_blue",
+            },
+            Tile::COLOREDKEY { color } => match color {
+                KeyColor::RED => "key_red",
+                KeyColor::GREEN => "key_green",
+                KeyColor::BLUE => "key_blue",
+            },
+            Tile::LEVER => "lever",
+            Tile::LEVERPULLED => "lever_pulled",
+            Tile::TOGGLEBLOCK => "toggle_block",
+            Tile::TOGGLEBLOCKOFF => "toggle_block_off",
+            Tile::CRUMBLINGPLATFORM => "crumbling_platform",
+            Tile::CRUMBLINGPLATFORMCRACKED => "crumbling_platform_cracked",
+            Tile::FALLINGBLOCK => "falling_block",
+        }
+    }
+    /// Get the directions that the spikes of this wall spike tile point to, as a bit mask of
+    /// `1` (left), `2` (right), `4` (top) and `8` (bottom), or None if this is no wall spike tile.
+    fn wall_spike_directions(&self) -> Option<u8> {
+        match self {
+            Tile::WALLSPIKESL => Some(0b0001),
+            Tile::WALLSPIKESR => Some(0b0010),
+            Tile::WALLSPIKESLR => Some(0b0011),
+            Tile::WALLSPIKEST => Some(0b0100),
+            Tile::WALLSPIKESLT => Some(0b0101),
+            Tile::WALLSPIKESRT => Some(0b0110),
+            Tile::WALLSPIKESRLT => Some(0b0111),
+            Tile::WALLSPIKESB => Some(0b1000),
+            Tile::WALLSPIKESLB => Some(0b1001),
+            Tile::WALLSPIKESRB => Some(0b1010),
+            Tile::WALLSPIKESRLB => Some(0b1011),
+            Tile::WALLSPIKESTB => Some(0b1100),
+            Tile::WALLSPIKESLTB => Some(0b1101),
+            Tile::WALLSPIKESRTB => Some(0b1110),
+            Tile::WALLSPIKESRLTB => Some(0b1111),
+            _ => None,
+        }
+    }
+    /// Get the wall spike tile with spikes pointing to the given directions,
+    /// encoded as in `wall_spike_directions()`
+    fn from_wall_spike_directions(directions: u8) -> Tile {
+        match directions {
+            0b0001 => Tile::WALLSPIKESL,
+            0b0010 => Tile::WALLSPIKESR,
+            0b0011 => Tile::WALLSPIKESLR,
+            0b0100 => Tile::WALLSPIKEST,
+            0b0101 => Tile::WALLSPIKESLT,
+            0b0110 => Tile::WALLSPIKESRT,
+            0b0111 => Tile::WALLSPIKESRLT,
+            0b1000 => Tile::WALLSPIKESB,
+            0b1001 => Tile::WALLSPIKESLB,
+            0b1010 => Tile::WALLSPIKESRB,
+            0b1011 => Tile::WALLSPIKESRLB,
+            0b1100 => Tile::WALLSPIKESTB,
+            0b1101 => Tile::WALLSPIKESLTB,
+            0b1110 => Tile::WALLSPIKESRTB,
+            _ => Tile::WALLSPIKESRLTB,
+        }
+    }
+    /// Get the tile that is seen if this tile is mirrored along the vertical axis,
+    /// i.e., left and right are swapped.
+    /// Tiles without an orientation are returned unchanged.
+    ///
+    /// ```rust
+    /// use libexodus::tiles::Tile;
+    /// assert_eq!(Tile::ARROWRIGHT, Tile::ARROWLEFT.mirrored_horizontally());
+    /// assert_eq!(Tile::WALLSPIKESRTB, Tile::WALLSPIKESLTB.mirrored_horizontally());
+    /// assert_eq!(Tile::WALL, Tile::WALL.mirrored_horizontally());
+    /// ```
+    pub fn mirrored_horizontally(&self) -> Tile {
+        if let Some(directions) = self.wall_spike_directions() {
+            let left_right = ((directions & 0b01) << 1) | ((directions & 0b10) >> 1);
+            return Tile::from_wall_spike_directions((directions & 0b1100) | left_right);
+        }
+        match self {
+            Tile::ARROWLEFT => Tile::ARROWRIGHT,
+            Tile::ARROWRIGHT => Tile::ARROWLEFT,
+            Tile::COBBLEROOFSLOPEL => Tile::COBBLEROOFSLOPER,
+            Tile::COBBLEROOFSLOPER => Tile::COBBLEROOFSLOPEL,
+            Tile::VENDINGMACHINEL => Tile::VENDINGMACHINER,
+            Tile::VENDINGMACHINER => Tile::VENDINGMACHINEL,
+            tile => tile.clone(),
+        }
+    }
+    /// Get the tile that is seen if this tile is mirrored along the horizontal axis,
+    /// i.e., top and bottom are swapped.
+    /// Tiles without an orientation are returned unchanged.
+    ///
+    /// ```rust
+    /// use libexodus::tiles::Tile;
+    /// assert_eq!(Tile::ARROWDOWN, Tile::ARROWUP.mirrored_vertically());
+    /// assert_eq!(Tile::WALLSPIKESLB, Tile::WALLSPIKESLT.mirrored_vertically());
+    /// ```
+    pub fn mirrored_vertically(&self) -> Tile {
+        if let Some(directions) = self.wall_spike_directions() {
+            let top_bottom = ((directions & 0b0100) << 1) | ((directions & 0b1000) >> 1);
+            return Tile::from_wall_spike_directions((directions & 0b0011) | top_bottom);
+        }
+        match self {
+            Tile::ARROWUP => Tile::ARROWDOWN,
+            Tile::ARROWDOWN => Tile::ARROWUP,
+            tile => tile.clone(),
+        }
+    }
+    /// Get the tile that is seen if this tile is rotated by 90 degrees clockwise.
+    /// Tiles without an orientation are returned unchanged.
+    ///
+    /// ```rust
+    /// use libexodus::tiles::Tile;
+    /// assert_eq!(Tile::ARROWRIGHT, Tile::ARROWUP.rotated_clockwise());
+    /// assert_eq!(Tile::WALLSPIKESRB, Tile::WALLSPIKESRT.rotated_clockwise());
+    /// ```
+    pub fn rotated_clockwise(&self) -> Tile {
+        if let Some(directions) = self.wall_spike_directions() {
+            // Left -> Top, Right -> Bottom, Top -> Right, Bottom -> Left
+            let mut rotated = 0;
+            if directions & 0b0001 != 0 {
+                rotated |= 0b0100;
+            }
+            if directions & 0b0010 != 0 {
+                rotated |= 0b1000;
+            }
+            if directions & 0b0100 != 0 {
+                rotated |= 0b0010;
+            }
+            if directions & 0b1000 != 0 {
+                rotated |= 0b0001;
+            }
+            return Tile::from_wall_spike_directions(rotated);
+        }
+        match self {
+            Tile::ARROWUP => Tile::ARROWRIGHT,
+            Tile::ARROWRIGHT => Tile::ARROWDOWN,
+            Tile::ARROWDOWN => Tile::ARROWLEFT,
+            Tile::ARROWLEFT => Tile::ARROWUP,
+            tile => tile.clone(),
         }
     }
 }
@@ -550,6 +908,30 @@ impl fmt::Display for Tile {
                 Tile::VENDINGMACHINEL => "L Vending Machine",
                 Tile::VENDINGMACHINER => "R Vending Machine",
                 Tile::STARCRYSTAL => "Star Crystal",
+                Tile::CHECKPOINT => "Checkpoint",
+                Tile::CRATE => "Crate",
+                Tile::COLOREDDOOR { color } => match color {
+                    KeyColor::RED => "Red Door",
+                    KeyColor::GREEN => "Green Door",
+                    KeyColor::BLUE => "Blue Door",
+                },
+                Tile::OPENCOLOREDDOOR { color } => match color {
+                    KeyColor::RED => "Open Red Door",
+                    KeyColor::GREEN => "Open Green Door",
+                    KeyColor::BLUE => "Open Blue Door",
+                },
+                Tile::COLOREDKEY { color } => match color {
+                    KeyColor::RED => "Red Key",
+                    KeyColor::GREEN => "Green Key",
+                    KeyColor::BLUE => "Blue Key",
+                },
+                Tile::LEVER => "Lever",
+                Tile::LEVERPULLED => "Pulled Lever",
+                Tile::TOGGLEBLOCK => "Toggle Block",
+                Tile::TOGGLEBLOCKOFF => "Toggle Block (Off)",
+                Tile::CRUMBLINGPLATFORM => "Crumbling Platform",
+                Tile::CRUMBLINGPLATFORMCRACKED => "Cracked Platform",
+                Tile::FALLINGBLOCK => "Falling Block",
             }
         )
     }
@@ -591,3 +973,94 @@ impl UITiles {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::enemies::{FLYER_SPRITE, WALKER_SPRITE};
+    use crate::player::Player;
+    use crate::tiles::{
+        ANGEL_SPRITE, AtlasIndex, EXITING_PLAYER_SPRITE, KeyColor, TeleportId, Tile, UITiles,
+    };
+    use std::collections::HashMap;
+    use strum::IntoEnumIterator;
+
+    /// Get all tiles, including all variants of tiles with a teleport ID or a key color
+    fn all_tiles() -> Vec<Tile> {
+        Tile::iter()
+            .flat_map(|tile| match tile {
+                Tile::TELEPORTENTRY { .. } => TeleportId::iter()
+                    .map(|teleport_id| Tile::TELEPORTENTRY { teleport_id })
+                    .collect(),
+                Tile::TELEPORTEXIT { .. } => TeleportId::iter()
+                    .map(|teleport_id| Tile::TELEPORTEXIT { teleport_id })
+                    .collect(),
+                Tile::COLOREDDOOR { .. } => KeyColor::iter()
+                    .map(|color| Tile::COLOREDDOOR { color })
+                    .collect(),
+                Tile::OPENCOLOREDDOOR { .. } => KeyColor::iter()
+                    .map(|color| Tile::OPENCOLOREDDOOR { color })
+                    .collect(),
+                Tile::COLOREDKEY { .. } => KeyColor::iter()
+                    .map(|color| Tile::COLOREDKEY { color })
+                    .collect(),
+                tile => vec![tile],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_atlas_indices_are_unique() {
+        let mut seen: HashMap<AtlasIndex, Tile> = HashMap::new();
+        for tile in all_tiles() {
+            if let Some(index) = tile.atlas_index()
+                && let Some(other) = seen.insert(index, tile.clone())
+            {
+                panic!("{:?} and {:?} share the atlas index {}", other, tile, index);
+            }
+        }
+    }
+
+    #[test]
+    fn test_colored_key_and_door_sprites_use_unused_slots() {
+        let mut right_player = Player::new();
+        right_player.set_face_right(true);
+        let mut left_player = Player::new();
+        left_player.set_face_right(false);
+        let used: Vec<AtlasIndex> = all_tiles()
+            .iter()
+            .filter(|tile| {
+                !matches!(
+                    tile,
+                    Tile::COLOREDDOOR { .. }
+                        | Tile::OPENCOLOREDDOOR { .. }
+                        | Tile::COLOREDKEY { .. }
+                )
+            })
+            .filter_map(Tile::atlas_index)
+            .chain(UITiles::iter().filter_map(|tile| tile.atlas_index()))
+            .chain([
+                right_player.atlas_index(),
+                left_player.atlas_index(),
+                WALKER_SPRITE,
+                FLYER_SPRITE,
+                ANGEL_SPRITE,
+                EXITING_PLAYER_SPRITE,
+            ])
+            .collect();
+        for color in KeyColor::iter() {
+            for tile in [
+                Tile::COLOREDDOOR { color },
+                Tile::OPENCOLOREDDOOR { color },
+                Tile::COLOREDKEY { color },
+            ] {
+                let index = tile.atlas_index().unwrap();
+                assert!(
+                    !used.contains(&index),
+                    "{:?} uses the atlas index {}, which is already in use",
+                    tile,
+                    index
+                );
+            }
+        }
+    }
+}
@@ -49,6 +49,15 @@ impl Tileset {
             Tileset::Antarctica => 32,
         }
     }
+    /// Get a unique string id, describing this tileset.
+    /// Consists only of underscores and lower-case characters.
+    pub fn str_id(&self) -> &str {
+        match self {
+            Tileset::TinyPlatformQuestTiles => "tiny_platform_quest_tiles",
+            Tileset::Classic => "classic",
+            Tileset::Antarctica => "antarctica",
+        }
+    }
 }
 
 impl Display for Tileset {
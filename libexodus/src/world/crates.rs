@@ -0,0 +1,175 @@
+use crate::directions::{Directions, FromDirection};
+use crate::tiles::Tile;
+use crate::world::GameWorld;
+
+impl GameWorld {
+    /// Push the crate at the given position by one tile into the given horizontal direction.
+    /// Afterwards, the crate falls down until it lands on a tile it cannot enter.
+    /// Crates that carry another crate are too heavy to be pushed.
+    /// Return the position at which the crate has come to rest, or None if the crate could not be pushed.
+    ///
+    /// All changes are undone by [GameWorld::reset_game_state].
+    ///
+    /// ```rust
+    /// use libexodus::directions::Directions;
+    /// use libexodus::tiles::Tile;
+    /// use libexodus::world::presets;
+    /// let mut world = presets::map_with_border(5, 5);
+    /// world.set(1, 2, Tile::WALL);
+    /// world.set(1, 3, Tile::CRATE);
+    /// assert_eq!(Some((2, 1)), world.push_crate((1, 3), Directions::EAST));
+    /// assert_eq!(Tile::CRATE, *world.get(2, 1).unwrap());
+    /// world.reset_game_state();
+    /// assert_eq!(Tile::CRATE, *world.get(1, 3).unwrap());
+    /// assert_eq!(Tile::AIR, *world.get(2, 1).unwrap());
+    /// ```
+    pub fn push_crate(
+        &mut self,
+        position: (usize, usize),
+        direction: Directions,
+    ) -> Option<(usize, usize)> {
+        let (x, y) = (position.0 as i32, position.1 as i32);
+        if self.get(x, y) != Some(&Tile::CRATE) || self.get(x, y + 1) == Some(&Tile::CRATE) {
+            return None;
+        }
+        let target_x = match direction {
+            Directions::EAST => x + 1,
+            Directions::WEST => x - 1,
+            Directions::NORTH | Directions::SOUTH => return None,
+        };
+        if !self
+            .get(target_x, y)
+            .is_some_and(|tile| tile.can_be_entered_by_crate(&FromDirection::from(direction)))
+        {
+            return None;
+        }
+        let mut target = (target_x as usize, position.1);
+        while target.1 > 0
+            && self
+                .get(target.0 as i32, target.1 as i32 - 1)
+                .is_some_and(|tile| tile.can_be_entered_by_crate(&FromDirection::FROMNORTH))
+        {
+            target.1 -= 1;
+        }
+        let uncovered = self
+            .game_state_changes
+            .covered_tiles
+            .remove(&position)
+            .unwrap_or(Tile::AIR);
+        self.set_during_game(position, uncovered);
+        let covered = self.data[target.0][target.1].clone();
+        self.game_state_changes
+            .covered_tiles
+            .insert(target, covered);
+        self.set_during_game(target, Tile::CRATE);
+        Some(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::directions::Directions;
+    use crate::tiles::Tile;
+    use crate::world::GameWorld;
+    use crate::world::presets::map_with_border;
+
+    /// Create a world with border and a crate at (2,1)
+    fn world_with_crate() -> GameWorld {
+        let mut world = map_with_border(7, 5);
+        world.set(2, 1, Tile::CRATE);
+        world
+    }
+
+    #[test]
+    fn test_push_crate_on_ground() {
+        let mut world = world_with_crate();
+        assert_eq!(Some((3, 1)), world.push_crate((2, 1), Directions::EAST));
+        assert_eq!(Tile::AIR, *world.get(2, 1).unwrap());
+        assert_eq!(Tile::CRATE, *world.get(3, 1).unwrap());
+        assert_eq!(Some((2, 1)), world.push_crate((3, 1), Directions::WEST));
+        assert_eq!(Tile::CRATE, *world.get(2, 1).unwrap());
+        assert_eq!(Tile::AIR, *world.get(3, 1).unwrap());
+    }
+
+    #[test]
+    fn test_push_crate_vertically() {
+        let mut world = world_with_crate();
+        assert_eq!(None, world.push_crate((2, 1), Directions::NORTH));
+        assert_eq!(None, world.push_crate((2, 1), Directions::SOUTH));
+        assert_eq!(Tile::CRATE, *world.get(2, 1).unwrap());
+    }
+
+    #[test]
+    fn test_push_crate_into_obstacles() {
+        let mut world = world_with_crate();
+        world.set(3, 1, Tile::COIN);
+        world.set(1, 1, Tile::WALLSPIKESR);
+        assert_eq!(None, world.push_crate((2, 1), Directions::EAST));
+        assert_eq!(None, world.push_crate((2, 1), Directions::WEST));
+        assert_eq!(Tile::CRATE, *world.get(2, 1).unwrap());
+    }
+
+    #[test]
+    fn test_push_non_crate() {
+        let mut world = world_with_crate();
+        assert_eq!(None, world.push_crate((3, 1), Directions::EAST));
+        assert_eq!(Tile::AIR, *world.get(4, 1).unwrap());
+    }
+
+    #[test]
+    fn test_push_stacked_crates() {
+        let mut world = world_with_crate();
+        world.set(2, 2, Tile::CRATE);
+        assert_eq!(None, world.push_crate((2, 1), Directions::EAST));
+        assert_eq!(Some((3, 1)), world.push_crate((2, 2), Directions::EAST));
+        assert_eq!(Some((1, 1)), world.push_crate((2, 1), Directions::WEST));
+        assert_eq!(Tile::CRATE, *world.get(3, 1).unwrap());
+    }
+
+    #[test]
+    fn test_crate_fills_gap() {
+        let mut world = map_with_border(7, 5);
+        for y in 0..3 {
+            world.set(1, y, Tile::WALL);
+            world.set(3, y, Tile::WALL);
+        }
+        world.set(2, 0, Tile::WALL);
+        world.set(2, 1, Tile::AIR);
+        world.set(2, 2, Tile::AIR);
+        world.set(1, 3, Tile::CRATE);
+        assert_eq!(Some((2, 1)), world.push_crate((1, 3), Directions::EAST));
+        assert_eq!(Tile::CRATE, *world.get(2, 1).unwrap());
+        assert_eq!(Tile::AIR, *world.get(2, 2).unwrap());
+        assert_eq!(Tile::AIR, *world.get(1, 3).unwrap());
+    }
+
+    #[test]
+    fn test_crate_covers_and_uncovers_spikes() {
+        let mut world = map_with_border(7, 5);
+        world.set(2, 2, Tile::CRATE);
+        world.set(2, 1, Tile::WALL);
+        world.set(3, 1, Tile::SPIKES);
+        assert_eq!(Some((3, 1)), world.push_crate((2, 2), Directions::EAST));
+        assert_eq!(Tile::CRATE, *world.get(3, 1).unwrap());
+        assert_eq!(Some((4, 1)), world.push_crate((3, 1), Directions::EAST));
+        assert_eq!(Tile::SPIKES, *world.get(3, 1).unwrap());
+        assert_eq!(Tile::CRATE, *world.get(4, 1).unwrap());
+    }
+
+    #[test]
+    fn test_reset_moved_crates() {
+        let mut world = world_with_crate();
+        world.set(4, 1, Tile::SPIKES);
+        world.set(3, 1, Tile::OPENDOOR);
+        assert_eq!(Some((3, 1)), world.push_crate((2, 1), Directions::EAST));
+        assert_eq!(Some((4, 1)), world.push_crate((3, 1), Directions::EAST));
+        world.reset_game_state();
+        assert_eq!(Tile::CRATE, *world.get(2, 1).unwrap());
+        assert_eq!(Tile::DOOR, *world.get(3, 1).unwrap());
+        assert_eq!(Tile::SPIKES, *world.get(4, 1).unwrap());
+        // Resetting the game state twice must not change anything
+        world.reset_game_state();
+        assert_eq!(Tile::CRATE, *world.get(2, 1).unwrap());
+        assert_eq!(Tile::SPIKES, *world.get(4, 1).unwrap());
+    }
+}
@@ -0,0 +1,191 @@
+use crate::world::GameWorld;
+use std::collections::VecDeque;
+
+/// Get all positions on a straight line between the two given positions, including both ends.
+/// The positions are ordered from `from` to `to`.
+///
+/// ```rust
+/// use libexodus::world::drawing::line;
+/// assert_eq!(vec![(0, 0), (1, 0), (2, 1), (3, 1)], line((0, 0), (3, 1)));
+/// ```
+pub fn line(from: (usize, usize), to: (usize, usize)) -> Vec<(usize, usize)> {
+    // Bresenham's line algorithm, working for all octants
+    let (mut x, mut y) = (from.0 as i64, from.1 as i64);
+    let (x1, y1) = (to.0 as i64, to.1 as i64);
+    let dx = (x1 - x).abs();
+    let dy = -(y1 - y).abs();
+    let sx = if x < x1 { 1 } else { -1 };
+    let sy = if y < y1 { 1 } else { -1 };
+    let mut err = dx + dy;
+    let mut ret: Vec<(usize, usize)> = vec![];
+    loop {
+        ret.push((x as usize, y as usize));
+        if x == x1 && y == y1 {
+            return ret;
+        }
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+    }
+}
+
+/// Get all positions of an axis-aligned rectangle spanned by the two given corners.
+/// If `filled` is false, only the border of the rectangle is returned.
+///
+/// ```rust
+/// use libexodus::world::drawing::rectangle;
+/// assert_eq!(9, rectangle((1, 1), (3, 3), true).len());
+/// assert_eq!(8, rectangle((3, 3), (1, 1), false).len());
+/// ```
+pub fn rectangle(
+    corner_a: (usize, usize),
+    corner_b: (usize, usize),
+    filled: bool,
+) -> Vec<(usize, usize)> {
+    let (min_x, max_x) = (corner_a.0.min(corner_b.0), corner_a.0.max(corner_b.0));
+    let (min_y, max_y) = (corner_a.1.min(corner_b.1), corner_a.1.max(corner_b.1));
+    let mut ret: Vec<(usize, usize)> = vec![];
+    for y in min_y..=max_y {
+        for x in min_x..=max_x {
+            if filled || x == min_x || x == max_x || y == min_y || y == max_y {
+                ret.push((x, y));
+            }
+        }
+    }
+    ret
+}
+
+/// Get the positions of all tiles that are connected to the given position horizontally or
+/// vertically by tiles that are equal to the tile at the given position, i.e., the area that is
+/// filled by a bucket fill tool.
+/// If the position lies outside of the world, nothing is returned.
+///
+/// ```rust
+/// use libexodus::tiles::Tile;
+/// use libexodus::world::GameWorld;
+/// use libexodus::world::drawing::flood_fill;
+/// let mut world = GameWorld::new(3, 3);
+/// world.set(1, 0, Tile::WALL);
+/// world.set(1, 1, Tile::WALL);
+/// world.set(1, 2, Tile::WALL);
+/// assert_eq!(3, flood_fill(&world, 0, 0).len());
+/// assert_eq!(3, flood_fill(&world, 1, 1).len());
+/// ```
+pub fn flood_fill(world: &GameWorld, x: usize, y: usize) -> Vec<(usize, usize)> {
+    let Some(start_tile) = world.get(x as i32, y as i32) else {
+        return vec![];
+    };
+    let mut visited = vec![false; world.width() * world.height()];
+    let mut queue: VecDeque<(usize, usize)> = VecDeque::from([(x, y)]);
+    visited[y * world.width() + x] = true;
+    let mut ret: Vec<(usize, usize)> = vec![];
+    while let Some((x, y)) = queue.pop_front() {
+        ret.push((x, y));
+        let neighbors = [
+            (x.checked_sub(1), Some(y)),
+            (Some(x + 1), Some(y)),
+            (Some(x), y.checked_sub(1)),
+            (Some(x), Some(y + 1)),
+        ];
+        for (nx, ny) in neighbors {
+            let (Some(nx), Some(ny)) = (nx, ny) else {
+                continue;
+            };
+            if nx >= world.width() || ny >= world.height() || visited[ny * world.width() + nx] {
+                continue;
+            }
+            if world.get(nx as i32, ny as i32) == Some(start_tile) {
+                visited[ny * world.width() + nx] = true;
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tiles::Tile;
+    use crate::world::GameWorld;
+    use crate::world::drawing::{flood_fill, line, rectangle};
+
+    #[test]
+    fn test_line_single_point() {
+        assert_eq!(vec![(2, 2)], line((2, 2), (2, 2)));
+    }
+
+    #[test]
+    fn test_line_horizontal_and_vertical() {
+        assert_eq!(vec![(3, 1), (2, 1), (1, 1)], line((3, 1), (1, 1)));
+        assert_eq!(vec![(0, 0), (0, 1), (0, 2)], line((0, 0), (0, 2)));
+    }
+
+    #[test]
+    fn test_line_diagonal() {
+        assert_eq!(vec![(2, 2), (1, 1), (0, 0)], line((2, 2), (0, 0)));
+        assert_eq!(vec![(0, 2), (1, 1), (2, 0)], line((0, 2), (2, 0)));
+    }
+
+    #[test]
+    fn test_line_is_connected() {
+        let points = line((0, 0), (7, 3));
+        assert_eq!(8, points.len());
+        for pair in points.windows(2) {
+            assert!(pair[0].0.abs_diff(pair[1].0) <= 1);
+            assert!(pair[0].1.abs_diff(pair[1].1) <= 1);
+        }
+        assert_eq!(Some(&(7, 3)), points.last());
+    }
+
+    #[test]
+    fn test_rectangle_hollow() {
+        let rect = rectangle((0, 0), (2, 1), false);
+        assert_eq!(vec![(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)], rect);
+        let rect = rectangle((0, 0), (2, 2), false);
+        assert_eq!(8, rect.len());
+        assert!(!rect.contains(&(1, 1)));
+    }
+
+    #[test]
+    fn test_rectangle_filled() {
+        let rect = rectangle((4, 1), (2, 2), true);
+        assert_eq!(vec![(2, 1), (3, 1), (4, 1), (2, 2), (3, 2), (4, 2)], rect);
+    }
+
+    #[test]
+    fn test_flood_fill_enclosed_area() {
+        let mut world = GameWorld::new(5, 5);
+        for (x, y) in rectangle((0, 0), (4, 4), false) {
+            world.set(x, y, Tile::WALL);
+        }
+        let area = flood_fill(&world, 2, 2);
+        assert_eq!(9, area.len());
+        assert_eq!((2, 2), area[0]);
+        assert!(
+            area.iter()
+                .all(|(x, y)| *world.get(*x as i32, *y as i32).unwrap() == Tile::AIR)
+        );
+        assert_eq!(16, flood_fill(&world, 0, 4).len());
+    }
+
+    #[test]
+    fn test_flood_fill_does_not_cross_diagonals() {
+        let mut world = GameWorld::new(2, 2);
+        world.set(1, 0, Tile::WALL);
+        world.set(0, 1, Tile::WALL);
+        assert_eq!(vec![(0, 0)], flood_fill(&world, 0, 0));
+    }
+
+    #[test]
+    fn test_flood_fill_out_of_bounds() {
+        let world = GameWorld::new(2, 2);
+        assert!(flood_fill(&world, 2, 0).is_empty());
+        assert!(flood_fill(&world, 0, 5).is_empty());
+    }
+}
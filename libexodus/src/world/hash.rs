@@ -3,6 +3,18 @@ use crate::world::io_error::GameWorldParseError;
 use bytebuffer::ByteBuffer;
 use sha2::{Digest, Sha256};
 
+/// Get the hex-string representation of the given map hash
+///
+/// ```rust
+/// use libexodus::world::hash::hash_to_str;
+/// let mut hash = [0u8; 32];
+/// hash[0] = 0xAB;
+/// assert_eq!(format!("AB{}", "00".repeat(31)), hash_to_str(&hash));
+/// ```
+pub fn hash_to_str(hash: &[u8; 32]) -> String {
+    hash.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
 #[derive(Debug)]
 pub enum RecomputeHashResult {
     /// The hash remained the same after re-computation
@@ -78,7 +90,7 @@ mod tests {
                 panic!("Result was {:?} - Hash was {:?}", x, map.hash)
             },
         };
-        let new_hash = map.hash.clone();
+        let new_hash = map.hash;
         match map.recompute_hash() {
             RecomputeHashResult::SAME => {},
             x => {
@@ -114,7 +126,7 @@ mod tests {
                 panic!("Result was {:?} - Hash was {:?}", x, map.hash)
             },
         };
-        let old_hash = map.hash.clone();
+        let old_hash = map.hash;
         map.set(0, 0, Tile::DOOR);
         match map.recompute_hash() {
             RecomputeHashResult::CHANGED { .. } => {},
@@ -123,7 +135,7 @@ mod tests {
             },
         };
         assert_hashes_are_not_equal(&map.hash, &old_hash);
-        let new_hash = map.hash.clone();
+        let new_hash = map.hash;
         map.set(0, 0, old_tile);
         match map.recompute_hash() {
             RecomputeHashResult::CHANGED { .. } => {},
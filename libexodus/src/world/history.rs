@@ -0,0 +1,696 @@
+use crate::tiles::{TeleportId, Tile};
+use crate::world::region::TileRegion;
+use crate::world::{GameWorld, OutOfBoundsError};
+
+/// The maximum number of edits that can be undone. Older edits are discarded.
+pub const MAX_HISTORY_LENGTH: usize = 1000;
+
+/// A single tile that has been changed by an edit
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TileChange {
+    pub position: (usize, usize),
+    pub old: Tile,
+    pub new: Tile,
+}
+
+/// A reversible edit of a game world
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EditCommand {
+    /// The given tiles have been changed, in the given order.
+    /// This includes tiles that have been changed implicitly, e.g., an old player spawn
+    /// that has been removed when placing a new one.
+    SetTiles { changes: Vec<TileChange> },
+    /// The text of the given message has been changed
+    SetMessage {
+        message_id: usize,
+        old: String,
+        new: String,
+    },
+    /// The given block has been linked to or unlinked from the given switch
+    SetLink {
+        switch: (usize, usize),
+        block: (usize, usize),
+        linked: bool,
+    },
+}
+
+impl EditCommand {
+    /// Execute this command (again) on the given world
+    fn apply(&self, world: &mut GameWorld) {
+        match self {
+            EditCommand::SetTiles { changes } => {
+                for change in changes {
+                    world.set(change.position.0, change.position.1, change.new.clone());
+                }
+            },
+            EditCommand::SetMessage {
+                message_id, new, ..
+            } => {
+                let _ = world.set_message(*message_id, new.clone());
+            },
+            EditCommand::SetLink {
+                switch,
+                block,
+                linked,
+            } => Self::set_link(world, *switch, *block, *linked),
+        }
+    }
+    /// Revert this command on the given world.
+    /// Tile changes are reverted in reverse order, such that player spawns and teleport exits
+    /// end up at their previous location.
+    fn revert(&self, world: &mut GameWorld) {
+        match self {
+            EditCommand::SetTiles { changes } => {
+                for change in changes.iter().rev() {
+                    world.set(change.position.0, change.position.1, change.old.clone());
+                }
+            },
+            EditCommand::SetMessage {
+                message_id, old, ..
+            } => {
+                let _ = world.set_message(*message_id, old.clone());
+            },
+            EditCommand::SetLink {
+                switch,
+                block,
+                linked,
+            } => Self::set_link(world, *switch, *block, !*linked),
+        }
+    }
+    fn set_link(
+        world: &mut GameWorld,
+        switch: (usize, usize),
+        block: (usize, usize),
+        linked: bool,
+    ) {
+        if linked {
+            world.link(switch, block);
+        } else {
+            world.unlink(switch, block);
+        }
+    }
+    /// Get all distinct positions of tiles that are changed by this command
+    pub fn changed_positions(&self) -> Vec<(usize, usize)> {
+        let mut ret: Vec<(usize, usize)> = vec![];
+        if let EditCommand::SetTiles { changes } = self {
+            for change in changes {
+                if !ret.contains(&change.position) {
+                    ret.push(change.position);
+                }
+            }
+        }
+        ret
+    }
+}
+
+/// The undo and redo history of edits that have been made to a game world.
+/// All edits need to be made through the history to be undoable.
+/// The history keeps the dirty flag of the world consistent, i.e., if all edits since the last
+/// save are undone, the world is clean again.
+#[derive(Clone, Debug)]
+pub struct EditHistory {
+    undo_stack: Vec<EditCommand>,
+    redo_stack: Vec<EditCommand>,
+    /// The length of the undo stack at which the world has been saved the last time,
+    /// or None if that state cannot be reached anymore
+    clean_index: Option<usize>,
+}
+
+impl Default for EditHistory {
+    fn default() -> Self {
+        EditHistory {
+            undo_stack: vec![],
+            redo_stack: vec![],
+            clean_index: Some(0),
+        }
+    }
+}
+
+impl EditHistory {
+    /// Set the tile at the given position and record the change as a single edit.
+    /// Return all positions of tiles that have been changed, which might be more than one,
+    /// e.g., if a player spawn or teleport exit has been moved.
+    /// If the given tile is a message tile, a new empty message is created,
+    /// unless there already is a message tile at the given position.
+    ///
+    /// ```rust
+    /// use libexodus::tiles::Tile;
+    /// use libexodus::world::GameWorld;
+    /// use libexodus::world::history::EditHistory;
+    /// let mut world = GameWorld::new(3, 3);
+    /// let mut history = EditHistory::default();
+    /// history.set_tile(&mut world, 1, 1, Tile::WALL);
+    /// assert!(world.is_dirty());
+    /// history.undo(&mut world);
+    /// assert_eq!(Tile::AIR, *world.get(1, 1).unwrap());
+    /// assert!(!world.is_dirty());
+    /// history.redo(&mut world);
+    /// assert_eq!(Tile::WALL, *world.get(1, 1).unwrap());
+    /// ```
+    pub fn set_tile(
+        &mut self,
+        world: &mut GameWorld,
+        x: usize,
+        y: usize,
+        tile: Tile,
+    ) -> Vec<(usize, usize)> {
+        self.set_tiles(world, [((x, y), tile)])
+    }
+    /// Set all given tiles in the given order and record all changes as a single edit,
+    /// e.g., to undo a drawn rectangle at once.
+    /// Positions outside of the map are ignored.
+    /// Return all positions of tiles that have been changed.
+    pub fn set_tiles(
+        &mut self,
+        world: &mut GameWorld,
+        tiles: impl IntoIterator<Item = ((usize, usize), Tile)>,
+    ) -> Vec<(usize, usize)> {
+        let changes = Self::execute_tile_changes(world, tiles);
+        if changes.is_empty() {
+            return vec![];
+        }
+        let command = EditCommand::SetTiles { changes };
+        let ret = command.changed_positions();
+        self.push(world, command);
+        ret
+    }
+    /// Set all given tiles in the given order and merge all changes into the last edit,
+    /// such that they are undone together, e.g., to continue a stroke that is painted by dragging
+    /// the mouse.
+    /// If the last edit did not change tiles or has been undone, a new edit is recorded instead.
+    /// Return all positions of tiles that have been changed.
+    pub fn extend_tiles(
+        &mut self,
+        world: &mut GameWorld,
+        tiles: impl IntoIterator<Item = ((usize, usize), Tile)>,
+    ) -> Vec<(usize, usize)> {
+        if !self.redo_stack.is_empty()
+            || !matches!(self.undo_stack.last(), Some(EditCommand::SetTiles { .. }))
+        {
+            return self.set_tiles(world, tiles);
+        }
+        let new_changes = Self::execute_tile_changes(world, tiles);
+        if new_changes.is_empty() {
+            return vec![];
+        }
+        let ret = EditCommand::SetTiles {
+            changes: new_changes.clone(),
+        }
+        .changed_positions();
+        if let Some(EditCommand::SetTiles { changes }) = self.undo_stack.last_mut() {
+            changes.extend(new_changes);
+        }
+        // The state after the last edit has changed, hence it cannot be the clean state anymore
+        if self.clean_index == Some(self.undo_stack.len()) {
+            self.clean_index = None;
+        }
+        world.set_dirty();
+        ret
+    }
+    /// Set all given tiles in the given order and return all changes that have been made
+    fn execute_tile_changes(
+        world: &mut GameWorld,
+        tiles: impl IntoIterator<Item = ((usize, usize), Tile)>,
+    ) -> Vec<TileChange> {
+        let mut changes: Vec<TileChange> = vec![];
+        for (position, tile) in tiles {
+            Self::execute_tile_change(world, position, tile, None, &mut changes);
+        }
+        changes
+    }
+    /// Set the given tile and append all changes that have been made to `changes`.
+    /// If the tile is a message tile, a new message with the given text is created. If no text is
+    /// given, an existing message tile at the given position is kept and a new empty message is
+    /// created otherwise.
+    fn execute_tile_change(
+        world: &mut GameWorld,
+        (x, y): (usize, usize),
+        tile: Tile,
+        message: Option<&str>,
+        changes: &mut Vec<TileChange>,
+    ) {
+        let Some(old) = world.get(x as i32, y as i32).cloned() else {
+            return;
+        };
+        // Setting these tiles implicitly changes another tile, which needs to be recorded first
+        let implicit = match tile {
+            Tile::PLAYERSPAWN => Some(world.player_spawn()),
+            Tile::TELEPORTEXIT { teleport_id } => world.get_teleport_location(teleport_id).copied(),
+            _ => None,
+        }
+        .filter(|position| *position != (x, y))
+        .and_then(|position| {
+            world
+                .get(position.0 as i32, position.1 as i32)
+                .map(|tile| (position, tile.clone()))
+        });
+        match (tile, message) {
+            (Tile::MESSAGE { .. }, None) if matches!(old, Tile::MESSAGE { .. }) => return,
+            (Tile::MESSAGE { .. }, message) => {
+                world.set_message_tile(x, y, message.unwrap_or("").to_string());
+            },
+            (tile, _) => {
+                world.set(x, y, tile);
+            },
+        }
+        if let Some((position, implicit_old)) = implicit {
+            let implicit_new = world.get(position.0 as i32, position.1 as i32).unwrap();
+            if *implicit_new != implicit_old {
+                changes.push(TileChange {
+                    position,
+                    old: implicit_old,
+                    new: implicit_new.clone(),
+                });
+            }
+        }
+        let new = world.get(x as i32, y as i32).unwrap();
+        if *new != old {
+            changes.push(TileChange {
+                position: (x, y),
+                old,
+                new: new.clone(),
+            });
+        }
+    }
+    /// Paste the given region into the world, such that its lower left corner is placed at the
+    /// given position, and record all changes as a single edit.
+    /// Parts of the region that lie outside of the world are cut off, and the player spawn is
+    /// never overwritten.
+    /// Messages are pasted as new messages. Teleports that have their exit inside the region are
+    /// remapped to an unused teleport ID, if their ID is already in use in the world.
+    /// Return all positions of tiles that have been changed.
+    ///
+    /// ```rust
+    /// use libexodus::tiles::Tile;
+    /// use libexodus::world::GameWorld;
+    /// use libexodus::world::history::EditHistory;
+    /// use libexodus::world::region::TileRegion;
+    /// let mut world = GameWorld::new(4, 4);
+    /// world.set(0, 0, Tile::WALL);
+    /// world.set(1, 0, Tile::COIN);
+    /// let region = TileRegion::copy_from(&world, (0, 0), (1, 0)).unwrap();
+    /// let mut history = EditHistory::default();
+    /// history.paste(&mut world, &region, (2, 3));
+    /// assert_eq!(Tile::WALL, *world.get(2, 3).unwrap());
+    /// assert_eq!(Tile::COIN, *world.get(3, 3).unwrap());
+    /// history.undo(&mut world);
+    /// assert_eq!(Tile::AIR, *world.get(2, 3).unwrap());
+    /// ```
+    pub fn paste(
+        &mut self,
+        world: &mut GameWorld,
+        region: &TileRegion,
+        origin: (usize, usize),
+    ) -> Vec<(usize, usize)> {
+        let teleport_ids = region.teleport_mapping(world, origin);
+        let remap =
+            |teleport_id: &TeleportId| *teleport_ids.get(teleport_id).unwrap_or(teleport_id);
+        let mut changes: Vec<TileChange> = vec![];
+        for ((x, y), tile) in region.tiles_at(origin) {
+            if world
+                .get(x as i32, y as i32)
+                .is_none_or(|old| *old == Tile::PLAYERSPAWN)
+            {
+                continue;
+            }
+            let (tile, message) = match tile {
+                Tile::MESSAGE { message_id } => (
+                    tile.clone(),
+                    Some(region.message(*message_id).unwrap_or("")),
+                ),
+                Tile::TELEPORTENTRY { teleport_id } => (
+                    Tile::TELEPORTENTRY {
+                        teleport_id: remap(teleport_id),
+                    },
+                    None,
+                ),
+                Tile::TELEPORTEXIT { teleport_id } => (
+                    Tile::TELEPORTEXIT {
+                        teleport_id: remap(teleport_id),
+                    },
+                    None,
+                ),
+                tile => (tile.clone(), None),
+            };
+            Self::execute_tile_change(world, (x, y), tile, message, &mut changes);
+        }
+        if changes.is_empty() {
+            return vec![];
+        }
+        let command = EditCommand::SetTiles { changes };
+        let ret = command.changed_positions();
+        self.push(world, command);
+        ret
+    }
+    /// Set the text of the given message and record the change as a single edit
+    pub fn set_message(
+        &mut self,
+        world: &mut GameWorld,
+        message_id: usize,
+        message: String,
+    ) -> Result<(), OutOfBoundsError> {
+        let old = world
+            .get_message(message_id)
+            .ok_or(OutOfBoundsError(message_id))?
+            .to_string();
+        if old == message {
+            return Ok(());
+        }
+        world.set_message(message_id, message.clone())?;
+        self.push(
+            world,
+            EditCommand::SetMessage {
+                message_id,
+                old,
+                new: message,
+            },
+        );
+        Ok(())
+    }
+    /// Link the given block to the given switch, or unlink it if it is already linked, and record
+    /// the change as a single edit.
+    /// Return true if the block is linked to the switch afterwards.
+    ///
+    /// ```rust
+    /// use libexodus::tiles::Tile;
+    /// use libexodus::world::GameWorld;
+    /// use libexodus::world::history::EditHistory;
+    /// let mut world = GameWorld::new(3, 3);
+    /// world.set(0, 0, Tile::LEVER);
+    /// let mut history = EditHistory::default();
+    /// assert!(history.toggle_link(&mut world, (0, 0), (2, 1)));
+    /// assert!(world.is_linked((0, 0), (2, 1)));
+    /// history.undo(&mut world);
+    /// assert!(!world.is_linked((0, 0), (2, 1)));
+    /// ```
+    pub fn toggle_link(
+        &mut self,
+        world: &mut GameWorld,
+        switch: (usize, usize),
+        block: (usize, usize),
+    ) -> bool {
+        if switch == block {
+            return false;
+        }
+        let linked = !world.is_linked(switch, block);
+        let command = EditCommand::SetLink {
+            switch,
+            block,
+            linked,
+        };
+        command.apply(world);
+        self.push(world, command);
+        linked
+    }
+    /// Push an edit that has already been executed onto the undo stack
+    fn push(&mut self, world: &mut GameWorld, command: EditCommand) {
+        // If the world is dirty although it should be clean, it has been edited without the
+        // history, hence the clean state cannot be restored by undoing edits.
+        if self.clean_index == Some(self.undo_stack.len()) && world.is_dirty() {
+            self.clean_index = None;
+        }
+        // The clean state cannot be reached anymore if it lies in the discarded redo stack
+        if self
+            .clean_index
+            .is_some_and(|index| index > self.undo_stack.len())
+        {
+            self.clean_index = None;
+        }
+        self.redo_stack.clear();
+        self.undo_stack.push(command);
+        if self.undo_stack.len() > MAX_HISTORY_LENGTH {
+            self.undo_stack.remove(0);
+            self.clean_index = self.clean_index.and_then(|index| index.checked_sub(1));
+        }
+        world.set_dirty();
+    }
+    /// Undo the last edit.
+    /// Return the positions of all tiles that have been changed, or None if there is nothing to undo.
+    pub fn undo(&mut self, world: &mut GameWorld) -> Option<Vec<(usize, usize)>> {
+        let command = self.undo_stack.pop()?;
+        command.revert(world);
+        let ret = command.changed_positions();
+        self.redo_stack.push(command);
+        self.update_dirty_flag(world);
+        Some(ret)
+    }
+    /// Redo the last edit that has been undone.
+    /// Return the positions of all tiles that have been changed, or None if there is nothing to redo.
+    pub fn redo(&mut self, world: &mut GameWorld) -> Option<Vec<(usize, usize)>> {
+        let command = self.redo_stack.pop()?;
+        command.apply(world);
+        let ret = command.changed_positions();
+        self.undo_stack.push(command);
+        self.update_dirty_flag(world);
+        Some(ret)
+    }
+    fn update_dirty_flag(&self, world: &mut GameWorld) {
+        if self.clean_index == Some(self.undo_stack.len()) {
+            world.set_clean();
+        } else {
+            world.set_dirty();
+        }
+    }
+    /// Check if there is an edit that can be undone
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+    /// Check if there is an edit that can be redone
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+    /// Mark the current state as clean, e.g., after the world has been saved
+    pub fn mark_clean(&mut self) {
+        self.clean_index = Some(self.undo_stack.len());
+    }
+    /// Discard all edits, e.g., after another world has been loaded
+    pub fn clear(&mut self) {
+        *self = EditHistory::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tiles::{TeleportId, Tile};
+    use crate::world::GameWorld;
+    use crate::world::history::{EditHistory, MAX_HISTORY_LENGTH};
+
+    #[test]
+    fn test_extend_tiles_is_undone_at_once() {
+        let mut world = GameWorld::new(4, 4);
+        let mut history = EditHistory::default();
+        history.set_tile(&mut world, 0, 0, Tile::SPIKES);
+        history.set_tile(&mut world, 1, 1, Tile::WALL);
+        assert_eq!(
+            vec![(2, 1), (3, 1)],
+            history.extend_tiles(&mut world, [((2, 1), Tile::WALL), ((3, 1), Tile::WALL)])
+        );
+        assert_eq!(Some(vec![(1, 1), (2, 1), (3, 1)]), history.undo(&mut world));
+        assert_eq!(Tile::SPIKES, *world.get(0, 0).unwrap());
+        assert_eq!(Tile::AIR, *world.get(3, 1).unwrap());
+        // After an undo, extending must not merge into the undone edit
+        history.extend_tiles(&mut world, [((3, 3), Tile::WALL)]);
+        assert_eq!(Some(vec![(3, 3)]), history.undo(&mut world));
+        assert_eq!(Tile::SPIKES, *world.get(0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_extend_tiles_after_save_is_dirty() {
+        let mut world = GameWorld::new(4, 4);
+        let mut history = EditHistory::default();
+        history.set_tile(&mut world, 1, 1, Tile::WALL);
+        world.set_clean();
+        history.mark_clean();
+        history.extend_tiles(&mut world, [((2, 1), Tile::WALL)]);
+        assert!(world.is_dirty());
+        history.undo(&mut world);
+        assert!(world.is_dirty());
+    }
+
+    #[test]
+    fn test_undo_redo_tiles() {
+        let mut world = GameWorld::new(4, 4);
+        let mut history = EditHistory::default();
+        history.set_tile(&mut world, 1, 1, Tile::WALL);
+        history.set_tile(&mut world, 1, 1, Tile::SPIKES);
+        assert_eq!(Some(vec![(1, 1)]), history.undo(&mut world));
+        assert_eq!(Tile::WALL, *world.get(1, 1).unwrap());
+        history.undo(&mut world);
+        assert_eq!(Tile::AIR, *world.get(1, 1).unwrap());
+        assert_eq!(None, history.undo(&mut world));
+        history.redo(&mut world);
+        history.redo(&mut world);
+        assert_eq!(Tile::SPIKES, *world.get(1, 1).unwrap());
+        assert_eq!(None, history.redo(&mut world));
+    }
+
+    #[test]
+    fn test_unchanged_tile_is_not_recorded() {
+        let mut world = GameWorld::new(4, 4);
+        let mut history = EditHistory::default();
+        assert!(history.set_tile(&mut world, 1, 1, Tile::AIR).is_empty());
+        assert!(history.set_tile(&mut world, 4, 1, Tile::WALL).is_empty());
+        assert!(!history.can_undo());
+        assert!(!world.is_dirty());
+    }
+
+    #[test]
+    fn test_new_edit_discards_redo_stack() {
+        let mut world = GameWorld::new(4, 4);
+        let mut history = EditHistory::default();
+        history.set_tile(&mut world, 1, 1, Tile::WALL);
+        history.undo(&mut world);
+        assert!(history.can_redo());
+        history.set_tile(&mut world, 2, 2, Tile::WALL);
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn test_undo_player_spawn_move() {
+        let mut world = GameWorld::new(4, 4);
+        world.set(1, 1, Tile::PLAYERSPAWN);
+        world.set(2, 1, Tile::COIN);
+        let mut history = EditHistory::default();
+        let changed = history.set_tile(&mut world, 2, 1, Tile::PLAYERSPAWN);
+        assert_eq!(vec![(1, 1), (2, 1)], changed);
+        assert_eq!((2, 1), world.player_spawn());
+        assert_eq!(Tile::AIR, *world.get(1, 1).unwrap());
+        history.undo(&mut world);
+        assert_eq!((1, 1), world.player_spawn());
+        assert_eq!(Tile::PLAYERSPAWN, *world.get(1, 1).unwrap());
+        assert_eq!(Tile::COIN, *world.get(2, 1).unwrap());
+        history.redo(&mut world);
+        assert_eq!((2, 1), world.player_spawn());
+        assert_eq!(Tile::AIR, *world.get(1, 1).unwrap());
+    }
+
+    #[test]
+    fn test_undo_teleport_exit_relocation() {
+        let teleport_id = TeleportId::ONE;
+        let mut world = GameWorld::new(4, 4);
+        world.set(1, 1, Tile::TELEPORTEXIT { teleport_id });
+        let mut history = EditHistory::default();
+        history.set_tile(&mut world, 3, 3, Tile::TELEPORTEXIT { teleport_id });
+        assert_eq!(Some(&(3, 3)), world.get_teleport_location(teleport_id));
+        assert_eq!(Tile::AIR, *world.get(1, 1).unwrap());
+        history.undo(&mut world);
+        assert_eq!(Some(&(1, 1)), world.get_teleport_location(teleport_id));
+        assert_eq!(
+            Tile::TELEPORTEXIT { teleport_id },
+            *world.get(1, 1).unwrap()
+        );
+        assert_eq!(Tile::AIR, *world.get(3, 3).unwrap());
+    }
+
+    #[test]
+    fn test_undo_message_edit() {
+        let mut world = GameWorld::new(4, 4);
+        let mut history = EditHistory::default();
+        history.set_tile(&mut world, 1, 1, Tile::MESSAGE { message_id: 42 });
+        let Tile::MESSAGE { message_id } = *world.get(1, 1).unwrap() else {
+            panic!("Expected a message tile");
+        };
+        assert_eq!(Some(""), world.get_message(message_id));
+        // Placing a message tile onto a message tile keeps the message
+        assert!(
+            history
+                .set_tile(&mut world, 1, 1, Tile::MESSAGE { message_id: 0 })
+                .is_empty()
+        );
+        history
+            .set_message(&mut world, message_id, "Hello".to_string())
+            .unwrap();
+        assert!(history.set_message(&mut world, 42, "".to_string()).is_err());
+        history.undo(&mut world);
+        assert_eq!(Some(""), world.get_message(message_id));
+        history.undo(&mut world);
+        assert_eq!(Tile::AIR, *world.get(1, 1).unwrap());
+        history.redo(&mut world);
+        history.redo(&mut world);
+        assert_eq!(Tile::MESSAGE { message_id }, *world.get(1, 1).unwrap());
+        assert_eq!(Some("Hello"), world.get_message(message_id));
+    }
+
+    #[test]
+    fn test_undo_link_of_removed_switch() {
+        let mut world = GameWorld::new(4, 4);
+        let mut history = EditHistory::default();
+        history.set_tile(&mut world, 1, 1, Tile::LEVER);
+        assert!(history.toggle_link(&mut world, (1, 1), (3, 1)));
+        assert!(!history.toggle_link(&mut world, (1, 1), (1, 1)));
+        history.set_tile(&mut world, 1, 1, Tile::AIR);
+        assert_eq!(0, world.active_links().count());
+        history.undo(&mut world);
+        assert_eq!(vec![(3, 1)], world.linked_blocks((1, 1)));
+        assert!(!history.toggle_link(&mut world, (1, 1), (3, 1)));
+        assert!(world.links().is_empty());
+        history.undo(&mut world);
+        assert!(world.is_linked((1, 1), (3, 1)));
+        history.undo(&mut world);
+        assert!(world.links().is_empty());
+    }
+
+    #[test]
+    fn test_set_tiles_is_a_single_edit() {
+        let mut world = GameWorld::new(4, 4);
+        let mut history = EditHistory::default();
+        history.set_tiles(
+            &mut world,
+            [
+                ((0, 0), Tile::WALL),
+                ((1, 0), Tile::WALL),
+                ((1, 0), Tile::COIN),
+            ],
+        );
+        assert_eq!(Some(vec![(0, 0), (1, 0)]), history.undo(&mut world));
+        assert_eq!(Tile::AIR, *world.get(1, 0).unwrap());
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn test_dirty_flag_after_save() {
+        let mut world = GameWorld::new(4, 4);
+        let mut history = EditHistory::default();
+        history.set_tile(&mut world, 1, 1, Tile::WALL);
+        world.set_clean();
+        history.mark_clean();
+        history.set_tile(&mut world, 2, 1, Tile::WALL);
+        assert!(world.is_dirty());
+        history.undo(&mut world);
+        assert!(!world.is_dirty());
+        history.undo(&mut world);
+        assert!(world.is_dirty());
+        // The saved state is discarded by a new edit
+        history.set_tile(&mut world, 3, 1, Tile::WALL);
+        history.undo(&mut world);
+        history.redo(&mut world);
+        assert!(world.is_dirty());
+    }
+
+    #[test]
+    fn test_dirty_world_never_becomes_clean() {
+        let mut world = GameWorld::new(4, 4);
+        world.set_dirty();
+        let mut history = EditHistory::default();
+        history.set_tile(&mut world, 1, 1, Tile::WALL);
+        history.undo(&mut world);
+        assert!(world.is_dirty());
+    }
+
+    #[test]
+    fn test_history_length_is_limited() {
+        let mut world = GameWorld::new(2, 2);
+        let mut history = EditHistory::default();
+        for i in 0..=MAX_HISTORY_LENGTH {
+            let tile = if i % 2 == 0 { Tile::WALL } else { Tile::COIN };
+            history.set_tile(&mut world, 0, 0, tile);
+        }
+        let mut undone = 0;
+        while history.undo(&mut world).is_some() {
+            undone += 1;
+        }
+        assert_eq!(MAX_HISTORY_LENGTH, undone);
+        assert_eq!(Tile::WALL, *world.get(0, 0).unwrap());
+        assert!(world.is_dirty());
+    }
+}
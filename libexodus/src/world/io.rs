@@ -1,19 +1,44 @@
+use crate::directions::FromDirection;
+use crate::enemies::{Enemy, EnemyKind};
 use crate::exodus_serializable::ExodusSerializable;
-use crate::tiles::{InteractionKind, TeleportId, Tile};
+use crate::tiles::{InteractionKind, KeyColor, TeleportId, Tile};
 use crate::tilesets::Tileset;
 use crate::world::GameWorld;
 use crate::world::hash::RecomputeHashResult;
 use crate::world::io_error::GameWorldParseError;
+use crate::world::metadata::{Difficulty, MapMetadata};
 use bincode;
+use std::collections::HashSet;
 use std::fs::{File, OpenOptions};
-use std::io::{BufReader, BufWriter, Read, Write};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
 use std::path::Path;
 
 pub(crate) const MAGICBYTES: [u8; 9] = [0x45, 0x78, 0x6f, 0x64, 0x75, 0x73, 0x4d, 0x61, 0x70];
-pub(crate) const MAX_MAP_WIDTH: usize = 1024;
-pub(crate) const MAX_MAP_HEIGHT: usize = 1024;
+pub const MAX_MAP_WIDTH: usize = 1024;
+pub const MAX_MAP_HEIGHT: usize = 1024;
 pub(crate) const HASH_LENGTH: usize = 32;
 
+/// The length of a chunk tag in bytes
+pub(crate) const CHUNK_TAG_LENGTH: usize = 4;
+/// Chunk containing the name, author and cached UUID of a map
+pub(crate) const CHUNK_METADATA: [u8; CHUNK_TAG_LENGTH] = *b"META";
+/// Chunk containing the map size and all map tiles
+pub(crate) const CHUNK_TILES: [u8; CHUNK_TAG_LENGTH] = *b"TILE";
+/// Chunk containing all messages of a map
+pub(crate) const CHUNK_MESSAGES: [u8; CHUNK_TAG_LENGTH] = *b"MSGS";
+/// Chunk containing the forced tileset of a map
+pub(crate) const CHUNK_TILESET: [u8; CHUNK_TAG_LENGTH] = *b"TSET";
+/// Chunk containing the map metadata, e.g., description and difficulty
+pub(crate) const CHUNK_INFO: [u8; CHUNK_TAG_LENGTH] = *b"INFO";
+/// Chunk containing the number of lives of a map, if it differs from the default
+pub(crate) const CHUNK_LIVES: [u8; CHUNK_TAG_LENGTH] = *b"LIVS";
+/// Chunk containing all patrolling enemies of a map, if there are any
+pub(crate) const CHUNK_ENEMIES: [u8; CHUNK_TAG_LENGTH] = *b"ENMY";
+/// Chunk containing the links between switches and the blocks they toggle, if there are any
+pub(crate) const CHUNK_LINKS: [u8; CHUNK_TAG_LENGTH] = *b"LNKS";
+/// Empty chunk that marks the end of a map file
+pub(crate) const CHUNK_END: [u8; CHUNK_TAG_LENGTH] = *b"END\0";
+
 ///
 /// This file contains code used to manipulate physical data representing game worlds.
 ///
@@ -21,19 +46,46 @@ pub(crate) const HASH_LENGTH: usize = 32;
 ///
 /// 1. Magic Bytes 0x 45 78 6f 64 75 73 4d 61 70
 ///
-/// 2. Map Format Version (current version: 0x01)
+/// 2. Map Format Version (current version: 0x03)
+///
+/// 3. A sequence of chunks. Each chunk consists of a 4-byte ASCII tag, the length of the chunk
+///    data as 64-bit unsigned integer encoded with bincode crate, and the chunk data itself.
+///    The sequence is terminated by an empty `END\0` chunk.
+///
+/// The following chunks are defined:
+///
+/// - `META`: Length of Name, Name, Length of Author, Author, encoded with bincode crate,
+///   followed by the cached UUID as 32-byte octet
 ///
-/// 3. Length of Name, Name, encoded with bincode crate
+/// - `TILE`: Map Width, Map Height and Map Tiles, each row is appended from bottom to top,
+///   i.e. starting at (0,0),(1,0),(2,0),...
 ///
-/// 4. Length of Author, Author, encoded with bincode crate
+/// - `MSGS`: Number of messages, followed by all messages in correct order
 ///
-/// 5. Cached UUID as 16-byte octet
+/// - `TSET`: The forced tileset, if any
 ///
-/// 6. Map Width, Map Height
+/// - `INFO`: Description, Difficulty (0x00 if not rated), Par Moves, Creation Time,
+///   Modification Time and Tags, all encoded with bincode crate
 ///
-/// 7. Map Tiles, each row is appended from bottom to top, i.e. starting at (0,0),(1,0),(2,0),...
+/// - `LIVS`: The number of lives of the player as 64-bit unsigned integer encoded with bincode
+///   crate. If the chunk is missing, the player has a single life.
 ///
-/// 8. All messages in correct order
+/// - `ENMY`: Number of enemies as 32-bit unsigned integer, followed by all enemies. Each enemy
+///   consists of its kind (0x01 for walking, 0x02 for flying enemies), its spawn coordinates,
+///   a bit mask of the directions it is deadly from (0x01 north, 0x02 south, 0x04 east,
+///   0x08 west), and, for walking enemies, 0x01 if it initially faces right and 0x00 otherwise,
+///   or, for flying enemies, the number of waypoints as 32-bit unsigned integer followed by the
+///   coordinates of all waypoints. All coordinates are encoded with bincode crate.
+///   If the chunk is missing, the map does not contain any enemies.
+///
+/// - `LNKS`: Number of linked switches as 32-bit unsigned integer, followed by all switches.
+///   Each switch consists of its coordinates, the number of linked blocks as 32-bit unsigned
+///   integer and the coordinates of all linked blocks, encoded with bincode crate.
+///   If the chunk is missing, the map does not contain any links.
+///
+/// The `META` and `TILE` chunks are mandatory, and the `TILE` chunk must come before the
+/// `MSGS`, `ENMY` and `LNKS` chunks. Unknown chunks are skipped, such that newer map files can still be loaded
+/// by older versions of the game. Unknown data at the end of a known chunk is ignored as well.
 ///
 /// The cached UUID is used for checksum validation, and will be re-calculated on map load.
 /// If it does not match, the load will fail.
@@ -41,7 +93,20 @@ pub(crate) const HASH_LENGTH: usize = 32;
 /// is loaded and saved again.
 impl GameWorld {
     /// Load a map from the given file.
+    /// Files with the .exmt extension are loaded from the human-readable text map format.
     pub fn load_from_file(path: &Path) -> Result<GameWorld, GameWorldParseError> {
+        GameWorld::load(path, true)
+    }
+    /// Load a map from the given file, even if the hash that is stored in the file does not match
+    /// the map content. The hash of the loaded map is recomputed from its content, such that the
+    /// hash is fixed as soon as the map is saved again.
+    pub fn load_from_file_unverified(path: &Path) -> Result<GameWorld, GameWorldParseError> {
+        GameWorld::load(path, false)
+    }
+    fn load(path: &Path, verify_hash: bool) -> Result<GameWorld, GameWorldParseError> {
+        if GameWorld::is_text_map_file(path) {
+            return GameWorld::load_from_text_file(path);
+        }
         let file = OpenOptions::new().read(true).open(path)?;
         let mut buf = BufReader::new(file);
         let mut ret: GameWorld = GameWorld {
@@ -55,12 +120,49 @@ impl GameWorld {
             messages: vec![],
             forced_tileset: None,
             teleport_metadata: Default::default(),
+            metadata: Default::default(),
+            lives: GameWorld::DEFAULT_LIVES,
+            enemies: vec![],
+            links: Default::default(),
+            game_state_changes: Default::default(),
         };
-        ret.parse(&mut buf)?;
-        Ok(ret)
+        match ret.parse(&mut buf) {
+            // The map has been parsed completely, and the hash has already been recomputed
+            Err(GameWorldParseError::HashMismatch { .. }) if !verify_hash => Ok(ret),
+            Err(err) => Err(err),
+            Ok(()) => Ok(ret),
+        }
+    }
+    /// Get the version of the binary map format the given file has been saved with.
+    /// Returns None for maps in the text map format, which is not versioned.
+    pub fn file_format_version(path: &Path) -> Result<Option<u8>, GameWorldParseError> {
+        if GameWorld::is_text_map_file(path) {
+            return Ok(None);
+        }
+        let mut file = OpenOptions::new().read(true).open(path)?;
+        let mut buf: [u8; MAGICBYTES.len() + 1] = [0; MAGICBYTES.len() + 1];
+        file.read_exact(&mut buf)?;
+        if buf[..MAGICBYTES.len()] != MAGICBYTES {
+            let mut actual = [0u8; MAGICBYTES.len()];
+            actual.copy_from_slice(&buf[..MAGICBYTES.len()]);
+            return Err(GameWorldParseError::InvalidMagicBytes {
+                expected: MAGICBYTES,
+                actual,
+            });
+        }
+        Ok(Some(buf[MAGICBYTES.len()]))
     }
     /// Save the map to the given file. The hash MUST be recomputed before saving the map - else, the next load will fail!
+    /// Files with the .exmt extension are saved in the human-readable text map format.
     pub fn save_to_file(&self, path: &Path) -> Result<(), GameWorldParseError> {
+        if GameWorld::is_text_map_file(path) {
+            self.save_to_text_file(path)?;
+            println!(
+                "Successfully saved map file {}",
+                path.to_str().unwrap_or("<NONE>")
+            );
+            return Ok(());
+        }
         let file: File = OpenOptions::new()
             .create(true)
             .truncate(true)
@@ -78,7 +180,7 @@ impl GameWorld {
 
 /// Implementation for Serializer
 impl ExodusSerializable for GameWorld {
-    const CURRENT_VERSION: u8 = 0x02;
+    const CURRENT_VERSION: u8 = 0x03;
     type ParseError = GameWorldParseError;
     fn serialize<T: Write>(&self, file: &mut T) -> Result<(), GameWorldParseError> {
         // Write magic bytes
@@ -87,22 +189,54 @@ impl ExodusSerializable for GameWorld {
         // Write Map Version
         file.write_all(&[Self::CURRENT_VERSION])?;
 
-        // Write Map Name
-        let name_b = bincode::serialize(&self.name)?;
-        file.write_all(&name_b)?;
-
-        // Write Map Author
-        let author_b = bincode::serialize(&self.author)?;
-        file.write_all(&author_b)?;
+        // Write Map Name, Map Author and cached UUID
+        let mut metadata: Vec<u8> = vec![];
+        metadata.write_all(&bincode::serialize(&self.name)?)?;
+        metadata.write_all(&bincode::serialize(&self.author)?)?;
+        metadata.write_all(&self.hash)?;
+        write_chunk(file, &CHUNK_METADATA, &metadata)?;
 
-        // Write cached UUID
-        file.write_all(&self.hash)?;
-
-        self.serialize_world_content(file)?;
+        // Write Map Tiles and Messages
+        let mut tiles: Vec<u8> = vec![];
+        let message_ids = self.serialize_tiles(&mut tiles)?;
+        write_chunk(file, &CHUNK_TILES, &tiles)?;
+        let mut messages: Vec<u8> = vec![];
+        self.serialize_messages(&mut messages, &message_ids)?;
+        write_chunk(file, &CHUNK_MESSAGES, &messages)?;
 
         // Write forced tileset. The tileset should not be considered when
         // calculating the hash of a map.
-        self.serialize_tileset(file)?;
+        let mut tileset: Vec<u8> = vec![];
+        self.serialize_tileset(&mut tileset)?;
+        write_chunk(file, &CHUNK_TILESET, &tileset)?;
+
+        // Write map metadata. Like the tileset, the metadata is not part of the hash.
+        let mut info: Vec<u8> = vec![];
+        self.serialize_info(&mut info)?;
+        write_chunk(file, &CHUNK_INFO, &info)?;
+
+        // Write the number of lives, if the map does not use the default
+        if self.lives != GameWorld::DEFAULT_LIVES {
+            let mut lives: Vec<u8> = vec![];
+            self.serialize_lives(&mut lives)?;
+            write_chunk(file, &CHUNK_LIVES, &lives)?;
+        }
+
+        // Write all enemies, if there are any
+        if !self.enemies.is_empty() {
+            let mut enemies: Vec<u8> = vec![];
+            self.serialize_enemies(&mut enemies)?;
+            write_chunk(file, &CHUNK_ENEMIES, &enemies)?;
+        }
+
+        // Write all links, if there are any
+        if self.active_links().next().is_some() {
+            let mut links: Vec<u8> = vec![];
+            self.serialize_links(&mut links)?;
+            write_chunk(file, &CHUNK_LINKS, &links)?;
+        }
+
+        write_chunk(file, &CHUNK_END, &[])?;
         Ok(())
     }
     fn parse<T: Read>(&mut self, file: &mut T) -> Result<(), GameWorldParseError> {
@@ -119,8 +253,18 @@ impl ExodusSerializable for GameWorld {
         // Parse Map Format
         let mut buf: [u8; 1] = [0; 1];
         file.read_exact(&mut buf)?;
+        // Older maps and maps without some optional data do not contain all chunks,
+        // hence all data that is not stored in every map is reset first
+        self.messages.clear();
+        self.forced_tileset = None;
+        self.metadata = MapMetadata::default();
+        self.lives = GameWorld::DEFAULT_LIVES;
+        self.enemies.clear();
+        self.links.clear();
+        self.game_state_changes = Default::default();
         match buf[0] {
             0x01 => self.parse_v1(file),
+            0x02 => self.parse_v2(file),
             Self::CURRENT_VERSION => self.parse_current_version(file),
             // Add older versions here
             _ => {
@@ -142,63 +286,78 @@ impl ExodusSerializable for GameWorld {
     /// Parse a map with the current version.
     /// The file read position must be already behind the version byte
     fn parse_current_version<T: Read>(&mut self, file: &mut T) -> Result<(), GameWorldParseError> {
-        // Parse Map Name
-        let name = self.parse_current_version_string(file)?;
-        self.set_name(name.as_str());
-
-        // Parse Map Author
-        let author = self.parse_current_version_string(file)?;
-        self.set_author(author.as_str());
-
-        let hash = self.parse_current_version_uuid(file)?;
-        self.hash = hash;
-
-        // Parse Map Width and Map Height
-        let map_width: usize = bincode::deserialize_from::<&mut T, usize>(file)?;
-        let map_height: usize = bincode::deserialize_from::<&mut T, usize>(file)?;
-        if map_width > MAX_MAP_WIDTH {
-            return Err(GameWorldParseError::InvalidMapWidth {
-                max_width: MAX_MAP_WIDTH,
-                actual_width: map_width,
-            });
-        }
-        if map_height > MAX_MAP_HEIGHT {
-            return Err(GameWorldParseError::InvalidMapHeight {
-                max_height: MAX_MAP_HEIGHT,
-                actual_height: map_height,
-            });
-        }
-        self.data = vec![vec![Tile::AIR; map_height]; map_width];
-        assert_eq!(map_width, self.width());
-        assert_eq!(map_height, self.height());
-
-        // Parse actual map content
-        // The message ID of the current message tile
-        let mut current_message_id = 0usize;
-        for y in 0..self.height() {
-            for x in 0..self.width() {
-                let mut buf = [0u8; 1];
-                file.read_exact(&mut buf).map_err(|e| {
-                    GameWorldParseError::UnexpectedEndOfTileData {
-                        io_error: e,
-                        position: (y * x) + x,
-                    }
-                })?;
-                let mut tile = Tile::from_bytes(buf[0])
-                    .ok_or(GameWorldParseError::InvalidTile { tile_bytes: buf[0] })?;
-                // Assign the current message ID to message tiles
-                if let Tile::MESSAGE { .. } = tile {
-                    tile = Tile::MESSAGE {
-                        message_id: current_message_id,
+        let mut parsed_chunks: HashSet<[u8; CHUNK_TAG_LENGTH]> = HashSet::new();
+        // The number of messages that are expected in the messages chunk
+        let mut num_messages: Option<usize> = None;
+        loop {
+            let mut tag = [0u8; CHUNK_TAG_LENGTH];
+            file.read_exact(&mut tag)?;
+            let length = bincode::deserialize_from::<&mut T, u64>(&mut *file)?;
+            if tag == CHUNK_END {
+                break;
+            }
+            let mut data: Vec<u8> = vec![];
+            file.by_ref().take(length).read_to_end(&mut data)?;
+            if data.len() as u64 != length {
+                return Err(GameWorldParseError::IOError {
+                    io_error: ErrorKind::UnexpectedEof.into(),
+                });
+            }
+            if !parsed_chunks.insert(tag) {
+                return Err(GameWorldParseError::DuplicateChunk { tag });
+            }
+            let mut chunk = data.as_slice();
+            match tag {
+                CHUNK_METADATA => {
+                    let name = self.parse_current_version_string(&mut chunk)?;
+                    self.set_name(name.as_str());
+                    let author = self.parse_current_version_string(&mut chunk)?;
+                    self.set_author(author.as_str());
+                    self.hash = self.parse_current_version_uuid(&mut chunk)?;
+                },
+                CHUNK_TILES => {
+                    num_messages = Some(self.parse_tiles(&mut chunk)?);
+                },
+                CHUNK_MESSAGES => {
+                    let Some(expected_len) = num_messages else {
+                        return Err(GameWorldParseError::MissingChunk { tag: CHUNK_TILES });
                     };
-                    current_message_id += 1;
-                }
-                self.set(x, y, tile);
+                    self.messages.clear();
+                    self.parse_messages(&mut chunk, expected_len)?;
+                },
+                CHUNK_TILESET => self.parse_tileset(&mut chunk)?,
+                CHUNK_INFO => self.parse_info(&mut chunk)?,
+                CHUNK_LIVES => self.parse_lives(&mut chunk)?,
+                CHUNK_ENEMIES => {
+                    if num_messages.is_none() {
+                        return Err(GameWorldParseError::MissingChunk { tag: CHUNK_TILES });
+                    }
+                    self.parse_enemies(&mut chunk)?;
+                },
+                CHUNK_LINKS => {
+                    if num_messages.is_none() {
+                        return Err(GameWorldParseError::MissingChunk { tag: CHUNK_TILES });
+                    }
+                    self.parse_links(&mut chunk)?;
+                },
+                // Unknown chunks are skipped
+                _ => {},
             }
         }
-        self.parse_messages(file, current_message_id)?;
-        self.parse_tileset(file)?;
-
+        for tag in [CHUNK_METADATA, CHUNK_TILES] {
+            if !parsed_chunks.contains(&tag) {
+                return Err(GameWorldParseError::MissingChunk { tag });
+            }
+        }
+        if let Some(expected_len) = num_messages
+            && expected_len > 0
+            && !parsed_chunks.contains(&CHUNK_MESSAGES)
+        {
+            return Err(GameWorldParseError::MissingMessageString {
+                expected_length: expected_len as u32,
+                actual_length: 0,
+            });
+        }
         Ok(())
     }
 }
@@ -238,10 +397,192 @@ impl GameWorld {
         }
         Ok(())
     }
+    fn parse_info<T: Read>(&mut self, file: &mut T) -> Result<(), GameWorldParseError> {
+        let mut metadata = MapMetadata {
+            description: self.parse_current_version_string(file)?,
+            ..Default::default()
+        };
+        let mut difficulty_buf = [0u8; 1];
+        file.read_exact(&mut difficulty_buf)?;
+        if difficulty_buf[0] != 0x00 {
+            let Some(difficulty) = Difficulty::from_bytes(difficulty_buf[0]) else {
+                return Err(GameWorldParseError::InvalidDifficulty {
+                    difficulty_bytes: difficulty_buf[0],
+                });
+            };
+            metadata.difficulty = Some(difficulty);
+        }
+        metadata.par_moves = bincode::deserialize_from::<&mut T, Option<u64>>(&mut *file)?
+            .map(|par_moves| par_moves as usize);
+        metadata.created = bincode::deserialize_from::<&mut T, Option<i64>>(&mut *file)?;
+        metadata.modified = bincode::deserialize_from::<&mut T, Option<i64>>(&mut *file)?;
+        metadata.tags = bincode::deserialize_from::<&mut T, Vec<String>>(&mut *file)?;
+        self.metadata = metadata;
+        Ok(())
+    }
+    fn parse_lives<T: Read>(&mut self, file: &mut T) -> Result<(), GameWorldParseError> {
+        let lives = bincode::deserialize_from::<&mut T, u64>(file)?;
+        self.set_lives(lives as usize);
+        Ok(())
+    }
+    /// Parse all enemies. The map tiles must have been parsed already,
+    /// since all enemy positions must lie inside the map.
+    fn parse_enemies<T: Read>(&mut self, file: &mut T) -> Result<(), GameWorldParseError> {
+        let num_enemies = bincode::deserialize_from::<&mut T, u32>(&mut *file)?;
+        self.enemies.clear();
+        for _ in 0..num_enemies {
+            let mut kind_buf = [0u8; 1];
+            file.read_exact(&mut kind_buf)?;
+            let spawn = self.parse_position(file, enemy_position_error)?;
+            let mut deadly_from_buf = [0u8; 1];
+            file.read_exact(&mut deadly_from_buf)?;
+            let enemy = match kind_buf[0] {
+                0x01 => {
+                    let mut facing_buf = [0u8; 1];
+                    file.read_exact(&mut facing_buf)?;
+                    Enemy::walker(spawn, facing_buf[0] != 0x00)
+                },
+                0x02 => {
+                    let num_waypoints = bincode::deserialize_from::<&mut T, u32>(&mut *file)?;
+                    let waypoints = (0..num_waypoints)
+                        .map(|_| self.parse_position(file, enemy_position_error))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Enemy::flyer(spawn, waypoints)
+                },
+                _ => {
+                    return Err(GameWorldParseError::InvalidEnemy {
+                        enemy_bytes: kind_buf[0],
+                    });
+                },
+            };
+            self.enemies
+                .push(enemy.with_deadly_from(directions_from_bytes(deadly_from_buf[0])));
+        }
+        Ok(())
+    }
+    /// Parse all links. The map tiles must have been parsed already,
+    /// since all switches and linked blocks must lie inside the map.
+    fn parse_links<T: Read>(&mut self, file: &mut T) -> Result<(), GameWorldParseError> {
+        let num_switches = bincode::deserialize_from::<&mut T, u32>(&mut *file)?;
+        self.links.clear();
+        for _ in 0..num_switches {
+            let switch = self.parse_position(file, link_position_error)?;
+            let num_blocks = bincode::deserialize_from::<&mut T, u32>(&mut *file)?;
+            for _ in 0..num_blocks {
+                let block = self.parse_position(file, link_position_error)?;
+                self.link(switch, block);
+            }
+        }
+        Ok(())
+    }
+    /// Parse a map position, which must lie inside the map.
+    /// Positions outside of the map are reported with the error returned by `outside`.
+    fn parse_position<T: Read>(
+        &self,
+        file: &mut T,
+        outside: fn(usize, usize) -> GameWorldParseError,
+    ) -> Result<(usize, usize), GameWorldParseError> {
+        let x = bincode::deserialize_from::<&mut T, u64>(&mut *file)? as usize;
+        let y = bincode::deserialize_from::<&mut T, u64>(&mut *file)? as usize;
+        if x >= self.width() || y >= self.height() {
+            return Err(outside(x, y));
+        }
+        Ok((x, y))
+    }
+}
+
+fn enemy_position_error(x: usize, y: usize) -> GameWorldParseError {
+    GameWorldParseError::InvalidEnemyPosition { x, y }
+}
+
+fn link_position_error(x: usize, y: usize) -> GameWorldParseError {
+    GameWorldParseError::InvalidLinkPosition { x, y }
+}
+
+/// The bits that encode the directions an enemy is deadly from
+const DIRECTION_BITS: [(FromDirection, u8); 4] = [
+    (FromDirection::FROMNORTH, 0x01),
+    (FromDirection::FROMSOUTH, 0x02),
+    (FromDirection::FROMEAST, 0x04),
+    (FromDirection::FROMWEST, 0x08),
+];
+
+/// Encode the given directions as bit mask
+fn directions_to_bytes(directions: &[FromDirection]) -> u8 {
+    DIRECTION_BITS
+        .iter()
+        .filter(|(direction, _)| directions.contains(direction))
+        .fold(0x00, |bits, (_, bit)| bits | bit)
+}
+
+/// Decode the given bit mask into a list of directions. Unknown bits are ignored.
+fn directions_from_bytes(bits: u8) -> Vec<FromDirection> {
+    DIRECTION_BITS
+        .iter()
+        .filter(|(_, bit)| bits & bit != 0)
+        .map(|(direction, _)| *direction)
+        .collect()
+}
+
+/// Write a chunk with the given tag and data
+fn write_chunk<T: Write>(
+    file: &mut T,
+    tag: &[u8; CHUNK_TAG_LENGTH],
+    data: &[u8],
+) -> Result<(), GameWorldParseError> {
+    file.write_all(tag)?;
+    file.write_all(&bincode::serialize(&(data.len() as u64))?)?;
+    file.write_all(data)?;
+    Ok(())
 }
 
 // Code to parse a map with the current version.
 impl GameWorld {
+    /// Parse the map size and all map tiles.
+    /// Returns the number of message tiles, i.e., the number of messages that are expected.
+    fn parse_tiles<T: Read>(&mut self, file: &mut T) -> Result<usize, GameWorldParseError> {
+        // Parse Map Width and Map Height
+        let map_width: usize = bincode::deserialize_from::<&mut T, usize>(file)?;
+        let map_height: usize = bincode::deserialize_from::<&mut T, usize>(file)?;
+        if map_width > MAX_MAP_WIDTH {
+            return Err(GameWorldParseError::InvalidMapWidth {
+                max_width: MAX_MAP_WIDTH,
+                actual_width: map_width,
+            });
+        }
+        if map_height > MAX_MAP_HEIGHT {
+            return Err(GameWorldParseError::InvalidMapHeight {
+                max_height: MAX_MAP_HEIGHT,
+                actual_height: map_height,
+            });
+        }
+        self.data = vec![vec![Tile::AIR; map_height]; map_width];
+
+        // The message ID of the current message tile
+        let mut current_message_id = 0usize;
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let mut buf = [0u8; 1];
+                file.read_exact(&mut buf).map_err(|e| {
+                    GameWorldParseError::UnexpectedEndOfTileData {
+                        io_error: e,
+                        position: y * self.width() + x,
+                    }
+                })?;
+                let mut tile = Tile::from_bytes(buf[0])
+                    .ok_or(GameWorldParseError::InvalidTile { tile_bytes: buf[0] })?;
+                // Assign the current message ID to message tiles
+                if let Tile::MESSAGE { .. } = tile {
+                    tile = Tile::MESSAGE {
+                        message_id: current_message_id,
+                    };
+                    current_message_id += 1;
+                }
+                self.set(x, y, tile);
+            }
+        }
+        Ok(current_message_id)
+    }
     /// Parse a string
     fn parse_current_version_string<T: Read>(
         &mut self,
@@ -265,6 +606,84 @@ impl GameWorld {
         &self,
         file: &mut T,
     ) -> Result<(), GameWorldParseError> {
+        let message_ids = self.serialize_tiles(file)?;
+        self.serialize_messages(file, &message_ids)?;
+        // The number of lives is only considered if it differs from the default,
+        // such that the hash of maps without lives stays the same.
+        // Each optional section is preceded by its chunk tag, such that different sections
+        // cannot produce the same bytes.
+        if self.lives != GameWorld::DEFAULT_LIVES {
+            file.write_all(&CHUNK_LIVES)?;
+            self.serialize_lives(file)?;
+        }
+        // Enemies are only considered if there are any, for the same reason
+        if !self.enemies.is_empty() {
+            file.write_all(&CHUNK_ENEMIES)?;
+            self.serialize_enemies(file)?;
+        }
+        if self.active_links().next().is_some() {
+            file.write_all(&CHUNK_LINKS)?;
+            self.serialize_links(file)?;
+        }
+        Ok(())
+    }
+    /// Serialize all enemies of this map
+    pub(crate) fn serialize_enemies<T: Write>(
+        &self,
+        file: &mut T,
+    ) -> Result<(), GameWorldParseError> {
+        let position = |(x, y): (usize, usize)| bincode::serialize(&(x as u64, y as u64));
+        file.write_all(&bincode::serialize(&(self.enemies.len() as u32))?)?;
+        for enemy in &self.enemies {
+            let kind: u8 = match enemy.kind() {
+                EnemyKind::WALKER { .. } => 0x01,
+                EnemyKind::FLYER { .. } => 0x02,
+            };
+            file.write_all(&[kind])?;
+            file.write_all(&position(enemy.spawn())?)?;
+            file.write_all(&[directions_to_bytes(enemy.deadly_from())])?;
+            match enemy.kind() {
+                EnemyKind::WALKER { facing_right } => file.write_all(&[*facing_right as u8])?,
+                EnemyKind::FLYER { waypoints } => {
+                    file.write_all(&bincode::serialize(&(waypoints.len() as u32))?)?;
+                    for waypoint in waypoints {
+                        file.write_all(&position(*waypoint)?)?;
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+    /// Serialize the links of all switches of this map
+    pub(crate) fn serialize_links<T: Write>(
+        &self,
+        file: &mut T,
+    ) -> Result<(), GameWorldParseError> {
+        let position = |(x, y): (usize, usize)| bincode::serialize(&(x as u64, y as u64));
+        file.write_all(&bincode::serialize(&(self.active_links().count() as u32))?)?;
+        for (switch, blocks) in self.active_links() {
+            file.write_all(&position(*switch)?)?;
+            file.write_all(&bincode::serialize(&(blocks.len() as u32))?)?;
+            for block in blocks {
+                file.write_all(&position(*block)?)?;
+            }
+        }
+        Ok(())
+    }
+    /// Serialize the number of lives of this map
+    pub(crate) fn serialize_lives<T: Write>(
+        &self,
+        file: &mut T,
+    ) -> Result<(), GameWorldParseError> {
+        file.write_all(&bincode::serialize(&(self.lives as u64))?)?;
+        Ok(())
+    }
+    /// Serialize the map size and all map tiles.
+    /// Returns the IDs of all messages in the order in which their tiles appear in the map.
+    pub(crate) fn serialize_tiles<T: Write>(
+        &self,
+        file: &mut T,
+    ) -> Result<Vec<usize>, GameWorldParseError> {
         // Write Map Width and Height
         let width_b = bincode::serialize(&self.width())?;
         file.write_all(&width_b)?;
@@ -283,8 +702,7 @@ impl GameWorld {
                 file.write_all(&[tile.to_bytes()])?;
             }
         }
-        self.serialize_messages(file, &message_ids)?;
-        Ok(())
+        Ok(message_ids)
     }
     pub(crate) fn serialize_messages<T: Write>(
         &self,
@@ -317,6 +735,89 @@ impl GameWorld {
         };
         Ok(())
     }
+    /// Serialize the metadata of this map
+    pub(crate) fn serialize_info<T: Write>(&self, file: &mut T) -> Result<(), GameWorldParseError> {
+        file.write_all(&bincode::serialize(&self.metadata.description)?)?;
+        file.write_all(&[self
+            .metadata
+            .difficulty
+            .map(|difficulty| difficulty.to_bytes())
+            .unwrap_or(0x00)])?;
+        file.write_all(&bincode::serialize(
+            &self.metadata.par_moves.map(|par_moves| par_moves as u64),
+        )?)?;
+        file.write_all(&bincode::serialize(&self.metadata.created)?)?;
+        file.write_all(&bincode::serialize(&self.metadata.modified)?)?;
+        file.write_all(&bincode::serialize(&self.metadata.tags)?)?;
+        Ok(())
+    }
+}
+/// Implementations for parsing v0x02 map files.
+/// The code duplication is intentional here because we want to keep perfect
+/// backwards-compatibility with older map formats while supporting frequent
+/// changes of the current map format version.
+impl GameWorld {
+    /// Parse a map with version 0x02.
+    fn parse_v2<T: Read>(&mut self, file: &mut T) -> Result<(), GameWorldParseError> {
+        // Parse Map Name
+        let name = self.parse_current_version_string(file)?;
+        self.set_name(name.as_str());
+
+        // Parse Map Author
+        let author = self.parse_current_version_string(file)?;
+        self.set_author(author.as_str());
+
+        let hash = self.parse_current_version_uuid(file)?;
+        self.hash = hash;
+
+        // Parse Map Width and Map Height
+        let map_width: usize = bincode::deserialize_from::<&mut T, usize>(file)?;
+        let map_height: usize = bincode::deserialize_from::<&mut T, usize>(file)?;
+        if map_width > MAX_MAP_WIDTH {
+            return Err(GameWorldParseError::InvalidMapWidth {
+                max_width: MAX_MAP_WIDTH,
+                actual_width: map_width,
+            });
+        }
+        if map_height > MAX_MAP_HEIGHT {
+            return Err(GameWorldParseError::InvalidMapHeight {
+                max_height: MAX_MAP_HEIGHT,
+                actual_height: map_height,
+            });
+        }
+        self.data = vec![vec![Tile::AIR; map_height]; map_width];
+        assert_eq!(map_width, self.width());
+        assert_eq!(map_height, self.height());
+
+        // Parse actual map content
+        // The message ID of the current message tile
+        let mut current_message_id = 0usize;
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let mut buf = [0u8; 1];
+                file.read_exact(&mut buf).map_err(|e| {
+                    GameWorldParseError::UnexpectedEndOfTileData {
+                        io_error: e,
+                        position: (y * x) + x,
+                    }
+                })?;
+                let mut tile = Tile::from_bytes(buf[0])
+                    .ok_or(GameWorldParseError::InvalidTile { tile_bytes: buf[0] })?;
+                // Assign the current message ID to message tiles
+                if let Tile::MESSAGE { .. } = tile {
+                    tile = Tile::MESSAGE {
+                        message_id: current_message_id,
+                    };
+                    current_message_id += 1;
+                }
+                self.set(x, y, tile);
+            }
+        }
+        self.parse_messages(file, current_message_id)?;
+        self.parse_tileset(file)?;
+
+        Ok(())
+    }
 }
 /// Implementations for parsing v0x01 map files.
 /// The code duplication is intentional here because we want to keep perfect
@@ -444,6 +945,18 @@ impl Tile {
             Tile::COBBLEROOFSLOPER => 0x81,
             Tile::VENDINGMACHINEL => 0x12,
             Tile::VENDINGMACHINER => 0x13,
+            Tile::CHECKPOINT => 0x14,
+            Tile::CRATE => 0x15,
+            Tile::CRUMBLINGPLATFORM => 0x16,
+            Tile::CRUMBLINGPLATFORMCRACKED => 0x17,
+            Tile::FALLINGBLOCK => 0x18,
+            Tile::COLOREDDOOR { color } => 0x25 + (color.const_to_u8() * 2),
+            Tile::OPENCOLOREDDOOR { color } => 0x26 + (color.const_to_u8() * 2),
+            Tile::COLOREDKEY { color } => 0x38 + color.const_to_u8(),
+            Tile::LEVER => 0x60,
+            Tile::LEVERPULLED => 0x61,
+            Tile::TOGGLEBLOCK => 0x62,
+            Tile::TOGGLEBLOCKOFF => 0x63,
         }
     }
 
@@ -461,6 +974,11 @@ impl Tile {
             0x11 => Some(Tile::EXIT),
             0x12 => Some(Tile::VENDINGMACHINEL),
             0x13 => Some(Tile::VENDINGMACHINER),
+            0x14 => Some(Tile::CHECKPOINT),
+            0x15 => Some(Tile::CRATE),
+            0x16 => Some(Tile::CRUMBLINGPLATFORM),
+            0x17 => Some(Tile::CRUMBLINGPLATFORMCRACKED),
+            0x18 => Some(Tile::FALLINGBLOCK),
             0x20 => Some(Tile::DOOR),
             0x21 => Some(Tile::OPENDOOR),
             0x30 => Some(Tile::COIN),
@@ -469,6 +987,15 @@ impl Tile {
             0x22 => Some(Tile::LADDER),
             0x23 => Some(Tile::LADDERSLOPE),
             0x24 => Some(Tile::LADDERNATURE),
+            0x25..=0x29 if byte % 2 == 1 => Some(Tile::COLOREDDOOR {
+                color: KeyColor::const_from_u8((byte - 0x25) / 2),
+            }),
+            0x26..=0x2a if byte.is_multiple_of(2) => Some(Tile::OPENCOLOREDDOOR {
+                color: KeyColor::const_from_u8((byte - 0x26) / 2),
+            }),
+            0x38..=0x3a => Some(Tile::COLOREDKEY {
+                color: KeyColor::const_from_u8(byte - 0x38),
+            }),
             0x40 => Some(Tile::SPIKES),
             0x41 => Some(Tile::SPIKESALT),
             0x42 => Some(Tile::SPIKESSLOPED),
@@ -487,7 +1014,11 @@ impl Tile {
             0x5C => Some(Tile::WALLSPIKESRT),
             0x5D => Some(Tile::WALLSPIKESRLB),
             0x5E => Some(Tile::WALLSPIKESRLT),
-            0x70..=0x7e if byte % 2 == 0 => Some(Tile::TELEPORTENTRY {
+            0x60 => Some(Tile::LEVER),
+            0x61 => Some(Tile::LEVERPULLED),
+            0x62 => Some(Tile::TOGGLEBLOCK),
+            0x63 => Some(Tile::TOGGLEBLOCKOFF),
+            0x70..=0x7e if byte.is_multiple_of(2) => Some(Tile::TELEPORTENTRY {
                 teleport_id: TeleportId::const_from_u8((byte - 0x70) / 2),
             }),
             0x71..=0x7f if byte % 2 == 1 => Some(Tile::TELEPORTEXIT {
@@ -520,6 +1051,7 @@ impl Tile {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::world::presets::map_with_border;
     use bincode::ErrorKind;
     use bytebuffer::ByteBuffer;
     use std::path::PathBuf;
@@ -533,18 +1065,27 @@ mod tests {
 
     #[test]
     fn test_bidirectional_serialization_for_tiles() {
-        for tile in Tile::iter().chain(TeleportId::iter().flat_map(|teleport_id| {
-            vec![
-                Tile::TELEPORTEXIT { teleport_id },
-                Tile::TELEPORTENTRY { teleport_id },
-            ]
-        })) {
+        for tile in Tile::iter()
+            .chain(TeleportId::iter().flat_map(|teleport_id| {
+                vec![
+                    Tile::TELEPORTEXIT { teleport_id },
+                    Tile::TELEPORTENTRY { teleport_id },
+                ]
+            }))
+            .chain(KeyColor::iter().flat_map(|color| {
+                vec![
+                    Tile::COLOREDDOOR { color },
+                    Tile::OPENCOLOREDDOOR { color },
+                    Tile::COLOREDKEY { color },
+                ]
+            }))
+        {
             let reference: &Tile = &tile;
             let actual = Tile::from_bytes(reference.to_bytes());
             assert!(
                 actual.is_some(),
                 "Deserializing Tile {} (0x{:02X}) resulted in an error: Tile not found in {}",
-                reference.to_string(),
+                reference,
                 reference.to_bytes(),
                 stringify!(Tile::from_bytes())
             );
@@ -553,9 +1094,9 @@ mod tests {
                 *reference,
                 actual,
                 "The Tile {} (0x{:02X}) deserialized into tile {} (0x{:02X}) !",
-                reference.to_string(),
+                reference,
                 reference.to_bytes(),
-                actual.to_string(),
+                actual,
                 actual.to_bytes(),
             );
         }
@@ -567,7 +1108,7 @@ mod tests {
         assert!(
             result.is_ok(),
             "Map failed to serialize with error: {}",
-            result.unwrap_err().to_string()
+            result.unwrap_err()
         );
         let mut result_map = GameWorld::new(1, 1);
         buf.set_rpos(0);
@@ -575,7 +1116,7 @@ mod tests {
         assert!(
             result.is_ok(),
             "Map failed to parse with error: {}",
-            result.unwrap_err().to_string()
+            result.unwrap_err()
         );
         assert_eq!(map.hash, result_map.hash);
         assert_eq!(map.author, result_map.author);
@@ -599,6 +1140,475 @@ mod tests {
         assert_eq!("Example World", world.get_name());
     }
 
+    #[test]
+    fn test_read_v1_map_into_edited_map() {
+        let file = get_test_data_folder().join("testmap_v0x01.exm");
+        let expected = GameWorld::load_from_file(file.as_path()).unwrap();
+        let mut world = GameWorld::new(2, 2);
+        world.set_message_tile(1, 1, "Stale message".to_string());
+        world.set_forced_tileset(Some(Tileset::Classic));
+        world.set_metadata(MapMetadata {
+            description: "Stale description".to_string(),
+            ..Default::default()
+        });
+        let bytes = std::fs::read(&file).unwrap();
+        world.parse(&mut bytes.as_slice()).unwrap();
+        assert_eq!(expected.messages, world.messages);
+        assert_eq!(None, world.forced_tileset());
+        assert_eq!(&MapMetadata::default(), world.metadata());
+    }
+
+    /// Make sure that all maps shipped with the game can be migrated to the current map format
+    /// without losing any data.
+    #[test]
+    fn test_migrate_asset_maps() {
+        let mut maps_dir = PathBuf::from(env!("PROJECTDIR"));
+        maps_dir.push("..");
+        maps_dir.push("assets");
+        maps_dir.push("maps");
+        let mut files: Vec<PathBuf> = std::fs::read_dir(&maps_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "exm"))
+            .collect();
+        files.push(get_test_data_folder().join("testmap_v0x01.exm"));
+        assert!(files.len() > 1, "No maps found in {:?}", maps_dir);
+        for file in files {
+            let original_bytes = std::fs::read(&file).unwrap();
+            assert!(
+                original_bytes[MAGICBYTES.len()] < GameWorld::CURRENT_VERSION,
+                "{:?} is already stored in the current map format",
+                file
+            );
+            let original = GameWorld::load_from_file(file.as_path())
+                .unwrap_or_else(|e| panic!("Could not load {:?}: {}", file, e));
+            let mut buf = ByteBuffer::new();
+            original.serialize(&mut buf).unwrap();
+            buf.set_rpos(0);
+            let mut migrated = GameWorld::default();
+            migrated
+                .parse(&mut buf)
+                .unwrap_or_else(|e| panic!("Could not parse migrated {:?}: {}", file, e));
+            assert_eq!(original.hash, migrated.hash, "{:?}", file);
+            assert_eq!(original.name, migrated.name, "{:?}", file);
+            assert_eq!(original.author, migrated.author, "{:?}", file);
+            assert_eq!(original.data, migrated.data, "{:?}", file);
+            assert_eq!(original.messages, migrated.messages, "{:?}", file);
+            assert_eq!(original.playerspawn, migrated.playerspawn, "{:?}", file);
+            assert_eq!(
+                original.forced_tileset, migrated.forced_tileset,
+                "{:?}",
+                file
+            );
+            // Saving the migrated map again must not change anything
+            let mut buf2 = ByteBuffer::new();
+            migrated.serialize(&mut buf2).unwrap();
+            assert_eq!(buf.as_bytes(), buf2.as_bytes(), "{:?}", file);
+        }
+    }
+
+    /// Create the bytes of a map file with the current version that consists of the given chunks
+    fn map_from_chunks(chunks: &[([u8; CHUNK_TAG_LENGTH], Vec<u8>)]) -> ByteBuffer {
+        let mut data: Vec<u8> = vec![];
+        data.extend_from_slice(&MAGICBYTES);
+        data.push(GameWorld::CURRENT_VERSION);
+        for (tag, chunk) in chunks {
+            write_chunk(&mut data, tag, chunk).unwrap();
+        }
+        write_chunk(&mut data, &CHUNK_END, &[]).unwrap();
+        ByteBuffer::from_bytes(&data)
+    }
+
+    /// Get all chunks of the given map, serialized with the current version
+    fn chunks_of(map: &mut GameWorld) -> Vec<([u8; CHUNK_TAG_LENGTH], Vec<u8>)> {
+        map.recompute_hash();
+        let mut metadata: Vec<u8> = vec![];
+        metadata.extend_from_slice(&bincode::serialize(&map.name).unwrap());
+        metadata.extend_from_slice(&bincode::serialize(&map.author).unwrap());
+        metadata.extend_from_slice(&map.hash);
+        let mut tiles: Vec<u8> = vec![];
+        let message_ids = map.serialize_tiles(&mut tiles).unwrap();
+        let mut messages: Vec<u8> = vec![];
+        map.serialize_messages(&mut messages, &message_ids).unwrap();
+        let mut tileset: Vec<u8> = vec![];
+        map.serialize_tileset(&mut tileset).unwrap();
+        let mut info: Vec<u8> = vec![];
+        map.serialize_info(&mut info).unwrap();
+        let mut chunks = vec![
+            (CHUNK_METADATA, metadata),
+            (CHUNK_TILES, tiles),
+            (CHUNK_MESSAGES, messages),
+            (CHUNK_TILESET, tileset),
+            (CHUNK_INFO, info),
+        ];
+        if map.lives() != GameWorld::DEFAULT_LIVES {
+            let mut lives: Vec<u8> = vec![];
+            map.serialize_lives(&mut lives).unwrap();
+            chunks.push((CHUNK_LIVES, lives));
+        }
+        if !map.enemies().is_empty() {
+            let mut enemies: Vec<u8> = vec![];
+            map.serialize_enemies(&mut enemies).unwrap();
+            chunks.push((CHUNK_ENEMIES, enemies));
+        }
+        if map.active_links().next().is_some() {
+            let mut links: Vec<u8> = vec![];
+            map.serialize_links(&mut links).unwrap();
+            chunks.push((CHUNK_LINKS, links));
+        }
+        chunks
+    }
+
+    #[test]
+    fn test_chunks_are_serialized_in_order() {
+        let mut map = GameWorld::exampleworld();
+        let chunks = chunks_of(&mut map);
+        let mut expected = map_from_chunks(&chunks);
+        let mut actual = ByteBuffer::new();
+        map.serialize(&mut actual).unwrap();
+        assert_eq!(
+            expected.read_bytes(expected.len()).unwrap(),
+            actual.as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_unknown_chunks_are_skipped() {
+        let mut map = GameWorld::showcaseworld();
+        map.set_forced_tileset(Some(Tileset::Classic));
+        let mut chunks = chunks_of(&mut map);
+        chunks.insert(0, (*b"XTRA", vec![0xff; 17]));
+        chunks.insert(3, (*b"NONE", vec![]));
+        chunks.push((*b"LAST", vec![0x00, 0x01, 0x02]));
+        let mut buf = map_from_chunks(&chunks);
+        let mut result = GameWorld::new(1, 1);
+        result.parse(&mut buf).unwrap();
+        assert_eq!(map.hash, result.hash);
+        assert_eq!(map.data, result.data);
+        assert_eq!(map.messages, result.messages);
+        assert_eq!(Some(Tileset::Classic), result.forced_tileset);
+    }
+
+    #[test]
+    fn test_chunk_with_extra_data() {
+        let mut map = GameWorld::exampleworld();
+        let mut chunks = chunks_of(&mut map);
+        for (_, chunk) in chunks.iter_mut() {
+            chunk.extend_from_slice(&[0xab; 5]);
+        }
+        let mut buf = map_from_chunks(&chunks);
+        let mut result = GameWorld::new(1, 1);
+        result.parse(&mut buf).unwrap();
+        assert_eq!(map.hash, result.hash);
+        assert_eq!(map.width(), result.width());
+        assert_eq!(map.height(), result.height());
+        assert_eq!(map.messages.len(), result.messages.len());
+    }
+
+    #[test]
+    fn test_map_without_optional_chunks() {
+        let mut map = GameWorld::new(3, 3);
+        map.set(0, 0, Tile::WALL).set(1, 1, Tile::PLAYERSPAWN);
+        let chunks: Vec<_> = chunks_of(&mut map)
+            .into_iter()
+            .filter(|(tag, _)| ![CHUNK_TILESET, CHUNK_MESSAGES, CHUNK_INFO].contains(tag))
+            .collect();
+        let mut buf = map_from_chunks(&chunks);
+        let mut result = GameWorld::new(1, 1);
+        result.parse(&mut buf).unwrap();
+        assert_eq!(map.hash, result.hash);
+        assert_eq!(None, result.forced_tileset);
+        assert_eq!(&MapMetadata::default(), result.metadata());
+    }
+
+    #[test]
+    fn test_write_and_read_map_metadata() {
+        let mut map = GameWorld::exampleworld();
+        let mut metadata = MapMetadata {
+            description: "An example map\nwith two lines".to_string(),
+            difficulty: Some(Difficulty::Expert),
+            par_moves: Some(42),
+            created: Some(1_700_000_000_000),
+            modified: Some(1_700_000_123_456),
+            tags: vec![],
+        };
+        metadata.set_tags(["example", "tutorial"]);
+        map.set_metadata(metadata.clone());
+        map.recompute_hash();
+        let mut buf = ByteBuffer::new();
+        map.serialize(&mut buf).unwrap();
+        let mut result = GameWorld::new(1, 1);
+        result.parse(&mut buf).unwrap();
+        assert_eq!(&metadata, result.metadata());
+        assert_eq!(map.hash, result.hash);
+    }
+
+    #[test]
+    fn test_write_and_read_map_lives() {
+        let mut map = GameWorld::exampleworld();
+        map.set_lives(3);
+        let result = test_write_and_read_map(&mut map);
+        assert_eq!(3, result.lives());
+    }
+
+    #[test]
+    fn test_read_map_without_lives_into_map_with_lives() {
+        let mut map = GameWorld::exampleworld();
+        map.recompute_hash();
+        let mut buf = ByteBuffer::new();
+        map.serialize(&mut buf).unwrap();
+        buf.set_rpos(0);
+        let mut result = GameWorld::new(1, 1);
+        result.set_lives(3);
+        result.parse(&mut buf).unwrap();
+        assert_eq!(GameWorld::DEFAULT_LIVES, result.lives());
+        assert_eq!(map.hash, result.hash);
+    }
+
+    #[test]
+    fn test_lives_change_hash_only_if_not_default() {
+        let mut map = GameWorld::exampleworld();
+        map.recompute_hash();
+        let hash = map.hash;
+        map.set_lives(GameWorld::DEFAULT_LIVES);
+        map.recompute_hash();
+        assert_eq!(hash, map.hash);
+        map.set_lives(2);
+        map.recompute_hash();
+        assert_ne!(hash, map.hash);
+    }
+
+    /// Create a map with a walking and a flying enemy
+    fn map_with_enemies() -> GameWorld {
+        let mut map = GameWorld::exampleworld();
+        map.add_enemy(Enemy::walker((1, 1), false)).add_enemy(
+            Enemy::flyer((2, 3), vec![(5, 3), (5, 1)])
+                .with_deadly_from(vec![FromDirection::FROMSOUTH, FromDirection::FROMWEST]),
+        );
+        map
+    }
+
+    #[test]
+    fn test_write_and_read_map_enemies() {
+        let mut map = map_with_enemies();
+        let mut result = test_write_and_read_map(&mut map);
+        assert_eq!(map.enemies(), result.enemies());
+        // Parsing a map without enemies into a map with enemies removes all enemies
+        let mut map = GameWorld::exampleworld();
+        let mut buf = map_from_chunks(&chunks_of(&mut map));
+        result.parse(&mut buf).unwrap();
+        assert!(result.enemies().is_empty());
+    }
+
+    #[test]
+    fn test_enemies_change_hash_only_if_present() {
+        let mut map = GameWorld::exampleworld();
+        map.recompute_hash();
+        let hash = map.hash;
+        let mut map = map_with_enemies();
+        map.recompute_hash();
+        assert_ne!(hash, map.hash);
+        map.remove_enemy(1);
+        map.remove_enemy(0);
+        map.recompute_hash();
+        assert_eq!(hash, map.hash);
+    }
+
+    #[test]
+    fn test_map_with_invalid_enemy() {
+        let mut map = map_with_enemies();
+        let mut chunks = chunks_of(&mut map);
+        let (_, enemies) = chunks.last_mut().unwrap();
+        // The kind of the first enemy follows the number of enemies
+        enemies[bincode::serialize(&0u32).unwrap().len()] = 0xee;
+        let mut buf = map_from_chunks(&chunks);
+        let result = GameWorld::new(1, 1).parse(&mut buf);
+        assert!(matches!(
+            result,
+            Err(GameWorldParseError::InvalidEnemy { enemy_bytes: 0xee })
+        ));
+    }
+
+    #[test]
+    fn test_map_with_enemy_outside_of_map() {
+        let mut map = GameWorld::new(3, 3);
+        map.add_enemy(Enemy::flyer((1, 1), vec![(3, 1)]));
+        let mut buf = map_from_chunks(&chunks_of(&mut map));
+        let result = GameWorld::new(1, 1).parse(&mut buf);
+        assert!(matches!(
+            result,
+            Err(GameWorldParseError::InvalidEnemyPosition { x: 3, y: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_map_with_enemies_before_tiles() {
+        let mut map = map_with_enemies();
+        let mut chunks = chunks_of(&mut map);
+        let enemies = chunks.pop().unwrap();
+        chunks.insert(0, enemies);
+        let mut buf = map_from_chunks(&chunks);
+        let result = GameWorld::new(1, 1).parse(&mut buf);
+        assert!(matches!(
+            result,
+            Err(GameWorldParseError::MissingChunk { tag: CHUNK_TILES })
+        ));
+    }
+
+    /// Create a map with two levers, where the first one is linked to two blocks
+    fn map_with_links() -> GameWorld {
+        let mut map = map_with_border(8, 4);
+        map.set(1, 1, Tile::LEVER)
+            .set(2, 1, Tile::LEVERPULLED)
+            .set(4, 1, Tile::TOGGLEBLOCK)
+            .set(5, 1, Tile::DOOR);
+        map.link((1, 1), (4, 1))
+            .link((1, 1), (5, 1))
+            .link((2, 1), (4, 1));
+        map
+    }
+
+    #[test]
+    fn test_write_and_read_map_links() {
+        let mut map = map_with_links();
+        let mut result = test_write_and_read_map(&mut map);
+        assert_eq!(map.links(), result.links());
+        // Parsing a map without links into a map with links removes all links
+        let mut map = GameWorld::exampleworld();
+        let mut buf = map_from_chunks(&chunks_of(&mut map));
+        result.parse(&mut buf).unwrap();
+        assert!(result.links().is_empty());
+    }
+
+    #[test]
+    fn test_links_of_removed_switches_are_not_saved() {
+        let mut map = map_with_links();
+        map.set(2, 1, Tile::AIR);
+        let result = test_write_and_read_map(&mut map);
+        assert_eq!(vec![(4, 1), (5, 1)], result.linked_blocks((1, 1)));
+        assert_eq!(1, result.links().len());
+    }
+
+    #[test]
+    fn test_links_change_hash_only_if_present() {
+        let mut map = map_with_links();
+        map.set_links([]);
+        map.recompute_hash();
+        let hash = map.hash;
+        // Links of positions without a switch are not considered
+        map.link((3, 1), (4, 1));
+        map.recompute_hash();
+        assert_eq!(hash, map.hash);
+        map.link((1, 1), (4, 1));
+        map.recompute_hash();
+        assert_ne!(hash, map.hash);
+    }
+
+    #[test]
+    fn test_map_with_link_outside_of_map() {
+        let mut map = GameWorld::new(3, 3);
+        map.set(1, 1, Tile::LEVER);
+        map.link((1, 1), (1, 3));
+        let mut buf = map_from_chunks(&chunks_of(&mut map));
+        let result = GameWorld::new(1, 1).parse(&mut buf);
+        assert!(matches!(
+            result,
+            Err(GameWorldParseError::InvalidLinkPosition { x: 1, y: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_map_with_links_before_tiles() {
+        let mut map = map_with_links();
+        let mut chunks = chunks_of(&mut map);
+        let links = chunks.pop().unwrap();
+        chunks.insert(0, links);
+        let mut buf = map_from_chunks(&chunks);
+        let result = GameWorld::new(1, 1).parse(&mut buf);
+        assert!(matches!(
+            result,
+            Err(GameWorldParseError::MissingChunk { tag: CHUNK_TILES })
+        ));
+    }
+
+    #[test]
+    fn test_map_with_invalid_difficulty() {
+        let mut map = GameWorld::exampleworld();
+        let mut chunks = chunks_of(&mut map);
+        let (_, info) = chunks.last_mut().unwrap();
+        // The difficulty byte follows the empty description
+        info[bincode::serialize("").unwrap().len()] = 0xee;
+        let mut buf = map_from_chunks(&chunks);
+        let result = GameWorld::new(1, 1).parse(&mut buf);
+        assert!(matches!(
+            result,
+            Err(GameWorldParseError::InvalidDifficulty {
+                difficulty_bytes: 0xee
+            })
+        ));
+    }
+
+    #[test]
+    fn test_map_with_missing_chunks() {
+        for missing in [CHUNK_METADATA, CHUNK_TILES] {
+            let mut map = GameWorld::exampleworld();
+            let chunks: Vec<_> = chunks_of(&mut map)
+                .into_iter()
+                .filter(|(tag, _)| *tag != missing)
+                .collect();
+            let mut buf = map_from_chunks(&chunks);
+            let result = GameWorld::new(1, 1).parse(&mut buf);
+            assert!(
+                matches!(result, Err(GameWorldParseError::MissingChunk { tag }) if tag == missing),
+                "Expected missing chunk {:?}, got {:?}",
+                missing,
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn test_map_with_missing_messages_chunk() {
+        let mut map = GameWorld::new(2, 2);
+        map.set_message_tile(0, 0, "Hello World".to_string());
+        let chunks: Vec<_> = chunks_of(&mut map)
+            .into_iter()
+            .filter(|(tag, _)| *tag != CHUNK_MESSAGES)
+            .collect();
+        let mut buf = map_from_chunks(&chunks);
+        let result = GameWorld::new(1, 1).parse(&mut buf);
+        assert!(matches!(
+            result,
+            Err(GameWorldParseError::MissingMessageString {
+                expected_length: 1,
+                actual_length: 0
+            })
+        ));
+    }
+
+    #[test]
+    fn test_map_with_duplicate_chunk() {
+        let mut map = GameWorld::exampleworld();
+        let mut chunks = chunks_of(&mut map);
+        chunks.push(chunks[1].clone());
+        let mut buf = map_from_chunks(&chunks);
+        let result = GameWorld::new(1, 1).parse(&mut buf);
+        assert!(matches!(
+            result,
+            Err(GameWorldParseError::DuplicateChunk { tag: CHUNK_TILES })
+        ));
+    }
+
+    #[test]
+    fn test_map_with_truncated_chunk() {
+        let mut map = GameWorld::exampleworld();
+        let mut buf = map_from_chunks(&chunks_of(&mut map));
+        // Cut the map file in the middle of the tiles chunk
+        let bytes = buf.read_bytes(buf.len() / 2).unwrap();
+        let result = GameWorld::new(1, 1).parse(&mut ByteBuffer::from_bytes(&bytes));
+        assert!(matches!(result, Err(GameWorldParseError::IOError { .. })));
+    }
+
     #[test]
     fn test_write_and_read_simple_map() {
         let mut reference_map = GameWorld::new(2, 2);
@@ -632,6 +1642,8 @@ mod tests {
         let mut data: Vec<u8> = vec![];
         data.extend_from_slice(&MAGICBYTES);
         data.push(GameWorld::CURRENT_VERSION);
+        data.extend_from_slice(&CHUNK_METADATA);
+        data.extend_from_slice(&bincode::serialize(&0u64).unwrap());
         data.extend_from_slice(&bincode::serialize(&map.name).unwrap());
         data.extend_from_slice(&bincode::serialize(&map.author).unwrap());
         map.set(0, 0, Tile::WALL)
@@ -658,6 +1670,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_load_map_file_with_invalid_hash_unverified() {
+        let mut map = GameWorld::exampleworld();
+        map.recompute_hash();
+        let mut buf = ByteBuffer::new();
+        map.serialize(&mut buf).unwrap();
+        let mut data = buf.into_vec();
+        // The hash is stored at the end of the metadata chunk
+        let hash_offset = MAGICBYTES.len() + 1 + CHUNK_TAG_LENGTH + 8;
+        let hash_offset = hash_offset + bincode::serialize(&map.name).unwrap().len();
+        let hash_offset = hash_offset + bincode::serialize(&map.author).unwrap().len();
+        assert_eq!(map.hash[..], data[hash_offset..hash_offset + HASH_LENGTH]);
+        data[hash_offset] ^= 0xff;
+        let path = std::env::temp_dir().join(format!(
+            "exodus_test_invalid_hash_{}.exm",
+            std::process::id()
+        ));
+        std::fs::write(&path, &data).unwrap();
+        assert!(matches!(
+            GameWorld::load_from_file(&path),
+            Err(GameWorldParseError::HashMismatch { .. })
+        ));
+        let loaded = GameWorld::load_from_file_unverified(&path).unwrap();
+        assert_eq!(map.hash, loaded.hash);
+        assert_eq!(
+            Some(GameWorld::CURRENT_VERSION),
+            GameWorld::file_format_version(&path).unwrap()
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
     #[test]
     fn test_write_and_read_map_with_empty_name() {
         let mut reference_map = GameWorld::new(2, 2);
@@ -826,7 +1869,7 @@ mod tests {
     fn test_map_with_invalid_name_string() {
         let mut data: Vec<u8> = vec![];
         data.extend_from_slice(&MAGICBYTES);
-        data.push(GameWorld::CURRENT_VERSION);
+        data.push(0x02);
         data.extend_from_slice(&[0xffu8; 100]);
         let mut buf = ByteBuffer::from_bytes(&data);
         let mut map = GameWorld::new(1, 1);
@@ -846,7 +1889,7 @@ mod tests {
     fn test_map_with_invalid_author_string() {
         let mut data: Vec<u8> = vec![];
         data.extend_from_slice(&MAGICBYTES);
-        data.push(GameWorld::CURRENT_VERSION);
+        data.push(0x02);
         data.extend_from_slice(&bincode::serialize("Test Name").unwrap());
         data.extend_from_slice(&[0xffu8; 100]);
         let mut buf = ByteBuffer::from_bytes(&data);
@@ -867,11 +1910,11 @@ mod tests {
     fn test_map_with_invalid_width() {
         let mut data: Vec<u8> = vec![];
         data.extend_from_slice(&MAGICBYTES);
-        data.push(GameWorld::CURRENT_VERSION);
+        data.push(0x02);
         data.extend_from_slice(&bincode::serialize("Test Name").unwrap());
         data.extend_from_slice(&bincode::serialize("Test Author").unwrap());
         data.extend_from_slice(&[0xffu8; HASH_LENGTH]);
-        data.extend_from_slice(&bincode::serialize(&((MAX_MAP_WIDTH + 1) as usize)).unwrap());
+        data.extend_from_slice(&bincode::serialize(&(MAX_MAP_WIDTH + 1)).unwrap());
         let mut buf = ByteBuffer::from_bytes(&data);
         let mut map = GameWorld::new(1, 1);
         let result = map.parse(&mut buf);
@@ -891,11 +1934,11 @@ mod tests {
     fn test_map_with_invalid_width_zero() {
         let mut data: Vec<u8> = vec![];
         data.extend_from_slice(&MAGICBYTES);
-        data.push(GameWorld::CURRENT_VERSION);
+        data.push(0x02);
         data.extend_from_slice(&bincode::serialize("Test Name").unwrap());
         data.extend_from_slice(&bincode::serialize("Test Author").unwrap());
         data.extend_from_slice(&[0xffu8; HASH_LENGTH]);
-        data.extend_from_slice(&bincode::serialize(&(0 as usize)).unwrap());
+        data.extend_from_slice(&bincode::serialize(&0_usize).unwrap());
         let mut buf = ByteBuffer::from_bytes(&data);
         let mut map = GameWorld::new(1, 1);
         let result = map.parse(&mut buf);
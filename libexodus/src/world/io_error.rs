@@ -1,4 +1,4 @@
-use crate::world::io::MAGICBYTES;
+use crate::world::io::{CHUNK_TAG_LENGTH, MAGICBYTES};
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 
@@ -46,6 +46,42 @@ pub enum GameWorldParseError {
         expected_length: u32,
         actual_length: u32,
     },
+    MissingChunk {
+        tag: [u8; CHUNK_TAG_LENGTH],
+    },
+    DuplicateChunk {
+        tag: [u8; CHUNK_TAG_LENGTH],
+    },
+    InvalidTextMapLine {
+        line: usize,
+    },
+    UnknownLegendEntry {
+        line: usize,
+        legend_id: String,
+    },
+    UnknownTextMapCharacter {
+        line: usize,
+        character: char,
+    },
+    InvalidTextMapRow {
+        line: usize,
+        expected_width: usize,
+        actual_width: usize,
+    },
+    InvalidDifficulty {
+        difficulty_bytes: u8,
+    },
+    InvalidEnemy {
+        enemy_bytes: u8,
+    },
+    InvalidEnemyPosition {
+        x: usize,
+        y: usize,
+    },
+    InvalidLinkPosition {
+        x: usize,
+        y: usize,
+    },
 }
 
 impl Display for GameWorldParseError {
@@ -108,6 +144,54 @@ impl Display for GameWorldParseError {
                 "Missing Message String! Expected number of messages: {} - actual: {}",
                 expected_length, actual_length
             ),
+            GameWorldParseError::MissingChunk { tag } => write!(
+                f,
+                "Missing mandatory chunk {} in Map File!",
+                String::from_utf8_lossy(tag)
+            ),
+            GameWorldParseError::DuplicateChunk { tag } => write!(
+                f,
+                "Unexpected duplicate chunk {} found in Map File!",
+                String::from_utf8_lossy(tag)
+            ),
+            GameWorldParseError::InvalidTextMapLine { line } => {
+                write!(f, "Invalid line {} in Text Map File!", line)
+            },
+            GameWorldParseError::UnknownLegendEntry { line, legend_id } => write!(
+                f,
+                "Unknown tile {} in legend of Text Map File at line {}",
+                legend_id, line
+            ),
+            GameWorldParseError::UnknownTextMapCharacter { line, character } => write!(
+                f,
+                "Character '{}' at line {} is not defined in the legend of the Text Map File",
+                character, line
+            ),
+            GameWorldParseError::InvalidTextMapRow {
+                line,
+                expected_width,
+                actual_width,
+            } => write!(
+                f,
+                "Invalid Map Row Width at line {}: {} (Expected width: {})",
+                line, actual_width, expected_width
+            ),
+            GameWorldParseError::InvalidDifficulty { difficulty_bytes } => write!(
+                f,
+                "Difficulty Byte not recognized as valid difficulty: 0x{:02x}",
+                difficulty_bytes
+            ),
+            GameWorldParseError::InvalidEnemy { enemy_bytes } => write!(
+                f,
+                "Enemy Byte not recognized as valid enemy: 0x{:02x}",
+                enemy_bytes
+            ),
+            GameWorldParseError::InvalidEnemyPosition { x, y } => {
+                write!(f, "Enemy position {},{} lies outside of the map", x, y)
+            },
+            GameWorldParseError::InvalidLinkPosition { x, y } => {
+                write!(f, "Link position {},{} lies outside of the map", x, y)
+            },
         }
     }
 }
@@ -142,6 +226,16 @@ impl GameWorldParseError {
             GameWorldParseError::HashMismatch { .. } => 9,
             GameWorldParseError::MissingMessageString { .. } => 10,
             GameWorldParseError::InvalidTileset { .. } => 11,
+            GameWorldParseError::MissingChunk { .. } => 12,
+            GameWorldParseError::DuplicateChunk { .. } => 13,
+            GameWorldParseError::InvalidTextMapLine { .. } => 14,
+            GameWorldParseError::UnknownLegendEntry { .. } => 15,
+            GameWorldParseError::UnknownTextMapCharacter { .. } => 16,
+            GameWorldParseError::InvalidTextMapRow { .. } => 17,
+            GameWorldParseError::InvalidDifficulty { .. } => 18,
+            GameWorldParseError::InvalidEnemy { .. } => 19,
+            GameWorldParseError::InvalidEnemyPosition { .. } => 20,
+            GameWorldParseError::InvalidLinkPosition { .. } => 21,
         }
     }
 }
@@ -0,0 +1,217 @@
+use crate::world::GameWorld;
+use std::fmt::{Display, Formatter};
+use strum_macros::EnumIter;
+
+/// The difficulty rating of a map, as declared by the map author
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, EnumIter)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+    Expert,
+}
+
+impl Difficulty {
+    /// Get the string identifier of this difficulty, e.g., used for text maps and translations
+    pub fn str_id(&self) -> &str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+            Difficulty::Expert => "expert",
+        }
+    }
+    /// Convert this difficulty into its byte representation for the map file format
+    pub const fn to_bytes(&self) -> u8 {
+        match self {
+            Difficulty::Easy => 0x01,
+            Difficulty::Medium => 0x02,
+            Difficulty::Hard => 0x03,
+            Difficulty::Expert => 0x04,
+        }
+    }
+    /// Convert the given byte into a difficulty, or return None if the byte is invalid
+    pub const fn from_bytes(byte: u8) -> Option<Self> {
+        match byte {
+            0x01 => Some(Difficulty::Easy),
+            0x02 => Some(Difficulty::Medium),
+            0x03 => Some(Difficulty::Hard),
+            0x04 => Some(Difficulty::Expert),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Difficulty::Easy => write!(f, "Easy"),
+            Difficulty::Medium => write!(f, "Medium"),
+            Difficulty::Hard => write!(f, "Hard"),
+            Difficulty::Expert => write!(f, "Expert"),
+        }
+    }
+}
+
+/// Additional information about a map that is not part of the map content.
+/// The metadata is not considered when computing the hash of a map, i.e., it can be edited
+/// without invalidating highscores and replays.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MapMetadata {
+    /// A human-readable description of the map
+    pub description: String,
+    /// The difficulty rating of the map, if the author has rated the map
+    pub difficulty: Option<Difficulty>,
+    /// The number of moves the author needs to beat the map, if declared by the author
+    pub par_moves: Option<usize>,
+    /// The time the map has been saved for the first time (ms from UNIX Epoch)
+    pub created: Option<i64>,
+    /// The time the map has been saved for the last time (ms from UNIX Epoch)
+    pub modified: Option<i64>,
+    /// Free-form tags of the map
+    pub tags: Vec<String>,
+}
+
+impl MapMetadata {
+    /// Set the given tags, removing surrounding whitespace, empty and duplicate tags
+    pub fn set_tags<'a>(&mut self, tags: impl IntoIterator<Item = &'a str>) -> &mut Self {
+        self.tags.clear();
+        for tag in tags.into_iter().map(str::trim) {
+            if !tag.is_empty() && !self.tags.iter().any(|t| t == tag) {
+                self.tags.push(tag.to_string());
+            }
+        }
+        self
+    }
+    /// Check if the metadata contains the given tag, ignoring case
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+    /// Update the modification time to the given timestamp (ms from UNIX Epoch).
+    /// If no creation time is set, the creation time is set as well.
+    pub fn update_timestamps(&mut self, timestamp: i64) -> &mut Self {
+        self.created.get_or_insert(timestamp);
+        self.modified = Some(timestamp);
+        self
+    }
+}
+
+/// Format the given timestamp (ms from UNIX Epoch) in the local time zone,
+/// or return None if the timestamp is out of range
+pub fn format_timestamp(timestamp: i64) -> Option<String> {
+    let time = chrono::DateTime::from_timestamp_millis(timestamp)?;
+    Some(
+        time.with_timezone(&chrono::offset::Local)
+            .format("%Y-%m-%d %H:%M")
+            .to_string(),
+    )
+}
+
+impl GameWorld {
+    /// Get the metadata of this world
+    pub fn metadata(&self) -> &MapMetadata {
+        &self.metadata
+    }
+    /// Set the metadata of this world
+    pub fn set_metadata(&mut self, metadata: MapMetadata) -> &mut Self {
+        self.metadata = metadata;
+        self
+    }
+    /// Update the modification time of this world to the current time
+    pub fn update_timestamps_with_current_time(&mut self) -> &mut Self {
+        let timestamp = chrono::offset::Local::now().timestamp_millis();
+        self.metadata.update_timestamps(timestamp);
+        self
+    }
+    /// Check if this world matches the given search query.
+    /// A world matches if each whitespace-separated word of the query is contained in the name,
+    /// author, description or any tag of the world, ignoring case.
+    ///
+    /// ```rust
+    /// use libexodus::world::GameWorld;
+    /// let mut world = GameWorld::new(3, 3);
+    /// world.set_name("Dungeon").set_author("Alice");
+    /// let mut metadata = world.metadata().clone();
+    /// metadata.set_tags(["puzzle", "short"]);
+    /// world.set_metadata(metadata);
+    /// assert!(world.matches_search("dungeon PUZZLE"));
+    /// assert!(world.matches_search(""));
+    /// assert!(!world.matches_search("alice long"));
+    /// ```
+    pub fn matches_search(&self, query: &str) -> bool {
+        let fields: Vec<String> = [&self.name, &self.author, &self.metadata.description]
+            .into_iter()
+            .chain(self.metadata.tags.iter())
+            .map(|field| field.to_lowercase())
+            .collect();
+        query
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .all(|word| fields.iter().any(|field| field.contains(word.as_str())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::world::GameWorld;
+    use crate::world::metadata::{Difficulty, MapMetadata};
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_bidirectional_serialization_for_difficulty() {
+        for difficulty in Difficulty::iter() {
+            assert_eq!(
+                Some(difficulty),
+                Difficulty::from_bytes(difficulty.to_bytes())
+            );
+        }
+        assert_eq!(None, Difficulty::from_bytes(0x00));
+    }
+
+    #[test]
+    fn test_set_tags() {
+        let mut metadata = MapMetadata::default();
+        metadata.set_tags([" puzzle", "", "short ", "puzzle"]);
+        assert_eq!(vec!["puzzle", "short"], metadata.tags);
+        assert!(metadata.has_tag("Puzzle"));
+        assert!(!metadata.has_tag("long"));
+    }
+
+    #[test]
+    fn test_update_timestamps() {
+        let mut metadata = MapMetadata::default();
+        metadata.update_timestamps(10);
+        assert_eq!(Some(10), metadata.created);
+        assert_eq!(Some(10), metadata.modified);
+        metadata.update_timestamps(20);
+        assert_eq!(Some(10), metadata.created);
+        assert_eq!(Some(20), metadata.modified);
+    }
+
+    #[test]
+    fn test_metadata_does_not_change_hash() {
+        let mut world = GameWorld::exampleworld();
+        world.recompute_hash();
+        let hash = *world.hash();
+        world.set_metadata(MapMetadata {
+            description: "A description".to_string(),
+            difficulty: Some(Difficulty::Hard),
+            par_moves: Some(12),
+            ..Default::default()
+        });
+        world.recompute_hash();
+        assert_eq!(&hash, world.hash());
+    }
+
+    #[test]
+    fn test_matches_search_in_description() {
+        let mut world = GameWorld::new(2, 2);
+        world.set_metadata(MapMetadata {
+            description: "A Long Way Down".to_string(),
+            ..Default::default()
+        });
+        assert!(world.matches_search("way"));
+        assert!(world.matches_search("  long   down "));
+        assert!(!world.matches_search("up"));
+    }
+}
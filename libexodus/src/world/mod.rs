@@ -1,15 +1,27 @@
+use crate::enemies::Enemy;
 use crate::tiles::{TeleportId, Tile, TileKind};
 use crate::tilesets::Tileset;
-use std::collections::HashMap;
+use crate::world::metadata::MapMetadata;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 use std::path::{Path, PathBuf};
 
+pub mod crates;
+pub mod drawing;
 pub mod exampleworlds;
 pub mod hash;
+pub mod history;
 pub mod io;
 pub mod io_error;
+pub mod metadata;
+pub mod platforms;
 pub mod presets;
+pub mod region;
+pub mod resize;
+pub mod switches;
+pub mod text_io;
+pub mod validation;
 
 #[derive(Debug)]
 pub struct OutOfBoundsError(usize);
@@ -25,6 +37,19 @@ struct TeleportMetadata {
     teleport_exit_locations: HashMap<TeleportId, (usize, usize)>,
 }
 
+/// Changes to the map that are made while playing, which are undone when the game state is reset
+#[derive(Clone, Default)]
+struct GameStateChanges {
+    /// The original tiles at all positions that have been changed while playing
+    original_tiles: HashMap<(usize, usize), Tile>,
+    /// The tiles that are covered by a crate that has been moved onto them
+    covered_tiles: HashMap<(usize, usize), Tile>,
+    /// The crumbling platform the player has cracked during the last move
+    cracked_platform: Option<(usize, usize)>,
+    /// The current positions of all falling blocks, or None if they have not been searched yet
+    falling_blocks: Option<Vec<(usize, usize)>>,
+}
+
 #[derive(Clone)]
 pub struct GameWorld {
     /// A human-readable name of this world
@@ -47,6 +72,16 @@ pub struct GameWorld {
     forced_tileset: Option<Tileset>,
     /// Info about the teleports in this map
     teleport_metadata: TeleportMetadata,
+    /// Additional information about this map, e.g., description and difficulty
+    metadata: MapMetadata,
+    /// The number of lives the player has when playing this map
+    lives: usize,
+    /// All patrolling enemies of this map
+    enemies: Vec<Enemy>,
+    /// The positions of all blocks that are toggled by the switch at the given position
+    links: BTreeMap<(usize, usize), BTreeSet<(usize, usize)>>,
+    /// Changes made to this map while playing
+    game_state_changes: GameStateChanges,
 }
 
 impl Default for GameWorld {
@@ -62,11 +97,18 @@ impl Default for GameWorld {
             messages: vec![],
             forced_tileset: None,
             teleport_metadata: Default::default(),
+            metadata: Default::default(),
+            lives: GameWorld::DEFAULT_LIVES,
+            enemies: vec![],
+            links: BTreeMap::new(),
+            game_state_changes: Default::default(),
         }
     }
 }
 
 impl GameWorld {
+    /// The number of lives the player has on maps that do not specify a number of lives
+    pub const DEFAULT_LIVES: usize = 1;
     pub fn new(width: usize, height: usize) -> Self {
         assert! {width > 0};
         assert! {height > 0};
@@ -81,15 +123,16 @@ impl GameWorld {
             messages: vec![],     // No messages
             forced_tileset: None, // Do not force a tile set
             teleport_metadata: Default::default(),
+            metadata: Default::default(),
+            lives: GameWorld::DEFAULT_LIVES,
+            enemies: vec![],
+            links: BTreeMap::new(),
+            game_state_changes: Default::default(),
         }
     }
     /// Get the unique ID of this map as hex-string representation
     pub fn hash_str(&self) -> String {
-        let mut ret = String::new();
-        for b in &self.hash {
-            ret.push_str(format!("{:02X}", *b).as_str());
-        }
-        ret
+        hash::hash_to_str(&self.hash)
     }
     /// Get the unique ID of this map as byte slice
     pub fn hash(&self) -> &[u8; 32] {
@@ -123,6 +166,38 @@ impl GameWorld {
         self.name = new_name.to_string();
         self
     }
+    /// Get the number of lives the player has when playing this world
+    pub fn lives(&self) -> usize {
+        self.lives
+    }
+    /// Set the number of lives the player has when playing this world. The player has at least one life.
+    pub fn set_lives(&mut self, lives: usize) -> &mut Self {
+        self.lives = lives.max(1);
+        self
+    }
+    /// Get all patrolling enemies of this world
+    pub fn enemies(&self) -> &[Enemy] {
+        &self.enemies
+    }
+    /// Add a patrolling enemy to this world
+    ///
+    /// ```rust
+    /// use libexodus::enemies::Enemy;
+    /// use libexodus::world::GameWorld;
+    /// let mut world = GameWorld::new(4, 2);
+    /// world.add_enemy(Enemy::walker((1, 1), true));
+    /// assert_eq!((1, 1), world.enemies()[0].spawn());
+    /// world.remove_enemy(0);
+    /// assert!(world.enemies().is_empty());
+    /// ```
+    pub fn add_enemy(&mut self, enemy: Enemy) -> &mut Self {
+        self.enemies.push(enemy);
+        self
+    }
+    /// Remove the enemy with the given index from this world and return it
+    pub fn remove_enemy(&mut self, index: usize) -> Option<Enemy> {
+        (index < self.enemies.len()).then(|| self.enemies.remove(index))
+    }
     /// Get the author name of this world
     pub fn get_author(&self) -> &str {
         self.author.as_str()
@@ -184,9 +259,12 @@ impl GameWorld {
                         self.playerspawn = (x, y);
                     },
                     TileKind::LADDER => {},
-                    TileKind::DOOR => {},
+                    TileKind::DOOR { .. } => {},
                     TileKind::COLLECTIBLE { .. } => {},
                     TileKind::EXIT => {},
+                    TileKind::CHECKPOINT => {},
+                    TileKind::CRATE => {},
+                    TileKind::SWITCH => {},
                 }
             },
         }
@@ -360,7 +438,8 @@ impl GameWorld {
         self
     }
     /// Reset the game state.
-    /// This will close opened doors.
+    /// This will close opened doors, move all crates and falling blocks back to their original
+    /// positions, restore crumbled platforms and undo all changes made by switches.
     ///
     /// ```rust
     /// use libexodus::tiles::Tile;
@@ -371,12 +450,26 @@ impl GameWorld {
     /// assert_eq!(Tile::DOOR,*world.get(1,0).unwrap())
     /// ```
     pub fn reset_game_state(&mut self) {
+        let changes = std::mem::take(&mut self.game_state_changes);
+        for ((x, y), tile) in changes.original_tiles {
+            self.set(x, y, tile);
+        }
         for x in 0..self.width() {
             for y in 0..self.height() {
-                if self.get(x as i32, y as i32).unwrap() == &Tile::OPENDOOR {
-                    self.set(x, y, Tile::DOOR);
+                if let Some(door) = self.get(x as i32, y as i32).unwrap().closed_door() {
+                    self.set(x, y, door);
                 }
             }
         }
     }
+    /// Set the tile at the given position while playing, remembering the original tile
+    /// such that the change can be undone when the game state is reset
+    fn set_during_game(&mut self, position: (usize, usize), tile: Tile) {
+        let original = self.data[position.0][position.1].clone();
+        self.game_state_changes
+            .original_tiles
+            .entry(position)
+            .or_insert(original);
+        self.set(position.0, position.1, tile);
+    }
 }
@@ -0,0 +1,181 @@
+use crate::tiles::Tile;
+use crate::world::GameWorld;
+
+/// The changes that have been made to crumbling platforms and falling blocks after a single move
+/// of the player
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PlatformChanges {
+    /// The positions of all platforms that have broken, i.e., that have been replaced with air
+    pub broken: Vec<(usize, usize)>,
+    /// The position of the platform the player is standing on, if it has cracked
+    pub cracked: Option<(usize, usize)>,
+    /// All falling blocks that have fallen down, from their old to their new position
+    pub fallen: Vec<((usize, usize), (usize, usize))>,
+}
+
+impl PlatformChanges {
+    /// Check if nothing has changed
+    pub fn is_empty(&self) -> bool {
+        self.broken.is_empty() && self.cracked.is_none() && self.fallen.is_empty()
+    }
+}
+
+impl GameWorld {
+    /// Update all crumbling platforms and falling blocks after the player has completed a single
+    /// move and arrived at the given position.
+    /// The cracked platform the player has stood on during the last move breaks, and the crumbling
+    /// platform below the player cracks. Afterwards, all falling blocks fall down until they land
+    /// on a tile that is not air, or on top of the player.
+    ///
+    /// All changes are undone by [GameWorld::reset_game_state].
+    ///
+    /// ```rust
+    /// use libexodus::tiles::Tile;
+    /// use libexodus::world::presets;
+    /// let mut world = presets::map_with_border(5, 6);
+    /// world.set(2, 2, Tile::CRUMBLINGPLATFORM);
+    /// world.set(2, 4, Tile::FALLINGBLOCK);
+    /// // The player lands on the platform, which holds the falling block
+    /// let changes = world.update_platforms((2, 3));
+    /// assert_eq!(Some((2, 2)), changes.cracked);
+    /// assert!(changes.fallen.is_empty());
+    /// // The player walks off the platform, which breaks and lets the block fall down
+    /// let changes = world.update_platforms((3, 3));
+    /// assert_eq!(vec![(2, 2)], changes.broken);
+    /// assert_eq!(vec![((2, 4), (2, 1))], changes.fallen);
+    /// world.reset_game_state();
+    /// assert_eq!(Tile::CRUMBLINGPLATFORM, *world.get(2, 2).unwrap());
+    /// assert_eq!(Tile::FALLINGBLOCK, *world.get(2, 4).unwrap());
+    /// ```
+    pub fn update_platforms(&mut self, player: (usize, usize)) -> PlatformChanges {
+        let mut changes = PlatformChanges::default();
+        if let Some(position) = self.game_state_changes.cracked_platform.take()
+            && self.data[position.0][position.1] == Tile::CRUMBLINGPLATFORMCRACKED
+        {
+            self.set_during_game(position, Tile::AIR);
+            changes.broken.push(position);
+        }
+        if player.1 > 0
+            && self.get(player.0 as i32, player.1 as i32 - 1) == Some(&Tile::CRUMBLINGPLATFORM)
+        {
+            let position = (player.0, player.1 - 1);
+            self.set_during_game(position, Tile::CRUMBLINGPLATFORMCRACKED);
+            self.game_state_changes.cracked_platform = Some(position);
+            changes.cracked = Some(position);
+        }
+        let mut falling_blocks = self
+            .game_state_changes
+            .falling_blocks
+            .take()
+            .unwrap_or_else(|| self.falling_block_positions());
+        // Lower blocks fall first, such that stacked blocks fall down together
+        falling_blocks.sort_by_key(|(x, y)| (*y, *x));
+        for block in falling_blocks.iter_mut() {
+            let mut target = *block;
+            while target.1 > 0
+                && self.data[target.0][target.1 - 1] == Tile::AIR
+                && (target.0, target.1 - 1) != player
+            {
+                target.1 -= 1;
+            }
+            if target != *block {
+                self.set_during_game(*block, Tile::AIR);
+                self.set_during_game(target, Tile::FALLINGBLOCK);
+                changes.fallen.push((*block, target));
+                *block = target;
+            }
+        }
+        self.game_state_changes.falling_blocks = Some(falling_blocks);
+        changes
+    }
+    /// Get the positions of all falling blocks in this map
+    fn falling_block_positions(&self) -> Vec<(usize, usize)> {
+        (0..self.width())
+            .flat_map(|x| (0..self.height()).map(move |y| (x, y)))
+            .filter(|(x, y)| self.data[*x][*y] == Tile::FALLINGBLOCK)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tiles::Tile;
+    use crate::world::GameWorld;
+    use crate::world::presets::map_with_border;
+
+    /// Create a world with border and a row of crumbling platforms from (2,2) to (4,2)
+    fn world_with_platforms() -> GameWorld {
+        let mut world = map_with_border(7, 6);
+        for x in 2..5 {
+            world.set(x, 2, Tile::CRUMBLINGPLATFORM);
+        }
+        world
+    }
+
+    #[test]
+    fn test_walk_over_crumbling_platforms() {
+        let mut world = world_with_platforms();
+        assert_eq!(Some((2, 2)), world.update_platforms((2, 3)).cracked);
+        assert_eq!(Tile::CRUMBLINGPLATFORMCRACKED, *world.get(2, 2).unwrap());
+        let changes = world.update_platforms((3, 3));
+        assert_eq!(vec![(2, 2)], changes.broken);
+        assert_eq!(Some((3, 2)), changes.cracked);
+        assert_eq!(Tile::AIR, *world.get(2, 2).unwrap());
+        // Jumping off the platform breaks it as well
+        let changes = world.update_platforms((3, 4));
+        assert_eq!(vec![(3, 2)], changes.broken);
+        assert_eq!(None, changes.cracked);
+        assert_eq!(Tile::CRUMBLINGPLATFORM, *world.get(4, 2).unwrap());
+        assert!(world.update_platforms((3, 4)).is_empty());
+    }
+
+    #[test]
+    fn test_falling_block_waits_for_player() {
+        let mut world = map_with_border(5, 6);
+        world.set(2, 4, Tile::FALLINGBLOCK);
+        world.set(2, 3, Tile::FALLINGBLOCK);
+        // The player stands below the blocks, hence they fall onto the player's head
+        assert_eq!(
+            vec![((2, 3), (2, 2)), ((2, 4), (2, 3))],
+            world.update_platforms((2, 1)).fallen
+        );
+        assert!(world.update_platforms((2, 1)).is_empty());
+        // The player has vacated the tile below the blocks
+        assert_eq!(
+            vec![((2, 2), (2, 1)), ((2, 3), (2, 2))],
+            world.update_platforms((3, 1)).fallen
+        );
+        assert_eq!(Tile::FALLINGBLOCK, *world.get(2, 1).unwrap());
+        assert_eq!(Tile::FALLINGBLOCK, *world.get(2, 2).unwrap());
+        assert_eq!(Tile::AIR, *world.get(2, 3).unwrap());
+    }
+
+    #[test]
+    fn test_falling_block_does_not_cover_tiles() {
+        let mut world = map_with_border(5, 6);
+        world.set(2, 4, Tile::FALLINGBLOCK);
+        world.set(2, 2, Tile::SPIKES);
+        assert_eq!(
+            vec![((2, 4), (2, 3))],
+            world.update_platforms((3, 1)).fallen
+        );
+        assert_eq!(Tile::SPIKES, *world.get(2, 2).unwrap());
+    }
+
+    #[test]
+    fn test_reset_platforms() {
+        let mut world = world_with_platforms();
+        world.set(3, 4, Tile::FALLINGBLOCK);
+        let changes = world.update_platforms((2, 3));
+        assert_eq!(vec![((3, 4), (3, 3))], changes.fallen);
+        world.update_platforms((1, 1));
+        world.reset_game_state();
+        assert_eq!(Tile::CRUMBLINGPLATFORM, *world.get(2, 2).unwrap());
+        assert_eq!(Tile::FALLINGBLOCK, *world.get(3, 4).unwrap());
+        assert_eq!(Tile::AIR, *world.get(3, 3).unwrap());
+        // The falling blocks and the cracked platform are forgotten when the game state is reset
+        let changes = world.update_platforms((2, 3));
+        assert!(changes.broken.is_empty());
+        assert_eq!(vec![((3, 4), (3, 3))], changes.fallen);
+    }
+}
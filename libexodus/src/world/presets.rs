@@ -1,3 +1,5 @@
+#[cfg(test)]
+use crate::tiles::Tile::PLAYERSPAWN;
 use crate::tiles::Tile::WALL;
 use crate::world::GameWorld;
 
@@ -17,3 +19,11 @@ pub fn map_with_border(width: usize, height: usize) -> GameWorld {
     }
     ret
 }
+
+/// Create a map with border, where the player spawns in the lower left corner
+#[cfg(test)]
+pub(crate) fn map_with_border_and_spawn(width: usize, height: usize) -> GameWorld {
+    let mut ret = map_with_border(width, height);
+    ret.set(1, 1, PLAYERSPAWN);
+    ret
+}
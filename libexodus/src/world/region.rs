@@ -0,0 +1,351 @@
+use crate::tiles::{TeleportId, Tile};
+use crate::world::GameWorld;
+use std::collections::{HashMap, HashSet};
+use strum::IntoEnumIterator;
+
+/// A rectangular region of tiles that has been copied from a game world, e.g., to paste it
+/// into the same or into another world.
+/// The texts of all message tiles are copied along with the tiles, such that the region does not
+/// depend on the world it has been copied from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TileRegion {
+    /// The tiles of this region, indexed by x and y coordinate, like in the game world
+    tiles: Vec<Vec<Tile>>,
+    /// The texts of all message tiles in this region, indexed by their message ID
+    messages: Vec<String>,
+}
+
+impl TileRegion {
+    /// Copy the rectangular region spanned by the two given corners from the given world.
+    /// The player spawn is not copied, since there may only be one player spawn on each map.
+    /// Return None if any of the corners lies outside of the world.
+    ///
+    /// ```rust
+    /// use libexodus::tiles::Tile;
+    /// use libexodus::world::GameWorld;
+    /// use libexodus::world::region::TileRegion;
+    /// let mut world = GameWorld::new(4, 4);
+    /// world.set(1, 2, Tile::WALL);
+    /// let region = TileRegion::copy_from(&world, (3, 3), (1, 2)).unwrap();
+    /// assert_eq!(3, region.width());
+    /// assert_eq!(2, region.height());
+    /// assert_eq!(Some(&Tile::WALL), region.get(0, 0));
+    /// assert!(TileRegion::copy_from(&world, (0, 0), (4, 0)).is_none());
+    /// ```
+    pub fn copy_from(
+        world: &GameWorld,
+        corner_a: (usize, usize),
+        corner_b: (usize, usize),
+    ) -> Option<TileRegion> {
+        world.get(corner_a.0 as i32, corner_a.1 as i32)?;
+        world.get(corner_b.0 as i32, corner_b.1 as i32)?;
+        let (min_x, max_x) = (corner_a.0.min(corner_b.0), corner_a.0.max(corner_b.0));
+        let (min_y, max_y) = (corner_a.1.min(corner_b.1), corner_a.1.max(corner_b.1));
+        let mut messages: Vec<String> = vec![];
+        let tiles = (min_x..=max_x)
+            .map(|x| {
+                (min_y..=max_y)
+                    .map(|y| match world.get(x as i32, y as i32).unwrap() {
+                        Tile::PLAYERSPAWN => Tile::AIR,
+                        Tile::MESSAGE { message_id } => {
+                            let message = world.get_message(*message_id).unwrap_or("");
+                            messages.push(message.to_string());
+                            Tile::MESSAGE {
+                                message_id: messages.len() - 1,
+                            }
+                        },
+                        tile => tile.clone(),
+                    })
+                    .collect()
+            })
+            .collect();
+        Some(TileRegion { tiles, messages })
+    }
+    pub fn width(&self) -> usize {
+        self.tiles.len()
+    }
+    pub fn height(&self) -> usize {
+        self.tiles.first().map(|column| column.len()).unwrap_or(0)
+    }
+    /// Get the tile at the given position relative to the lower left corner of this region
+    pub fn get(&self, x: usize, y: usize) -> Option<&Tile> {
+        self.tiles.get(x)?.get(y)
+    }
+    /// Get the text of the message with the given ID, as used by the message tiles of this region
+    pub fn message(&self, message_id: usize) -> Option<&str> {
+        self.messages
+            .get(message_id)
+            .map(|message| message.as_str())
+    }
+    /// Iterate over all tiles of this region, together with their positions if the lower left
+    /// corner of this region is placed at the given position
+    pub fn tiles_at(
+        &self,
+        origin: (usize, usize),
+    ) -> impl Iterator<Item = ((usize, usize), &Tile)> + '_ {
+        self.tiles.iter().enumerate().flat_map(move |(x, column)| {
+            column
+                .iter()
+                .enumerate()
+                .map(move |(y, tile)| ((origin.0 + x, origin.1 + y), tile))
+        })
+    }
+    /// Mirror this region along the vertical axis, i.e., swap left and right
+    pub fn mirror_horizontally(&mut self) {
+        self.tiles.reverse();
+        for tile in self.tiles.iter_mut().flatten() {
+            *tile = tile.mirrored_horizontally();
+        }
+    }
+    /// Mirror this region along the horizontal axis, i.e., swap top and bottom
+    pub fn mirror_vertically(&mut self) {
+        for column in self.tiles.iter_mut() {
+            column.reverse();
+            for tile in column.iter_mut() {
+                *tile = tile.mirrored_vertically();
+            }
+        }
+    }
+    /// Rotate this region by 90 degrees clockwise
+    pub fn rotate_clockwise(&mut self) {
+        let (width, height) = (self.width(), self.height());
+        let mut rotated: Vec<Vec<Tile>> = vec![vec![Tile::AIR; width]; height];
+        for (x, column) in self.tiles.iter().enumerate() {
+            for (y, tile) in column.iter().enumerate() {
+                rotated[y][width - 1 - x] = tile.rotated_clockwise();
+            }
+        }
+        self.tiles = rotated;
+    }
+    /// Get the teleport IDs that need to be used instead of the IDs in this region if it is pasted
+    /// into the given world at the given position.
+    /// Since there may only be one exit for each teleport, teleports that have their exit inside
+    /// this region get a new ID if their ID is already used in the world outside of the pasted
+    /// area. If there are no unused IDs left, the ID is kept and the existing exit is moved.
+    pub(crate) fn teleport_mapping(
+        &self,
+        world: &GameWorld,
+        origin: (usize, usize),
+    ) -> HashMap<TeleportId, TeleportId> {
+        let inside = |x: usize, y: usize| {
+            (origin.0..origin.0 + self.width()).contains(&x)
+                && (origin.1..origin.1 + self.height()).contains(&y)
+        };
+        let mut used_in_world: HashSet<TeleportId> = HashSet::new();
+        for x in 0..world.width() {
+            for y in 0..world.height() {
+                if inside(x, y) {
+                    continue;
+                }
+                match world.get(x as i32, y as i32) {
+                    Some(Tile::TELEPORTENTRY { teleport_id })
+                    | Some(Tile::TELEPORTEXIT { teleport_id }) => {
+                        used_in_world.insert(*teleport_id);
+                    },
+                    _ => {},
+                }
+            }
+        }
+        let mut taken: HashSet<TeleportId> = used_in_world.clone();
+        let mut exits: Vec<TeleportId> = vec![];
+        for tile in self.tiles.iter().flatten() {
+            match tile {
+                Tile::TELEPORTENTRY { teleport_id } => {
+                    taken.insert(*teleport_id);
+                },
+                Tile::TELEPORTEXIT { teleport_id } => {
+                    taken.insert(*teleport_id);
+                    exits.push(*teleport_id);
+                },
+                _ => {},
+            }
+        }
+        let mut mapping: HashMap<TeleportId, TeleportId> = HashMap::new();
+        for teleport_id in exits {
+            if !used_in_world.contains(&teleport_id) {
+                continue;
+            }
+            if let Some(free_id) = TeleportId::iter().find(|id| !taken.contains(id)) {
+                taken.insert(free_id);
+                mapping.insert(teleport_id, free_id);
+            }
+        }
+        mapping
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tiles::{TeleportId, Tile};
+    use crate::world::GameWorld;
+    use crate::world::history::EditHistory;
+    use crate::world::region::TileRegion;
+
+    /// Create a 3x2 region with distinguishable tiles:
+    /// ```text
+    /// C ▲ K
+    /// W → ◀
+    /// ```
+    fn test_region() -> TileRegion {
+        let mut world = GameWorld::new(3, 2);
+        world.set(0, 0, Tile::WALL);
+        world.set(1, 0, Tile::ARROWRIGHT);
+        world.set(2, 0, Tile::WALLSPIKESL);
+        world.set(0, 1, Tile::COIN);
+        world.set(1, 1, Tile::ARROWUP);
+        world.set(2, 1, Tile::KEY);
+        TileRegion::copy_from(&world, (0, 0), (2, 1)).unwrap()
+    }
+
+    #[test]
+    fn test_copy_does_not_copy_player_spawn() {
+        let mut world = GameWorld::new(3, 3);
+        world.set(1, 1, Tile::PLAYERSPAWN);
+        let region = TileRegion::copy_from(&world, (0, 0), (2, 2)).unwrap();
+        assert!(
+            region
+                .tiles_at((0, 0))
+                .all(|(_, tile)| *tile != Tile::PLAYERSPAWN)
+        );
+    }
+
+    #[test]
+    fn test_mirror_horizontally() {
+        let mut region = test_region();
+        region.mirror_horizontally();
+        assert_eq!(Some(&Tile::WALLSPIKESR), region.get(0, 0));
+        assert_eq!(Some(&Tile::ARROWLEFT), region.get(1, 0));
+        assert_eq!(Some(&Tile::WALL), region.get(2, 0));
+        assert_eq!(Some(&Tile::KEY), region.get(0, 1));
+        assert_eq!(Some(&Tile::ARROWUP), region.get(1, 1));
+    }
+
+    #[test]
+    fn test_mirror_vertically() {
+        let mut region = test_region();
+        region.mirror_vertically();
+        assert_eq!(Some(&Tile::COIN), region.get(0, 0));
+        assert_eq!(Some(&Tile::ARROWDOWN), region.get(1, 0));
+        assert_eq!(Some(&Tile::WALLSPIKESL), region.get(2, 1));
+    }
+
+    #[test]
+    fn test_rotate_clockwise() {
+        let mut region = test_region();
+        region.rotate_clockwise();
+        assert_eq!(2, region.width());
+        assert_eq!(3, region.height());
+        // The former upper left corner is now the upper right corner
+        assert_eq!(Some(&Tile::COIN), region.get(1, 2));
+        assert_eq!(Some(&Tile::WALL), region.get(0, 2));
+        assert_eq!(Some(&Tile::ARROWRIGHT), region.get(1, 1));
+        assert_eq!(Some(&Tile::ARROWDOWN), region.get(0, 1));
+        assert_eq!(Some(&Tile::WALLSPIKEST), region.get(0, 0));
+        assert_eq!(Some(&Tile::KEY), region.get(1, 0));
+    }
+
+    #[test]
+    fn test_rotate_four_times_is_identity() {
+        let mut region = test_region();
+        for _ in 0..4 {
+            region.rotate_clockwise();
+        }
+        assert_eq!(test_region(), region);
+    }
+
+    #[test]
+    fn test_paste_messages_into_other_world() {
+        let mut source = GameWorld::new(3, 3);
+        source.set_message_tile(1, 1, "Hello".to_string());
+        let region = TileRegion::copy_from(&source, (1, 1), (1, 1)).unwrap();
+        let mut target = GameWorld::new(3, 3);
+        target.set_message_tile(0, 0, "Existing".to_string());
+        let mut history = EditHistory::default();
+        history.paste(&mut target, &region, (2, 2));
+        let Some(Tile::MESSAGE { message_id }) = target.get(2, 2) else {
+            panic!("Expected a message tile");
+        };
+        assert_eq!(Some("Hello"), target.get_message(*message_id));
+        assert_eq!(Some("Existing"), target.get_message(0));
+    }
+
+    #[test]
+    fn test_paste_remaps_used_teleport() {
+        let mut source = GameWorld::new(3, 1);
+        source.set(
+            0,
+            0,
+            Tile::TELEPORTENTRY {
+                teleport_id: TeleportId::ONE,
+            },
+        );
+        source.set(
+            2,
+            0,
+            Tile::TELEPORTEXIT {
+                teleport_id: TeleportId::ONE,
+            },
+        );
+        let region = TileRegion::copy_from(&source, (0, 0), (2, 0)).unwrap();
+        let mut target = GameWorld::new(3, 2);
+        let mut history = EditHistory::default();
+        // Paste the region twice into the same world
+        history.paste(&mut target, &region, (0, 0));
+        history.paste(&mut target, &region, (0, 1));
+        assert_eq!(Some(&(2, 0)), target.get_teleport_location(TeleportId::ONE));
+        assert_eq!(
+            Some(&Tile::TELEPORTENTRY {
+                teleport_id: TeleportId::TWO
+            }),
+            target.get(0, 1)
+        );
+        assert_eq!(Some(&(2, 1)), target.get_teleport_location(TeleportId::TWO));
+        // Pasting over the same area again keeps the teleport ID
+        history.paste(&mut target, &region, (0, 0));
+        assert_eq!(Some(&(2, 0)), target.get_teleport_location(TeleportId::ONE));
+    }
+
+    #[test]
+    fn test_paste_entry_without_exit_keeps_id() {
+        let mut source = GameWorld::new(1, 1);
+        source.set(
+            0,
+            0,
+            Tile::TELEPORTENTRY {
+                teleport_id: TeleportId::THREE,
+            },
+        );
+        let region = TileRegion::copy_from(&source, (0, 0), (0, 0)).unwrap();
+        let mut target = GameWorld::new(3, 1);
+        target.set(
+            2,
+            0,
+            Tile::TELEPORTEXIT {
+                teleport_id: TeleportId::THREE,
+            },
+        );
+        EditHistory::default().paste(&mut target, &region, (0, 0));
+        assert_eq!(
+            Some(&Tile::TELEPORTENTRY {
+                teleport_id: TeleportId::THREE
+            }),
+            target.get(0, 0)
+        );
+    }
+
+    #[test]
+    fn test_paste_keeps_player_spawn_and_clips_region() {
+        let mut world = GameWorld::new(3, 3);
+        world.set(2, 2, Tile::PLAYERSPAWN);
+        let mut filled = GameWorld::new(2, 2);
+        filled.fill(&Tile::WALL);
+        let region = TileRegion::copy_from(&filled, (0, 0), (1, 1)).unwrap();
+        let mut history = EditHistory::default();
+        let changed = history.paste(&mut world, &region, (1, 1));
+        assert_eq!(vec![(1, 1), (1, 2), (2, 1)], changed);
+        assert_eq!(Some(&Tile::PLAYERSPAWN), world.get(2, 2));
+        assert_eq!((2, 2), world.player_spawn());
+        history.undo(&mut world);
+        assert_eq!(Some(&Tile::AIR), world.get(1, 1));
+    }
+}
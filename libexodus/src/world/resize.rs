@@ -0,0 +1,315 @@
+use crate::tiles::Tile;
+use crate::world::GameWorld;
+use crate::world::io::{MAX_MAP_HEIGHT, MAX_MAP_WIDTH};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use strum_macros::EnumIter;
+
+/// The part of a map that stays in place when the map is resized.
+/// Rows and columns are added or removed on the opposite side(s).
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, EnumIter)]
+pub enum ResizeAnchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    #[default]
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl ResizeAnchor {
+    /// Get the offset that is added to the coordinates of all tiles if a map is resized from the
+    /// old size to the new size
+    fn offset(&self, old_size: (usize, usize), new_size: (usize, usize)) -> (i64, i64) {
+        let dw = new_size.0 as i64 - old_size.0 as i64;
+        let dh = new_size.1 as i64 - old_size.1 as i64;
+        let dx = match self {
+            ResizeAnchor::TopLeft | ResizeAnchor::Left | ResizeAnchor::BottomLeft => 0,
+            ResizeAnchor::Top | ResizeAnchor::Center | ResizeAnchor::Bottom => dw / 2,
+            ResizeAnchor::TopRight | ResizeAnchor::Right | ResizeAnchor::BottomRight => dw,
+        };
+        // The y axis points up, i.e., the bottom row has the y coordinate 0
+        let dy = match self {
+            ResizeAnchor::BottomLeft | ResizeAnchor::Bottom | ResizeAnchor::BottomRight => 0,
+            ResizeAnchor::Left | ResizeAnchor::Center | ResizeAnchor::Right => dh / 2,
+            ResizeAnchor::TopLeft | ResizeAnchor::Top | ResizeAnchor::TopRight => dh,
+        };
+        (dx, dy)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum ResizeError {
+    InvalidMapWidth {
+        max_width: usize,
+        actual_width: usize,
+    },
+    InvalidMapHeight {
+        max_height: usize,
+        actual_height: usize,
+    },
+}
+
+impl Error for ResizeError {}
+
+impl Display for ResizeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResizeError::InvalidMapWidth {
+                max_width,
+                actual_width,
+            } => write!(
+                f,
+                "Invalid Map Width: {} (Allowed widths: 1 to {})",
+                actual_width, max_width
+            ),
+            ResizeError::InvalidMapHeight {
+                max_height,
+                actual_height,
+            } => write!(
+                f,
+                "Invalid Map Height: {} (Allowed heights: 1 to {})",
+                actual_height, max_height
+            ),
+        }
+    }
+}
+
+impl GameWorld {
+    /// Resize this map to the given size, keeping the given anchor in place.
+    /// Tiles that end up outside of the map are removed. The player spawn and all teleport exits
+    /// are moved along with their tiles. If the player spawn ends up outside of the map, it is
+    /// moved to the closest position inside the map. Enemies are moved as well, and removed if
+    /// their spawn or any of their waypoints ends up outside of the map. Links are moved along
+    /// with their switches and blocks, and removed if either of them ends up outside of the map.
+    ///
+    /// ```rust
+    /// use libexodus::tiles::Tile;
+    /// use libexodus::world::GameWorld;
+    /// use libexodus::world::resize::ResizeAnchor;
+    /// let mut world = GameWorld::new(3, 3);
+    /// world.set(0, 0, Tile::WALL);
+    /// world.resize(5, 4, ResizeAnchor::TopRight).unwrap();
+    /// assert_eq!(5, world.width());
+    /// assert_eq!(4, world.height());
+    /// assert_eq!(Some(&Tile::WALL), world.get(2, 1));
+    /// assert!(world.resize(0, 4, ResizeAnchor::Center).is_err());
+    /// ```
+    pub fn resize(
+        &mut self,
+        width: usize,
+        height: usize,
+        anchor: ResizeAnchor,
+    ) -> Result<(), ResizeError> {
+        if width == 0 || width > MAX_MAP_WIDTH {
+            return Err(ResizeError::InvalidMapWidth {
+                max_width: MAX_MAP_WIDTH,
+                actual_width: width,
+            });
+        }
+        if height == 0 || height > MAX_MAP_HEIGHT {
+            return Err(ResizeError::InvalidMapHeight {
+                max_height: MAX_MAP_HEIGHT,
+                actual_height: height,
+            });
+        }
+        let (dx, dy) = anchor.offset((self.width(), self.height()), (width, height));
+        let shifted = |(x, y): (usize, usize)| (x as i64 + dx, y as i64 + dy);
+        let inside =
+            |(x, y): (i64, i64)| (0..width as i64).contains(&x) && (0..height as i64).contains(&y);
+        let mut data: Vec<Vec<Tile>> = vec![vec![Tile::AIR; height]; width];
+        for (x, column) in self.data.iter().enumerate() {
+            for (y, tile) in column.iter().enumerate() {
+                let (new_x, new_y) = shifted((x, y));
+                if inside((new_x, new_y)) {
+                    data[new_x as usize][new_y as usize] = tile.clone();
+                }
+            }
+        }
+        let spawn_tile_present = matches!(
+            self.get(self.playerspawn.0 as i32, self.playerspawn.1 as i32),
+            Some(Tile::PLAYERSPAWN)
+        );
+        self.data = data;
+        self.teleport_metadata.teleport_exit_locations = self
+            .teleport_metadata
+            .teleport_exit_locations
+            .drain()
+            .filter_map(|(teleport_id, position)| {
+                let (x, y) = shifted(position);
+                inside((x, y)).then_some((teleport_id, (x as usize, y as usize)))
+            })
+            .collect();
+        self.enemies = self
+            .enemies
+            .iter()
+            .filter_map(|enemy| {
+                enemy.moved(|position| {
+                    let (x, y) = shifted(position);
+                    inside((x, y)).then_some((x as usize, y as usize))
+                })
+            })
+            .collect();
+        let moved = |position: (usize, usize)| {
+            let (x, y) = shifted(position);
+            inside((x, y)).then_some((x as usize, y as usize))
+        };
+        let links: Vec<((usize, usize), (usize, usize))> = self
+            .links
+            .iter()
+            .flat_map(|(switch, blocks)| blocks.iter().map(move |block| (*switch, *block)))
+            .filter_map(|(switch, block)| Some((moved(switch)?, moved(block)?)))
+            .collect();
+        self.set_links(links);
+        let (spawn_x, spawn_y) = shifted(self.playerspawn);
+        if inside((spawn_x, spawn_y)) {
+            self.playerspawn = (spawn_x as usize, spawn_y as usize);
+        } else {
+            // The player spawn has been cut off, move it to the closest position inside the map
+            self.playerspawn = (
+                spawn_x.clamp(0, width as i64 - 1) as usize,
+                spawn_y.clamp(0, height as i64 - 1) as usize,
+            );
+            if spawn_tile_present {
+                self.set(self.playerspawn.0, self.playerspawn.1, Tile::PLAYERSPAWN);
+            }
+        }
+        self.set_dirty();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::enemies::Enemy;
+    use crate::tiles::{TeleportId, Tile};
+    use crate::world::GameWorld;
+    use crate::world::io::{MAX_MAP_HEIGHT, MAX_MAP_WIDTH};
+    use crate::world::resize::{ResizeAnchor, ResizeError};
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_grow_keeps_tiles_at_anchor() {
+        let mut world = GameWorld::new(2, 2);
+        world.set(0, 0, Tile::WALL);
+        world.set(1, 1, Tile::COIN);
+        world.resize(4, 4, ResizeAnchor::Center).unwrap();
+        assert_eq!(Some(&Tile::WALL), world.get(1, 1));
+        assert_eq!(Some(&Tile::COIN), world.get(2, 2));
+        assert_eq!(Some(&Tile::AIR), world.get(0, 0));
+        assert_eq!(Some(&Tile::AIR), world.get(3, 3));
+        assert!(world.is_dirty());
+    }
+
+    #[test]
+    fn test_shrink_and_grow_every_anchor() {
+        for anchor in ResizeAnchor::iter() {
+            let mut world = GameWorld::new(3, 3);
+            world.set(1, 1, Tile::KEY);
+            world.resize(5, 5, anchor).unwrap();
+            world.resize(3, 3, anchor).unwrap();
+            assert_eq!(Some(&Tile::KEY), world.get(1, 1), "{:?}", anchor);
+        }
+    }
+
+    #[test]
+    fn test_shrink_removes_tiles() {
+        let mut world = GameWorld::new(3, 3);
+        world.set(0, 2, Tile::WALL);
+        world.set(2, 0, Tile::COIN);
+        world.resize(2, 2, ResizeAnchor::TopLeft).unwrap();
+        assert_eq!(Some(&Tile::WALL), world.get(0, 1));
+        assert!(
+            (0..2)
+                .flat_map(|x| (0..2).map(move |y| (x, y)))
+                .all(|(x, y)| world.get(x, y) != Some(&Tile::COIN))
+        );
+    }
+
+    #[test]
+    fn test_resize_moves_player_spawn_and_teleports() {
+        let mut world = GameWorld::new(3, 3);
+        world.set(1, 1, Tile::PLAYERSPAWN);
+        world.set(
+            2,
+            2,
+            Tile::TELEPORTEXIT {
+                teleport_id: TeleportId::ONE,
+            },
+        );
+        world.set(
+            0,
+            0,
+            Tile::TELEPORTEXIT {
+                teleport_id: TeleportId::TWO,
+            },
+        );
+        world.resize(4, 4, ResizeAnchor::TopRight).unwrap();
+        assert_eq!((2, 2), world.player_spawn());
+        assert_eq!(Some(&Tile::PLAYERSPAWN), world.get(2, 2));
+        assert_eq!(Some(&(3, 3)), world.get_teleport_location(TeleportId::ONE));
+        world.resize(2, 2, ResizeAnchor::TopRight).unwrap();
+        assert_eq!(Some(&(1, 1)), world.get_teleport_location(TeleportId::ONE));
+        assert_eq!(None, world.get_teleport_location(TeleportId::TWO));
+        assert_eq!((0, 0), world.player_spawn());
+    }
+
+    #[test]
+    fn test_resize_moves_and_removes_enemies() {
+        let mut world = GameWorld::new(4, 3);
+        world.add_enemy(Enemy::walker((1, 1), true));
+        world.add_enemy(Enemy::flyer((0, 2), vec![(3, 2)]));
+        world.resize(5, 3, ResizeAnchor::Right).unwrap();
+        assert_eq!(vec![(2, 1)], world.enemies()[0].positions());
+        assert_eq!(vec![(1, 2), (4, 2)], world.enemies()[1].positions());
+        world.resize(4, 3, ResizeAnchor::Left).unwrap();
+        assert_eq!(1, world.enemies().len());
+        assert_eq!((2, 1), world.enemies()[0].spawn());
+    }
+
+    #[test]
+    fn test_resize_moves_and_removes_links() {
+        let mut world = GameWorld::new(4, 3);
+        world.set(1, 1, Tile::LEVER);
+        world.link((1, 1), (3, 1)).link((1, 1), (0, 2));
+        world.resize(5, 3, ResizeAnchor::Right).unwrap();
+        assert_eq!(vec![(1, 2), (4, 1)], world.linked_blocks((2, 1)));
+        world.resize(4, 3, ResizeAnchor::Left).unwrap();
+        assert_eq!(1, world.links().len());
+        assert_eq!(vec![(1, 2)], world.linked_blocks((2, 1)));
+    }
+
+    #[test]
+    fn test_resize_moves_cut_off_player_spawn_inside() {
+        let mut world = GameWorld::new(5, 5);
+        world.set(4, 4, Tile::PLAYERSPAWN);
+        world.resize(3, 2, ResizeAnchor::BottomLeft).unwrap();
+        assert_eq!((2, 1), world.player_spawn());
+        assert_eq!(Some(&Tile::PLAYERSPAWN), world.get(2, 1));
+    }
+
+    #[test]
+    fn test_resize_rejects_invalid_sizes() {
+        let mut world = GameWorld::new(3, 3);
+        assert_eq!(
+            Err(ResizeError::InvalidMapWidth {
+                max_width: MAX_MAP_WIDTH,
+                actual_width: MAX_MAP_WIDTH + 1
+            }),
+            world.resize(MAX_MAP_WIDTH + 1, 3, ResizeAnchor::Center)
+        );
+        assert_eq!(
+            Err(ResizeError::InvalidMapHeight {
+                max_height: MAX_MAP_HEIGHT,
+                actual_height: 0
+            }),
+            world.resize(3, 0, ResizeAnchor::Center)
+        );
+        assert_eq!(3, world.width());
+        assert!(!world.is_dirty());
+    }
+}
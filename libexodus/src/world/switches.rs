@@ -0,0 +1,203 @@
+use crate::tiles::TileKind;
+use crate::world::GameWorld;
+use std::collections::{BTreeMap, BTreeSet};
+
+impl GameWorld {
+    /// Get all links of this world, i.e., the positions of all blocks that are toggled by the
+    /// switch at the given position.
+    /// Links of positions that do not contain a switch are kept, such that they are restored if
+    /// a removed switch is placed again, e.g., by undoing an edit.
+    pub fn links(&self) -> &BTreeMap<(usize, usize), BTreeSet<(usize, usize)>> {
+        &self.links
+    }
+    /// Get the links of all switches of this world, skipping links of positions that do not
+    /// contain a switch anymore. Only these links are stored in map files.
+    pub fn active_links(
+        &self,
+    ) -> impl Iterator<Item = (&(usize, usize), &BTreeSet<(usize, usize)>)> {
+        self.links.iter().filter(|((x, y), blocks)| {
+            !blocks.is_empty()
+                && self
+                    .get(*x as i32, *y as i32)
+                    .is_some_and(|tile| tile.kind() == TileKind::SWITCH)
+        })
+    }
+    /// Get the positions of all blocks that are toggled by the switch at the given position
+    pub fn linked_blocks(&self, switch: (usize, usize)) -> Vec<(usize, usize)> {
+        self.links
+            .get(&switch)
+            .map(|blocks| blocks.iter().copied().collect())
+            .unwrap_or_default()
+    }
+    /// Check if the block at the given position is toggled by the given switch
+    pub fn is_linked(&self, switch: (usize, usize), block: (usize, usize)) -> bool {
+        self.links
+            .get(&switch)
+            .is_some_and(|blocks| blocks.contains(&block))
+    }
+    /// Link the block at the given position to the given switch, such that it is toggled whenever
+    /// the switch is toggled. A switch cannot be linked to itself.
+    ///
+    /// ```rust
+    /// use libexodus::tiles::Tile;
+    /// use libexodus::world::GameWorld;
+    /// let mut world = GameWorld::new(4, 2);
+    /// world.set(0, 0, Tile::LEVER).set(3, 0, Tile::TOGGLEBLOCK);
+    /// world.link((0, 0), (3, 0));
+    /// assert_eq!(vec![(3, 0)], world.linked_blocks((0, 0)));
+    /// world.unlink((0, 0), (3, 0));
+    /// assert!(world.links().is_empty());
+    /// ```
+    pub fn link(&mut self, switch: (usize, usize), block: (usize, usize)) -> &mut Self {
+        if switch != block {
+            self.links.entry(switch).or_default().insert(block);
+        }
+        self
+    }
+    /// Remove the link between the given switch and the block at the given position
+    pub fn unlink(&mut self, switch: (usize, usize), block: (usize, usize)) -> &mut Self {
+        if let Some(blocks) = self.links.get_mut(&switch) {
+            blocks.remove(&block);
+            if blocks.is_empty() {
+                self.links.remove(&switch);
+            }
+        }
+        self
+    }
+    /// Replace all links of this world, e.g., after the map has been resized
+    pub(crate) fn set_links(
+        &mut self,
+        links: impl IntoIterator<Item = ((usize, usize), (usize, usize))>,
+    ) {
+        self.links.clear();
+        for (switch, block) in links {
+            self.link(switch, block);
+        }
+    }
+    /// Toggle the switch at the given position and all blocks that are linked to it.
+    /// Linked tiles that cannot be toggled, e.g., because a crate has been pushed onto them,
+    /// are left unchanged.
+    /// Return the positions of all tiles that have been changed, including the switch itself.
+    ///
+    /// All changes are undone by [GameWorld::reset_game_state].
+    ///
+    /// ```rust
+    /// use libexodus::tiles::Tile;
+    /// use libexodus::world::GameWorld;
+    /// let mut world = GameWorld::new(4, 2);
+    /// world.set(0, 0, Tile::LEVER).set(2, 0, Tile::TOGGLEBLOCK).set(3, 0, Tile::DOOR);
+    /// world.link((0, 0), (2, 0)).link((0, 0), (3, 0));
+    /// assert_eq!(vec![(0, 0), (2, 0), (3, 0)], world.toggle_switch((0, 0)));
+    /// assert_eq!(Tile::LEVERPULLED, *world.get(0, 0).unwrap());
+    /// assert_eq!(Tile::TOGGLEBLOCKOFF, *world.get(2, 0).unwrap());
+    /// assert_eq!(Tile::OPENDOOR, *world.get(3, 0).unwrap());
+    /// world.reset_game_state();
+    /// assert_eq!(Tile::LEVER, *world.get(0, 0).unwrap());
+    /// assert_eq!(Tile::TOGGLEBLOCK, *world.get(2, 0).unwrap());
+    /// ```
+    pub fn toggle_switch(&mut self, switch: (usize, usize)) -> Vec<(usize, usize)> {
+        if !self
+            .get(switch.0 as i32, switch.1 as i32)
+            .is_some_and(|tile| tile.kind() == TileKind::SWITCH)
+        {
+            return vec![];
+        }
+        let mut changed: Vec<(usize, usize)> = vec![];
+        for position in std::iter::once(switch).chain(self.linked_blocks(switch)) {
+            if let Some(toggled) = self
+                .get(position.0 as i32, position.1 as i32)
+                .and_then(|tile| tile.toggled())
+            {
+                self.set_during_game(position, toggled);
+                changed.push(position);
+            }
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tiles::{KeyColor, Tile};
+    use crate::world::GameWorld;
+    use crate::world::presets::map_with_border;
+
+    /// Create a world with border, a lever at (1,1) and a toggle block at (4,1) that is linked to the lever
+    fn world_with_lever() -> GameWorld {
+        let mut world = map_with_border(7, 5);
+        world.set(1, 1, Tile::LEVER);
+        world.set(4, 1, Tile::TOGGLEBLOCK);
+        world.link((1, 1), (4, 1));
+        world
+    }
+
+    #[test]
+    fn test_toggle_switch_twice() {
+        let mut world = world_with_lever();
+        world.set(5, 1, Tile::TOGGLEBLOCKOFF);
+        world.link((1, 1), (5, 1));
+        assert_eq!(vec![(1, 1), (4, 1), (5, 1)], world.toggle_switch((1, 1)));
+        assert_eq!(Tile::LEVERPULLED, *world.get(1, 1).unwrap());
+        assert_eq!(Tile::TOGGLEBLOCKOFF, *world.get(4, 1).unwrap());
+        assert_eq!(Tile::TOGGLEBLOCK, *world.get(5, 1).unwrap());
+        assert_eq!(vec![(1, 1), (4, 1), (5, 1)], world.toggle_switch((1, 1)));
+        assert_eq!(Tile::LEVER, *world.get(1, 1).unwrap());
+        assert_eq!(Tile::TOGGLEBLOCK, *world.get(4, 1).unwrap());
+        assert_eq!(Tile::TOGGLEBLOCKOFF, *world.get(5, 1).unwrap());
+    }
+
+    #[test]
+    fn test_toggle_non_switch() {
+        let mut world = world_with_lever();
+        world.link((2, 1), (4, 1));
+        assert!(world.toggle_switch((2, 1)).is_empty());
+        assert!(world.toggle_switch((10, 1)).is_empty());
+        assert_eq!(Tile::TOGGLEBLOCK, *world.get(4, 1).unwrap());
+    }
+
+    #[test]
+    fn test_toggle_colored_doors() {
+        let mut world = world_with_lever();
+        let color = KeyColor::GREEN;
+        world.set(4, 1, Tile::COLOREDDOOR { color });
+        world.set(5, 1, Tile::WALL);
+        world.link((1, 1), (5, 1));
+        // The wall cannot be toggled
+        assert_eq!(vec![(1, 1), (4, 1)], world.toggle_switch((1, 1)));
+        assert_eq!(Tile::OPENCOLOREDDOOR { color }, *world.get(4, 1).unwrap());
+        world.toggle_switch((1, 1));
+        assert_eq!(Tile::COLOREDDOOR { color }, *world.get(4, 1).unwrap());
+    }
+
+    #[test]
+    fn test_link_switch_to_itself() {
+        let mut world = world_with_lever();
+        world.link((1, 1), (1, 1));
+        assert_eq!(vec![(4, 1)], world.linked_blocks((1, 1)));
+        assert!(world.is_linked((1, 1), (4, 1)));
+        assert!(!world.is_linked((4, 1), (1, 1)));
+    }
+
+    #[test]
+    fn test_active_links_skip_removed_switches() {
+        let mut world = world_with_lever();
+        assert_eq!(1, world.active_links().count());
+        world.set(1, 1, Tile::AIR);
+        assert_eq!(0, world.active_links().count());
+        assert_eq!(1, world.links().len());
+        world.set(1, 1, Tile::LEVERPULLED);
+        assert_eq!(1, world.active_links().count());
+    }
+
+    #[test]
+    fn test_reset_toggled_blocks() {
+        let mut world = world_with_lever();
+        world.toggle_switch((1, 1));
+        world.toggle_switch((1, 1));
+        world.toggle_switch((1, 1));
+        world.reset_game_state();
+        assert_eq!(Tile::LEVER, *world.get(1, 1).unwrap());
+        assert_eq!(Tile::TOGGLEBLOCK, *world.get(4, 1).unwrap());
+        assert_eq!(vec![(4, 1)], world.linked_blocks((1, 1)));
+    }
+}
@@ -0,0 +1,982 @@
+use crate::directions::FromDirection;
+use crate::enemies::{Enemy, EnemyKind};
+use crate::tiles::{KeyColor, TeleportId, Tile};
+use crate::tilesets::Tileset;
+use crate::world::GameWorld;
+use crate::world::io::{MAX_MAP_HEIGHT, MAX_MAP_WIDTH};
+use crate::world::io_error::GameWorldParseError;
+use crate::world::metadata::Difficulty;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use strum::IntoEnumIterator;
+
+/// The identifier in the first line of each text map
+pub(crate) const TEXT_MAGIC: &str = "ExodusTextMap";
+/// The current version of the text map format
+pub(crate) const TEXT_FORMAT_VERSION: u8 = 0x01;
+/// The file extension of maps that are stored in the text map format
+pub(crate) const TEXT_MAP_FILE_EXTENSION: &str = "exmt";
+/// The characters that are used in the legend, if none of the characters of a tile's string id is available
+const FALLBACK_LEGEND_CHARACTERS: &str = "0123456789!$%&*+-=?@^~<>/|{}()";
+/// The characters that represent the directions an enemy is deadly from
+const DIRECTION_CHARACTERS: [(FromDirection, char); 4] = [
+    (FromDirection::FROMNORTH, 'n'),
+    (FromDirection::FROMSOUTH, 's'),
+    (FromDirection::FROMEAST, 'e'),
+    (FromDirection::FROMWEST, 'w'),
+];
+
+///
+/// Definition of the human-readable Text Map Format:
+///
+/// 1. The identifier `ExodusTextMap`, followed by a space and the text format version (current version: 1)
+///
+/// 2. Header lines in the form `key: value`. The name, author and description are stored as
+///    quoted strings, the forced tileset and difficulty (if any) as their string ids, the number
+///    of lives (if not the default), the par moves and timestamps (if any) as numbers. Each tag is stored in a separate `tag` line
+///    as quoted string. Unknown keys are ignored.
+///
+/// 3. The `[legend]` section, containing one line per tile used in the map. Each line consists
+///    of a single character, a space and the string id of the tile. The string id of teleports
+///    is followed by a colon and the number of the teleport.
+///
+/// 4. The `[messages]` section, containing all messages as quoted strings, one per line, in the
+///    order in which their tiles appear in the binary map format, i.e., starting at the bottom row
+///
+/// 5. The `[enemies]` section, containing one line per enemy. Each line consists of the kind of
+///    the enemy (`walker` or `flyer`), its spawn, `left` or `right` for walking enemies or all
+///    waypoints for flying enemies, and the directions the enemy is deadly from, e.g.,
+///    `deadly:sew` for south, east and west. Positions are stored as `x,y` map coordinates,
+///    i.e., the bottom row has the y coordinate 0.
+///
+/// 6. The `[links]` section, containing one line per switch. Each line consists of the position
+///    of the switch, followed by the positions of all blocks that are toggled by the switch.
+///
+/// 7. The `[map]` section, containing one line per map row, starting with the top row.
+///    Each tile is represented by its legend character.
+///
+/// Empty lines are ignored. Quoted strings may contain the escape sequences
+/// `\\`, `\"`, `\n`, `\r` and `\t`.
+/// The hash of a text map is not stored, but computed when the map is loaded.
+/// Converting a map to the text format and back is lossless.
+impl GameWorld {
+    /// Convert this map into the human-readable text map format
+    ///
+    /// ```rust
+    /// use libexodus::tiles::Tile;
+    /// use libexodus::world::GameWorld;
+    /// let mut world = GameWorld::new(3, 2);
+    /// world.set_name("Tiny").set_author("Me");
+    /// world.set(0, 0, Tile::WALL).set(1, 0, Tile::PLAYERSPAWN).set(2, 1, Tile::EXIT);
+    /// let text = world.to_text();
+    /// assert!(text.ends_with("[map]\n..x\nwP.\n"));
+    /// let result = GameWorld::from_text(&text).unwrap();
+    /// assert_eq!("Tiny", result.get_name());
+    /// assert_eq!(&Tile::EXIT, result.get(2, 1).unwrap());
+    /// ```
+    pub fn to_text(&self) -> String {
+        let legend = legend();
+        let mut ret = format!("{} {}\n", TEXT_MAGIC, TEXT_FORMAT_VERSION);
+        ret.push_str(&format!("name: {}\n", quote(&self.name)));
+        ret.push_str(&format!("author: {}\n", quote(&self.author)));
+        if let Some(tileset) = &self.forced_tileset {
+            ret.push_str(&format!("tileset: {}\n", tileset.str_id()));
+        }
+        if self.lives != GameWorld::DEFAULT_LIVES {
+            ret.push_str(&format!("lives: {}\n", self.lives));
+        }
+        let metadata = &self.metadata;
+        if !metadata.description.is_empty() {
+            ret.push_str(&format!("description: {}\n", quote(&metadata.description)));
+        }
+        if let Some(difficulty) = &metadata.difficulty {
+            ret.push_str(&format!("difficulty: {}\n", difficulty.str_id()));
+        }
+        if let Some(par_moves) = metadata.par_moves {
+            ret.push_str(&format!("par_moves: {}\n", par_moves));
+        }
+        if let Some(created) = metadata.created {
+            ret.push_str(&format!("created: {}\n", created));
+        }
+        if let Some(modified) = metadata.modified {
+            ret.push_str(&format!("modified: {}\n", modified));
+        }
+        for tag in &metadata.tags {
+            ret.push_str(&format!("tag: {}\n", quote(tag)));
+        }
+
+        // Write all tiles that are used in this map
+        ret.push_str("\n[legend]\n");
+        ret.push_str(&self.legend_lines(&legend));
+
+        // Write all messages in the same order as the binary map format
+        let mut messages: Vec<String> = vec![];
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                if let Tile::MESSAGE { message_id } = &self.data[x][y] {
+                    messages.push(quote(self.get_message(*message_id).unwrap_or_default()));
+                }
+            }
+        }
+        if !messages.is_empty() {
+            ret.push_str("\n[messages]\n");
+            for message in messages {
+                ret.push_str(&message);
+                ret.push('\n');
+            }
+        }
+
+        if !self.enemies.is_empty() {
+            ret.push_str("\n[enemies]\n");
+            for enemy in &self.enemies {
+                ret.push_str(&enemy_line(enemy));
+                ret.push('\n');
+            }
+        }
+
+        if self.active_links().next().is_some() {
+            ret.push_str("\n[links]\n");
+            for (switch, blocks) in self.active_links() {
+                let words: Vec<String> = std::iter::once(switch)
+                    .chain(blocks)
+                    .map(|block| position_word(*block))
+                    .collect();
+                ret.push_str(&words.join(" "));
+                ret.push('\n');
+            }
+        }
+
+        // Write the map, starting with the top row
+        ret.push_str("\n[map]\n");
+        ret.push_str(&self.map_lines(&legend));
+        ret
+    }
+
+    /// Render this map as ASCII art, using the same characters as the text map format.
+    /// The map is followed by an empty line and the legend of all tiles that are used in the map.
+    ///
+    /// ```rust
+    /// use libexodus::tiles::Tile;
+    /// use libexodus::world::GameWorld;
+    /// let mut world = GameWorld::new(2, 2);
+    /// world.set(0, 0, Tile::WALL).set(1, 1, Tile::EXIT);
+    /// assert_eq!(".x\nw.\n\n. air\nw wall\nx exit\n", world.to_ascii());
+    /// ```
+    pub fn to_ascii(&self) -> String {
+        let legend = legend();
+        format!(
+            "{}\n{}",
+            self.map_lines(&legend),
+            self.legend_lines(&legend)
+        )
+    }
+
+    /// Get one legend line for each tile that is used in this map
+    fn legend_lines(&self, legend: &[LegendEntry]) -> String {
+        let mut ret = String::new();
+        for entry in legend.iter().filter(|entry| {
+            self.data
+                .iter()
+                .flatten()
+                .any(|tile| tile.to_bytes() == entry.tile.to_bytes())
+        }) {
+            ret.push_str(&format!("{} {}\n", entry.character, entry.legend_id));
+        }
+        ret
+    }
+
+    /// Get one line per map row, starting with the top row
+    fn map_lines(&self, legend: &[LegendEntry]) -> String {
+        let characters: HashMap<u8, char> = legend
+            .iter()
+            .map(|entry| (entry.tile.to_bytes(), entry.character))
+            .collect();
+        let mut ret = String::new();
+        for y in (0..self.height()).rev() {
+            for x in 0..self.width() {
+                ret.push(characters[&self.data[x][y].to_bytes()]);
+            }
+            ret.push('\n');
+        }
+        ret
+    }
+
+    /// Parse a map from the human-readable text map format.
+    /// The hash of the map is computed from the parsed map.
+    pub fn from_text(text: &str) -> Result<GameWorld, GameWorldParseError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim_end()))
+            .filter(|(_, line)| !line.is_empty());
+
+        // Parse the identifier and version
+        let (line_no, line) = lines
+            .next()
+            .ok_or(GameWorldParseError::InvalidTextMapLine { line: 1 })?;
+        let version = line
+            .strip_prefix(TEXT_MAGIC)
+            .and_then(|version| version.trim().parse::<u8>().ok())
+            .ok_or(GameWorldParseError::InvalidTextMapLine { line: line_no })?;
+        if version != TEXT_FORMAT_VERSION {
+            return Err(GameWorldParseError::InvalidVersion {
+                invalid_version: version,
+            });
+        }
+
+        let tiles: HashMap<String, Tile> = legend()
+            .into_iter()
+            .map(|entry| (entry.legend_id, entry.tile))
+            .collect();
+        let mut world = GameWorld::default();
+        let mut section = TextMapSection::Header;
+        let mut characters: HashMap<char, Tile> = HashMap::new();
+        let mut messages: Vec<String> = vec![];
+        let mut enemies: Vec<(usize, &str)> = vec![];
+        let mut links: Vec<(usize, &str)> = vec![];
+        let mut rows: Vec<(usize, &str)> = vec![];
+        for (line_no, line) in lines {
+            if line.starts_with('[') {
+                section = match line {
+                    "[legend]" => TextMapSection::Legend,
+                    "[messages]" => TextMapSection::Messages,
+                    "[enemies]" => TextMapSection::Enemies,
+                    "[links]" => TextMapSection::Links,
+                    "[map]" => TextMapSection::Map,
+                    // Unknown sections are skipped
+                    _ => TextMapSection::Unknown,
+                };
+                continue;
+            }
+            match section {
+                TextMapSection::Header => {
+                    let (key, value) = line
+                        .split_once(':')
+                        .ok_or(GameWorldParseError::InvalidTextMapLine { line: line_no })?;
+                    let value = value.trim();
+                    let invalid_line = GameWorldParseError::InvalidTextMapLine { line: line_no };
+                    match key.trim() {
+                        "name" => {
+                            world.name = unquote(value)
+                                .ok_or(GameWorldParseError::InvalidTextMapLine { line: line_no })?
+                        },
+                        "author" => {
+                            world.author = unquote(value)
+                                .ok_or(GameWorldParseError::InvalidTextMapLine { line: line_no })?
+                        },
+                        "tileset" => {
+                            world.forced_tileset = Some(
+                                Tileset::iter()
+                                    .find(|tileset| tileset.str_id() == value)
+                                    .ok_or(GameWorldParseError::InvalidTextMapLine {
+                                        line: line_no,
+                                    })?,
+                            )
+                        },
+                        "lives" => {
+                            match value.parse::<usize>() {
+                                Ok(lives) if lives > 0 => world.set_lives(lives),
+                                _ => return Err(invalid_line),
+                            };
+                        },
+                        "description" => {
+                            world.metadata.description = unquote(value).ok_or(invalid_line)?
+                        },
+                        "difficulty" => {
+                            world.metadata.difficulty = Some(
+                                Difficulty::iter()
+                                    .find(|difficulty| difficulty.str_id() == value)
+                                    .ok_or(invalid_line)?,
+                            )
+                        },
+                        "par_moves" => {
+                            world.metadata.par_moves = Some(value.parse().or(Err(invalid_line))?)
+                        },
+                        "created" => {
+                            world.metadata.created = Some(value.parse().or(Err(invalid_line))?)
+                        },
+                        "modified" => {
+                            world.metadata.modified = Some(value.parse().or(Err(invalid_line))?)
+                        },
+                        "tag" => world
+                            .metadata
+                            .tags
+                            .push(unquote(value).ok_or(invalid_line)?),
+                        // Unknown header keys are ignored
+                        _ => {},
+                    }
+                },
+                TextMapSection::Legend => {
+                    let mut chars = line.chars();
+                    let (Some(character), Some(' ')) = (chars.next(), chars.next()) else {
+                        return Err(GameWorldParseError::InvalidTextMapLine { line: line_no });
+                    };
+                    let legend_id = chars.as_str().trim();
+                    let tile = tiles.get(legend_id).ok_or_else(|| {
+                        GameWorldParseError::UnknownLegendEntry {
+                            line: line_no,
+                            legend_id: legend_id.to_string(),
+                        }
+                    })?;
+                    if characters.insert(character, tile.clone()).is_some() {
+                        return Err(GameWorldParseError::InvalidTextMapLine { line: line_no });
+                    }
+                },
+                TextMapSection::Messages => messages.push(
+                    unquote(line)
+                        .ok_or(GameWorldParseError::InvalidTextMapLine { line: line_no })?,
+                ),
+                TextMapSection::Enemies => enemies.push((line_no, line)),
+                TextMapSection::Links => links.push((line_no, line)),
+                TextMapSection::Map => rows.push((line_no, line)),
+                TextMapSection::Unknown => {},
+            }
+        }
+
+        // Parse Map Width and Map Height
+        let map_height = rows.len();
+        let map_width = rows
+            .first()
+            .map(|(_, row)| row.chars().count())
+            .unwrap_or(0);
+        if map_width == 0 || map_width > MAX_MAP_WIDTH {
+            return Err(GameWorldParseError::InvalidMapWidth {
+                max_width: MAX_MAP_WIDTH,
+                actual_width: map_width,
+            });
+        }
+        if map_height > MAX_MAP_HEIGHT {
+            return Err(GameWorldParseError::InvalidMapHeight {
+                max_height: MAX_MAP_HEIGHT,
+                actual_height: map_height,
+            });
+        }
+        for (line_no, row) in &rows {
+            let actual_width = row.chars().count();
+            if actual_width != map_width {
+                return Err(GameWorldParseError::InvalidTextMapRow {
+                    line: *line_no,
+                    expected_width: map_width,
+                    actual_width,
+                });
+            }
+        }
+        world.data = vec![vec![Tile::AIR; map_height]; map_width];
+
+        // Parse actual map content in the same order as the binary map format
+        let mut current_message_id = 0usize;
+        for (y, (line_no, row)) in rows.iter().rev().enumerate() {
+            for (x, character) in row.chars().enumerate() {
+                let mut tile = characters.get(&character).cloned().ok_or(
+                    GameWorldParseError::UnknownTextMapCharacter {
+                        line: *line_no,
+                        character,
+                    },
+                )?;
+                // Assign the current message ID to message tiles
+                if let Tile::MESSAGE { .. } = tile {
+                    tile = Tile::MESSAGE {
+                        message_id: current_message_id,
+                    };
+                    current_message_id += 1;
+                }
+                world.set(x, y, tile);
+            }
+        }
+        if messages.len() != current_message_id {
+            return Err(GameWorldParseError::MissingMessageString {
+                expected_length: current_message_id as u32,
+                actual_length: messages.len() as u32,
+            });
+        }
+        world.messages = messages;
+
+        // Parse all enemies, which must lie inside the map
+        for (line_no, line) in enemies {
+            let enemy = parse_enemy_line(line, &world)
+                .ok_or(GameWorldParseError::InvalidTextMapLine { line: line_no })?;
+            world.enemies.push(enemy);
+        }
+
+        // Parse all links, which must lie inside the map
+        for (line_no, line) in links {
+            let positions = line
+                .split_whitespace()
+                .map(|word| parse_position_word(word, &world))
+                .collect::<Option<Vec<(usize, usize)>>>()
+                .ok_or(GameWorldParseError::InvalidTextMapLine { line: line_no })?;
+            let (switch, blocks) = positions
+                .split_first()
+                .ok_or(GameWorldParseError::InvalidTextMapLine { line: line_no })?;
+            for block in blocks {
+                world.link(*switch, *block);
+            }
+        }
+        world.recompute_hash();
+        world.set_clean();
+        Ok(world)
+    }
+
+    /// Check if the given path denotes a map in the text map format
+    pub fn is_text_map_file(path: &Path) -> bool {
+        path.extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case(TEXT_MAP_FILE_EXTENSION))
+    }
+
+    /// Load a map from the given file in the text map format
+    pub(crate) fn load_from_text_file(path: &Path) -> Result<GameWorld, GameWorldParseError> {
+        let text = fs::read_to_string(path)?;
+        let mut ret = GameWorld::from_text(&text)?;
+        ret.set_filename(path.to_path_buf());
+        Ok(ret)
+    }
+
+    /// Save the map to the given file in the text map format
+    pub(crate) fn save_to_text_file(&self, path: &Path) -> Result<(), GameWorldParseError> {
+        fs::write(path, self.to_text())?;
+        Ok(())
+    }
+}
+
+/// The sections of a text map
+enum TextMapSection {
+    Header,
+    Legend,
+    Messages,
+    Enemies,
+    Links,
+    Map,
+    Unknown,
+}
+
+/// An entry in the legend of a text map
+struct LegendEntry {
+    tile: Tile,
+    legend_id: String,
+    character: char,
+}
+
+/// Get the legend entries of all tiles that can be stored in a map.
+/// The characters are derived from the string ids of the tiles and are always assigned in the
+/// same order, such that the same tile is always represented by the same character.
+fn legend() -> Vec<LegendEntry> {
+    let mut ret: Vec<LegendEntry> = vec![];
+    for tile in Tile::iter().flat_map(|tile| match tile {
+        Tile::TELEPORTENTRY { .. } => TeleportId::iter()
+            .map(|teleport_id| Tile::TELEPORTENTRY { teleport_id })
+            .collect(),
+        Tile::TELEPORTEXIT { .. } => TeleportId::iter()
+            .map(|teleport_id| Tile::TELEPORTEXIT { teleport_id })
+            .collect(),
+        Tile::COLOREDDOOR { .. } => KeyColor::iter()
+            .map(|color| Tile::COLOREDDOOR { color })
+            .collect(),
+        Tile::OPENCOLOREDDOOR { .. } => KeyColor::iter()
+            .map(|color| Tile::OPENCOLOREDDOOR { color })
+            .collect(),
+        Tile::COLOREDKEY { .. } => KeyColor::iter()
+            .map(|color| Tile::COLOREDKEY { color })
+            .collect(),
+        tile => vec![tile],
+    }) {
+        // Use exactly the tile that would be loaded from a binary map
+        let Some(tile) = Tile::from_bytes(tile.to_bytes()) else {
+            continue;
+        };
+        let legend_id = match &tile {
+            Tile::TELEPORTENTRY { teleport_id } | Tile::TELEPORTEXIT { teleport_id } => {
+                format!("{}:{}", tile.str_id(), teleport_id.const_to_u8() + 1)
+            },
+            _ => tile.str_id().to_string(),
+        };
+        let mut candidates: Vec<char> = vec![];
+        if tile == Tile::AIR {
+            candidates.push('.');
+        }
+        for c in legend_id.chars().filter(|c| c.is_ascii_alphanumeric()) {
+            candidates.push(c.to_ascii_lowercase());
+            candidates.push(c.to_ascii_uppercase());
+        }
+        candidates.extend(FALLBACK_LEGEND_CHARACTERS.chars());
+        let character = candidates
+            .into_iter()
+            .find(|c| !ret.iter().any(|entry| entry.character == *c))
+            .expect("There are not enough characters to represent all tiles");
+        ret.push(LegendEntry {
+            tile,
+            legend_id,
+            character,
+        });
+    }
+    ret
+}
+
+/// Get the line that represents the given enemy in the `[enemies]` section
+fn enemy_line(enemy: &Enemy) -> String {
+    let mut words = vec![enemy.str_id().to_string(), position_word(enemy.spawn())];
+    match enemy.kind() {
+        EnemyKind::WALKER { facing_right } => {
+            words.push(if *facing_right { "right" } else { "left" }.to_string())
+        },
+        EnemyKind::FLYER { waypoints } => {
+            words.extend(waypoints.iter().map(|waypoint| position_word(*waypoint)))
+        },
+    }
+    let deadly_from: String = DIRECTION_CHARACTERS
+        .iter()
+        .filter(|(direction, _)| enemy.is_deadly_from(direction))
+        .map(|(_, character)| *character)
+        .collect();
+    words.push(format!("deadly:{}", deadly_from));
+    words.join(" ")
+}
+
+/// Parse a line of the `[enemies]` section, or return None if the line is not a valid enemy
+/// inside the given map. If the deadly directions are omitted, the defaults of the enemy are used.
+fn parse_enemy_line(line: &str, world: &GameWorld) -> Option<Enemy> {
+    let parse_position = |word: &str| parse_position_word(word, world);
+    let mut words = line.split_whitespace();
+    let kind = words.next()?;
+    let spawn = parse_position(words.next()?)?;
+    // Walking enemies are followed by their initial direction
+    let facing_right = match kind {
+        "walker" => Some(match words.next()? {
+            "left" => false,
+            "right" => true,
+            _ => return None,
+        }),
+        "flyer" => None,
+        _ => return None,
+    };
+    let mut waypoints: Vec<(usize, usize)> = vec![];
+    let mut deadly_from: Option<Vec<FromDirection>> = None;
+    for word in words {
+        if let Some(characters) = word.strip_prefix("deadly:") {
+            deadly_from = Some(
+                characters
+                    .chars()
+                    .map(|c| {
+                        DIRECTION_CHARACTERS
+                            .iter()
+                            .find(|(_, character)| *character == c)
+                            .map(|(direction, _)| *direction)
+                    })
+                    .collect::<Option<Vec<_>>>()?,
+            );
+        } else {
+            waypoints.push(parse_position(word)?);
+        }
+    }
+    let enemy = match facing_right {
+        Some(facing_right) if waypoints.is_empty() => Enemy::walker(spawn, facing_right),
+        Some(_) => return None,
+        None => Enemy::flyer(spawn, waypoints),
+    };
+    Some(match deadly_from {
+        Some(deadly_from) => enemy.with_deadly_from(deadly_from),
+        None => enemy,
+    })
+}
+
+/// Get the word that represents the given map position, e.g., `3,2`
+fn position_word((x, y): (usize, usize)) -> String {
+    format!("{},{}", x, y)
+}
+
+/// Parse the given `x,y` position, or return None if the position does not lie inside the given map
+fn parse_position_word(word: &str, world: &GameWorld) -> Option<(usize, usize)> {
+    let (x, y) = word.split_once(',')?;
+    let (x, y) = (x.parse::<usize>().ok()?, y.parse::<usize>().ok()?);
+    (x < world.width() && y < world.height()).then_some((x, y))
+}
+
+/// Convert the given string into a quoted string, escaping all special characters
+fn quote(value: &str) -> String {
+    let mut ret = String::from('"');
+    for c in value.chars() {
+        match c {
+            '\\' => ret.push_str("\\\\"),
+            '"' => ret.push_str("\\\""),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\t' => ret.push_str("\\t"),
+            c => ret.push(c),
+        }
+    }
+    ret.push('"');
+    ret
+}
+
+/// Parse the given quoted string, or return None if the string is not a valid quoted string
+fn unquote(value: &str) -> Option<String> {
+    let mut chars = value.strip_prefix('"')?.strip_suffix('"')?.chars();
+    let mut ret = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => ret.push(match chars.next()? {
+                '\\' => '\\',
+                '"' => '"',
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                _ => return None,
+            }),
+            '"' => return None,
+            c => ret.push(c),
+        }
+    }
+    Some(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exodus_serializable::ExodusSerializable;
+    use crate::world::metadata::MapMetadata;
+    use bytebuffer::ByteBuffer;
+    use std::path::PathBuf;
+
+    /// Convert the given map into the text format and back, and make sure both binary
+    /// representations are identical
+    fn assert_lossless_round_trip(map: &mut GameWorld) -> GameWorld {
+        map.recompute_hash();
+        let result = GameWorld::from_text(&map.to_text())
+            .unwrap_or_else(|e| panic!("Could not parse text map: {}\n{}", e, map.to_text()));
+        let mut expected = ByteBuffer::new();
+        map.serialize(&mut expected).unwrap();
+        let mut actual = ByteBuffer::new();
+        result.serialize(&mut actual).unwrap();
+        assert_eq!(expected.as_bytes(), actual.as_bytes());
+        assert_eq!(map.hash, result.hash);
+        result
+    }
+
+    #[test]
+    fn test_legend_characters_are_unique() {
+        let legend = legend();
+        for (i, entry) in legend.iter().enumerate() {
+            assert!(!entry.character.is_whitespace());
+            assert!(
+                legend[i + 1..]
+                    .iter()
+                    .all(|other| other.character != entry.character
+                        && other.legend_id != entry.legend_id),
+                "Duplicate legend entry for {}",
+                entry.legend_id
+            );
+        }
+    }
+
+    #[test]
+    fn test_round_trip_example_maps() {
+        assert_lossless_round_trip(&mut GameWorld::exampleworld());
+        assert_lossless_round_trip(&mut GameWorld::showcaseworld());
+    }
+
+    #[test]
+    fn test_round_trip_all_tiles() {
+        let mut map = GameWorld::new(legend().len(), 1);
+        for (i, entry) in legend().into_iter().enumerate() {
+            map.set(i, 0, entry.tile);
+        }
+        assert_lossless_round_trip(&mut map);
+    }
+
+    #[test]
+    fn test_round_trip_asset_maps() {
+        let mut maps_dir = PathBuf::from(env!("PROJECTDIR"));
+        maps_dir.push("..");
+        maps_dir.push("assets");
+        maps_dir.push("maps");
+        for entry in fs::read_dir(&maps_dir).unwrap() {
+            let mut map = GameWorld::load_from_file(entry.unwrap().path().as_path()).unwrap();
+            assert_lossless_round_trip(&mut map);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_special_strings() {
+        let mut map = GameWorld::new(3, 3);
+        map.set_name("A \"quoted\" name")
+            .set_author("Back\\slash\tand tab")
+            .set_forced_tileset(Some(Tileset::Antarctica))
+            .set_message_tile(0, 2, "Line 1\nLine 2\r\n".to_string())
+            .set_message_tile(2, 0, "".to_string())
+            .set_message_tile(1, 1, " [map] ".to_string());
+        let result = assert_lossless_round_trip(&mut map);
+        assert_eq!("A \"quoted\" name", result.get_name());
+        assert_eq!("Back\\slash\tand tab", result.get_author());
+        assert_eq!(Some(Tileset::Antarctica), result.forced_tileset());
+        assert_eq!(
+            Some("Line 1\nLine 2\r\n"),
+            result.get(0, 2).and_then(|tile| match tile {
+                Tile::MESSAGE { message_id } => result.get_message(*message_id),
+                _ => None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_round_trip_metadata() {
+        let mut map = GameWorld::exampleworld();
+        let mut metadata = MapMetadata {
+            description: "Multi-line\n\"description\"".to_string(),
+            difficulty: Some(Difficulty::Medium),
+            par_moves: Some(17),
+            created: Some(-5),
+            modified: Some(1_700_000_000_000),
+            tags: vec![],
+        };
+        metadata.set_tags(["tag: with colon", "second"]);
+        map.set_metadata(metadata.clone());
+        let result = assert_lossless_round_trip(&mut map);
+        assert_eq!(&metadata, result.metadata());
+    }
+
+    #[test]
+    fn test_round_trip_lives() {
+        let mut map = GameWorld::exampleworld();
+        map.set_lives(5);
+        assert!(map.to_text().contains("\nlives: 5\n"));
+        let result = assert_lossless_round_trip(&mut map);
+        assert_eq!(5, result.lives());
+    }
+
+    #[test]
+    fn test_round_trip_enemies() {
+        let mut map = GameWorld::exampleworld();
+        map.add_enemy(Enemy::walker((3, 2), true))
+            .add_enemy(Enemy::flyer((6, 5), vec![(9, 5), (9, 3)]))
+            .add_enemy(Enemy::flyer((2, 8), vec![]).with_deadly_from(vec![]));
+        let text = map.to_text();
+        assert!(text.contains(
+            "\n[enemies]\nwalker 3,2 right deadly:sew\nflyer 6,5 9,5 9,3 deadly:nsew\nflyer 2,8 deadly:\n"
+        ));
+        let result = assert_lossless_round_trip(&mut map);
+        assert_eq!(map.enemies(), result.enemies());
+    }
+
+    #[test]
+    fn test_round_trip_links() {
+        let mut map = GameWorld::new(6, 2);
+        map.set(0, 0, Tile::LEVER).set(1, 1, Tile::LEVERPULLED);
+        map.set(3, 0, Tile::TOGGLEBLOCK).set(4, 1, Tile::DOOR);
+        map.link((0, 0), (3, 0))
+            .link((0, 0), (4, 1))
+            .link((1, 1), (3, 0));
+        // Links of removed switches are not stored
+        map.link((5, 0), (3, 0));
+        let text = map.to_text();
+        assert!(text.contains("\n[links]\n0,0 3,0 4,1\n1,1 3,0\n"));
+        let result = assert_lossless_round_trip(&mut map);
+        assert_eq!(2, result.links().len());
+        assert_eq!(vec![(3, 0), (4, 1)], result.linked_blocks((0, 0)));
+    }
+
+    #[test]
+    fn test_invalid_links() {
+        for link in ["switch", "0,0 2,0", "2,0 0,0", "0;0 1,0"] {
+            let text = format!(
+                "ExodusTextMap 1\n[links]\n{}\n[legend]\n. air\n[map]\n..\n",
+                link
+            );
+            assert!(
+                matches!(
+                    GameWorld::from_text(&text),
+                    Err(GameWorldParseError::InvalidTextMapLine { line: 3 })
+                ),
+                "{}",
+                link
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_enemies_with_default_directions() {
+        let text = "ExodusTextMap 1\n[enemies]\nwalker 0,0 left\nflyer 1,0 0,0\n[legend]\n. air\n[map]\n..\n";
+        let world = GameWorld::from_text(text).unwrap();
+        assert_eq!(
+            &[
+                Enemy::walker((0, 0), false),
+                Enemy::flyer((1, 0), vec![(0, 0)])
+            ],
+            world.enemies()
+        );
+    }
+
+    #[test]
+    fn test_invalid_enemies() {
+        for enemy in [
+            "dragon 0,0",
+            "walker 0,0",
+            "walker 0,0 up",
+            "walker 0,0 left 1,0",
+            "walker 2,0 left",
+            "flyer 0,0 0,1",
+            "flyer 0,0 1;0",
+            "flyer 0,0 deadly:x",
+        ] {
+            let text = format!(
+                "ExodusTextMap 1\n[enemies]\n{}\n[legend]\n. air\n[map]\n..\n",
+                enemy
+            );
+            assert!(
+                matches!(
+                    GameWorld::from_text(&text),
+                    Err(GameWorldParseError::InvalidTextMapLine { line: 3 })
+                ),
+                "{}",
+                enemy
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_metadata() {
+        for header in [
+            "difficulty: impossible",
+            "par_moves: many",
+            "lives: 0",
+            "created: \"now\"",
+        ] {
+            let text = format!("ExodusTextMap 1\n{}\n[legend]\n. air\n[map]\n.\n", header);
+            assert!(matches!(
+                GameWorld::from_text(&text),
+                Err(GameWorldParseError::InvalidTextMapLine { line: 2 })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_handwritten_map() {
+        let text = "ExodusTextMap 1\r\n\
+            name: \"Handwritten\"\r\n\
+            comment: Unknown keys are ignored\r\n\
+            \r\n\
+            [legend]\r\n\
+            # wall\r\n\
+            . air\r\n\
+            s playerspawn\r\n\
+            1 teleport_entry:1\r\n\
+            2 teleport_exit:1\r\n\
+            \r\n\
+            [extras]\r\n\
+            Unknown sections are ignored\r\n\
+            [map]\r\n\
+            #####\r\n\
+            #2..#\r\n\
+            #s.1#\r\n\
+            #####\r\n";
+        let world = GameWorld::from_text(text).unwrap();
+        assert_eq!("Handwritten", world.get_name());
+        assert_eq!("", world.get_author());
+        assert_eq!(5, world.width());
+        assert_eq!(4, world.height());
+        assert_eq!((1, 1), world.player_spawn());
+        assert_eq!(
+            &Tile::TELEPORTENTRY {
+                teleport_id: TeleportId::ONE
+            },
+            world.get(3, 1).unwrap()
+        );
+        assert_eq!(Some(&(1, 2)), world.get_teleport_location(TeleportId::ONE));
+    }
+
+    #[test]
+    fn test_invalid_identifier() {
+        let result = GameWorld::from_text("ExodusMap 1\n[map]\n.\n");
+        assert!(matches!(
+            result,
+            Err(GameWorldParseError::InvalidTextMapLine { line: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_version() {
+        let result = GameWorld::from_text("ExodusTextMap 9\n[legend]\n. air\n[map]\n.\n");
+        assert!(matches!(
+            result,
+            Err(GameWorldParseError::InvalidVersion { invalid_version: 9 })
+        ));
+    }
+
+    #[test]
+    fn test_unknown_legend_entry() {
+        let result = GameWorld::from_text("ExodusTextMap 1\n[legend]\nx lava\n[map]\nx\n");
+        assert!(matches!(
+            result,
+            Err(GameWorldParseError::UnknownLegendEntry { line: 3, legend_id }) if legend_id == "lava"
+        ));
+    }
+
+    #[test]
+    fn test_unknown_character() {
+        let result = GameWorld::from_text("ExodusTextMap 1\n[legend]\n. air\n[map]\n..\n.x\n");
+        assert!(matches!(
+            result,
+            Err(GameWorldParseError::UnknownTextMapCharacter {
+                line: 6,
+                character: 'x'
+            })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_row_length() {
+        let result = GameWorld::from_text("ExodusTextMap 1\n[legend]\n. air\n[map]\n..\n...\n");
+        assert!(matches!(
+            result,
+            Err(GameWorldParseError::InvalidTextMapRow {
+                line: 6,
+                expected_width: 2,
+                actual_width: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn test_missing_map() {
+        let result = GameWorld::from_text("ExodusTextMap 1\n[legend]\n. air\n");
+        assert!(matches!(
+            result,
+            Err(GameWorldParseError::InvalidMapWidth {
+                actual_width: 0,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_missing_message() {
+        let result = GameWorld::from_text(
+            "ExodusTextMap 1\n[legend]\nm message\n[messages]\n\"Hello\"\n[map]\nmm\n",
+        );
+        assert!(matches!(
+            result,
+            Err(GameWorldParseError::MissingMessageString {
+                expected_length: 2,
+                actual_length: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn test_is_text_map_file() {
+        assert!(GameWorld::is_text_map_file(Path::new("maps/test.exmt")));
+        assert!(GameWorld::is_text_map_file(Path::new("maps/TEST.EXMT")));
+        assert!(!GameWorld::is_text_map_file(Path::new("maps/test.exm")));
+        assert!(!GameWorld::is_text_map_file(Path::new("maps/exmt")));
+    }
+
+    #[test]
+    fn test_quote_and_unquote() {
+        for value in ["", "abc", "\"", "\\", "a\nb", "\t\r", "\\n"] {
+            assert_eq!(Some(value.to_string()), unquote(&quote(value)));
+        }
+        assert_eq!(None, unquote("abc"));
+        assert_eq!(None, unquote("\"abc"));
+        assert_eq!(None, unquote("\"a\"b\""));
+        assert_eq!(None, unquote("\"\\x\""));
+    }
+}
@@ -0,0 +1,542 @@
+//! Checks that find common mistakes in maps before they are saved, e.g., maps without an exit or
+//! teleports that lead nowhere.
+use crate::directions::FromDirection;
+use crate::simulation::solver::{SolverResult, solve_with_limit};
+use crate::tiles::{KeyColor, TeleportId, Tile, TileKind};
+use crate::world::GameWorld;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use strum::IntoEnumIterator;
+
+/// The maximum number of states the solver explores when checking if the exit can be reached.
+/// This is much lower than the default limit of the solver, since the result is shown to the map
+/// author every time a map is saved.
+pub const VALIDATION_MAX_STATES: usize = 50_000;
+
+/// The severity of a validation issue
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum ValidationSeverity {
+    /// The map can be played, but might not work the way the map author intended
+    Warning,
+    /// The map cannot be beaten
+    Error,
+}
+
+/// A single problem that has been found in a map
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValidationIssue {
+    /// The map does not contain any exit
+    NoExit,
+    /// The map contains an exit, but the player cannot reach it
+    ExitUnreachable,
+    /// The map is too complex to check if the exit can be reached
+    ReachabilityUnknown { explored_states: usize },
+    /// There is at least one teleport entry with the given id, but no matching teleport exit
+    TeleportWithoutExit { teleport_id: TeleportId },
+    /// The player spawn is outside of the map or inside a solid or deadly tile
+    PlayerSpawnBlocked { position: (usize, usize) },
+    /// The map contains doors of the given color (or uncolored doors), but no matching keys
+    DoorsWithoutKeys {
+        doors: usize,
+        color: Option<KeyColor>,
+    },
+    /// The map contains uncolored doors, but the keys can only be bought at vending machines
+    DoorsWithPurchasableKeys { doors: usize },
+    /// The map contains fewer keys than doors of the given color (or uncolored keys and doors),
+    /// hence not all doors can be opened
+    FewerKeysThanDoors {
+        doors: usize,
+        keys: usize,
+        color: Option<KeyColor>,
+    },
+    /// The switch at the given position is not linked to any block, hence it does nothing
+    SwitchWithoutLinks { position: (usize, usize) },
+}
+
+impl ValidationIssue {
+    pub fn severity(&self) -> ValidationSeverity {
+        match self {
+            ValidationIssue::NoExit => ValidationSeverity::Error,
+            ValidationIssue::ExitUnreachable => ValidationSeverity::Error,
+            ValidationIssue::ReachabilityUnknown { .. } => ValidationSeverity::Warning,
+            ValidationIssue::TeleportWithoutExit { .. } => ValidationSeverity::Error,
+            ValidationIssue::PlayerSpawnBlocked { .. } => ValidationSeverity::Error,
+            ValidationIssue::DoorsWithoutKeys { .. } => ValidationSeverity::Error,
+            ValidationIssue::DoorsWithPurchasableKeys { .. } => ValidationSeverity::Warning,
+            ValidationIssue::FewerKeysThanDoors { .. } => ValidationSeverity::Warning,
+            ValidationIssue::SwitchWithoutLinks { .. } => ValidationSeverity::Warning,
+        }
+    }
+    /// Get a unique string id, describing this issue. Suitable for i18n keys.
+    pub fn str_id(&self) -> &str {
+        match self {
+            ValidationIssue::NoExit => "no_exit",
+            ValidationIssue::ExitUnreachable => "exit_unreachable",
+            ValidationIssue::ReachabilityUnknown { .. } => "reachability_unknown",
+            ValidationIssue::TeleportWithoutExit { .. } => "teleport_without_exit",
+            ValidationIssue::PlayerSpawnBlocked { .. } => "player_spawn_blocked",
+            ValidationIssue::DoorsWithoutKeys { color: None, .. } => "doors_without_keys",
+            ValidationIssue::DoorsWithoutKeys { color: Some(_), .. } => {
+                "colored_doors_without_keys"
+            },
+            ValidationIssue::DoorsWithPurchasableKeys { .. } => "doors_with_purchasable_keys",
+            ValidationIssue::FewerKeysThanDoors { color: None, .. } => "fewer_keys_than_doors",
+            ValidationIssue::FewerKeysThanDoors { color: Some(_), .. } => {
+                "fewer_colored_keys_than_doors"
+            },
+            ValidationIssue::SwitchWithoutLinks { .. } => "switch_without_links",
+        }
+    }
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationIssue::NoExit => write!(f, "The map does not contain an exit"),
+            ValidationIssue::ExitUnreachable => write!(f, "The exit cannot be reached"),
+            ValidationIssue::ReachabilityUnknown { explored_states } => write!(
+                f,
+                "Could not check if the exit can be reached (gave up after {} states)",
+                explored_states
+            ),
+            ValidationIssue::TeleportWithoutExit { teleport_id } => write!(
+                f,
+                "Teleport {} does not have a teleport exit",
+                teleport_id.const_to_u8() + 1
+            ),
+            ValidationIssue::PlayerSpawnBlocked { position } => write!(
+                f,
+                "The player spawn at {},{} is blocked",
+                position.0, position.1
+            ),
+            ValidationIssue::DoorsWithoutKeys { doors, color: None } => {
+                write!(f, "The map contains {} door(s), but no keys", doors)
+            },
+            ValidationIssue::DoorsWithoutKeys {
+                doors,
+                color: Some(color),
+            } => write!(
+                f,
+                "The map contains {} {} door(s), but no {} keys",
+                doors,
+                color.str_id(),
+                color.str_id()
+            ),
+            ValidationIssue::DoorsWithPurchasableKeys { doors } => write!(
+                f,
+                "The map contains {} door(s), but the keys can only be bought at vending machines",
+                doors
+            ),
+            ValidationIssue::FewerKeysThanDoors {
+                doors,
+                keys,
+                color: None,
+            } => write!(
+                f,
+                "The map contains {} door(s), but only {} key(s)",
+                doors, keys
+            ),
+            ValidationIssue::FewerKeysThanDoors {
+                doors,
+                keys,
+                color: Some(color),
+            } => write!(
+                f,
+                "The map contains {} {} door(s), but only {} {} key(s)",
+                doors,
+                color.str_id(),
+                keys,
+                color.str_id()
+            ),
+            ValidationIssue::SwitchWithoutLinks { position } => write!(
+                f,
+                "The switch at {},{} is not linked to any block",
+                position.0, position.1
+            ),
+        }
+    }
+}
+
+/// All issues that have been found in a map
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ValidationReport {
+    issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Get all issues, errors first
+    pub fn issues(&self) -> &[ValidationIssue] {
+        &self.issues
+    }
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity() == ValidationSeverity::Error)
+    }
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity() == ValidationSeverity::Warning)
+    }
+    /// Check if the map contains at least one error, i.e., if the map cannot be beaten
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+    /// Check if no issues have been found
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+    fn push(&mut self, issue: ValidationIssue) {
+        self.issues.push(issue);
+    }
+    /// Sort the issues, errors first. Called once all issues have been found.
+    fn finish(mut self) -> Self {
+        self.issues
+            .sort_by_key(|issue| std::cmp::Reverse(issue.severity()));
+        self
+    }
+}
+
+/// Validate the given map, including a check if the exit can be reached.
+/// The reachability check uses the solver and explores at most [VALIDATION_MAX_STATES] states.
+///
+/// ```rust
+/// use libexodus::tiles::Tile;
+/// use libexodus::world::presets;
+/// use libexodus::world::validation::{validate, ValidationIssue};
+/// let mut world = presets::map_with_border(6, 3);
+/// assert_eq!(&[ValidationIssue::NoExit], validate(&world).issues());
+/// world.set(4, 1, Tile::EXIT);
+/// assert!(validate(&world).is_empty());
+/// world.set(3, 1, Tile::WALL);
+/// assert_eq!(&[ValidationIssue::ExitUnreachable], validate(&world).issues());
+/// ```
+pub fn validate(world: &GameWorld) -> ValidationReport {
+    let mut report = validate_structure(world);
+    // The solver cannot give any useful information if the map is broken anyway
+    if report.has_errors() {
+        return report;
+    }
+    match solve_with_limit(world, VALIDATION_MAX_STATES) {
+        SolverResult::Solved(_) => {},
+        SolverResult::Unsolvable => report.push(ValidationIssue::ExitUnreachable),
+        SolverResult::SearchLimitExceeded { explored_states } => {
+            report.push(ValidationIssue::ReachabilityUnknown { explored_states })
+        },
+    }
+    report.finish()
+}
+
+/// Validate the given map without checking if the exit can be reached.
+/// This is fast enough to validate a large number of maps at once.
+pub fn validate_structure(world: &GameWorld) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let tiles = || (0..world.width()).flat_map(|x| (0..world.height()).map(move |y| (x, y)));
+    let tile_at = |(x, y): (usize, usize)| world.get(x as i32, y as i32);
+    if !tiles().any(|position| tile_at(position) == Some(&Tile::EXIT)) {
+        report.push(ValidationIssue::NoExit);
+    }
+    let spawn = world.player_spawn();
+    let spawn_blocked = tile_at(spawn).is_none_or(|tile| {
+        [
+            FromDirection::FROMNORTH,
+            FromDirection::FROMSOUTH,
+            FromDirection::FROMEAST,
+            FromDirection::FROMWEST,
+        ]
+        .iter()
+        .any(|direction| tile.can_collide_from(direction) || tile.is_deadly_from(direction))
+    });
+    if spawn_blocked {
+        report.push(ValidationIssue::PlayerSpawnBlocked { position: spawn });
+    }
+    let teleports_without_exit: BTreeSet<TeleportId> = tiles()
+        .filter_map(|position| match tile_at(position) {
+            Some(Tile::TELEPORTENTRY { teleport_id }) => Some(*teleport_id),
+            _ => None,
+        })
+        .filter(|teleport_id| world.get_teleport_location(*teleport_id).is_none())
+        .collect();
+    for teleport_id in teleports_without_exit {
+        report.push(ValidationIssue::TeleportWithoutExit { teleport_id });
+    }
+    // Doors that are opened by switches do not need a key
+    let linked_blocks: BTreeSet<(usize, usize)> = world
+        .active_links()
+        .flat_map(|(_, blocks)| blocks.iter().copied())
+        .collect();
+    // Uncolored keys can be bought at vending machines
+    let has_vending_machines = tiles().any(|position| {
+        matches!(
+            tile_at(position),
+            Some(Tile::VENDINGMACHINEL | Tile::VENDINGMACHINER)
+        )
+    });
+    for color in std::iter::once(None).chain(KeyColor::iter().map(Some)) {
+        let (door, key) = (Tile::door_of_color(color), Tile::key_of_color(color));
+        let doors = tiles()
+            .filter(|position| {
+                tile_at(*position) == Some(&door) && !linked_blocks.contains(position)
+            })
+            .count();
+        let keys = tiles()
+            .filter(|position| tile_at(*position) == Some(&key))
+            .count();
+        if doors > 0 && keys == 0 && color.is_none() && has_vending_machines {
+            report.push(ValidationIssue::DoorsWithPurchasableKeys { doors });
+        } else if doors > 0 && keys == 0 {
+            report.push(ValidationIssue::DoorsWithoutKeys { doors, color });
+        } else if keys < doors {
+            report.push(ValidationIssue::FewerKeysThanDoors { doors, keys, color });
+        }
+    }
+    for position in tiles() {
+        if tile_at(position).is_some_and(|tile| tile.kind() == TileKind::SWITCH)
+            && world.linked_blocks(position).is_empty()
+        {
+            report.push(ValidationIssue::SwitchWithoutLinks { position });
+        }
+    }
+    report.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tiles::{KeyColor, TeleportId, Tile};
+    use crate::world::GameWorld;
+    use crate::world::presets::map_with_border;
+    use crate::world::validation::{
+        ValidationIssue, ValidationSeverity, validate, validate_structure,
+    };
+
+    fn solvable_map() -> GameWorld {
+        let mut world = map_with_border(8, 3);
+        world.set(1, 1, Tile::PLAYERSPAWN);
+        world.set(6, 1, Tile::EXIT);
+        world
+    }
+
+    #[test]
+    fn test_solvable_map_has_no_issues() {
+        let report = validate(&solvable_map());
+        assert!(report.is_empty(), "{:?}", report);
+        assert!(!report.has_errors());
+    }
+
+    #[test]
+    fn test_no_exit() {
+        let mut world = solvable_map();
+        world.set(6, 1, Tile::AIR);
+        let report = validate(&world);
+        assert_eq!(&[ValidationIssue::NoExit], report.issues());
+        assert!(report.has_errors());
+    }
+
+    #[test]
+    fn test_unreachable_exit() {
+        let mut world = solvable_map();
+        world.set(4, 1, Tile::WALL);
+        assert_eq!(
+            &[ValidationIssue::ExitUnreachable],
+            validate(&world).issues()
+        );
+        // The structural validation does not run the solver
+        assert!(validate_structure(&world).is_empty());
+    }
+
+    #[test]
+    fn test_unreachable_exit_with_vending_machine() {
+        let mut world = solvable_map();
+        world.set(4, 1, Tile::WALL);
+        world.set(2, 1, Tile::VENDINGMACHINEL);
+        // Without star crystals, nothing can be bought at the vending machine
+        assert_eq!(
+            &[ValidationIssue::ExitUnreachable],
+            validate(&world).issues()
+        );
+    }
+
+    #[test]
+    fn test_teleport_without_exit() {
+        let mut world = solvable_map();
+        let entry = Tile::TELEPORTENTRY {
+            teleport_id: TeleportId::TWO,
+        };
+        world.set(2, 2, entry.clone());
+        world.set(3, 2, entry);
+        world.set(
+            4,
+            2,
+            Tile::TELEPORTENTRY {
+                teleport_id: TeleportId::ONE,
+            },
+        );
+        world.set(
+            5,
+            2,
+            Tile::TELEPORTEXIT {
+                teleport_id: TeleportId::ONE,
+            },
+        );
+        assert_eq!(
+            &[ValidationIssue::TeleportWithoutExit {
+                teleport_id: TeleportId::TWO
+            }],
+            validate_structure(&world).issues()
+        );
+    }
+
+    #[test]
+    fn test_player_spawn_inside_wall() {
+        let mut world = solvable_map();
+        world.set(1, 1, Tile::WALL);
+        assert_eq!(
+            &[ValidationIssue::PlayerSpawnBlocked { position: (1, 1) }],
+            validate(&world).issues()
+        );
+        world.set(1, 1, Tile::SPIKES);
+        assert!(validate(&world).has_errors());
+        // Air on the player spawn is fine, the player is spawned there anyway
+        world.set(1, 1, Tile::AIR);
+        assert!(validate(&world).is_empty());
+    }
+
+    #[test]
+    fn test_player_spawn_outside_of_map() {
+        let mut world = GameWorld::new(1, 1);
+        world.set(0, 0, Tile::EXIT);
+        assert_eq!(
+            &[ValidationIssue::PlayerSpawnBlocked { position: (1, 1) }],
+            validate_structure(&world).issues()
+        );
+    }
+
+    #[test]
+    fn test_doors_with_keys_from_vending_machine() {
+        let mut world = map_with_border(11, 3);
+        world.set(1, 1, Tile::PLAYERSPAWN);
+        for x in 2..5 {
+            world.set(x, 1, Tile::STARCRYSTAL);
+        }
+        world.set(5, 0, Tile::VENDINGMACHINEL);
+        world.set(7, 1, Tile::DOOR);
+        world.set(9, 1, Tile::EXIT);
+        let report = validate(&world);
+        assert_eq!(
+            &[ValidationIssue::DoorsWithPurchasableKeys { doors: 1 }],
+            report.issues()
+        );
+        assert!(!report.has_errors());
+        // Without enough star crystals, the key cannot be bought
+        world.set(4, 1, Tile::AIR);
+        assert_eq!(
+            &[
+                ValidationIssue::ExitUnreachable,
+                ValidationIssue::DoorsWithPurchasableKeys { doors: 1 }
+            ],
+            validate(&world).issues()
+        );
+    }
+
+    #[test]
+    fn test_doors_and_keys() {
+        let mut world = solvable_map();
+        world.set(3, 1, Tile::DOOR);
+        world.set(3, 2, Tile::DOOR);
+        assert_eq!(
+            &[ValidationIssue::DoorsWithoutKeys {
+                doors: 2,
+                color: None
+            }],
+            validate_structure(&world).issues()
+        );
+        world.set(2, 1, Tile::KEY);
+        let report = validate_structure(&world);
+        assert_eq!(
+            &[ValidationIssue::FewerKeysThanDoors {
+                doors: 2,
+                keys: 1,
+                color: None
+            }],
+            report.issues()
+        );
+        assert!(!report.has_errors());
+        assert_eq!(1, report.warnings().count());
+        world.set(2, 2, Tile::KEY);
+        assert!(validate(&world).is_empty());
+    }
+
+    #[test]
+    fn test_colored_doors_and_keys() {
+        let mut world = solvable_map();
+        world.set(
+            3,
+            1,
+            Tile::COLOREDDOOR {
+                color: KeyColor::RED,
+            },
+        );
+        world.set(2, 1, Tile::KEY);
+        world.set(
+            2,
+            2,
+            Tile::COLOREDKEY {
+                color: KeyColor::BLUE,
+            },
+        );
+        assert_eq!(
+            &[ValidationIssue::DoorsWithoutKeys {
+                doors: 1,
+                color: Some(KeyColor::RED)
+            }],
+            validate_structure(&world).issues()
+        );
+        world.set(
+            2,
+            2,
+            Tile::COLOREDKEY {
+                color: KeyColor::RED,
+            },
+        );
+        assert!(validate_structure(&world).is_empty());
+    }
+
+    #[test]
+    fn test_switches_and_linked_doors() {
+        let mut world = solvable_map();
+        world.set(2, 1, Tile::LEVER);
+        world.set(4, 1, Tile::DOOR);
+        assert_eq!(
+            &[
+                ValidationIssue::DoorsWithoutKeys {
+                    doors: 1,
+                    color: None
+                },
+                ValidationIssue::SwitchWithoutLinks { position: (2, 1) }
+            ],
+            validate_structure(&world).issues()
+        );
+        // The door is opened by the lever
+        world.link((2, 1), (4, 1));
+        let report = validate(&world);
+        assert!(report.is_empty(), "{:?}", report);
+    }
+
+    #[test]
+    fn test_errors_are_sorted_first() {
+        let mut world = solvable_map();
+        world.set(6, 1, Tile::AIR);
+        world.set(3, 1, Tile::DOOR);
+        world.set(3, 2, Tile::DOOR);
+        world.set(2, 1, Tile::KEY);
+        let report = validate(&world);
+        assert_eq!(
+            vec![ValidationSeverity::Error, ValidationSeverity::Warning],
+            report
+                .issues()
+                .iter()
+                .map(|issue| issue.severity())
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -147,7 +147,7 @@ mod tests {
     /// Nature Wall Tile and " " being Air.
     pub fn assert_map_content_matches(expected: &str, map: &GameWorld) {
         print_map(map);
-        let lines: Vec<&str> = expected.split("\n").filter(|l| l.len() > 0).collect();
+        let lines: Vec<&str> = expected.split("\n").filter(|l| !l.is_empty()).collect();
         let expected_h = lines.len();
         let expected_w = lines[0].len();
         assert_eq!(expected_h, map.height());
@@ -188,7 +188,7 @@ mod tests {
                     }
                 );
             }
-            print!("\n");
+            println!();
         }
     }
 }
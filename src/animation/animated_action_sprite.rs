@@ -36,6 +36,10 @@ pub enum AnimatedSpriteAction {
     Teleport {
         location: (usize, usize),
     },
+    /// Respawn the player at the given location after they lost a life
+    Respawn {
+        location: (usize, usize),
+    },
     /// Respawn this animation at the given location once it has finished
     RespawnAnimation {
         animation: Box<AnimatedActionSprite>,
@@ -229,6 +233,17 @@ fn animated_action_sprite_handler(
                         (location.0 as f32, location.1 as f32 + 0.75),
                     )
                 },
+                AnimatedSpriteAction::Respawn { location } => {
+                    debug!(
+                        "Respawning Player at ({},{}), triggered by AnimatedActionSprite",
+                        location.0, location.1
+                    );
+                    respawn_player(
+                        &mut commands,
+                        &tileset_manager,
+                        (location.0 as f32, location.1 as f32),
+                    )
+                },
             }
             return;
         }
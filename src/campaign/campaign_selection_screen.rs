@@ -0,0 +1,180 @@
+use crate::campaign::campaign_trail::{CampaignTrail, MainCampaignTrail, SelectedCampaignTrail};
+use crate::campaign::campaign_trail_editor::CampaignTrailEditor;
+use crate::textures::egui_textures::EguiButtonTextures;
+use crate::ui::uicontrols::{add_navbar_with_extra_buttons, menu_esc_control};
+use crate::ui::{BUTTON_HEIGHT, UIMARGIN, image_button};
+use crate::{AppState, GameDirectoriesWrapper};
+use bevy::prelude::*;
+use bevy_egui::egui::{Align, Layout, RichText};
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+use libexodus::tiles::UITiles;
+
+/// The screen that lists all installed campaign trails and lets the player choose the campaign
+/// trail that is shown in the Campaign Trail Screen
+pub struct CampaignSelectionScreenPlugin;
+
+impl Plugin for CampaignSelectionScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            EguiPrimaryContextPass,
+            campaign_selection_screen_ui.run_if(in_state(AppState::CampaignSelectionScreen)),
+        )
+        .add_systems(
+            Update,
+            menu_esc_control.run_if(in_state(AppState::CampaignSelectionScreen)),
+        );
+    }
+}
+
+fn campaign_selection_screen_ui(
+    mut egui_ctx: EguiContexts,
+    mut state: ResMut<NextState<AppState>>,
+    egui_textures: Res<EguiButtonTextures>,
+    trails: Query<(Entity, &CampaignTrail, Has<MainCampaignTrail>)>,
+    selected: Query<Entity, With<SelectedCampaignTrail>>,
+    directories: Res<GameDirectoriesWrapper>,
+    mut commands: Commands,
+) {
+    add_navbar_with_extra_buttons(
+        egui_ctx.ctx_mut().unwrap(),
+        &mut state,
+        &egui_textures,
+        &t!("campaign_selection_screen.title"),
+        |ui, state| {
+            let new_button = image_button(
+                ui,
+                &egui_textures,
+                &UITiles::CREATENEWBUTTON,
+                "campaign_selection_screen.create_new_trail",
+            );
+            if new_button.clicked() {
+                commands.insert_resource(CampaignTrailEditor::new_trail(
+                    &directories.game_directories,
+                ));
+                state.set(AppState::CampaignTrailEditor);
+            }
+        },
+        1,
+    );
+    // Show the main campaign trail first, followed by all other bundled and all community trails
+    let mut trails: Vec<(Entity, &CampaignTrail, bool, String)> = trails
+        .iter()
+        .map(|(entity, trail, is_main)| (entity, trail, is_main, trail.title()))
+        .collect();
+    trails.sort_by(
+        |(_, trail_a, main_a, title_a), (_, trail_b, main_b, title_b)| {
+            main_b
+                .cmp(main_a)
+                .then_with(|| trail_b.bundled.cmp(&trail_a.bundled))
+                .then_with(|| title_a.cmp(title_b))
+        },
+    );
+    egui::CentralPanel::default().show(egui_ctx.ctx_mut().unwrap(), |ui| {
+        if trails.is_empty() {
+            ui.label(t!("campaign_selection_screen.no_trails"));
+            return;
+        }
+        egui::ScrollArea::new([false, true])
+            .auto_shrink([false; 2])
+            .max_width(ui.available_width())
+            .show(ui, |ui| {
+                egui::Grid::new("campaign_trails_grid")
+                    .striped(true)
+                    .num_columns(1)
+                    .show(ui, |ui| {
+                        for (entity, trail, _, title) in &trails {
+                            ui.scope(|ui| {
+                                ui.set_width(ui.available_width());
+                                ui.set_height(BUTTON_HEIGHT * 1.8);
+                                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                                    ui.add_space(ui.spacing().item_spacing.x);
+                                    // Only community trails can be edited, since bundled trails
+                                    // are part of the game assets
+                                    if let Some(path) = &trail.path {
+                                        let edit_btn = image_button(
+                                            ui,
+                                            &egui_textures,
+                                            &UITiles::EDITBUTTON,
+                                            "campaign_selection_screen.edit_trail",
+                                        );
+                                        if edit_btn.clicked() {
+                                            match CampaignTrailEditor::open_trail(
+                                                path,
+                                                &directories.game_directories,
+                                            ) {
+                                                Ok(editor) => {
+                                                    commands.insert_resource(editor);
+                                                    state.set(AppState::CampaignTrailEditor);
+                                                },
+                                                Err(e) => error!(
+                                                    "Could not open the campaign trail at {}: {}",
+                                                    path.to_str().unwrap_or("<invalid>"),
+                                                    e
+                                                ),
+                                            }
+                                        }
+                                    }
+                                    let play_btn = image_button(
+                                        ui,
+                                        &egui_textures,
+                                        &UITiles::PLAYBUTTON,
+                                        "campaign_selection_screen.play_trail",
+                                    );
+                                    if play_btn.clicked() {
+                                        for selected_entity in &selected {
+                                            commands
+                                                .entity(selected_entity)
+                                                .remove::<SelectedCampaignTrail>();
+                                        }
+                                        commands.entity(*entity).insert(SelectedCampaignTrail);
+                                        state.set(AppState::CampaignTrailScreen);
+                                    }
+                                    ui.with_layout(Layout::top_down(Align::LEFT), |ui| {
+                                        ui.add_space(UIMARGIN);
+                                        labels_trail(ui, trail, title);
+                                        ui.add_space(UIMARGIN);
+                                    });
+                                });
+                            });
+                            ui.end_row();
+                        }
+                    });
+            });
+    });
+}
+
+/// Show the title, description and number of maps of the given campaign trail
+fn labels_trail(ui: &mut egui::Ui, trail: &CampaignTrail, title: &str) {
+    ui.with_layout(Layout::left_to_right(Align::TOP), |ui| {
+        ui.label(RichText::new(title).text_style(egui::TextStyle::Name("MapTitle".into())));
+        ui.add_space(UIMARGIN);
+        ui.vertical(|ui| {
+            ui.add_space(4.);
+            let mut info = t!(
+                "campaign_selection_screen.maps_fmt",
+                maps = trail.num_maps().to_string().as_str()
+            )
+            .to_string();
+            if !trail.bundled {
+                info = format!(
+                    "{}  {}",
+                    info,
+                    t!("campaign_selection_screen.community_trail")
+                );
+            }
+            ui.label(
+                RichText::new(info)
+                    .text_style(egui::TextStyle::Name("MapAuthor".into()))
+                    .color(ui.visuals().weak_text_color()),
+            );
+        });
+    });
+    let description = trail.description();
+    if !description.is_empty() {
+        ui.label(
+            RichText::new(description)
+                .text_style(egui::TextStyle::Name("MapAuthor".into()))
+                .color(ui.visuals().weak_text_color()),
+        );
+    }
+}
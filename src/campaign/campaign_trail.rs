@@ -1,13 +1,12 @@
 use crate::animation::animated_action_sprite::{AnimatedActionSprite, AnimatedSpriteAction};
-use crate::campaign::campaign_maps::CampaignMaps;
 use crate::game::HighscoresDatabaseWrapper;
 use crate::game::constants::{
-    EXITED_PLAYER_ASCEND_SPEED, EXITED_PLAYER_DECAY_SPEED, EXITED_PLAYER_ZOOM_SPEED,
+    EXITED_PLAYER_ASCEND_SPEED, EXITED_PLAYER_DECAY_SPEED, EXITED_PLAYER_ZOOM_SPEED, PLAYER_SPEED_,
 };
 /// This file contains all required UI and logic structs that are required to show the user a
 /// campaign trail where they can choose a map to play and save their progress while doing so.
-/// Since in the future, multiple campaign trails may be supported, we derive the campaign trail
-/// storage struct from Component instead of Resource.
+/// Since multiple campaign trails are supported, we derive the campaign trail storage struct from
+/// Component instead of Resource.
 ///
 /// A campaign trail is shown as world in the same way as an usual "Game World" is shown to the
 /// user when they play the game, therefore we try to re-use as many functions from the game
@@ -15,29 +14,84 @@ use crate::game::constants::{
 /// Especially the movement, camera and tile placement functions are exactly the same, except in the
 /// campaign screen, the player is not affected by gravity and may move upwards or downwards.
 use crate::game::player::{
-    PlayerComponent, ReturnTo, despawn_players, keyboard_controls, player_movement, setup_player,
+    PlayerComponent, ReturnTo, despawn_players, keyboard_action, player_movement,
+    set_player_direction, setup_player,
 };
-use crate::game::scoreboard::{Scoreboard, egui_highscore_label};
+use crate::game::replay::RecordedReplay;
+use crate::game::scoreboard::{GameOverState, Scoreboard, egui_highscore_label};
 use crate::game::tilewrapper::MapWrapper;
 use crate::textures::egui_textures::EguiButtonTextures;
 use crate::ui::uicontrols::{WindowUiOverlayInfo, add_navbar, menu_esc_control};
 use crate::ui::{CAMPAIGN_MAPINFO_HEIGHT, UiSizeChangedEvent, check_ui_size_changed};
-use crate::{AppLabels, AppState, GameConfig, LAYER_ID};
-use bevy::platform::collections::HashSet;
+use crate::{AppLabels, AppState, GameConfig, GameDirectoriesWrapper, LAYER_ID};
 use bevy::prelude::*;
 use bevy::render::view::RenderLayers;
 use bevy_egui::egui::{Align, Layout};
 use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
-use libexodus::campaign::graph::{Coord, Graph, Node, NodeID, NodeKind};
+use libexodus::campaign::graph::{Coord, Graph, NodeKind};
+use libexodus::campaign::progress::CampaignProgress;
+use libexodus::directories::GameDirectories;
+use libexodus::movement::Movement;
 use libexodus::tiles::{EXITING_PLAYER_SPRITE, InteractionKind, Tile};
 use libexodus::world::GameWorld;
 use std::cmp::{max, min};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
 
 #[derive(Component, Default)]
 pub struct CampaignTrail {
     pub trail: Graph,
-    /// The last player position in the campaign trail, as graph coordinates (not map coordinates!)
-    pub last_player_position: (Coord, Coord),
+    /// The name of the campaign trail, used to identify the progress files of the players
+    pub name: String,
+    /// All maps of the campaign trail, mapped from the file names that are used in the trail
+    pub maps: HashMap<String, GameWorld>,
+    /// True if the campaign trail is bundled with the game.
+    /// The title and description of bundled trails and the names of their maps are translated.
+    pub bundled: bool,
+    /// The path of the campaign trail file, if the campaign trail has been loaded from the user
+    /// campaigns folder
+    pub path: Option<PathBuf>,
+    /// The progress of the current player in the campaign trail, including the last player
+    /// position as graph coordinates (not map coordinates!).
+    /// The progress is loaded from the progress file when the campaign trail is shown.
+    pub progress: Option<CampaignProgress>,
+}
+
+impl CampaignTrail {
+    /// Get the title of the campaign trail to display in the UI
+    pub fn title(&self) -> String {
+        if self.trail.title().is_empty() {
+            self.name.clone()
+        } else if self.bundled {
+            t!(format!("campaign.trail.{}", self.trail.title())).to_string()
+        } else {
+            self.trail.title().to_string()
+        }
+    }
+    /// Get the description of the campaign trail to display in the UI
+    pub fn description(&self) -> String {
+        if self.bundled && !self.trail.description().is_empty() {
+            t!(format!("campaign.trail.{}", self.trail.description())).to_string()
+        } else {
+            self.trail.description().to_string()
+        }
+    }
+    /// Get the name of the given map of this campaign trail to display in the UI
+    pub fn map_name(&self, map: &GameWorld) -> String {
+        if self.bundled {
+            t!(format!("campaign.map.{}", map.get_name())).to_string()
+        } else {
+            map.get_name().to_string()
+        }
+    }
+    /// Get the number of maps in this campaign trail
+    pub fn num_maps(&self) -> usize {
+        self.trail
+            .nodes()
+            .filter(|node| matches!(node.kind, NodeKind::MapFilename { .. }))
+            .count()
+    }
 }
 
 /// Marker Struct that marks the main campaign trail entry point
@@ -73,7 +127,7 @@ impl Plugin for CampaignTrailPlugin {
         )
         .add_systems(
             Update,
-            keyboard_controls.run_if(in_state(AppState::CampaignTrailScreen)),
+            trail_keyboard_controls.run_if(in_state(AppState::CampaignTrailScreen)),
         )
         .add_systems(
             Update,
@@ -92,7 +146,132 @@ impl Plugin for CampaignTrailPlugin {
                 .run_if(in_state(AppState::CampaignTrailScreen))
                 .in_set(AppLabels::PlayerMovement),
         )
-        .add_systems(OnExit(AppState::CampaignTrailScreen), despawn_players);
+        .add_systems(
+            OnExit(AppState::CampaignTrailScreen),
+            save_progress_on_exit.before(despawn_players),
+        )
+        .add_systems(OnExit(AppState::CampaignTrailScreen), despawn_players)
+        .add_systems(OnEnter(AppState::GameOverScreen), complete_campaign_map);
+    }
+}
+
+/// Load the campaign progress of the given player in the campaign trail with the given name.
+/// If no progress has been saved yet, or the progress file is invalid, a new empty progress is returned.
+fn load_campaign_progress(
+    directories: &GameDirectories,
+    trail: &str,
+    player: &str,
+) -> CampaignProgress {
+    let progress_file = directories.campaign_progress_file(trail, player);
+    if !progress_file.exists() {
+        debug!(
+            "No campaign progress found at {}",
+            progress_file.to_str().unwrap_or("<invalid>")
+        );
+        return CampaignProgress::new(trail.to_string(), player.to_string());
+    }
+    match CampaignProgress::load_from_file(progress_file.as_path()) {
+        Ok(progress) => {
+            info!(
+                "Loaded campaign progress from {}",
+                progress_file.to_str().unwrap_or("<invalid>")
+            );
+            progress
+        },
+        Err(e) => {
+            error!(
+                "Could not load campaign progress from {} - starting with an empty progress! {}",
+                progress_file.to_str().unwrap_or("<invalid>"),
+                e
+            );
+            CampaignProgress::new(trail.to_string(), player.to_string())
+        },
+    }
+}
+
+/// Save the given campaign progress to its progress file, creating the progress directory if necessary
+fn save_campaign_progress(directories: &GameDirectories, progress: &CampaignProgress) {
+    if let Err(e) = fs::create_dir_all(&directories.progress_dir) {
+        error!(
+            "Could not create the campaign progress directory at {}: {}",
+            directories.progress_dir.to_str().unwrap_or("<invalid>"),
+            e
+        );
+        return;
+    }
+    let progress_file = directories.campaign_progress_file(progress.trail(), progress.player());
+    match progress.save_to_file(progress_file.as_path()) {
+        Ok(_) => info!(
+            "Successfully saved campaign progress to {}",
+            progress_file.to_str().unwrap_or("<invalid>")
+        ),
+        Err(e) => error!(
+            "Could not save campaign progress to {}: {}",
+            progress_file.to_str().unwrap_or("<invalid>"),
+            e
+        ),
+    }
+}
+
+/// Store the current player position in the campaign progress and save the progress to its file
+/// when leaving the Campaign Trail Screen.
+/// If the player has already left the trail to play a map, the position has been stored before.
+fn save_progress_on_exit(
+    mut trail_query: Query<&mut CampaignTrail, With<SelectedCampaignTrail>>,
+    player_query: Query<&Transform, With<PlayerComponent>>,
+    config: Res<GameConfig>,
+    directories: Res<GameDirectoriesWrapper>,
+) {
+    let Ok(mut trail) = trail_query.single_mut() else {
+        return;
+    };
+    let trail = trail.as_mut();
+    let Some(progress) = trail.progress.as_mut() else {
+        return;
+    };
+    if let Ok(player_pos) = player_query.single() {
+        let player_map_x = (player_pos.translation.x / (config.texture_size())) as Coord;
+        let player_map_y = (player_pos.translation.y / (config.texture_size())) as Coord;
+        progress.set_position((
+            player_map_x + trail.trail.min_x(),
+            player_map_y + trail.trail.min_y(),
+        ));
+    }
+    save_campaign_progress(&directories.game_directories, progress);
+}
+
+/// Mark the campaign map the player has just won as completed in the campaign progress.
+/// The map is identified by the last player position in the campaign trail.
+fn complete_campaign_map(
+    mut trail_query: Query<&mut CampaignTrail, With<SelectedCampaignTrail>>,
+    return_to: Res<ReturnTo>,
+    game_status: Res<GameOverState>,
+    recorded: Res<RecordedReplay>,
+    directories: Res<GameDirectoriesWrapper>,
+) {
+    if return_to.0 != AppState::CampaignTrailScreen
+        || recorded.is_playback
+        || !matches!(*game_status, GameOverState::Won { .. })
+    {
+        return;
+    }
+    let Ok(mut trail) = trail_query.single_mut() else {
+        return;
+    };
+    let trail = trail.as_mut();
+    let Some(progress) = trail.progress.as_mut() else {
+        return;
+    };
+    let Some(node) = trail.trail.get_node_at(progress.position()) else {
+        warn!(
+            "Could not find the completed campaign map at {:?}",
+            progress.position()
+        );
+        return;
+    };
+    if matches!(node.kind, NodeKind::MapFilename { .. }) && progress.complete(node.id) {
+        info!("Completed campaign map at node {}", node.id);
+        save_campaign_progress(&directories.game_directories, progress);
     }
 }
 
@@ -102,77 +281,93 @@ impl Plugin for CampaignTrailPlugin {
 /// each time a map has been newly unlocked, this function must be called to unlock those maps
 /// in the campaign trail.
 fn reset_trail(
-    trail_query: Query<&CampaignTrail, With<SelectedCampaignTrail>>,
+    mut trail_query: Query<&mut CampaignTrail, With<SelectedCampaignTrail>>,
     mut commands: Commands,
     highscores: Res<HighscoresDatabaseWrapper>,
-    campaign_maps: Res<CampaignMaps>,
+    config: Res<GameConfig>,
+    directories: Res<GameDirectoriesWrapper>,
 ) {
-    let trail: &CampaignTrail = match trail_query.single() {
-        Ok(trail) => trail,
+    let trail: &mut CampaignTrail = match trail_query.single_mut() {
+        Ok(trail) => trail.into_inner(),
         Err(e) => {
             error!("{}", e);
             return;
         },
     };
+    // (Re-)load the progress if the trail is shown for the first time or the player has changed
+    let player = &config.config.player_id;
+    if trail
+        .progress
+        .as_ref()
+        .is_none_or(|progress| progress.player() != player)
+    {
+        trail.progress = Some(load_campaign_progress(
+            &directories.game_directories,
+            &trail.name,
+            player,
+        ));
+    }
+    let progress = trail
+        .progress
+        .as_ref()
+        .expect("The campaign progress has been loaded before");
+    let last_player_position = progress.position();
     let trail_graph = &trail.trail;
     let offset_x = -trail_graph.min_x();
     let offset_y = -trail_graph.min_y();
     let mut world = GameWorld::new(trail_graph.width(), trail_graph.height());
     world.fill(&Tile::CAMPAIGNTRAILBORDER);
-    if (trail.last_player_position.0 + offset_x) >= trail_graph.width() as Coord
-        || (trail.last_player_position.1 + offset_y) >= trail_graph.height() as Coord
-    {
+    let Some(player_position) = trail_graph.player_position(last_player_position) else {
         error!(
-            "The player position in the campaign trail {:?} was out of bounds {},{}",
-            trail.last_player_position,
-            trail_graph.width(),
-            trail_graph.height()
-        );
-        world.set(
-            (trail.trail.start_node().unwrap().coord.0 + offset_x) as usize,
-            (trail.trail.start_node().unwrap().coord.1 + offset_y) as usize,
-            Tile::PLAYERSPAWN,
+            "The campaign trail {} does not have a start node",
+            trail.name
         );
-    } else {
-        world.set(
-            (trail.last_player_position.0 + offset_x) as usize,
-            (trail.last_player_position.1 + offset_y) as usize,
-            Tile::PLAYERSPAWN,
+        return;
+    };
+    if player_position != last_player_position {
+        error!(
+            "The player position in the campaign trail {:?} is not on a node",
+            last_player_position
         );
     }
+    world.set(
+        (player_position.0 + offset_x) as usize,
+        (player_position.1 + offset_y) as usize,
+        Tile::PLAYERSPAWN,
+    );
 
+    // A map is won if the current player has completed it in this campaign trail or has a
+    // highscore in it. All maps that are 'behind' maps that have not been won yet are locked.
+    let unlocked = trail_graph.unlocked_map_nodes(|node| {
+        progress.is_completed(&node.id)
+            || match &node.kind {
+                NodeKind::Empty => false,
+                NodeKind::MapFilename { map } => trail.maps.get(map).is_some_and(|world| {
+                    highscores
+                        .highscores
+                        .get_best(world.hash(), player)
+                        .is_some()
+                }),
+            }
+    });
     for node in trail_graph.nodes() {
         world.set(
             (node.coord.0 + offset_x) as usize,
             (node.coord.1 + offset_y) as usize,
             match &node.kind {
                 NodeKind::Empty => Tile::CAMPAIGNTRAILWALKWAY,
-                NodeKind::MapFilename { map } => match campaign_maps.maps.get(map) {
-                    None => {
+                NodeKind::MapFilename { map } => {
+                    let interaction = InteractionKind::LaunchMap {
+                        map_name: map.clone(),
+                    };
+                    if !trail.maps.contains_key(map) {
                         error!("Map file not found: {}", map);
-                        Tile::CAMPAIGNTRAILLOCKEDMAPENTRYPOINT {
-                            interaction: InteractionKind::LaunchMap {
-                                map_name: map.clone(),
-                            },
-                        }
-                    },
-                    Some(world) => {
-                        // Insert an unlocked map entry point if the map has already been won before by the current player
-                        if highscores.highscores.get(world.hash()).is_some() {
-                            Tile::CAMPAIGNTRAILMAPENTRYPOINT {
-                                interaction: InteractionKind::LaunchMap {
-                                    map_name: map.clone(),
-                                },
-                            }
-                        } else {
-                            // The map has not been won yet, insert a locked map entry point.
-                            Tile::CAMPAIGNTRAILLOCKEDMAPENTRYPOINT {
-                                interaction: InteractionKind::LaunchMap {
-                                    map_name: map.clone(),
-                                },
-                            }
-                        }
-                    },
+                        Tile::CAMPAIGNTRAILLOCKEDMAPENTRYPOINT { interaction }
+                    } else if unlocked.contains(&node.id) {
+                        Tile::CAMPAIGNTRAILMAPENTRYPOINT { interaction }
+                    } else {
+                        Tile::CAMPAIGNTRAILLOCKEDMAPENTRYPOINT { interaction }
+                    }
                 },
             },
         );
@@ -200,59 +395,21 @@ fn reset_trail(
             }
         }
     }
-    // Traverse the graph using a breadth-first search to unlock all maps that are reachable from
-    // a won map, starting at node id 0. Unlock all maps adjacent to unlocked maps.
-    let mut stack: Vec<&Node> = vec![
-        trail_graph
-            .start_node()
-            .expect("The campaign graph did not have a start node!"),
-    ];
-    let mut visited: HashSet<NodeID> = HashSet::new();
-    visited.insert(0);
-    while let Some(cur) = stack.pop() {
-        for node in trail_graph
-            .edges()
-            .get(&cur.id)
-            .unwrap()
-            .iter()
-            .map(|id| trail_graph.get_node(id).unwrap())
-        {
-            match world.get(
-                (node.coord.0 + offset_x) as i32,
-                (node.coord.1 + offset_y) as i32,
-            ) {
-                Some(Tile::CAMPAIGNTRAILLOCKEDMAPENTRYPOINT { interaction }) => {
-                    world.set(
-                        (node.coord.0 + offset_x) as usize,
-                        (node.coord.1 + offset_y) as usize,
-                        Tile::CAMPAIGNTRAILMAPENTRYPOINT {
-                            interaction: interaction.clone(),
-                        },
-                    );
-                },
-                _ => {
-                    if !visited.contains(&node.id) {
-                        visited.insert(node.id);
-                        stack.push(node);
-                    }
-                },
-            }
-        }
-    }
     debug!(
         "Loaded a campaign trail with size {0}x{1}, Offset {2}x{3} and player spawn at {4},{5} in a world size of {6}x{7}",
         trail.trail.width(),
         trail.trail.height(),
         offset_x,
         offset_y,
-        trail.last_player_position.0,
-        trail.last_player_position.1,
+        last_player_position.0,
+        last_player_position.1,
         world.width(),
         world.height()
     );
     commands.insert_resource(MapWrapper {
         world,
         previous_best: None,
+        previous_best_verified: false,
     });
 }
 fn campaign_screen_ui(
@@ -263,44 +420,55 @@ fn campaign_screen_ui(
     mut window_size_event_writer: EventWriter<UiSizeChangedEvent>,
     player_query: Query<&Transform, With<PlayerComponent>>,
     campaign_trail: Res<MapWrapper>,
-    campaign_maps: Res<CampaignMaps>,
+    current_campaign_trail: Query<&CampaignTrail, With<SelectedCampaignTrail>>,
     highscores: Res<HighscoresDatabaseWrapper>,
     config: Res<GameConfig>,
 ) {
+    let Ok(current_campaign_trail) = current_campaign_trail.single() else {
+        return;
+    };
     if let Ok(player_pos) = player_query.single() {
         let navbar_response = add_navbar(
             egui_ctx.ctx_mut().unwrap(),
             &mut state,
             &egui_textures,
-            &t!("campaign_screen.title"),
+            &current_campaign_trail.title(),
         );
         let ui_top_height = navbar_response.response.rect.height();
 
         // Bottom UI
-        let (in_map, scoreboard, map_name) = match campaign_trail.world.get(
+        let (in_map, scoreboard, verified, map_name) = match campaign_trail.world.get(
             (player_pos.translation.x / (config.texture_size())) as i32,
             (player_pos.translation.y / (config.texture_size())) as i32,
         ) {
             Some(Tile::CAMPAIGNTRAILMAPENTRYPOINT { interaction }) => match interaction {
                 InteractionKind::LaunchMap { map_name } => {
-                    let map = campaign_maps.maps.get(map_name).unwrap_or_else(|| {
-                        panic!("Could not find map with file name \"{}\"!", map_name)
-                    });
+                    let map = current_campaign_trail
+                        .maps
+                        .get(map_name)
+                        .unwrap_or_else(|| {
+                            panic!("Could not find map with file name \"{}\"!", map_name)
+                        });
                     let name = &config.config.player_id;
                     if let Some((_, score)) = &highscores.highscores.get_best(map.hash(), name) {
+                        let verified = highscores
+                            .highscores
+                            .verify_best(map, name)
+                            .is_some_and(|verification| verification.is_verified());
                         (
                             true,
                             Some(Scoreboard::from(*score)),
-                            map.get_name().to_string(),
+                            verified,
+                            current_campaign_trail.map_name(map),
                         )
                     } else {
-                        (true, None, map.get_name().to_string())
+                        (true, None, false, current_campaign_trail.map_name(map))
                     }
                 },
-                InteractionKind::TeleportTo { .. } => (false, None, "".to_string()),
-                InteractionKind::VendingMachine => (false, None, "".to_string()),
+                InteractionKind::TeleportTo { .. } => (false, None, false, "".to_string()),
+                InteractionKind::VendingMachine => (false, None, false, "".to_string()),
             },
-            _ => (false, None, "".to_string()),
+            _ => (false, None, false, "".to_string()),
         };
         let bot =
             egui::TopBottomPanel::bottom("map_info").show(egui_ctx.ctx_mut().unwrap(), |ui| {
@@ -309,12 +477,12 @@ fn campaign_screen_ui(
                 ui.vertical(|ui| {
                     ui.with_layout(Layout::left_to_right(Align::Min), |ui| {
                         if in_map {
-                            ui.label(t!(format!("campaign.map.{}", map_name)));
+                            ui.label(&map_name);
                         }
                     });
                     ui.with_layout(Layout::left_to_right(Align::Min), |ui| {
                         if in_map {
-                            egui_highscore_label(ui, &scoreboard, &egui_textures);
+                            egui_highscore_label(ui, &scoreboard, verified, &egui_textures);
                         }
                     });
                     ui.with_layout(Layout::right_to_left(Align::Min), |ui| {
@@ -337,12 +505,56 @@ fn campaign_screen_ui(
     }
 }
 
+/// Move the player along the campaign trail.
+/// On the campaign trail, there are no game rules: The player is not affected by gravity and only
+/// collides with solid tiles and the boundaries of the trail.
+fn trail_keyboard_controls(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut players: Query<(&mut PlayerComponent, &mut Sprite, &Transform)>,
+    config: Res<GameConfig>,
+    campaign_trail: Res<MapWrapper>,
+) {
+    let texture_size = config.texture_size();
+    for (mut player, mut sprite, transform) in players.iter_mut() {
+        let player = &mut player.player;
+        if !player.movement_queue_is_empty() {
+            continue;
+        }
+        let Some(action) = keyboard_action(&keyboard_input) else {
+            continue;
+        };
+        if let Some(right) = action.faces_right() {
+            set_player_direction(player, sprite.as_mut(), right);
+        }
+        let mut position = (
+            (transform.translation.x / texture_size) as i32,
+            (transform.translation.y / texture_size) as i32,
+        );
+        let movements = action.movements(
+            (position.0 as usize, position.1 as usize),
+            &campaign_trail.world,
+            PLAYER_SPEED_ * texture_size,
+        );
+        // Blocked movements are dropped, and all following movements start at the current position
+        for movement in movements {
+            let target = movement.int_target_from_direction(position.0 as f32, position.1 as f32);
+            let blocked = campaign_trail
+                .world
+                .get(target.0, target.1)
+                .is_none_or(|tile| tile.can_collide_from(&movement.direction().into()));
+            if !blocked {
+                player.push_movement_queue(Movement { target, ..movement });
+                position = target;
+            }
+        }
+    }
+}
+
 pub fn play_map_keyboard_controls(
     mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
     player_query: Query<(&mut PlayerComponent, &Transform, Entity, &Sprite)>,
     config: Res<GameConfig>,
     campaign_trail: Res<MapWrapper>,
-    campaign_maps: Res<CampaignMaps>,
     mut commands: Commands,
     highscores: Res<HighscoresDatabaseWrapper>,
     mut current_campaign_trail: Query<&mut CampaignTrail, With<SelectedCampaignTrail>>,
@@ -366,9 +578,14 @@ pub fn play_map_keyboard_controls(
         {
             match interaction {
                 InteractionKind::LaunchMap { map_name } => {
-                    let map = campaign_maps.maps.get(map_name).unwrap_or_else(|| {
-                        panic!("Could not find map with file name \"{}\"!", map_name)
-                    });
+                    let current_campaign_trail =
+                        current_campaign_trail.single_mut().unwrap().into_inner();
+                    let map = current_campaign_trail
+                        .maps
+                        .get(map_name)
+                        .unwrap_or_else(|| {
+                            panic!("Could not find map with file name \"{}\"!", map_name)
+                        });
                     debug!("Queueing Map {}", map_name);
                     commands.insert_resource(MapWrapper {
                         world: map.clone(),
@@ -379,17 +596,19 @@ pub fn play_map_keyboard_controls(
                             Some((_, score)) => Some(Scoreboard::from(*score)),
                             _ => None,
                         },
+                        previous_best_verified: highscores
+                            .highscores
+                            .verify_best(map, &config.config.player_id)
+                            .is_some_and(|verification| verification.is_verified()),
                     });
                     // Insert the ReturnTo resource and update the player position, such that it
                     // can be restored later:
-                    let trail_graph = &current_campaign_trail.single().unwrap().trail;
-                    let offset_x = -trail_graph.min_x();
-                    let offset_y = -trail_graph.min_y();
+                    let offset_x = -current_campaign_trail.trail.min_x();
+                    let offset_y = -current_campaign_trail.trail.min_y();
                     commands.insert_resource(ReturnTo(AppState::CampaignTrailScreen));
-                    current_campaign_trail
-                        .single_mut()
-                        .unwrap()
-                        .last_player_position = (player_map_x - offset_x, player_map_y - offset_y);
+                    if let Some(progress) = current_campaign_trail.progress.as_mut() {
+                        progress.set_position((player_map_x - offset_x, player_map_y - offset_y));
+                    }
 
                     commands.entity(entity).despawn();
                     let mut exit_atlas = sprite.texture_atlas.clone().unwrap();
@@ -0,0 +1,681 @@
+//! A graphical editor for campaign trails.
+//! Nodes are placed on a grid and connected through straight edges, and each node may be assigned
+//! a map from the maps folder.
+//! The trail is validated live while editing, and only valid trails can be saved.
+use crate::campaign::campaign_trail::CampaignTrail;
+use crate::campaign::load_user_campaign_trail;
+use crate::game::constants::MAPEDITOR_BUTTON_SIZE;
+use crate::textures::egui_textures::EguiButtonTextures;
+use crate::ui::{UIMARGIN, image_button};
+use crate::{AppState, GameDirectoriesWrapper};
+use bevy::prelude::*;
+use bevy_egui::egui::{Align2, Color32, FontId, Pos2, Sense, Stroke, StrokeKind, Vec2};
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+use libexodus::campaign::graph::{Coord, Graph, NodeID, NodeKind};
+use libexodus::directories::GameDirectories;
+use libexodus::exodus_serializable::ExodusSerializable;
+use libexodus::tiles::UITiles;
+use std::fs::{File, create_dir_all};
+use std::io::{BufReader, BufWriter};
+use std::path::{Component, Path, PathBuf};
+
+/// The size of a single grid cell in the campaign trail editor, in pixels
+const TRAIL_EDITOR_CELL_SIZE: f32 = 48.0;
+/// The maximum distance of the mouse pointer to an edge to select it, in pixels
+const TRAIL_EDITOR_EDGE_HIT_DISTANCE: f32 = 6.0;
+/// The width of the property panel on the right side of the campaign trail editor
+const TRAIL_EDITOR_PANEL_WIDTH: f32 = 300.0;
+
+/// The tool that is used when clicking on the grid of the campaign trail editor
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+enum TrailEditorTool {
+    /// Select nodes and edges and drag nodes to move them
+    #[default]
+    Select,
+    /// Add an empty node wherever the grid is clicked
+    AddNode,
+    /// Connect two nodes by clicking them one after another
+    Connect,
+    /// Delete the clicked node or edge
+    Delete,
+}
+
+impl TrailEditorTool {
+    const ALL: [TrailEditorTool; 4] = [
+        TrailEditorTool::Select,
+        TrailEditorTool::AddNode,
+        TrailEditorTool::Connect,
+        TrailEditorTool::Delete,
+    ];
+    fn str_id(&self) -> &'static str {
+        match self {
+            TrailEditorTool::Select => "select",
+            TrailEditorTool::AddNode => "add_node",
+            TrailEditorTool::Connect => "connect",
+            TrailEditorTool::Delete => "delete",
+        }
+    }
+}
+
+/// The node or edge that is currently selected in the campaign trail editor
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum TrailSelection {
+    Node(NodeID),
+    Edge(NodeID, NodeID),
+}
+
+/// The campaign trail that is currently edited, together with the state of the editor.
+/// This resource is inserted before entering the Campaign Trail Editor.
+#[derive(Resource)]
+pub struct CampaignTrailEditor {
+    graph: Graph,
+    /// The name of the campaign trail, which is used as file name of new trails
+    name: String,
+    /// The path the campaign trail is saved to, or None if the trail has never been saved
+    path: Option<PathBuf>,
+    /// All map files that can be assigned to a node, relative to the folder of the trail file
+    available_maps: Vec<String>,
+    tool: TrailEditorTool,
+    selection: Option<TrailSelection>,
+    /// The first node of the edge that is created with the Connect tool
+    connect_from: Option<NodeID>,
+    /// The node that is currently dragged with the Select tool
+    dragging: Option<NodeID>,
+    /// The offset of the graph origin from the center of the grid, in pixels
+    pan: Vec2,
+    /// True if the trail has been changed since it has been saved the last time
+    dirty: bool,
+    /// The result of the last save attempt that is shown to the user
+    status: Option<Result<String, String>>,
+}
+
+impl CampaignTrailEditor {
+    /// Create a new campaign trail that only contains the start node
+    pub fn new_trail(directories: &GameDirectories) -> Self {
+        let mut graph = Graph::default();
+        graph.add_node(NodeKind::Empty, (0, 0));
+        CampaignTrailEditor::with_graph(graph, None, directories)
+    }
+    /// Open the campaign trail file at the given path
+    pub fn open_trail(path: &Path, directories: &GameDirectories) -> Result<Self, String> {
+        let mut graph = Graph::default();
+        let file = File::open(path).map_err(|e| e.to_string())?;
+        graph
+            .parse(&mut BufReader::new(file))
+            .map_err(|e| e.to_string())?;
+        Ok(CampaignTrailEditor::with_graph(
+            graph,
+            Some(path.to_path_buf()),
+            directories,
+        ))
+    }
+    fn with_graph(graph: Graph, path: Option<PathBuf>, directories: &GameDirectories) -> Self {
+        let mut editor = CampaignTrailEditor {
+            graph,
+            name: path
+                .as_ref()
+                .and_then(|path| path.file_stem())
+                .and_then(|stem| stem.to_str())
+                .unwrap_or_default()
+                .to_string(),
+            dirty: path.is_none(),
+            path,
+            available_maps: vec![],
+            tool: TrailEditorTool::default(),
+            selection: None,
+            connect_from: None,
+            dragging: None,
+            pan: Vec2::ZERO,
+            status: None,
+        };
+        editor.available_maps = list_available_maps(directories, &editor.trail_folder(directories));
+        editor
+    }
+    /// Get the folder that contains the trail file. All map files are relative to this folder.
+    fn trail_folder(&self, directories: &GameDirectories) -> PathBuf {
+        self.path
+            .as_ref()
+            .and_then(|path| path.parent())
+            .map(|folder| folder.to_path_buf())
+            .unwrap_or_else(|| directories.campaigns_dir.clone())
+    }
+    /// Get the path the campaign trail is saved to
+    fn save_path(&self, directories: &GameDirectories) -> Option<PathBuf> {
+        if let Some(path) = &self.path {
+            return Some(path.clone());
+        }
+        let name = self.name.trim();
+        if name.is_empty()
+            || name
+                .chars()
+                .any(|c| matches!(c, '/' | '<' | '>' | ':' | '"' | '|' | '\\' | '?' | '*'))
+        {
+            return None;
+        }
+        Some(directories.campaigns_dir.join(format!("{}.tgf", name)))
+    }
+}
+
+/// List all map files inside the maps folder, relative to the given trail folder
+fn list_available_maps(directories: &GameDirectories, trail_folder: &Path) -> Vec<String> {
+    let mut maps: Vec<String> = directories
+        .iter_maps()
+        .filter_map(|entry| relative_path(trail_folder, entry.path()))
+        .collect();
+    maps.sort();
+    maps
+}
+
+/// Get the given path relative to the given folder, using forward slashes as separators
+fn relative_path(folder: &Path, path: &Path) -> Option<String> {
+    let folder: Vec<Component> = folder.components().collect();
+    let path: Vec<Component> = path.components().collect();
+    let common = folder
+        .iter()
+        .zip(path.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let parts: Option<Vec<&str>> = std::iter::repeat_n(Some(".."), folder.len() - common)
+        .chain(path[common..].iter().map(|c| c.as_os_str().to_str()))
+        .collect();
+    Some(parts?.join("/"))
+}
+
+pub struct CampaignTrailEditorPlugin;
+
+impl Plugin for CampaignTrailEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            EguiPrimaryContextPass,
+            campaign_trail_editor_ui.run_if(
+                in_state(AppState::CampaignTrailEditor).and(resource_exists::<CampaignTrailEditor>),
+            ),
+        )
+        .add_systems(
+            OnExit(AppState::CampaignTrailEditor),
+            |mut commands: Commands| commands.remove_resource::<CampaignTrailEditor>(),
+        );
+    }
+}
+
+/// Convert graph coordinates to the center of the grid cell on the screen.
+/// Positive Y coordinates point to the top in the graph, but to the bottom on the screen.
+fn graph_to_screen(origin: Pos2, coord: (Coord, Coord)) -> Pos2 {
+    origin
+        + Vec2::new(
+            coord.0 as f32 * TRAIL_EDITOR_CELL_SIZE,
+            -coord.1 as f32 * TRAIL_EDITOR_CELL_SIZE,
+        )
+}
+
+/// Convert a screen position to the graph coordinates of the grid cell at this position
+fn screen_to_graph(origin: Pos2, pos: Pos2) -> (Coord, Coord) {
+    let offset = (pos - origin) / TRAIL_EDITOR_CELL_SIZE;
+    (offset.x.round() as Coord, -offset.y.round() as Coord)
+}
+
+/// Get the distance of the given point to the line segment between a and b
+fn distance_to_segment(point: Pos2, a: Pos2, b: Pos2) -> f32 {
+    let ab = b - a;
+    let t = if ab.length_sq() > 0. {
+        ((point - a).dot(ab) / ab.length_sq()).clamp(0., 1.)
+    } else {
+        0.
+    };
+    point.distance(a + t * ab)
+}
+
+/// Find the node or edge at the given screen position
+fn find_at(graph: &Graph, origin: Pos2, pos: Pos2) -> Option<TrailSelection> {
+    if let Some(node) = graph.get_node_at(screen_to_graph(origin, pos)) {
+        return Some(TrailSelection::Node(node.id));
+    }
+    graph.unique_edges().into_iter().find_map(|(a, b)| {
+        let pos_a = graph_to_screen(origin, graph.get_node(&a)?.coord);
+        let pos_b = graph_to_screen(origin, graph.get_node(&b)?.coord);
+        (distance_to_segment(pos, pos_a, pos_b) <= TRAIL_EDITOR_EDGE_HIT_DISTANCE)
+            .then_some(TrailSelection::Edge(a, b))
+    })
+}
+
+/// Handle a click on the grid with the currently selected tool
+fn handle_click(editor: &mut CampaignTrailEditor, origin: Pos2, pos: Pos2) {
+    let clicked = find_at(&editor.graph, origin, pos);
+    match editor.tool {
+        TrailEditorTool::Select => editor.selection = clicked,
+        TrailEditorTool::AddNode => {
+            if clicked.is_none()
+                && let Some(id) = editor
+                    .graph
+                    .add_node(NodeKind::Empty, screen_to_graph(origin, pos))
+            {
+                editor.selection = Some(TrailSelection::Node(id));
+                editor.dirty = true;
+            }
+        },
+        TrailEditorTool::Connect => match (editor.connect_from, clicked) {
+            (None, Some(TrailSelection::Node(id))) => editor.connect_from = Some(id),
+            (Some(from), Some(TrailSelection::Node(to))) => {
+                if editor.graph.connect(&from, &to) {
+                    editor.selection = Some(TrailSelection::Edge(from.min(to), from.max(to)));
+                    editor.dirty = true;
+                }
+                editor.connect_from = None;
+            },
+            _ => editor.connect_from = None,
+        },
+        TrailEditorTool::Delete => {
+            match clicked {
+                Some(TrailSelection::Node(id)) => {
+                    editor.graph.remove_node(&id);
+                },
+                Some(TrailSelection::Edge(a, b)) => {
+                    editor.graph.disconnect(&a, &b);
+                },
+                None => return,
+            }
+            editor.selection = None;
+            editor.dirty = true;
+        },
+    }
+}
+
+/// Draw the grid, all edges and all nodes of the campaign trail and handle the mouse input
+fn trail_grid(ui: &mut egui::Ui, editor: &mut CampaignTrailEditor) {
+    let (response, painter) = ui.allocate_painter(ui.available_size(), Sense::click_and_drag());
+    let rect = response.rect;
+    let origin = rect.center() + editor.pan;
+    let visuals = ui.visuals().clone();
+
+    // Mouse input. The grid is panned by dragging with the secondary mouse button.
+    if response.dragged_by(egui::PointerButton::Secondary)
+        || response.dragged_by(egui::PointerButton::Middle)
+    {
+        editor.pan += response.drag_delta();
+    }
+    if editor.tool == TrailEditorTool::Select {
+        if response.drag_started_by(egui::PointerButton::Primary)
+            && let Some(pos) = response.interact_pointer_pos()
+            && let Some(node) = editor.graph.get_node_at(screen_to_graph(origin, pos))
+        {
+            editor.dragging = Some(node.id);
+            editor.selection = Some(TrailSelection::Node(node.id));
+        }
+        if let Some(id) = editor.dragging
+            && let Some(pos) = response.interact_pointer_pos()
+        {
+            let coord = screen_to_graph(origin, pos);
+            if editor.graph.get_node(&id).is_some_and(|n| n.coord != coord)
+                && editor.graph.move_node(&id, coord)
+            {
+                editor.dirty = true;
+            }
+        }
+        if response.drag_stopped() {
+            editor.dragging = None;
+        }
+    }
+    if response.clicked()
+        && let Some(pos) = response.interact_pointer_pos()
+    {
+        handle_click(editor, origin, pos);
+    }
+
+    // Grid
+    let grid_stroke = Stroke::new(1.0, visuals.faint_bg_color);
+    let first = screen_to_graph(origin, rect.left_top());
+    let last = screen_to_graph(origin, rect.right_bottom());
+    for x in first.0..=last.0 {
+        let screen_x = graph_to_screen(origin, (x, 0)).x;
+        painter.vline(screen_x, rect.y_range(), grid_stroke);
+    }
+    for y in last.1..=first.1 {
+        let screen_y = graph_to_screen(origin, (0, y)).y;
+        painter.hline(rect.x_range(), screen_y, grid_stroke);
+    }
+
+    // Edges
+    for (a, b) in editor.graph.unique_edges() {
+        let (Some(node_a), Some(node_b)) = (editor.graph.get_node(&a), editor.graph.get_node(&b))
+        else {
+            continue;
+        };
+        let (pos_a, pos_b) = (
+            graph_to_screen(origin, node_a.coord),
+            graph_to_screen(origin, node_b.coord),
+        );
+        let color = if editor.selection == Some(TrailSelection::Edge(a, b)) {
+            visuals.selection.bg_fill
+        } else {
+            visuals.widgets.inactive.fg_stroke.color
+        };
+        painter.line_segment([pos_a, pos_b], Stroke::new(4.0, color));
+        if let Some(label) = editor.graph.edge_label(&a, &b) {
+            painter.text(
+                pos_a + (pos_b - pos_a) / 2.,
+                Align2::CENTER_BOTTOM,
+                label,
+                FontId::proportional(12.0),
+                visuals.weak_text_color(),
+            );
+        }
+    }
+
+    // Nodes
+    for node in editor.graph.nodes() {
+        let center = graph_to_screen(origin, node.coord);
+        let selected = editor.selection == Some(TrailSelection::Node(node.id))
+            || editor.connect_from == Some(node.id);
+        let fill = match &node.kind {
+            NodeKind::Empty => visuals.widgets.inactive.bg_fill,
+            NodeKind::MapFilename { map } if editor.available_maps.contains(map) => {
+                visuals.hyperlink_color
+            },
+            NodeKind::MapFilename { .. } => visuals.warn_fg_color,
+        };
+        let stroke = Stroke::new(
+            2.0,
+            if selected {
+                visuals.selection.bg_fill
+            } else {
+                visuals.widgets.inactive.fg_stroke.color
+            },
+        );
+        match &node.kind {
+            NodeKind::Empty => {
+                painter.circle(center, TRAIL_EDITOR_CELL_SIZE / 6., fill, stroke);
+            },
+            NodeKind::MapFilename { .. } => {
+                let node_rect =
+                    egui::Rect::from_center_size(center, Vec2::splat(TRAIL_EDITOR_CELL_SIZE / 2.));
+                painter.rect_filled(node_rect, 4.0, fill);
+                painter.rect_stroke(node_rect, 4.0, stroke, StrokeKind::Outside);
+            },
+        }
+        if node.coord == (0, 0) {
+            painter.circle_stroke(
+                center,
+                TRAIL_EDITOR_CELL_SIZE / 2.5,
+                Stroke::new(1.0, visuals.strong_text_color()),
+            );
+        }
+        painter.text(
+            center + Vec2::new(0., TRAIL_EDITOR_CELL_SIZE / 4.),
+            Align2::CENTER_TOP,
+            node.id.to_string(),
+            FontId::monospace(10.0),
+            visuals.weak_text_color(),
+        );
+    }
+}
+
+/// Show the properties of the campaign trail and of the selected node or edge
+fn trail_properties(ui: &mut egui::Ui, editor: &mut CampaignTrailEditor) {
+    ui.label(t!("campaign_trail_editor.file_name"));
+    ui.add_enabled(
+        editor.path.is_none(),
+        egui::TextEdit::singleline(&mut editor.name),
+    )
+    .on_hover_text(t!("campaign_trail_editor.file_name_tooltip"));
+    ui.label(t!("campaign_trail_editor.trail_title"));
+    let mut title = editor.graph.title().to_string();
+    if ui.text_edit_singleline(&mut title).changed() {
+        editor.graph.set_title(&title);
+        editor.dirty = true;
+    }
+    ui.label(t!("campaign_trail_editor.trail_description"));
+    let mut description = editor.graph.description().to_string();
+    if ui.text_edit_multiline(&mut description).changed() {
+        editor.graph.set_description(&description);
+        editor.dirty = true;
+    }
+    ui.separator();
+    match editor.selection {
+        Some(TrailSelection::Node(id)) => {
+            let Some(node) = editor.graph.get_node(&id) else {
+                editor.selection = None;
+                return;
+            };
+            ui.label(t!(
+                "campaign_trail_editor.node_fmt",
+                id = id.to_string().as_str(),
+                x = node.coord.0.to_string().as_str(),
+                y = node.coord.1.to_string().as_str()
+            ));
+            let current = match &node.kind {
+                NodeKind::Empty => None,
+                NodeKind::MapFilename { map } => Some(map.clone()),
+            };
+            let mut selected = current.clone();
+            egui::ComboBox::from_id_salt("trail_editor_node_map")
+                .width(ui.available_width())
+                .selected_text(
+                    selected
+                        .clone()
+                        .unwrap_or_else(|| t!("campaign_trail_editor.empty_node").to_string()),
+                )
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(
+                        &mut selected,
+                        None,
+                        t!("campaign_trail_editor.empty_node"),
+                    );
+                    for map in &editor.available_maps {
+                        ui.selectable_value(&mut selected, Some(map.clone()), map);
+                    }
+                });
+            if selected != current {
+                editor.graph.set_node_kind(
+                    &id,
+                    match selected {
+                        None => NodeKind::Empty,
+                        Some(map) => NodeKind::MapFilename { map },
+                    },
+                );
+                editor.dirty = true;
+            }
+            if ui.button(t!("campaign_trail_editor.delete")).clicked() {
+                editor.graph.remove_node(&id);
+                editor.selection = None;
+                editor.dirty = true;
+            }
+        },
+        Some(TrailSelection::Edge(a, b)) => {
+            if !editor.graph.is_connected(&a, &b) {
+                editor.selection = None;
+                return;
+            }
+            ui.label(t!(
+                "campaign_trail_editor.edge_fmt",
+                a = a.to_string().as_str(),
+                b = b.to_string().as_str()
+            ));
+            ui.label(t!("campaign_trail_editor.edge_label"));
+            let mut label = editor.graph.edge_label(&a, &b).unwrap_or("").to_string();
+            if ui.text_edit_singleline(&mut label).changed() {
+                editor.graph.set_edge_label(&a, &b, &label);
+                editor.dirty = true;
+            }
+            if ui.button(t!("campaign_trail_editor.delete")).clicked() {
+                editor.graph.disconnect(&a, &b);
+                editor.selection = None;
+                editor.dirty = true;
+            }
+        },
+        None => {
+            ui.label(t!("campaign_trail_editor.no_selection"));
+        },
+    }
+}
+
+/// Save the edited campaign trail and replace the loaded campaign trail with the saved one
+fn save_trail(
+    editor: &mut CampaignTrailEditor,
+    directories: &GameDirectories,
+    trails: &Query<(Entity, &CampaignTrail)>,
+    commands: &mut Commands,
+) -> Result<String, String> {
+    let path = editor
+        .save_path(directories)
+        .ok_or_else(|| t!("campaign_trail_editor.invalid_file_name").to_string())?;
+    let name = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default()
+        .to_string();
+    if trails
+        .iter()
+        .any(|(_, trail)| trail.name == name && trail.path.as_ref() != Some(&path))
+    {
+        return Err(t!(
+            "campaign_trail_editor.name_collision_fmt",
+            name = name.as_str()
+        )
+        .to_string());
+    }
+    if let Some(folder) = path.parent() {
+        create_dir_all(folder).map_err(|e| e.to_string())?;
+    }
+    let file = File::create(&path).map_err(|e| e.to_string())?;
+    editor
+        .graph
+        .serialize(&mut BufWriter::new(file))
+        .map_err(|e| e.to_string())?;
+    info!(
+        "Saved campaign trail to {}",
+        path.to_str().unwrap_or("<invalid>")
+    );
+    editor.path = Some(path.clone());
+    editor.name = name;
+    editor.dirty = false;
+    // Reload the saved trail, such that the changes are visible in the campaign selection screen
+    for (entity, _) in trails
+        .iter()
+        .filter(|(_, trail)| trail.path.as_ref() == Some(&path))
+    {
+        commands.entity(entity).despawn();
+    }
+    if let Some(trail) = load_user_campaign_trail(&path) {
+        commands.spawn(trail);
+    }
+    Ok(t!(
+        "campaign_trail_editor.saved_fmt",
+        path = path.to_str().unwrap_or("<invalid>")
+    )
+    .to_string())
+}
+
+fn campaign_trail_editor_ui(
+    mut egui_ctx: EguiContexts,
+    mut editor: ResMut<CampaignTrailEditor>,
+    mut state: ResMut<NextState<AppState>>,
+    egui_textures: Res<EguiButtonTextures>,
+    directories: Res<GameDirectoriesWrapper>,
+    trails: Query<(Entity, &CampaignTrail)>,
+    mut commands: Commands,
+) {
+    let editor = editor.as_mut();
+    let validation = editor.graph.validate();
+    let ctx = egui_ctx.ctx_mut().unwrap();
+    egui::TopBottomPanel::top("trail_editor_toolbar").show(ctx, |ui| {
+        ui.horizontal(|ui| {
+            ui.set_height(MAPEDITOR_BUTTON_SIZE);
+            let back_button = image_button(
+                ui,
+                &egui_textures,
+                &UITiles::BACKBUTTON,
+                "campaign_trail_editor.exit_tooltip",
+            );
+            if back_button.clicked() {
+                state.set(AppState::CampaignSelectionScreen);
+            }
+            ui.add_enabled_ui(validation.is_ok(), |ui| {
+                let save_button = image_button(
+                    ui,
+                    &egui_textures,
+                    &UITiles::SAVEBUTTON,
+                    "campaign_trail_editor.save_tooltip",
+                )
+                .on_disabled_hover_text(t!("campaign_trail_editor.invalid_tooltip"));
+                if save_button.clicked() {
+                    editor.status = Some(save_trail(
+                        editor,
+                        &directories.game_directories,
+                        &trails,
+                        &mut commands,
+                    ));
+                }
+            });
+            ui.separator();
+            for tool in TrailEditorTool::ALL {
+                if ui
+                    .selectable_value(
+                        &mut editor.tool,
+                        tool,
+                        t!(format!("campaign_trail_editor.tools.{}", tool.str_id())),
+                    )
+                    .on_hover_text(t!(format!(
+                        "campaign_trail_editor.tools.{}_tooltip",
+                        tool.str_id()
+                    )))
+                    .clicked()
+                {
+                    editor.connect_from = None;
+                    editor.dragging = None;
+                }
+            }
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let title = if editor.dirty {
+                    format!("{}*", t!("campaign_trail_editor.title"))
+                } else {
+                    t!("campaign_trail_editor.title").to_string()
+                };
+                ui.label(
+                    egui::RichText::new(title)
+                        .text_style(egui::TextStyle::Heading)
+                        .color(ui.visuals().weak_text_color()),
+                );
+            });
+        });
+    });
+    egui::TopBottomPanel::bottom("trail_editor_validation").show(ctx, |ui| {
+        ui.add_space(UIMARGIN);
+        match &validation {
+            Ok(()) => {
+                ui.label(t!("campaign_trail_editor.valid"));
+            },
+            Err(e) => {
+                ui.colored_label(ui.visuals().error_fg_color, e.to_string());
+            },
+        }
+        match &editor.status {
+            Some(Ok(message)) => {
+                ui.label(message);
+            },
+            Some(Err(message)) => {
+                ui.colored_label(
+                    ui.visuals().error_fg_color,
+                    t!(
+                        "campaign_trail_editor.save_error_fmt",
+                        error = message.as_str()
+                    ),
+                );
+            },
+            None => {},
+        }
+        ui.add_space(UIMARGIN);
+    });
+    egui::SidePanel::right("trail_editor_properties")
+        .resizable(false)
+        .exact_width(TRAIL_EDITOR_PANEL_WIDTH)
+        .show(ctx, |ui| {
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                trail_properties(ui, editor);
+            });
+        });
+    egui::CentralPanel::default()
+        .frame(egui::Frame::NONE.fill(Color32::TRANSPARENT))
+        .show(ctx, |ui| {
+            trail_grid(ui, editor);
+        });
+}
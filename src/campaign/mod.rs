@@ -1,84 +1,189 @@
-use crate::campaign::campaign_maps::CampaignMapsLoadingPlugin;
-use crate::campaign::campaign_trail::{CampaignTrail, MainCampaignTrail, SelectedCampaignTrail};
+use crate::campaign::campaign_maps::{CampaignMaps, CampaignMapsLoadingPlugin};
+use crate::campaign::campaign_trail::{CampaignTrail, MainCampaignTrail};
 use crate::campaign::campaign_trail_asset_loader::CampaignTrailAsset;
-use crate::{AllAssetHandles, AppState};
+use crate::{AllAssetHandles, AppState, GameDirectoriesWrapper};
 use bevy::app::{App, Plugin};
-use bevy::asset::{AssetServer, LoadState};
+use bevy::asset::{AssetServer, LoadedFolder, RecursiveDependencyLoadState};
 use bevy::prelude::*;
+use libexodus::campaign::graph::{Graph, NodeKind};
+use libexodus::exodus_serializable::ExodusSerializable;
+use libexodus::world::GameWorld;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
 
 pub mod campaign_map_asset_loader;
 pub mod campaign_maps;
+pub mod campaign_selection_screen;
 pub mod campaign_trail;
 pub mod campaign_trail_asset_loader;
+pub mod campaign_trail_editor;
 
-pub struct MainCampaignLoader;
-/// Plugin that loads the main campaign trail from the campaign.tgf file
-impl Plugin for MainCampaignLoader {
+/// The name of the main campaign trail, used to identify the progress files of the players
+const MAIN_CAMPAIGN_TRAIL_NAME: &str = "campaign";
+/// The asset folder that contains all bundled campaign trails
+const BUNDLED_CAMPAIGN_TRAILS_FOLDER: &str = "campaigns";
+
+pub struct CampaignTrailsLoader;
+/// Plugin that loads all bundled campaign trails from the campaigns asset folder and all
+/// campaign trails that are installed in the user campaigns folder
+impl Plugin for CampaignTrailsLoader {
     fn build(&self, app: &mut App) {
         app.add_plugins(CampaignMapsLoadingPlugin)
-            .add_systems(Startup, load_main_campaign);
+            .add_systems(Startup, load_bundled_campaign_trails);
         app.add_systems(
             Update,
-            insert_main_campaign.run_if(in_state(AppState::Process)),
+            insert_campaign_trails.run_if(in_state(AppState::Process)),
         );
     }
 }
-#[derive(Component)]
-struct MainCampaign {
-    handle: Handle<CampaignTrailAsset>,
+#[derive(Resource)]
+struct BundledCampaignTrails {
+    handle: Handle<LoadedFolder>,
 }
 
-fn load_main_campaign(
+fn load_bundled_campaign_trails(
     asset_server: Res<AssetServer>,
     mut commands: Commands,
     mut all_assets: ResMut<AllAssetHandles>,
 ) {
-    debug!("Loading Main Campaign Trail Asset from {}", "campaign.tgf");
-    let handle = asset_server.load("campaign.tgf");
-    all_assets.file_handles.push(handle.clone().untyped());
-    commands
-        .spawn(MainCampaign { handle })
-        .insert(MainCampaignTrail);
+    debug!(
+        "Loading Campaign Trail Assets from {}",
+        BUNDLED_CAMPAIGN_TRAILS_FOLDER
+    );
+    let handle = asset_server.load_folder(BUNDLED_CAMPAIGN_TRAILS_FOLDER);
+    all_assets.handles.push(handle.clone());
+    commands.insert_resource(BundledCampaignTrails { handle });
 }
 
-/// Removes all loaded Graph Assets that are marked with the MainCampaignTrail marker struct
-/// and loads them into CampaignTrails
-fn insert_main_campaign(
+/// Removes all loaded Graph Assets, loads them into CampaignTrails together with their maps and
+/// loads all campaign trails from the user campaigns folder.
+/// The main campaign trail is marked with the MainCampaignTrail marker struct.
+fn insert_campaign_trails(
     mut state: ResMut<NextState<AppState>>,
     mut assets: ResMut<Assets<CampaignTrailAsset>>,
-    main_asset: Query<(Entity, &MainCampaign), With<MainCampaignTrail>>,
+    folder_assets: Res<Assets<LoadedFolder>>,
+    bundled: Option<Res<BundledCampaignTrails>>,
     asset_server: Res<AssetServer>,
+    campaign_maps: Res<CampaignMaps>,
+    directories: Res<GameDirectoriesWrapper>,
     mut commands: Commands,
 ) {
-    for (entity, asset) in &main_asset {
-        match asset_server.get_load_state(&asset.handle) {
-            Some(LoadState::Loaded) => {
-                let graph = assets.remove(&asset.handle).expect(
-                    "The Campaign Trail was removed from the asset manager before loading!",
-                );
-                commands.entity(entity).despawn();
-                commands
-                    .spawn(CampaignTrail {
-                        trail: graph.0,
-                        ..default()
+    let Some(bundled) = bundled else {
+        return;
+    };
+    match asset_server.get_recursive_dependency_load_state(bundled.handle.id()) {
+        Some(RecursiveDependencyLoadState::Loaded) => {
+            let mut names: HashSet<String> = HashSet::new();
+            for handle in &folder_assets
+                .get(&bundled.handle)
+                .expect("The Campaign Trails folder was removed from the asset manager!")
+                .handles
+            {
+                let name = asset_server
+                    .get_path(handle.id())
+                    .and_then(|path| {
+                        path.path()
+                            .file_stem()
+                            .and_then(|stem| stem.to_str())
+                            .map(|stem| stem.to_string())
+                    })
+                    .expect("Could not get the file name of a Campaign Trail!");
+                let graph = assets
+                    .remove(&handle.clone().typed::<CampaignTrailAsset>())
+                    .expect("The Campaign Trail was removed from the asset manager before loading!")
+                    .0;
+                let maps = graph
+                    .nodes()
+                    .filter_map(|node| match &node.kind {
+                        NodeKind::Empty => None,
+                        NodeKind::MapFilename { map } => campaign_maps
+                            .maps
+                            .get(map)
+                            .map(|world| (map.clone(), world.clone())),
                     })
-                    .insert(MainCampaignTrail)
-                    .insert(SelectedCampaignTrail);
-                // TODO Change this line as soon as multiple campaign trails are supported:.insert(SelectedCampaignTrail);
-                // TODO Change this to support multiple campaign trails:
-                state.set(AppState::MainMenu);
-            },
-            Some(LoadState::Failed(error)) => panic!(
-                "Failed to load the Campaign Trail from {}: {}",
-                asset_server
-                    .get_path(&asset.handle)
-                    .expect("Could not get path from handle!")
-                    .path()
-                    .to_str()
-                    .unwrap(),
-                error
-            ),
-            _ => {},
+                    .collect();
+                debug!("Successfully loaded bundled campaign trail {}", &name);
+                let mut entity = commands.spawn(CampaignTrail {
+                    trail: graph,
+                    name: name.clone(),
+                    maps,
+                    bundled: true,
+                    ..default()
+                });
+                if name == MAIN_CAMPAIGN_TRAIL_NAME {
+                    entity.insert(MainCampaignTrail);
+                }
+                names.insert(name);
+            }
+            for path in directories.game_directories.iter_campaign_trails() {
+                let Some(trail) = load_user_campaign_trail(&path) else {
+                    continue;
+                };
+                if !names.insert(trail.name.clone()) {
+                    warn!(
+                        "Skipped the campaign trail at {}, because there is already a campaign trail named {}",
+                        path.to_str().unwrap_or("<Invalid Path>"),
+                        trail.name
+                    );
+                    continue;
+                }
+                debug!("Successfully loaded user campaign trail {}", &trail.name);
+                commands.spawn(trail);
+            }
+            commands.remove_resource::<BundledCampaignTrails>();
+            state.set(AppState::MainMenu);
+        },
+        Some(RecursiveDependencyLoadState::Failed(error)) => {
+            panic!("Failed to load the Campaign Trails: {}", error)
+        },
+        _ => {},
+    }
+}
+
+/// Load the campaign trail at the given path from the user campaigns folder.
+/// All map files of the campaign trail are resolved relative to the folder of the trail file.
+/// Returns None, if the campaign trail could not be loaded.
+pub(crate) fn load_user_campaign_trail(path: &Path) -> Option<CampaignTrail> {
+    let name = path.file_stem()?.to_str()?.to_string();
+    let mut graph = Graph::default();
+    let parsed = File::open(path)
+        .map_err(|e| e.to_string())
+        .and_then(|file| {
+            graph
+                .parse(&mut BufReader::new(file))
+                .map_err(|e| e.to_string())
+        });
+    if let Err(e) = parsed {
+        error!(
+            "Could not load campaign trail at {}! Error: {}",
+            path.to_str().unwrap_or("<Invalid Path>"),
+            e
+        );
+        return None;
+    }
+    let folder = path.parent()?;
+    let mut maps: HashMap<String, GameWorld> = HashMap::new();
+    for node in graph.nodes() {
+        if let NodeKind::MapFilename { map } = &node.kind {
+            match GameWorld::load_from_file(&folder.join(map)) {
+                Ok(world) => {
+                    maps.insert(map.clone(), world);
+                },
+                Err(e) => error!(
+                    "Could not load map {} of campaign trail {}! Error: {}",
+                    map, name, e
+                ),
+            }
         }
     }
+    Some(CampaignTrail {
+        trail: graph,
+        name,
+        maps,
+        bundled: false,
+        path: Some(path.to_path_buf()),
+        ..default()
+    })
 }
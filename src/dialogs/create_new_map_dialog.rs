@@ -1,5 +1,6 @@
 use crate::dialogs::delete_map_dialog::DeleteMapDialog;
 use crate::dialogs::edit_message_dialog::EditMessageDialog;
+use crate::dialogs::resize_map_dialog::ResizeMapDialog;
 use crate::dialogs::save_file_dialog::SaveFileDialog;
 use crate::dialogs::unsaved_changes_dialog::UnsavedChangesDialog;
 use crate::dialogs::{DialogResource, UIDialog};
@@ -111,6 +112,7 @@ pub fn bevy_job_handler(world: &mut World) {
                         world.insert_resource(MapWrapper {
                             world: map.clone(),
                             previous_best: None,
+                            previous_best_verified: false,
                         });
                         world.send_event(UiSizeChangedEvent);
                         // Update the Dialog
@@ -327,6 +329,10 @@ impl UIDialog for CreateNewMapDialog {
     fn as_delete_map_dialog(&mut self) -> Option<&mut DeleteMapDialog> {
         None
     }
+
+    fn as_resize_map_dialog(&mut self) -> Option<&mut ResizeMapDialog> {
+        None
+    }
 }
 
 fn ui_for_generation_kind(kind: &mut WorldGenerationKind, ui: &mut Ui) {
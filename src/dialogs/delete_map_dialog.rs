@@ -1,6 +1,7 @@
 use crate::dialogs::UIDialog;
 use crate::dialogs::create_new_map_dialog::CreateNewMapDialog;
 use crate::dialogs::edit_message_dialog::EditMessageDialog;
+use crate::dialogs::resize_map_dialog::ResizeMapDialog;
 use crate::dialogs::save_file_dialog::SaveFileDialog;
 use crate::dialogs::unsaved_changes_dialog::UnsavedChangesDialog;
 use crate::game::scoreboard::egui_highscore_label;
@@ -56,7 +57,12 @@ impl UIDialog for DeleteMapDialog {
             );
             ui.separator();
             crate::ui::mapselectionscreen::labels_name_author(ui, &self.map.world);
-            egui_highscore_label(ui, &self.map.previous_best, egui_textures);
+            egui_highscore_label(
+                ui,
+                &self.map.previous_best,
+                self.map.previous_best_verified,
+                egui_textures,
+            );
             ui.separator();
             ui.label(
                 RichText::new(t!("map_selection_screen.dialog.delete_map_dialog_hint"))
@@ -104,4 +110,8 @@ impl UIDialog for DeleteMapDialog {
     fn as_delete_map_dialog(&mut self) -> Option<&mut DeleteMapDialog> {
         Some(self)
     }
+
+    fn as_resize_map_dialog(&mut self) -> Option<&mut ResizeMapDialog> {
+        None
+    }
 }
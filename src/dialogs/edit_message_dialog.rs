@@ -1,6 +1,7 @@
 use crate::dialogs::UIDialog;
 use crate::dialogs::create_new_map_dialog::CreateNewMapDialog;
 use crate::dialogs::delete_map_dialog::DeleteMapDialog;
+use crate::dialogs::resize_map_dialog::ResizeMapDialog;
 use crate::dialogs::save_file_dialog::SaveFileDialog;
 use crate::dialogs::unsaved_changes_dialog::UnsavedChangesDialog;
 use crate::textures::egui_textures::EguiButtonTextures;
@@ -89,4 +90,8 @@ impl UIDialog for EditMessageDialog {
     fn as_delete_map_dialog(&mut self) -> Option<&mut DeleteMapDialog> {
         None
     }
+
+    fn as_resize_map_dialog(&mut self) -> Option<&mut ResizeMapDialog> {
+        None
+    }
 }
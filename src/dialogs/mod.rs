@@ -1,6 +1,7 @@
 use crate::dialogs::create_new_map_dialog::CreateNewMapDialog;
 use crate::dialogs::delete_map_dialog::DeleteMapDialog;
 use crate::dialogs::edit_message_dialog::EditMessageDialog;
+use crate::dialogs::resize_map_dialog::ResizeMapDialog;
 use crate::dialogs::save_file_dialog::SaveFileDialog;
 use crate::dialogs::unsaved_changes_dialog::UnsavedChangesDialog;
 use crate::textures::egui_textures::EguiButtonTextures;
@@ -11,6 +12,7 @@ use libexodus::directories::GameDirectories;
 pub mod create_new_map_dialog;
 pub mod delete_map_dialog;
 pub mod edit_message_dialog;
+pub mod resize_map_dialog;
 pub mod save_file_dialog;
 pub mod unsaved_changes_dialog;
 
@@ -30,6 +32,7 @@ pub trait UIDialog {
     fn as_edit_message_dialog(&mut self) -> Option<&mut EditMessageDialog>;
     fn as_create_new_map_dialog(&mut self) -> Option<&mut CreateNewMapDialog>;
     fn as_delete_map_dialog(&mut self) -> Option<&mut DeleteMapDialog>;
+    fn as_resize_map_dialog(&mut self) -> Option<&mut ResizeMapDialog>;
 }
 #[derive(Resource)]
 pub struct DialogResource {
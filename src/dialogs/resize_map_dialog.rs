@@ -0,0 +1,158 @@
+use crate::dialogs::UIDialog;
+use crate::dialogs::create_new_map_dialog::CreateNewMapDialog;
+use crate::dialogs::delete_map_dialog::DeleteMapDialog;
+use crate::dialogs::edit_message_dialog::EditMessageDialog;
+use crate::dialogs::save_file_dialog::SaveFileDialog;
+use crate::dialogs::unsaved_changes_dialog::UnsavedChangesDialog;
+use crate::textures::egui_textures::EguiButtonTextures;
+use bevy::prelude::Commands;
+use bevy_egui::egui;
+use bevy_egui::egui::Ui;
+use libexodus::directories::GameDirectories;
+use libexodus::world::io::{MAX_MAP_HEIGHT, MAX_MAP_WIDTH};
+use libexodus::world::resize::ResizeAnchor;
+
+#[derive(Eq, PartialEq)]
+enum ResizeMapDialogState {
+    Choosing,
+    Done,
+    Cancelled,
+}
+
+pub struct ResizeMapDialog {
+    width: usize,
+    height: usize,
+    anchor: ResizeAnchor,
+    state: ResizeMapDialogState,
+}
+
+/// The anchors in the order in which they are shown in the dialog, row by row from the top
+const ANCHOR_GRID: [[(ResizeAnchor, &str); 3]; 3] = [
+    [
+        (ResizeAnchor::TopLeft, "↖"),
+        (ResizeAnchor::Top, "↑"),
+        (ResizeAnchor::TopRight, "↗"),
+    ],
+    [
+        (ResizeAnchor::Left, "←"),
+        (ResizeAnchor::Center, "•"),
+        (ResizeAnchor::Right, "→"),
+    ],
+    [
+        (ResizeAnchor::BottomLeft, "↙"),
+        (ResizeAnchor::Bottom, "↓"),
+        (ResizeAnchor::BottomRight, "↘"),
+    ],
+];
+
+impl ResizeMapDialog {
+    /// Instantiate a new ResizeMapDialog for a map with the given size
+    pub fn new(width: usize, height: usize) -> Self {
+        ResizeMapDialog {
+            width,
+            height,
+            anchor: ResizeAnchor::default(),
+            state: ResizeMapDialogState::Choosing,
+        }
+    }
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+    pub fn get_anchor(&self) -> ResizeAnchor {
+        self.anchor
+    }
+}
+
+impl UIDialog for ResizeMapDialog {
+    fn dialog_title(&self) -> String {
+        t!("map_editor.dialog.resize_dialog_title").to_string()
+    }
+
+    fn draw(
+        &mut self,
+        ui: &mut Ui,
+        _egui_textures: &EguiButtonTextures,
+        _directories: &GameDirectories,
+        _commands: &mut Commands,
+    ) {
+        ui.vertical_centered(|ui| {
+            egui::Grid::new("resize_map_size_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label(t!("map_editor.dialog.resize_dialog_width"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.width)
+                            .range(1..=MAX_MAP_WIDTH)
+                            .speed(0.1),
+                    );
+                    ui.end_row();
+                    ui.label(t!("map_editor.dialog.resize_dialog_height"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.height)
+                            .range(1..=MAX_MAP_HEIGHT)
+                            .speed(0.1),
+                    );
+                    ui.end_row();
+                });
+            ui.separator();
+            ui.label(t!("map_editor.dialog.resize_dialog_anchor"))
+                .on_hover_text(t!("map_editor.dialog.resize_dialog_anchor_tooltip"));
+            egui::Grid::new("resize_map_anchor_grid").show(ui, |ui| {
+                for row in ANCHOR_GRID {
+                    for (anchor, symbol) in row {
+                        ui.selectable_value(&mut self.anchor, anchor, symbol);
+                    }
+                    ui.end_row();
+                }
+            });
+            ui.separator();
+            ui.scope(|ui| {
+                ui.horizontal_top(|ui| {
+                    let ok_btn = ui.button(t!("common_buttons.ok"));
+                    let cancel_btn = ui.button(t!("common_buttons.cancel"));
+                    if ok_btn.clicked() {
+                        self.state = ResizeMapDialogState::Done;
+                    }
+                    if cancel_btn.clicked() {
+                        self.state = ResizeMapDialogState::Cancelled;
+                    }
+                })
+            });
+        });
+    }
+
+    fn is_done(&self) -> bool {
+        self.state == ResizeMapDialogState::Done
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.state == ResizeMapDialogState::Cancelled
+    }
+
+    fn as_save_file_dialog(&mut self) -> Option<&mut SaveFileDialog> {
+        None
+    }
+
+    fn as_unsaved_changes_dialog(&mut self) -> Option<&mut UnsavedChangesDialog> {
+        None
+    }
+
+    fn as_edit_message_dialog(&mut self) -> Option<&mut EditMessageDialog> {
+        None
+    }
+
+    fn as_create_new_map_dialog(&mut self) -> Option<&mut CreateNewMapDialog> {
+        None
+    }
+
+    fn as_delete_map_dialog(&mut self) -> Option<&mut DeleteMapDialog> {
+        None
+    }
+
+    fn as_resize_map_dialog(&mut self) -> Option<&mut ResizeMapDialog> {
+        Some(self)
+    }
+}
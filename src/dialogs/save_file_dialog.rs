@@ -2,6 +2,7 @@ use crate::dialogs::UIDialog;
 use crate::dialogs::create_new_map_dialog::CreateNewMapDialog;
 use crate::dialogs::delete_map_dialog::DeleteMapDialog;
 use crate::dialogs::edit_message_dialog::EditMessageDialog;
+use crate::dialogs::resize_map_dialog::ResizeMapDialog;
 use crate::dialogs::unsaved_changes_dialog::UnsavedChangesDialog;
 use crate::egui_extensions::selectable_value_with_image::selectable_value_with_image;
 use crate::game::constants::DROPDOWN_THUMBNAIL_SIZE;
@@ -9,12 +10,16 @@ use crate::textures::egui_textures::EguiButtonTextures;
 use crate::ui::UIPANELCBWIDTH;
 use bevy::log::{debug, warn};
 use bevy::prelude::Commands;
+use bevy::tasks::Task;
+use bevy::tasks::futures::check_ready;
 use bevy_egui::egui;
 use bevy_egui::egui::load::SizedTexture;
 use bevy_egui::egui::{RichText, Ui, UiBuilder};
 use libexodus::directories::{GameDirectories, InvalidMapNameError};
-use libexodus::tiles::UITiles;
+use libexodus::tiles::{KeyColor, UITiles};
 use libexodus::tilesets::Tileset;
+use libexodus::world::metadata::{Difficulty, MapMetadata, format_timestamp};
+use libexodus::world::validation::{ValidationIssue, ValidationReport, ValidationSeverity};
 use std::path::{Path, PathBuf};
 use strum::IntoEnumIterator;
 
@@ -46,10 +51,32 @@ pub struct SaveFileDialog {
     force_texturepack: bool,
     /// The texture pack the player is forced to use
     texturepack: Tileset,
+    /// The metadata of the map. The timestamps are shown, but cannot be edited
+    metadata: MapMetadata,
+    /// Whether or not the map author declares a par move count
+    declare_par_moves: bool,
+    /// The par move count declared by the map author
+    par_moves: usize,
+    /// The comma-separated tags of the map
+    tags: String,
+    /// The number of lives the player has when playing the map
+    lives: usize,
+    /// The issues that have been found in the map when the dialog was opened.
+    /// Until the validation task has finished, this only contains the structural issues.
+    validation: ValidationReport,
+    /// The full validation of the map, including the check if the exit can be reached, which
+    /// runs in the background until it has finished
+    validation_task: Option<Task<ValidationReport>>,
+    /// Whether or not to save the map into the campaigns folder instead of the maps folder
+    save_to_campaign: bool,
+    /// Whether or not to refuse saving maps with errors into the campaign folder
+    block_invalid_campaign_maps: bool,
 }
 
 impl SaveFileDialog {
-    /// Instantiate a new SaveFileDialog from the given world
+    /// Instantiate a new SaveFileDialog from the given world.
+    /// The given structural validation report is shown until the given validation task has
+    /// finished.
     pub fn new(
         filename: Option<&Path>,
         mapname: &str,
@@ -57,10 +84,21 @@ impl SaveFileDialog {
         uuid: &str,
         directories: &GameDirectories,
         forced_textures: Option<Tileset>,
+        metadata: &MapMetadata,
+        lives: usize,
+        validation: ValidationReport,
+        validation_task: Task<ValidationReport>,
     ) -> Self {
+        let save_to_campaign = filename.is_some_and(|p| directories.is_campaign_map(p));
         SaveFileDialog {
             file_name: filename
-                .map(|p| directories.relative_map_dir_from_path(p))
+                .map(|p| {
+                    if save_to_campaign {
+                        directories.relative_campaign_map_dir_from_path(p)
+                    } else {
+                        directories.relative_map_dir_from_path(p)
+                    }
+                })
                 .unwrap_or(Err(InvalidMapNameError::EmptyName))
                 .unwrap_or_else(|e| {
                     warn!("Could not resolve map path: {}", e);
@@ -74,6 +112,15 @@ impl SaveFileDialog {
             error_text: "".to_string(),
             force_texturepack: forced_textures.is_some(),
             texturepack: forced_textures.unwrap_or_default(),
+            metadata: metadata.clone(),
+            declare_par_moves: metadata.par_moves.is_some(),
+            par_moves: metadata.par_moves.unwrap_or_default(),
+            tags: metadata.tags.join(", "),
+            lives,
+            validation,
+            validation_task: Some(validation_task),
+            save_to_campaign,
+            block_invalid_campaign_maps: true,
         }
     }
     /// Resolve the file name and return the full path
@@ -86,6 +133,17 @@ impl SaveFileDialog {
     pub fn get_map_author(&self) -> &str {
         self.map_author.as_str()
     }
+    /// Get the map metadata with all changes the user made
+    pub fn get_metadata(&self) -> MapMetadata {
+        let mut ret = self.metadata.clone();
+        ret.par_moves = self.declare_par_moves.then_some(self.par_moves);
+        ret.set_tags(self.tags.split(','));
+        ret
+    }
+    /// Get the number of lives the player has when playing the map
+    pub fn get_lives(&self) -> usize {
+        self.lives
+    }
     pub fn get_forced_tileset(&self) -> Option<Tileset> {
         if self.force_texturepack {
             Some(self.texturepack)
@@ -95,6 +153,74 @@ impl SaveFileDialog {
     }
 }
 
+/// Get the translated description of the given validation issue
+pub fn validation_issue_text(issue: &ValidationIssue) -> String {
+    let key = format!("validation.{}", issue.str_id());
+    match issue {
+        ValidationIssue::NoExit | ValidationIssue::ExitUnreachable => t!(key).to_string(),
+        ValidationIssue::ReachabilityUnknown { explored_states } => {
+            t!(key, states = explored_states.to_string().as_str()).to_string()
+        },
+        ValidationIssue::TeleportWithoutExit { teleport_id } => t!(
+            key,
+            teleport = (teleport_id.const_to_u8() + 1).to_string().as_str()
+        )
+        .to_string(),
+        ValidationIssue::PlayerSpawnBlocked { position }
+        | ValidationIssue::SwitchWithoutLinks { position } => t!(
+            key,
+            x = position.0.to_string().as_str(),
+            y = position.1.to_string().as_str()
+        )
+        .to_string(),
+        ValidationIssue::DoorsWithoutKeys { doors, color } => t!(
+            key,
+            doors = doors.to_string().as_str(),
+            color = key_color_text(color).as_str()
+        )
+        .to_string(),
+        ValidationIssue::DoorsWithPurchasableKeys { doors } => {
+            t!(key, doors = doors.to_string().as_str()).to_string()
+        },
+        ValidationIssue::FewerKeysThanDoors { doors, keys, color } => t!(
+            key,
+            doors = doors.to_string().as_str(),
+            keys = keys.to_string().as_str(),
+            color = key_color_text(color).as_str()
+        )
+        .to_string(),
+    }
+}
+
+/// Get the translated name of the given key color, or an empty string for uncolored keys
+fn key_color_text(color: &Option<KeyColor>) -> String {
+    match color {
+        None => String::new(),
+        Some(color) => t!(format!("key_color.{}", color.str_id())).to_string(),
+    }
+}
+
+/// Show all issues of the given validation report as a list, errors first
+pub fn validation_report_labels(ui: &mut Ui, report: &ValidationReport) {
+    for issue in report.issues() {
+        let (prefix, color) = match issue.severity() {
+            ValidationSeverity::Error => (t!("validation.error"), ui.visuals().error_fg_color),
+            ValidationSeverity::Warning => (t!("validation.warning"), ui.visuals().warn_fg_color),
+        };
+        ui.label(
+            RichText::new(format!("{}: {}", prefix, validation_issue_text(issue))).color(color),
+        );
+    }
+}
+
+/// Get the translated name of the given difficulty
+pub fn difficulty_text(difficulty: &Option<Difficulty>) -> String {
+    match difficulty {
+        Some(difficulty) => t!(format!("difficulty.{}", difficulty.str_id())).to_string(),
+        None => t!("difficulty.none").to_string(),
+    }
+}
+
 impl UIDialog for SaveFileDialog {
     fn dialog_title(&self) -> String {
         t!("map_editor.dialog.save_dialog_title").to_string()
@@ -107,6 +233,10 @@ impl UIDialog for SaveFileDialog {
         directories: &GameDirectories,
         _commands: &mut Commands,
     ) {
+        if let Some(report) = self.validation_task.as_mut().and_then(check_ready) {
+            self.validation = report;
+            self.validation_task = None;
+        }
         ui.vertical_centered_justified(|ui| {
             ui.add_enabled_ui(self.state == SaveFileDialogState::Choosing, |ui| {
                 // File Name and Save Button
@@ -123,9 +253,32 @@ impl UIDialog for SaveFileDialog {
                         });
                         let save = ui.button(t!("common_buttons.save"));
                         if save.clicked() {
-                            let map_dir = directories.path_from_userinput(self.file_name.as_str());
+                            let map_dir = if self.save_to_campaign {
+                                directories
+                                    .campaign_map_path_from_userinput(self.file_name.as_str())
+                            } else {
+                                directories.path_from_userinput(self.file_name.as_str())
+                            };
                             debug!("{:?}", map_dir);
                             match map_dir {
+                                Ok(path)
+                                    if self.block_invalid_campaign_maps
+                                        && self.validation_task.is_some()
+                                        && directories.is_campaign_map(&path) =>
+                                {
+                                    self.error_text =
+                                        t!("validation.campaign_map_still_checking").to_string();
+                                    self.state = SaveFileDialogState::Error;
+                                },
+                                Ok(path)
+                                    if self.block_invalid_campaign_maps
+                                        && self.validation.has_errors()
+                                        && directories.is_campaign_map(&path) =>
+                                {
+                                    self.error_text =
+                                        t!("validation.campaign_map_blocked").to_string();
+                                    self.state = SaveFileDialogState::Error;
+                                },
                                 Ok(path) => {
                                     self.file_path = Some(path);
                                     self.state = if self.file_path.as_ref().unwrap().exists() {
@@ -142,6 +295,35 @@ impl UIDialog for SaveFileDialog {
                         }
                     });
                 });
+                ui.scope(|ui| {
+                    ui.set_width(UIPANELCBWIDTH);
+                    ui.checkbox(
+                        &mut self.save_to_campaign,
+                        t!("map_editor.dialog.save_dialog_save_to_campaign"),
+                    )
+                    .on_hover_text(t!("map_editor.dialog.save_dialog_save_to_campaign_tooltip"));
+                });
+                // Validation Results
+                if !self.validation.is_empty() || self.validation_task.is_some() {
+                    ui.separator();
+                    ui.scope(|ui| {
+                        ui.set_width(UIPANELCBWIDTH);
+                        ui.vertical(|ui| validation_report_labels(ui, &self.validation));
+                        if self.validation_task.is_some() {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label(t!("validation.checking_reachability"));
+                            });
+                        }
+                        if self.validation.has_errors() {
+                            ui.checkbox(
+                                &mut self.block_invalid_campaign_maps,
+                                t!("validation.block_campaign_maps"),
+                            )
+                            .on_hover_text(t!("validation.block_campaign_maps_tooltip"));
+                        }
+                    });
+                }
                 // Map Properties
                 ui.separator();
                 ui.scope(|ui| {
@@ -163,6 +345,83 @@ impl UIDialog for SaveFileDialog {
                             .on_hover_text(t!("map_editor.dialog.save_dialog_map_author_tooltip"))
                     });
                 });
+                ui.scope(|ui| {
+                    ui.set_width(UIPANELCBWIDTH);
+                    ui.label(format!(
+                        "{}:",
+                        t!("map_editor.dialog.save_dialog_map_description")
+                    ));
+                    ui.text_edit_multiline(&mut self.metadata.description)
+                        .on_hover_text(t!("map_editor.dialog.save_dialog_map_description_tooltip"));
+                });
+                ui.scope(|ui| {
+                    ui.set_width(UIPANELCBWIDTH);
+                    ui.horizontal(|ui| {
+                        ui.label(format!(
+                            "{}:",
+                            t!("map_editor.dialog.save_dialog_map_difficulty")
+                        ));
+                        egui::ComboBox::from_id_salt("map_difficulty")
+                            .selected_text(difficulty_text(&self.metadata.difficulty))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(
+                                    &mut self.metadata.difficulty,
+                                    None,
+                                    difficulty_text(&None),
+                                );
+                                for difficulty in Difficulty::iter() {
+                                    ui.selectable_value(
+                                        &mut self.metadata.difficulty,
+                                        Some(difficulty),
+                                        difficulty_text(&Some(difficulty)),
+                                    );
+                                }
+                            })
+                            .response
+                            .on_hover_text(t!(
+                                "map_editor.dialog.save_dialog_map_difficulty_tooltip"
+                            ));
+                    });
+                });
+                ui.scope(|ui| {
+                    ui.set_width(UIPANELCBWIDTH);
+                    ui.horizontal(|ui| {
+                        ui.checkbox(
+                            &mut self.declare_par_moves,
+                            format!("{}:", t!("map_editor.dialog.save_dialog_map_par_moves")),
+                        )
+                        .on_hover_text(t!("map_editor.dialog.save_dialog_map_par_moves_tooltip"));
+                        ui.add_enabled(
+                            self.declare_par_moves,
+                            egui::DragValue::new(&mut self.par_moves)
+                                .range(1..=usize::MAX)
+                                .speed(0.1),
+                        );
+                    });
+                });
+                ui.scope(|ui| {
+                    ui.set_width(UIPANELCBWIDTH);
+                    ui.horizontal(|ui| {
+                        ui.label(format!(
+                            "{}:",
+                            t!("map_editor.dialog.save_dialog_map_lives")
+                        ));
+                        ui.add(
+                            egui::DragValue::new(&mut self.lives)
+                                .range(1..=usize::MAX)
+                                .speed(0.1),
+                        )
+                        .on_hover_text(t!("map_editor.dialog.save_dialog_map_lives_tooltip"));
+                    });
+                });
+                ui.scope(|ui| {
+                    ui.set_width(UIPANELCBWIDTH);
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{}:", t!("map_editor.dialog.save_dialog_map_tags")));
+                        ui.text_edit_singleline(&mut self.tags)
+                            .on_hover_text(t!("map_editor.dialog.save_dialog_map_tags_tooltip"))
+                    });
+                });
                 ui.separator();
                 ui.checkbox(
                     &mut self.force_texturepack,
@@ -201,6 +460,26 @@ impl UIDialog for SaveFileDialog {
                         ui.label(self.hash.as_str());
                     });
                 });
+                for (label, timestamp) in [
+                    (
+                        t!("map_editor.dialog.save_dialog_map_created"),
+                        self.metadata.created,
+                    ),
+                    (
+                        t!("map_editor.dialog.save_dialog_map_modified"),
+                        self.metadata.modified,
+                    ),
+                ] {
+                    if let Some(timestamp) = timestamp.and_then(format_timestamp) {
+                        ui.scope(|ui| {
+                            ui.set_width(UIPANELCBWIDTH);
+                            ui.horizontal(|ui| {
+                                ui.label(format!("{}:", label));
+                                ui.label(timestamp);
+                            });
+                        });
+                    }
+                }
             });
             ui.scope(|ui| {
                 ui.horizontal(|ui| {
@@ -281,4 +560,8 @@ impl UIDialog for SaveFileDialog {
     fn as_delete_map_dialog(&mut self) -> Option<&mut DeleteMapDialog> {
         None
     }
+
+    fn as_resize_map_dialog(&mut self) -> Option<&mut ResizeMapDialog> {
+        None
+    }
 }
@@ -2,6 +2,7 @@ use crate::dialogs::UIDialog;
 use crate::dialogs::create_new_map_dialog::CreateNewMapDialog;
 use crate::dialogs::delete_map_dialog::DeleteMapDialog;
 use crate::dialogs::edit_message_dialog::EditMessageDialog;
+use crate::dialogs::resize_map_dialog::ResizeMapDialog;
 use crate::dialogs::save_file_dialog::SaveFileDialog;
 use crate::textures::egui_textures::EguiButtonTextures;
 use bevy::prelude::Commands;
@@ -91,4 +92,8 @@ impl UIDialog for UnsavedChangesDialog {
     fn as_delete_map_dialog(&mut self) -> Option<&mut DeleteMapDialog> {
         None
     }
+
+    fn as_resize_map_dialog(&mut self) -> Option<&mut ResizeMapDialog> {
+        None
+    }
 }
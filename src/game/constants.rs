@@ -2,6 +2,8 @@
 pub const PLAYER_Z: f32 = 10.0;
 /// The World Tiles' Z coordinate
 pub const WORLD_Z: f32 = 5.0;
+/// The Crates' Z coordinate, such that crates are rendered in front of the tiles they cover
+pub const CRATE_Z: f32 = 6.0;
 /// The Render Plane' Z coordinate
 pub const RENDER_PLANE_Z: f32 = 99.0;
 /// The Map Editor Preview Tiles' Z coordinate
@@ -12,8 +14,6 @@ pub const MAPEDITOR_PREVIEWTILE_ALPHA: f32 = 0.40;
 pub const MAPEDITOR_PREVIEWTILE_AIR_ATLAS_INDEX: usize = 43;
 /// The speed of the player movement
 pub const PLAYER_SPEED_: f32 = 4.0;
-/// The pickup distance for coins
-pub const COLLECTIBLE_PICKUP_DISTANCE: f32 = 0.1;
 /// The speed a dead player ascends to heaven at
 pub const DEAD_PLAYER_ASCEND_SPEED: f32 = 3.0;
 /// The speed a dead player zooms while ascending
@@ -44,3 +44,11 @@ pub const KEY_OPEN_ANIMATION_ASCEND_SPEED: f32 = 7.0;
 pub const KEY_OPEN_ANIMATION_ZOOM_SPEED: f32 = 0.1;
 /// The decay speed of a key in the "Door Open" Animation
 pub const KEY_OPEN_ANIMATION_DECAY_SPEED: f32 = -3.5;
+/// The speed a defeated enemy ascends
+pub const DEFEATED_ENEMY_ASCEND_SPEED: f32 = 3.0;
+/// The speed a defeated enemy zooms while ascending
+pub const DEFEATED_ENEMY_ZOOM_SPEED: f32 = -0.1;
+/// The speed a defeated enemy decays
+pub const DEFEATED_ENEMY_DECAY_SPEED: f32 = -3.0;
+/// The delay in seconds between two actions when a replay is played back
+pub const REPLAY_ACTION_DELAY: f32 = 0.2;
@@ -0,0 +1,137 @@
+use crate::animation::animated_action_sprite::{AnimatedActionSprite, AnimatedSpriteAction};
+use crate::game::constants::*;
+use crate::game::simulation::TimelineEntryReached;
+use crate::game::tilewrapper::MapWrapper;
+use crate::{AppLabels, AppState, GameConfig, LAYER_ID, TilesetManager};
+use bevy::prelude::*;
+use bevy::render::view::RenderLayers;
+use libexodus::enemies::EnemyState;
+use libexodus::simulation::{SimulationEvent, TimelineEntry};
+
+pub struct EnemyPlugin;
+
+impl Plugin for EnemyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(AppState::Playing),
+            setup_enemies.after(AppLabels::World),
+        )
+        .add_systems(
+            Update,
+            (update_enemies, enemy_movement)
+                .chain()
+                .run_if(in_state(AppState::Playing))
+                .after(AppLabels::Timeline),
+        )
+        .add_systems(OnExit(AppState::Playing), despawn_enemies);
+    }
+}
+
+/// A patrolling enemy, together with its current state in the simulation of the game
+#[derive(Component)]
+pub struct EnemyComponent {
+    pub state: EnemyState,
+    /// The index of the enemy in the list of enemies of the world
+    pub index: usize,
+}
+
+/// Spawn all enemies of the current map at their spawn positions
+fn setup_enemies(
+    mut commands: Commands,
+    worldwrapper: Res<MapWrapper>,
+    atlas_handle: Res<TilesetManager>,
+    config: Res<GameConfig>,
+) {
+    let texture_size = config.texture_size();
+    for (index, enemy) in worldwrapper.world.enemies().iter().enumerate() {
+        let state = enemy.initial_state();
+        let (x, y) = state.position();
+        let mut sprite = Sprite::from_atlas_image(
+            atlas_handle.current_texture_handle(),
+            TextureAtlas {
+                layout: atlas_handle.current_atlas_handle(),
+                index: enemy.atlas_index(),
+            },
+        );
+        sprite.flip_x = !state.is_facing_right();
+        commands.spawn((
+            sprite,
+            Transform::from_translation(Vec3::new(
+                x as f32 * texture_size,
+                y as f32 * texture_size,
+                PLAYER_Z - 0.05,
+            )),
+            EnemyComponent { state, index },
+            RenderLayers::layer(LAYER_ID),
+        ));
+    }
+}
+
+fn despawn_enemies(mut commands: Commands, enemies: Query<Entity, With<EnemyComponent>>) {
+    for entity in enemies.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Defeat the given enemy and let it ascend
+pub fn defeat_enemy(commands: &mut Commands, entity: Entity, enemy: &mut EnemyComponent) {
+    enemy.state.defeat();
+    commands.entity(entity).remove::<EnemyComponent>().insert(
+        AnimatedActionSprite::from_ascend_and_zoom(
+            DEFEATED_ENEMY_DECAY_SPEED,
+            DEFEATED_ENEMY_ASCEND_SPEED,
+            DEFEATED_ENEMY_ZOOM_SPEED,
+            AnimatedSpriteAction::None,
+        ),
+    );
+}
+
+/// Update the states of all enemies that have been moved by the simulation,
+/// and defeat the enemies the player has defeated
+fn update_enemies(
+    mut commands: Commands,
+    mut entries_reached: EventReader<TimelineEntryReached>,
+    mut enemies: Query<(Entity, &mut EnemyComponent)>,
+) {
+    for TimelineEntryReached(entry) in entries_reached.read() {
+        match entry {
+            TimelineEntry::EnemiesMoved { states } => {
+                for (_, mut enemy) in enemies.iter_mut() {
+                    if let Some(state) = states.get(enemy.index) {
+                        enemy.state = state.clone();
+                    }
+                }
+            },
+            TimelineEntry::Event(SimulationEvent::EnemyDefeated { position }) => {
+                // Like in the simulation, the enemy that comes first in the world is defeated
+                if let Some((entity, mut enemy)) = enemies
+                    .iter_mut()
+                    .filter(|(_, enemy)| enemy.state.is_at(*position))
+                    .min_by_key(|(_, enemy)| enemy.index)
+                {
+                    defeat_enemy(&mut commands, entity, &mut enemy);
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+/// Move the enemy sprites towards the current positions of the enemies
+fn enemy_movement(
+    mut enemies: Query<(&EnemyComponent, &mut Transform, &mut Sprite)>,
+    config: Res<GameConfig>,
+    time: Res<Time>,
+) {
+    let texture_size = config.texture_size();
+    for (enemy, mut transform, mut sprite) in enemies.iter_mut() {
+        sprite.flip_x = !enemy.state.is_facing_right();
+        let (x, y) = enemy.state.position();
+        let target = Vec2::new(x as f32 * texture_size, y as f32 * texture_size);
+        let current = transform.translation.truncate();
+        let max_distance = PLAYER_SPEED_ * texture_size * time.delta_secs();
+        let next = current + (target - current).clamp_length_max(max_distance);
+        transform.translation.x = next.x;
+        transform.translation.y = next.y;
+    }
+}
@@ -7,15 +7,21 @@ use std::path::PathBuf;
 
 pub mod camera;
 pub mod constants;
+pub mod enemies;
 mod pickup_item;
 pub mod player;
+pub mod replay;
 pub mod scoreboard;
+pub mod simulation;
 pub mod tilewrapper;
 mod ui;
 mod vending_machine;
 pub(crate) mod world;
 
+use crate::game::enemies::EnemyPlugin;
 use crate::game::player::{PlayerPlugin, ReturnTo};
+use crate::game::replay::ReplayPlugin;
+use crate::game::simulation::SimulationPlugin;
 use crate::game::tilewrapper::{MapWrapper, reset_score};
 use crate::game::ui::GameUIPlugin;
 use crate::game::vending_machine::VendingMachinePlugin;
@@ -30,9 +36,12 @@ impl Plugin for GamePlugin {
         app.init_resource::<MapWrapper>()
             .add_plugins(WorldPlugin)
             .add_plugins(GameUIPlugin)
+            .add_plugins(SimulationPlugin)
             .add_plugins(PlayerPlugin)
+            .add_plugins(EnemyPlugin)
             .add_plugins(PickupItemPlugin)
             .add_plugins(VendingMachinePlugin)
+            .add_plugins(ReplayPlugin)
             .add_systems(
                 Update,
                 back_with_esc_controls.run_if(in_state(AppState::Playing)),
@@ -0,0 +1,167 @@
+use crate::game::constants::REPLAY_ACTION_DELAY;
+use crate::game::player::PlayerComponent;
+use crate::game::scoreboard::Scoreboard;
+use crate::game::simulation::GameSimulation;
+use crate::game::tilewrapper::MapWrapper;
+use crate::{AppLabels, AppState};
+use bevy::prelude::*;
+use libexodus::replay::Replay;
+use std::fs;
+use std::path::Path;
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplayMode>()
+            .init_resource::<RecordedReplay>()
+            .add_systems(
+                OnEnter(AppState::Playing),
+                start_recording.after(AppLabels::World),
+            )
+            .add_systems(
+                Update,
+                replay_controls
+                    .run_if(in_state(AppState::Playing).and(is_playing_back))
+                    .after(AppLabels::Timeline),
+            )
+            .add_systems(OnExit(AppState::Playing), stop_playback);
+    }
+}
+
+/// Determines if the player is controlled by the keyboard or by a replay
+#[derive(Resource, Default)]
+pub enum ReplayMode {
+    /// The player is controlled by the keyboard and all actions are recorded
+    #[default]
+    Record,
+    /// The player is controlled by the given replay
+    Playback {
+        replay: Replay,
+        next_action: usize,
+        next_purchase: usize,
+        timer: Timer,
+    },
+}
+
+impl ReplayMode {
+    /// Create a new playback mode that plays back the given replay from the start
+    pub fn playback(replay: Replay) -> Self {
+        ReplayMode::Playback {
+            replay,
+            next_action: 0,
+            next_purchase: 0,
+            timer: Timer::from_seconds(REPLAY_ACTION_DELAY, TimerMode::Once),
+        }
+    }
+}
+
+/// The replay of the most recent game
+#[derive(Resource, Default)]
+pub struct RecordedReplay {
+    pub replay: Replay,
+    /// True, if the game was not played by the player, but played back from a replay
+    pub is_playback: bool,
+}
+
+/// Run condition that checks if the player is currently controlled by a replay
+pub fn is_playing_back(mode: Res<ReplayMode>) -> bool {
+    matches!(*mode, ReplayMode::Playback { .. })
+}
+
+/// Start a new recording for the current map, or restart the playback of the current replay
+fn start_recording(
+    mut mode: ResMut<ReplayMode>,
+    mut recorded: ResMut<RecordedReplay>,
+    map: Res<MapWrapper>,
+) {
+    match &mut *mode {
+        ReplayMode::Record => {
+            *recorded = RecordedReplay {
+                replay: Replay::new(*map.world.hash()),
+                is_playback: false,
+            };
+        },
+        ReplayMode::Playback {
+            replay,
+            next_action,
+            next_purchase,
+            timer,
+        } => {
+            *next_action = 0;
+            *next_purchase = 0;
+            timer.reset();
+            *recorded = RecordedReplay {
+                replay: replay.clone(),
+                is_playback: true,
+            };
+        },
+    }
+}
+
+/// Return to recording mode after a game has been played back
+fn stop_playback(mut mode: ResMut<ReplayMode>) {
+    *mode = ReplayMode::Record;
+}
+
+/// Execute the next action of the replay once the previous turn has been animated.
+/// The purchases that have been made before the action are made right before it.
+fn replay_controls(
+    mut mode: ResMut<ReplayMode>,
+    players: Query<&PlayerComponent>,
+    mut game: ResMut<GameSimulation>,
+    mut scoreboard: ResMut<Scoreboard>,
+    time: Res<Time>,
+) {
+    let ReplayMode::Playback {
+        replay,
+        next_action,
+        next_purchase,
+        timer,
+    } = &mut *mode
+    else {
+        return;
+    };
+    if !game.is_idle(&players) || !timer.tick(time.delta()).finished() {
+        return;
+    }
+    while let Some((_, item)) = replay
+        .purchases()
+        .get(*next_purchase)
+        .filter(|(index, _)| index == next_action)
+    {
+        game.simulation.purchase(*item);
+        *next_purchase += 1;
+    }
+    if let Some(action) = replay.actions().get(*next_action) {
+        game.step(*action);
+        scoreboard.moves = game.simulation.moves();
+        *next_action += 1;
+        timer.reset();
+    }
+}
+
+/// Save the given replay to the given file, creating the parent directory if necessary
+pub fn save_replay(replay: &Replay, path: &Path) {
+    if let Some(parent) = path.parent() {
+        if let Err(e) = fs::create_dir_all(parent) {
+            error!(
+                "Could not create the replays directory at {}: {}",
+                parent.to_str().unwrap_or("<invalid>"),
+                e
+            );
+            return;
+        }
+    }
+    match replay.save_to_file(path) {
+        Ok(_) => info!(
+            "Successfully saved replay to {}",
+            path.to_str().unwrap_or("<invalid>")
+        ),
+        Err(e) => error!(
+            "Could not save replay to {}: {}",
+            path.to_str().unwrap_or("<invalid>"),
+            e
+        ),
+    }
+}
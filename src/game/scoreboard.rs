@@ -7,7 +7,9 @@ use bevy_egui::egui::load::SizedTexture;
 use bevy_egui::egui::{Align, Layout, Response, RichText, Ui};
 use libexodus::highscores::highscore::Highscore;
 use libexodus::player::Player;
-use libexodus::tiles::Tile;
+use libexodus::simulation::Simulation;
+use libexodus::tiles::{KeyColor, Tile};
+use strum::EnumCount;
 
 #[derive(Resource, Clone, Debug)]
 pub enum GameOverState {
@@ -29,7 +31,12 @@ pub struct Scoreboard {
     pub crystals: usize,
     pub coins: i32,
     pub moves: usize,
+    /// The number of uncolored keys the player holds
     pub keys: usize,
+    /// The number of keys of each color the player holds, indexed by color
+    pub colored_keys: [usize; KeyColor::COUNT],
+    /// The number of lives the player has lost so far
+    pub deaths: usize,
 }
 
 impl FromWorld for Scoreboard {
@@ -39,6 +46,8 @@ impl FromWorld for Scoreboard {
             coins: 0,
             moves: 0,
             keys: 0,
+            colored_keys: [0; KeyColor::COUNT],
+            deaths: 0,
         }
     }
 }
@@ -50,19 +59,47 @@ impl Scoreboard {
             crystals,
             moves,
             keys,
+            colored_keys: [0; KeyColor::COUNT],
+            deaths: 0,
+        }
+    }
+    /// Get the number of keys of the given color, or of uncolored keys if no color is given
+    pub fn held_keys(&self, color: Option<KeyColor>) -> usize {
+        match color {
+            None => self.keys,
+            Some(color) => self.colored_keys[color.const_to_u8() as usize],
         }
     }
 }
 
 impl From<&Highscore> for Scoreboard {
     fn from(value: &Highscore) -> Self {
-        Scoreboard::new(value.coins() as i32, 0usize, value.moves() as usize, 0usize)
+        Scoreboard {
+            deaths: value.deaths() as usize,
+            ..Scoreboard::new(value.coins() as i32, 0usize, value.moves() as usize, 0usize)
+        }
+    }
+}
+impl From<&Simulation> for Scoreboard {
+    fn from(value: &Simulation) -> Self {
+        Scoreboard {
+            colored_keys: value.all_colored_keys(),
+            deaths: value.deaths(),
+            ..Scoreboard::new(
+                value.coins() as i32,
+                value.crystals(),
+                value.moves(),
+                value.keys(),
+            )
+        }
     }
 }
-/// Create a EGUI Scoreboard Label that shows a previous highscore
+/// Create a EGUI Scoreboard Label that shows a previous highscore.
+/// Highscores that have not been verified by a replay are flagged.
 pub fn egui_highscore_label(
     ui: &mut Ui,
     scoreboard: &Option<Scoreboard>,
+    verified: bool,
     textures: &EguiButtonTextures,
 ) -> Response {
     ui.with_layout(Layout::left_to_right(Align::Min), |ui| {
@@ -107,6 +144,25 @@ pub fn egui_highscore_label(
                     ))
                     .text_style(egui::TextStyle::Name("Highscore".into())),
                 );
+                if score.deaths > 0 {
+                    ui.add_space(UIMARGIN);
+                    ui.label(
+                        RichText::new(t!(
+                            "map_selection_screen.deaths_fmt",
+                            deaths = &score.deaths.to_string()
+                        ))
+                        .text_style(egui::TextStyle::Name("Highscore".into())),
+                    );
+                }
+                if !verified {
+                    ui.add_space(UIMARGIN);
+                    ui.label(
+                        RichText::new(t!("map_selection_screen.unverified_highscore"))
+                            .text_style(egui::TextStyle::Name("Highscore".into()))
+                            .color(ui.style().visuals.warn_fg_color),
+                    )
+                    .on_hover_text(t!("map_selection_screen.unverified_highscore_tooltip"));
+                }
             },
         }
     })
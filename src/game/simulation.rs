@@ -0,0 +1,161 @@
+use crate::animation::animated_action_sprite::AnimatedSpriteAction;
+use crate::game::constants::PLAYER_SPEED_;
+use crate::game::player::{PlayerComponent, exit_player, kill_player};
+use crate::game::scoreboard::{GameOverState, Scoreboard};
+use crate::game::tilewrapper::MapWrapper;
+use crate::{AppLabels, AppState, GameConfig};
+use bevy::prelude::*;
+use libexodus::movement::Movement;
+use libexodus::player::Player;
+use libexodus::simulation::{PlayerAction, Simulation, SimulationEvent, TimelineEntry};
+use std::collections::VecDeque;
+
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<TimelineEntryReached>()
+            .add_systems(
+                OnEnter(AppState::Playing),
+                setup_simulation.after(AppLabels::World),
+            )
+            .add_systems(
+                Update,
+                animate_timeline
+                    .run_if(in_state(AppState::Playing))
+                    .in_set(AppLabels::Timeline)
+                    .after(AppLabels::PlayerMovement),
+            );
+    }
+}
+
+/// The game that is currently played.
+/// All game rules are executed by the headless simulation, while the game only animates the
+/// timeline of each turn.
+#[derive(Resource)]
+pub struct GameSimulation {
+    pub simulation: Simulation,
+    /// The entries of the timeline of the current turn that have not been animated yet
+    timeline: VecDeque<TimelineEntry>,
+}
+
+impl GameSimulation {
+    /// Execute the given action as a single turn and enqueue its timeline to be animated
+    pub fn step(&mut self, action: PlayerAction) {
+        let timeline = self.simulation.step_with_timeline(action);
+        self.timeline.extend(timeline);
+    }
+    /// Check if the previous turn has been animated completely, such that the player can take
+    /// the next action
+    pub fn is_idle(&self, players: &Query<&PlayerComponent>) -> bool {
+        self.timeline.is_empty()
+            && players
+                .single()
+                .is_ok_and(|player| player.player.movement_queue_is_empty())
+    }
+}
+
+/// An event that is sent whenever the animation of the current turn has reached the given
+/// entry of the timeline.
+/// Movements of the player and events that replace the player sprite, i.e., dying, teleporting
+/// and exiting, are handled by the timeline animation itself.
+#[derive(Event)]
+pub struct TimelineEntryReached(pub TimelineEntry);
+
+/// Start a new simulation of the current map and let the player fall down from the spawn
+fn setup_simulation(mut commands: Commands, map: Res<MapWrapper>) {
+    let mut simulation = Simulation::new(map.world.clone(), Player::new());
+    let timeline = simulation.settle_with_timeline().into();
+    commands.insert_resource(GameSimulation {
+        simulation,
+        timeline,
+    });
+}
+
+/// Animate the timeline of the current turn.
+/// The movements of the player are enqueued to the player's movement queue, and all following
+/// entries are only handled after the player has arrived.
+/// Once the whole timeline has been animated, the scoreboard is updated from the simulation.
+fn animate_timeline(
+    mut commands: Commands,
+    mut game: ResMut<GameSimulation>,
+    mut players: Query<(Entity, &mut PlayerComponent, &mut Sprite, &Transform)>,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut entries_reached: EventWriter<TimelineEntryReached>,
+    config: Res<GameConfig>,
+) {
+    let Ok((player_entity, mut player, mut sprite, transform)) = players.single_mut() else {
+        // Wait until the player has been respawned or teleported
+        return;
+    };
+    let speed = PLAYER_SPEED_ * config.texture_size();
+    while let Some(entry) = game.timeline.front() {
+        let is_movement = matches!(entry, TimelineEntry::PlayerMoved { .. });
+        if !is_movement && !player.player.movement_queue_is_empty() {
+            return;
+        }
+        match game.timeline.pop_front().unwrap() {
+            TimelineEntry::PlayerMoved { from, to } => {
+                let velocity = (
+                    (to.0 as f32 - from.0 as f32).signum() * speed,
+                    (to.1 as f32 - from.1 as f32).signum() * speed,
+                );
+                player.player.push_movement_queue(Movement {
+                    velocity,
+                    target: (to.0 as i32, to.1 as i32),
+                    is_manual: false,
+                });
+            },
+            TimelineEntry::Event(SimulationEvent::Died { .. }) => {
+                let action = match game.timeline.front() {
+                    Some(TimelineEntry::Event(SimulationEvent::Respawned { position })) => {
+                        let location = *position;
+                        game.timeline.pop_front();
+                        AnimatedSpriteAction::Respawn { location }
+                    },
+                    _ => AnimatedSpriteAction::GameOverTrigger {
+                        state: GameOverState::Lost,
+                    },
+                };
+                kill_player(
+                    &mut commands,
+                    player_entity,
+                    sprite.as_mut(),
+                    transform,
+                    action,
+                );
+                return;
+            },
+            TimelineEntry::Event(SimulationEvent::Teleported { to, .. }) => {
+                exit_player(
+                    &mut commands,
+                    player_entity,
+                    sprite.as_mut(),
+                    transform,
+                    AnimatedSpriteAction::Teleport { location: to },
+                );
+                return;
+            },
+            TimelineEntry::Event(SimulationEvent::Exited { .. }) => {
+                exit_player(
+                    &mut commands,
+                    player_entity,
+                    sprite.as_mut(),
+                    transform,
+                    AnimatedSpriteAction::GameOverTrigger {
+                        state: GameOverState::Won {
+                            score: Scoreboard::from(&game.simulation),
+                        },
+                    },
+                );
+                return;
+            },
+            entry => {
+                entries_reached.write(TimelineEntryReached(entry));
+            },
+        }
+    }
+    if player.player.movement_queue_is_empty() {
+        *scoreboard = Scoreboard::from(&game.simulation);
+    }
+}
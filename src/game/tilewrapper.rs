@@ -9,6 +9,8 @@ use libexodus::world::presets::map_with_border;
 pub struct MapWrapper {
     pub world: GameWorld,
     pub previous_best: Option<Scoreboard>,
+    /// True, if the previous best highscore has been verified by playing back its replay
+    pub previous_best_verified: bool,
 }
 
 impl FromWorld for MapWrapper {
@@ -16,6 +18,7 @@ impl FromWorld for MapWrapper {
         MapWrapper {
             world: map_with_border(24, 10),
             previous_best: None,
+            previous_best_verified: false,
         }
     }
 }
@@ -24,4 +27,5 @@ pub fn reset_score(mut scoreboard: ResMut<Scoreboard>) {
     scoreboard.coins = 0;
     scoreboard.moves = 0;
     scoreboard.crystals = 0;
+    scoreboard.deaths = 0;
 }
@@ -11,8 +11,9 @@ use bevy_egui::egui::load::SizedTexture;
 use bevy_egui::egui::{Align, Align2, Layout};
 use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
 use libexodus::player::Player;
-use libexodus::tiles::Tile;
+use libexodus::tiles::{KeyColor, Tile};
 use regex::Regex;
+use strum::IntoEnumIterator;
 
 // The font has been taken from https://ggbot.itch.io/public-pixel-font (CC0 Public Domain)
 
@@ -36,6 +37,7 @@ fn game_ui_system(
     current_size: ResMut<WindowUiOverlayInfo>,
     mut event_writer: EventWriter<UiSizeChangedEvent>,
     textures: Res<EguiButtonTextures>,
+    worldwrapper: Res<MapWrapper>,
 ) {
     let bot_panel =
         egui::TopBottomPanel::bottom("")
@@ -61,21 +63,34 @@ fn game_ui_system(
                             ));
                             ui.label(format!("{}", scoreboard.coins));
 
-                            if scoreboard.keys > 0 {
+                            // Only show the remaining lives on maps where the player has more than one life
+                            if worldwrapper.world.lives() > 1 {
                                 ui.separator();
-                            }
-                            // ui.label(t!("game_ui.keys"));
-                            // ui.image(SizedTexture::new(
-                            //     textures.textures[&Tile::KEY.atlas_index().unwrap()].0,
-                            //     (h, h),
-                            // ));
-                            // ui.label(format!("{}", scoreboard.keys));
-                            for _ in 0..scoreboard.keys {
-                                ui.image(SizedTexture::new(
-                                    textures.textures[&Tile::KEY.atlas_index().unwrap()].0,
-                                    (h, h),
+                                ui.label(t!("game_ui.lives"));
+                                ui.label(format!(
+                                    "{}",
+                                    worldwrapper.world.lives().saturating_sub(scoreboard.deaths)
                                 ));
                             }
+
+                            // Show the held keys, grouped by color
+                            let key_colors =
+                                std::iter::once(None).chain(KeyColor::iter().map(Some));
+                            if key_colors
+                                .clone()
+                                .any(|color| scoreboard.held_keys(color) > 0)
+                            {
+                                ui.separator();
+                            }
+                            for color in key_colors {
+                                let key = Tile::key_of_color(color);
+                                for _ in 0..scoreboard.held_keys(color) {
+                                    ui.image(SizedTexture::new(
+                                        textures.textures[&key.atlas_index().unwrap()].0,
+                                        (h, h),
+                                    ));
+                                }
+                            }
                             if scoreboard.crystals > 0 {
                                 ui.separator();
                             }
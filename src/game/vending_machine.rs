@@ -4,59 +4,52 @@ use crate::game::constants::{
     PICKUP_ITEM_ZOOM_SPEED, PLAYER_Z,
 };
 use crate::game::player::PlayerComponent;
-use crate::game::scoreboard::Scoreboard;
+use crate::game::replay::{RecordedReplay, is_playing_back};
+use crate::game::simulation::{GameSimulation, TimelineEntryReached};
 use crate::textures::egui_textures::EguiButtonTextures;
 use crate::textures::tileset_manager::TilesetManager;
 use crate::ui::VENDINGMACHINEWIDTH;
-use crate::{AppState, LAYER_ID};
+use crate::{AppLabels, AppState, LAYER_ID};
 use bevy::prelude::*;
 use bevy::render::view::RenderLayers;
 use bevy_egui::egui::load::SizedTexture;
 use bevy_egui::egui::{RichText, WidgetText};
 use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
 use egui::Label;
+use libexodus::simulation::{Purchase, SimulationEvent, TimelineEntry};
 use libexodus::tiles::Tile;
 use std::borrow::Cow;
-use std::cmp::max;
-
-const COST_COINS: usize = 1;
-const COST_KEY: usize = 3;
 
 #[derive(Resource)]
 struct VendingMachineItems {
     items: Vec<Box<dyn VendingMachineItem>>,
 }
 
-#[derive(Event)]
-pub struct VendingMachineTriggered;
 #[derive(Resource)]
 struct HasVendingMachine;
 
 pub struct VendingMachinePlugin;
 impl Plugin for VendingMachinePlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<VendingMachineTriggered>()
-            .add_systems(
-                Update,
-                vending_machine_triggered_event_listener.run_if(in_state(AppState::Playing)),
-            )
-            .add_systems(
-                OnEnter(AppState::Playing),
-                vending_machine_triggered_event_clearer,
-            )
-            .add_systems(
-                EguiPrimaryContextPass,
-                vending_machine_ui
-                    .run_if(in_state(AppState::Playing).and(resource_exists::<HasVendingMachine>)),
-            )
-            .add_systems(
-                Update,
-                vending_machine_key_handler
-                    .run_if(in_state(AppState::Playing).and(resource_exists::<HasVendingMachine>)),
-            )
-            .insert_resource(VendingMachineItems {
-                items: vec![Box::new(CoinsItem), Box::new(KeysItem)],
-            });
+        app.add_systems(
+            Update,
+            vending_machine_triggered_event_listener
+                .run_if(in_state(AppState::Playing).and(not(is_playing_back)))
+                .after(AppLabels::Timeline),
+        )
+        .add_systems(
+            EguiPrimaryContextPass,
+            vending_machine_ui
+                .run_if(in_state(AppState::Playing).and(resource_exists::<HasVendingMachine>)),
+        )
+        .add_systems(
+            Update,
+            vending_machine_key_handler
+                .run_if(in_state(AppState::Playing).and(resource_exists::<HasVendingMachine>)),
+        )
+        .insert_resource(VendingMachineItems {
+            items: vec![Box::new(CoinsItem), Box::new(KeysItem)],
+        });
     }
 }
 fn index_to_keycode(index: usize) -> KeyCode {
@@ -75,7 +68,8 @@ fn index_to_keycode(index: usize) -> KeyCode {
 }
 fn vending_machine_key_handler(
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut scoreboard: ResMut<Scoreboard>,
+    mut game: ResMut<GameSimulation>,
+    mut recorded: ResMut<RecordedReplay>,
     items: Res<VendingMachineItems>,
     mut commands: Commands,
     player_positions: Query<&Transform, With<PlayerComponent>>,
@@ -84,10 +78,12 @@ fn vending_machine_key_handler(
     let player_pos = player_positions.single().unwrap();
     for (i, item) in items.items.iter().enumerate() {
         let keycode = index_to_keycode(i + 1);
-        if keyboard_input.just_pressed(keycode) && scoreboard.crystals >= item.cost() {
-            item.purchase(
+        if keyboard_input.just_pressed(keycode) && game.simulation.crystals() >= item.cost() {
+            purchase(
+                item.as_ref(),
+                &mut game,
+                &mut recorded,
                 &mut commands,
-                &mut scoreboard,
                 &atlas_handle,
                 (player_pos.translation.x, player_pos.translation.y),
             );
@@ -109,24 +105,25 @@ fn vending_machine_key_handler(
     }
 }
 fn vending_machine_triggered_event_listener(
-    mut reader: EventReader<VendingMachineTriggered>,
+    mut entries_reached: EventReader<TimelineEntryReached>,
     mut commands: Commands,
 ) {
-    if reader.read().next().is_some() {
-        reader.clear();
+    if entries_reached.read().any(|TimelineEntryReached(entry)| {
+        matches!(
+            entry,
+            TimelineEntry::Event(SimulationEvent::VendingMachineTriggered { .. })
+        )
+    }) {
         // debug!("A vending machine has been triggered!");
         commands.insert_resource(HasVendingMachine);
     }
 }
 
-fn vending_machine_triggered_event_clearer(mut events: ResMut<Events<VendingMachineTriggered>>) {
-    events.clear();
-}
-
 fn vending_machine_ui(
     mut commands: Commands,
     mut egui_ctx: EguiContexts,
-    mut scoreboard: ResMut<Scoreboard>,
+    mut game: ResMut<GameSimulation>,
+    mut recorded: ResMut<RecordedReplay>,
     items: Res<VendingMachineItems>,
     player_positions: Query<&Transform, With<PlayerComponent>>,
     atlas_handle: Res<TilesetManager>,
@@ -156,15 +153,17 @@ fn vending_machine_ui(
                     item.button_text(i + 1),
                     item.button_tooltip(i + 1),
                     item.cost(),
-                    scoreboard.crystals,
+                    game.simulation.crystals(),
                     &egui_textures,
                     Some(Tile::STARCRYSTAL),
                 );
                 if response.clicked() {
                     let player_pos = player_positions.single().unwrap();
-                    item.purchase(
+                    purchase(
+                        item.as_ref(),
+                        &mut game,
+                        &mut recorded,
                         &mut commands,
-                        &mut scoreboard,
                         &atlas_handle,
                         (player_pos.translation.x, player_pos.translation.y),
                     );
@@ -189,6 +188,21 @@ fn vending_machine_ui(
             }
         });
 }
+/// Purchase the given item in the simulation.
+/// If the purchase was successful, record it in the replay and animate it.
+fn purchase(
+    item: &dyn VendingMachineItem,
+    game: &mut GameSimulation,
+    recorded: &mut RecordedReplay,
+    commands: &mut Commands,
+    atlas_handle: &TilesetManager,
+    player_pos_px: (f32, f32),
+) {
+    if game.simulation.purchase(item.item()) {
+        recorded.replay.push_purchase(item.item());
+        item.animate_purchase(commands, atlas_handle, player_pos_px);
+    }
+}
 fn click_close_button(commands: &mut Commands) {
     commands.remove_resource::<HasVendingMachine>();
 }
@@ -237,13 +251,16 @@ fn vending_machine_button(
 }
 
 trait VendingMachineItem: Sync + Send {
-    fn cost(&self) -> usize;
+    /// The item that is purchased in the simulation
+    fn item(&self) -> Purchase;
+    fn cost(&self) -> usize {
+        self.item().cost()
+    }
     fn button_text(&self, index: usize) -> Cow<'_, str>;
     fn button_tooltip(&self, index: usize) -> Cow<'_, str>;
-    fn purchase(
+    fn animate_purchase(
         &self,
         commands: &mut Commands,
-        scoreboard: &mut Scoreboard,
         atlas_handle: &TilesetManager,
         player_pos_px: (f32, f32),
     );
@@ -252,8 +269,8 @@ trait VendingMachineItem: Sync + Send {
 struct KeysItem;
 
 impl VendingMachineItem for KeysItem {
-    fn cost(&self) -> usize {
-        COST_KEY
+    fn item(&self) -> Purchase {
+        Purchase::Key
     }
 
     fn button_text(&self, index: usize) -> Cow<'_, str> {
@@ -272,15 +289,12 @@ impl VendingMachineItem for KeysItem {
         )
     }
 
-    fn purchase(
+    fn animate_purchase(
         &self,
         commands: &mut Commands,
-        scoreboard: &mut Scoreboard,
         atlas_handle: &TilesetManager,
         player_pos_px: (f32, f32),
     ) {
-        scoreboard.crystals = max(0i32, scoreboard.crystals as i32 - COST_KEY as i32) as usize;
-        scoreboard.keys += 1;
         // Animate the purchase:
         let mut action = AnimatedActionSprite::from_ascend_and_zoom(
             PICKUP_ITEM_DECAY_SPEED,
@@ -335,8 +349,8 @@ fn spawn_animation(
 struct CoinsItem;
 
 impl VendingMachineItem for CoinsItem {
-    fn cost(&self) -> usize {
-        COST_COINS
+    fn item(&self) -> Purchase {
+        Purchase::FiveCoins
     }
 
     fn button_text(&self, index: usize) -> Cow<'_, str> {
@@ -355,15 +369,12 @@ impl VendingMachineItem for CoinsItem {
         )
     }
 
-    fn purchase(
+    fn animate_purchase(
         &self,
         commands: &mut Commands,
-        scoreboard: &mut Scoreboard,
         atlas_handle: &TilesetManager,
         player_pos_px: (f32, f32),
     ) {
-        scoreboard.crystals = max(0i32, scoreboard.crystals as i32 - COST_COINS as i32) as usize;
-        scoreboard.coins += 5;
         // Animate Star Crystal
         spawn_animation(
             commands,
@@ -1,12 +1,18 @@
+use crate::animation::animated_action_sprite::{AnimatedActionSprite, AnimatedSpriteAction};
 use crate::game::camera::{destroy_camera, handle_ui_resize, setup_camera};
-use crate::game::constants::WORLD_Z;
+use crate::game::constants::{
+    CRATE_Z, KEY_OPEN_ANIMATION_ASCEND_SPEED, KEY_OPEN_ANIMATION_DECAY_SPEED,
+    KEY_OPEN_ANIMATION_ZOOM_SPEED, PLAYER_SPEED_, PLAYER_Z, WORLD_Z,
+};
 use crate::game::pickup_item::insert_wrappers;
+use crate::game::simulation::{GameSimulation, TimelineEntryReached};
 use crate::game::tilewrapper::MapWrapper;
 use crate::textures::tileset_manager::TilesetManager;
-use crate::{AppLabels, AppState, LAYER_ID};
+use crate::{AppLabels, AppState, GameConfig, LAYER_ID};
 use bevy::ecs::system::EntityCommands;
 use bevy::prelude::*;
 use bevy::render::view::RenderLayers;
+use libexodus::simulation::{SimulationEvent, TimelineEntry};
 use libexodus::tiles::{Tile, TileKind};
 use libexodus::world::GameWorld;
 
@@ -33,6 +39,26 @@ impl Plugin for WorldPlugin {
                 .run_if(in_state(AppState::Playing))
                 .after(AppLabels::GameUI),
         )
+        .add_systems(
+            Update,
+            (open_doors, push_crates, crate_movement)
+                .chain()
+                .run_if(in_state(AppState::Playing))
+                .after(AppLabels::Timeline),
+        )
+        .add_systems(
+            Update,
+            retexture_toggled_blocks
+                .run_if(in_state(AppState::Playing))
+                .after(AppLabels::Timeline),
+        )
+        .add_systems(
+            Update,
+            (update_platform_sprites, platform_movement)
+                .chain()
+                .run_if(in_state(AppState::Playing))
+                .after(AppLabels::Timeline),
+        )
         .add_systems(OnExit(AppState::Playing), destroy_camera)
         .add_systems(OnExit(AppState::Playing), destroy_world)
         // Map Editor needs a world as well:
@@ -51,11 +77,259 @@ pub struct WorldTile;
 pub struct DoorWrapper;
 
 pub fn insert_door_wrappers(tile: &Tile, bundle: &mut EntityCommands) {
-    if tile.kind() == TileKind::DOOR {
+    if let TileKind::DOOR { .. } = tile.kind() {
         bundle.insert(DoorWrapper);
     }
 }
 
+/// Open the doors the player has unlocked with a key, and let the used key ascend
+fn open_doors(
+    mut commands: Commands,
+    mut entries_reached: EventReader<TimelineEntryReached>,
+    mut doors: Query<(Entity, &Transform, &mut Sprite), With<DoorWrapper>>,
+    game: Res<GameSimulation>,
+    atlas_handle: Res<TilesetManager>,
+    config: Res<GameConfig>,
+) {
+    for TimelineEntryReached(entry) in entries_reached.read() {
+        let TimelineEntry::Event(SimulationEvent::DoorOpened { position }) = entry else {
+            continue;
+        };
+        let Some(opened_door) = game
+            .simulation
+            .world()
+            .get(position.0 as i32, position.1 as i32)
+            .cloned()
+        else {
+            continue;
+        };
+        let Some(TileKind::DOOR { color }) = opened_door.closed_door().map(|door| door.kind())
+        else {
+            continue;
+        };
+        let (target_x_px, target_y_px) = (
+            position.0 as f32 * config.texture_size(),
+            position.1 as f32 * config.texture_size(),
+        );
+        for (entity, transform, mut sprite) in doors.iter_mut() {
+            if transform.translation.x != target_x_px || transform.translation.y != target_y_px {
+                continue;
+            }
+            // Found the door. Change its texture to an open door
+            commands.entity(entity).remove::<DoorWrapper>();
+            if let Some(ref mut a) = sprite.texture_atlas {
+                a.index = opened_door.atlas_index().unwrap();
+            }
+            // Spawn a "Key Used" Animation:
+            commands.spawn((
+                Sprite::from_atlas_image(
+                    sprite.image.clone(),
+                    TextureAtlas {
+                        layout: atlas_handle.current_atlas_handle(),
+                        index: Tile::key_of_color(color).atlas_index().unwrap(),
+                    },
+                ),
+                Transform::from_translation(Vec3::new(target_x_px, target_y_px, PLAYER_Z - 0.1)),
+                AnimatedActionSprite::from_ascend_and_zoom(
+                    KEY_OPEN_ANIMATION_DECAY_SPEED,
+                    KEY_OPEN_ANIMATION_ASCEND_SPEED,
+                    KEY_OPEN_ANIMATION_ZOOM_SPEED,
+                    AnimatedSpriteAction::None,
+                ),
+                RenderLayers::layer(LAYER_ID),
+            ));
+        }
+    }
+}
+
+/// A crate, together with its current position on the map.
+/// The crate sprite is moved towards this position by the crate movement system.
+#[derive(Component)]
+pub struct CrateWrapper {
+    pub position: (usize, usize),
+}
+
+pub fn insert_crate_wrappers(tile: &Tile, tile_position: &Vec2, bundle: &mut EntityCommands) {
+    if tile.kind() == TileKind::CRATE {
+        bundle.insert(CrateWrapper {
+            position: (tile_position.x as usize, tile_position.y as usize),
+        });
+    }
+}
+
+/// Update the positions of the crates the player has pushed
+fn push_crates(
+    mut entries_reached: EventReader<TimelineEntryReached>,
+    mut crates: Query<&mut CrateWrapper>,
+) {
+    for TimelineEntryReached(entry) in entries_reached.read() {
+        let TimelineEntry::Event(SimulationEvent::CratePushed { from, to }) = entry else {
+            continue;
+        };
+        for mut crate_wrapper in crates.iter_mut() {
+            if crate_wrapper.position == *from {
+                crate_wrapper.position = *to;
+                break;
+            }
+        }
+    }
+}
+
+/// Move all pushed crates towards their current positions.
+/// Crates move horizontally first and fall down afterwards.
+fn crate_movement(
+    mut crates: Query<(&CrateWrapper, &mut Transform)>,
+    config: Res<GameConfig>,
+    time: Res<Time>,
+) {
+    let max_distance = PLAYER_SPEED_ * config.texture_size() * time.delta_secs();
+    for (crate_wrapper, mut transform) in crates.iter_mut() {
+        // Render crates in front of the tiles they cover
+        transform.translation.z = CRATE_Z;
+        move_towards(
+            &mut transform,
+            crate_wrapper.position,
+            max_distance,
+            config.texture_size(),
+        );
+    }
+}
+
+/// Move the given tile towards the given map position by at most `max_distance` pixels,
+/// moving horizontally first
+fn move_towards(
+    transform: &mut Transform,
+    position: (usize, usize),
+    max_distance: f32,
+    texture_size: f32,
+) {
+    let target_x = position.0 as f32 * texture_size;
+    let target_y = position.1 as f32 * texture_size;
+    if transform.translation.x != target_x {
+        let dx = target_x - transform.translation.x;
+        transform.translation.x += dx.clamp(-max_distance, max_distance);
+    } else if transform.translation.y != target_y {
+        let dy = target_y - transform.translation.y;
+        transform.translation.y += dy.clamp(-max_distance, max_distance);
+    }
+}
+
+/// A crumbling platform or a falling block, together with its current position on the map
+#[derive(Component)]
+pub struct PlatformWrapper {
+    pub position: (usize, usize),
+}
+
+pub fn insert_platform_wrappers(tile: &Tile, tile_position: &Vec2, bundle: &mut EntityCommands) {
+    if matches!(
+        tile,
+        Tile::CRUMBLINGPLATFORM | Tile::CRUMBLINGPLATFORMCRACKED | Tile::FALLINGBLOCK
+    ) {
+        bundle.insert(PlatformWrapper {
+            position: (tile_position.x as usize, tile_position.y as usize),
+        });
+    }
+}
+
+/// Retexture cracked platforms, despawn broken platforms and let falling blocks fall down
+fn update_platform_sprites(
+    mut commands: Commands,
+    mut entries_reached: EventReader<TimelineEntryReached>,
+    mut platforms: Query<(Entity, &mut PlatformWrapper, &mut Sprite)>,
+) {
+    for TimelineEntryReached(entry) in entries_reached.read() {
+        let TimelineEntry::Event(event) = entry else {
+            continue;
+        };
+        for (entity, mut platform_wrapper, mut sprite) in platforms.iter_mut() {
+            let position = platform_wrapper.position;
+            match event {
+                SimulationEvent::PlatformBroken { position: broken } if *broken == position => {
+                    commands.entity(entity).despawn();
+                },
+                SimulationEvent::PlatformCracked { position: cracked } if *cracked == position => {
+                    if let Some(atlas) = sprite.texture_atlas.as_mut() {
+                        atlas.index = Tile::CRUMBLINGPLATFORMCRACKED.atlas_index().unwrap();
+                    }
+                },
+                SimulationEvent::BlockFell { from, to } if *from == position => {
+                    platform_wrapper.position = *to;
+                },
+                _ => {},
+            }
+        }
+    }
+}
+
+/// Move all falling blocks towards their current positions
+fn platform_movement(
+    mut platforms: Query<(&PlatformWrapper, &mut Transform)>,
+    config: Res<GameConfig>,
+    time: Res<Time>,
+) {
+    let max_distance = PLAYER_SPEED_ * config.texture_size() * time.delta_secs();
+    for (platform_wrapper, mut transform) in platforms.iter_mut() {
+        move_towards(
+            &mut transform,
+            platform_wrapper.position,
+            max_distance,
+            config.texture_size(),
+        );
+    }
+}
+
+/// A tile that can be toggled by a switch, together with its position on the map
+#[derive(Component)]
+pub struct ToggleWrapper {
+    pub position: (usize, usize),
+}
+
+pub fn insert_toggle_wrappers(tile: &Tile, tile_position: &Vec2, bundle: &mut EntityCommands) {
+    if tile.toggled().is_some() {
+        bundle.insert(ToggleWrapper {
+            position: (tile_position.x as usize, tile_position.y as usize),
+        });
+    }
+}
+
+/// Change the textures of all tiles toggled by a switch to match the simulated world.
+/// Toggled doors are closed or opened without using a key.
+fn retexture_toggled_blocks(
+    mut commands: Commands,
+    mut entries_reached: EventReader<TimelineEntryReached>,
+    mut blocks: Query<(Entity, &ToggleWrapper, &mut Sprite)>,
+    game: Res<GameSimulation>,
+) {
+    let world = game.simulation.world();
+    for TimelineEntryReached(entry) in entries_reached.read() {
+        let TimelineEntry::Event(SimulationEvent::SwitchToggled { position: switch }) = entry
+        else {
+            continue;
+        };
+        let linked_blocks = world.linked_blocks(*switch);
+        for (entity, toggle_wrapper, mut sprite) in blocks.iter_mut() {
+            if toggle_wrapper.position != *switch
+                && !linked_blocks.contains(&toggle_wrapper.position)
+            {
+                continue;
+            }
+            let (x, y) = toggle_wrapper.position;
+            let Some(tile) = world.get(x as i32, y as i32) else {
+                continue;
+            };
+            if let (Some(atlas), Some(index)) = (sprite.texture_atlas.as_mut(), tile.atlas_index())
+            {
+                atlas.index = index;
+            }
+            if let TileKind::DOOR { .. } = tile.kind() {
+                commands.entity(entity).insert(DoorWrapper);
+            } else {
+                commands.entity(entity).remove::<DoorWrapper>();
+            }
+        }
+    }
+}
+
 /// Despawn the world
 pub fn destroy_world(mut commands: Commands, q_worldtiles: Query<Entity, With<WorldTile>>) {
     for entity in q_worldtiles.iter() {
@@ -87,8 +361,11 @@ pub fn spawn_tile(
         WorldTile, // WorldTiles are attached to each world tile, while TileWrappers are additionally attached to non-interactive world tiles.
         layer.clone(),
     ));
-    insert_wrappers(tile, &mut bundle);
+    insert_wrappers(tile, tile_position, &mut bundle);
     insert_door_wrappers(tile, &mut bundle);
+    insert_crate_wrappers(tile, tile_position, &mut bundle);
+    insert_toggle_wrappers(tile, tile_position, &mut bundle);
+    insert_platform_wrappers(tile, tile_position, &mut bundle);
 }
 /// Spawn the world
 pub fn setup_game_world(commands: &mut Commands, world: &GameWorld, atlas_handle: &TilesetManager) {
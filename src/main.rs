@@ -1,7 +1,9 @@
 use crate::animation::AnimationPlugin;
-use crate::campaign::MainCampaignLoader;
+use crate::campaign::CampaignTrailsLoader;
+use crate::campaign::campaign_selection_screen::CampaignSelectionScreenPlugin;
 use crate::campaign::campaign_trail::CampaignTrailPlugin;
 use crate::campaign::campaign_trail_asset_loader::CampaignTrailAssetPlugin;
+use crate::campaign::campaign_trail_editor::CampaignTrailEditorPlugin;
 use crate::game::{GamePlugin, HighscoresDatabaseWrapper};
 use crate::mapeditor::MapEditorPlugin;
 use crate::textures::Textures;
@@ -32,12 +34,12 @@ mod game;
 mod mapeditor;
 mod textures;
 mod ui;
-mod util;
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
 pub enum AppLabels {
     PlayerMovement,
-    Gravity,
+    /// Animate the timeline of the turns executed by the simulation of the game
+    Timeline,
     World,
     ResetScore,
     Player,
@@ -56,7 +58,11 @@ pub enum AppState {
     MapSelectionScreenDialog,
     CreditsScreen,
     ConfigScreen,
+    /// Choose one of the installed campaign trails
+    CampaignSelectionScreen,
     CampaignTrailScreen,
+    /// Edit a campaign trail in the graphical campaign trail editor
+    CampaignTrailEditor,
     Playing,
     MapEditor,
     MapEditorDialog,
@@ -312,11 +318,13 @@ fn main() {
         .add_systems(Update, resize_notificator)
         .add_systems(Update, finished_init.run_if(in_state(AppState::Init)))
         .add_plugins(CampaignTrailAssetPlugin)
-        .add_plugins(MainCampaignLoader)
+        .add_plugins(CampaignTrailsLoader)
         .add_plugins(GamePlugin)
         .add_plugins(Ui)
         .add_plugins(MapEditorPlugin)
         .add_plugins(CampaignTrailPlugin)
+        .add_plugins(CampaignSelectionScreenPlugin)
+        .add_plugins(CampaignTrailEditorPlugin)
         .add_plugins(AnimationPlugin)
         .add_plugins(LoadingPlugin)
         .run();
@@ -0,0 +1,256 @@
+use crate::game::camera::{LayerCamera, MainCamera};
+use crate::game::tilewrapper::MapWrapper;
+use crate::mapeditor::edit_world::{TileViewFilter, refresh_view_at, replace_world_tiles_at};
+use crate::mapeditor::preview_tile::ShapePreview;
+use crate::mapeditor::{
+    DrawingTool, EditorHistory, MapeditorSystems, compute_cursor_position_in_world,
+};
+use crate::{AppState, GameConfig, TilesetManager};
+use bevy::prelude::*;
+use bevy::render::view::RenderLayers;
+use bevy::window::PrimaryWindow;
+use libexodus::tiles::Tile;
+use libexodus::world::drawing::rectangle;
+use libexodus::world::region::TileRegion;
+
+pub struct ClipboardPlugin;
+
+impl Plugin for ClipboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EditorClipboard>()
+            .init_resource::<Selection>()
+            .add_event::<ClipboardAction>()
+            .add_systems(OnEnter(AppState::MapSelectionScreen), discard_selection)
+            .add_systems(
+                Update,
+                (
+                    discard_selection.run_if(resource_changed::<DrawingTool>),
+                    selection_mouse_handler,
+                    clipboard_keyboard_handler,
+                    clipboard_action_handler,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::MapEditor))
+                    .in_set(MapeditorSystems::GameBoardMouseHandlers),
+            );
+    }
+}
+
+/// The region that has been copied in the map editor.
+/// The clipboard is kept if another map is edited, such that regions can be copied between maps.
+#[derive(Resource, Default)]
+pub struct EditorClipboard {
+    pub region: Option<TileRegion>,
+}
+
+/// The rectangular selection in the map editor
+#[derive(Resource, Default)]
+pub struct Selection {
+    /// The two corners that span the selection, or None if nothing is selected
+    pub corners: Option<((usize, usize), (usize, usize))>,
+    /// True while the selection is being dragged
+    dragging: bool,
+}
+
+/// An action that is executed on the selection or on the clipboard
+#[derive(Event, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ClipboardAction {
+    /// Copy the selected region into the clipboard
+    Copy,
+    /// Copy the selected region into the clipboard and replace it with air
+    Cut,
+    /// Start pasting the clipboard with the stamp tool
+    Paste,
+    MirrorHorizontally,
+    MirrorVertically,
+    RotateClockwise,
+}
+
+fn discard_selection(mut selection: ResMut<Selection>, mut shape_preview: ResMut<ShapePreview>) {
+    *selection = Selection::default();
+    shape_preview.set_if_neq(ShapePreview::default());
+}
+
+/// Select regions with the selection tool and paste the clipboard with the stamp tool.
+/// The lower left corner of the pasted region is placed at the cursor.
+fn selection_mouse_handler(
+    mut commands: Commands,
+    wnds: Query<&Window, With<PrimaryWindow>>,
+    q_layer_camera: Query<(&Camera, &GlobalTransform), With<LayerCamera>>,
+    q_main_camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut map: ResMut<MapWrapper>,
+    mut history: ResMut<EditorHistory>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    tool: Res<DrawingTool>,
+    mut selection: ResMut<Selection>,
+    clipboard: Res<EditorClipboard>,
+    mut shape_preview: ResMut<ShapePreview>,
+    tile_entity_query: Query<(Entity, &Transform), TileViewFilter>,
+    atlas: Res<TilesetManager>,
+    layer_query: Query<&RenderLayers, With<LayerCamera>>,
+    config: Res<GameConfig>,
+) {
+    if tool.places_selected_tile() {
+        return;
+    }
+    let (layer_camera, layer_camera_transform) = q_layer_camera.single().unwrap();
+    let (main_camera, main_camera_transform) = q_main_camera.single().unwrap();
+    let cursor = compute_cursor_position_in_world(
+        &wnds,
+        main_camera,
+        main_camera_transform,
+        layer_camera,
+        layer_camera_transform,
+        config.texture_size(),
+    )
+    .filter(|(x, y)| map.world.get(*x, *y).is_some())
+    .map(|(x, y)| (x as usize, y as usize));
+    match *tool {
+        DrawingTool::Select => {
+            if buttons.just_pressed(MouseButton::Left)
+                && let Some(position) = cursor
+            {
+                selection.corners = Some((position, position));
+                selection.dragging = true;
+            } else if buttons.just_pressed(MouseButton::Right) {
+                selection.corners = None;
+            }
+            if selection.dragging {
+                if let Some(position) = cursor
+                    && let Some((start, _)) = selection.corners
+                {
+                    selection.corners = Some((start, position));
+                }
+                selection.dragging = buttons.pressed(MouseButton::Left);
+            }
+            // Show the border of the selection
+            shape_preview.set_if_neq(ShapePreview {
+                tiles: selection
+                    .corners
+                    .map(|(a, b)| rectangle(a, b, false))
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|position| (position, Tile::AIR))
+                    .collect(),
+            });
+        },
+        DrawingTool::Stamp => {
+            let (Some(region), Some(position)) = (&clipboard.region, cursor) else {
+                shape_preview.set_if_neq(ShapePreview::default());
+                return;
+            };
+            shape_preview.set_if_neq(ShapePreview {
+                tiles: region
+                    .tiles_at(position)
+                    .filter(|((x, y), _)| map.world.get(*x as i32, *y as i32).is_some())
+                    .map(|(position, tile)| (position, tile.clone()))
+                    .collect(),
+            });
+            if buttons.just_released(MouseButton::Left) {
+                let changed = history.history.paste(&mut map.world, region, position);
+                debug!(
+                    "Pasted a {}x{} region at {},{}",
+                    region.width(),
+                    region.height(),
+                    position.0,
+                    position.1
+                );
+                let layer: &RenderLayers = layer_query.single().unwrap();
+                for (x, y) in changed {
+                    refresh_view_at(
+                        &Vec2::new(x as f32, y as f32),
+                        &map.world,
+                        &mut commands,
+                        &tile_entity_query,
+                        &atlas,
+                        layer,
+                    );
+                }
+            }
+        },
+        _ => {},
+    }
+}
+
+/// Copy on Ctrl+C, cut on Ctrl+X and paste on Ctrl+V
+fn clipboard_keyboard_handler(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut actions: EventWriter<ClipboardAction>,
+) {
+    if !keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        return;
+    }
+    if keyboard_input.just_pressed(KeyCode::KeyC) {
+        actions.write(ClipboardAction::Copy);
+    } else if keyboard_input.just_pressed(KeyCode::KeyX) {
+        actions.write(ClipboardAction::Cut);
+    } else if keyboard_input.just_pressed(KeyCode::KeyV) {
+        actions.write(ClipboardAction::Paste);
+    }
+}
+
+fn clipboard_action_handler(
+    mut commands: Commands,
+    mut actions: EventReader<ClipboardAction>,
+    mut map: ResMut<MapWrapper>,
+    mut history: ResMut<EditorHistory>,
+    mut tool: ResMut<DrawingTool>,
+    mut selection: ResMut<Selection>,
+    mut clipboard: ResMut<EditorClipboard>,
+    tile_entity_query: Query<(Entity, &Transform), TileViewFilter>,
+    atlas: Res<TilesetManager>,
+    layer_query: Query<&RenderLayers, With<LayerCamera>>,
+) {
+    for action in actions.read() {
+        debug!("Executing clipboard action {:?}", action);
+        match action {
+            ClipboardAction::Copy | ClipboardAction::Cut => {
+                let Some((a, b)) = selection.corners else {
+                    continue;
+                };
+                clipboard.region = TileRegion::copy_from(&map.world, a, b);
+                if *action == ClipboardAction::Cut {
+                    // The player spawn is not copied, hence it is not removed either
+                    let positions: Vec<(usize, usize)> = rectangle(a, b, true)
+                        .into_iter()
+                        .filter(|(x, y)| {
+                            map.world.get(*x as i32, *y as i32) != Some(&Tile::PLAYERSPAWN)
+                        })
+                        .collect();
+                    replace_world_tiles_at(
+                        positions,
+                        &Tile::AIR,
+                        false,
+                        &mut commands,
+                        &mut map,
+                        &mut history,
+                        &tile_entity_query,
+                        &atlas,
+                        layer_query.single().unwrap(),
+                    );
+                    selection.corners = None;
+                }
+            },
+            ClipboardAction::Paste => {
+                if clipboard.region.is_some() {
+                    *tool = DrawingTool::Stamp;
+                }
+            },
+            ClipboardAction::MirrorHorizontally => {
+                if let Some(region) = clipboard.region.as_mut() {
+                    region.mirror_horizontally();
+                }
+            },
+            ClipboardAction::MirrorVertically => {
+                if let Some(region) = clipboard.region.as_mut() {
+                    region.mirror_vertically();
+                }
+            },
+            ClipboardAction::RotateClockwise => {
+                if let Some(region) = clipboard.region.as_mut() {
+                    region.rotate_clockwise();
+                }
+            },
+        }
+    }
+}
@@ -4,36 +4,59 @@ use crate::game::camera::{LayerCamera, MainCamera};
 use crate::game::tilewrapper::MapWrapper;
 use crate::game::world::{WorldTile, spawn_tile};
 use crate::mapeditor::player_spawn::PlayerSpawnComponent;
-use crate::mapeditor::{MapeditorSystems, SelectedTile, compute_cursor_position_in_world};
+use crate::mapeditor::preview_tile::ShapePreview;
+use crate::mapeditor::{
+    DrawingTool, EditorHistory, MapeditorSystems, SelectedTile, compute_cursor_position_in_world,
+};
 use crate::{AppState, GameConfig, TilesetManager};
 use bevy::prelude::*;
 use bevy::render::view::RenderLayers;
 use bevy::window::PrimaryWindow;
 use libexodus::tiles::Tile;
+use libexodus::world::GameWorld;
+use libexodus::world::drawing::{flood_fill, line, rectangle};
 
 pub struct EditWorldPlugin;
 
 impl Plugin for EditWorldPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            // This ordering of function calls needs to be kept intact, else the player spawn placement will NOT work anymore!!!
-            (mouse_down_handler, mouse_down_handler_playerspawn)
-                .chain()
-                .run_if(in_state(AppState::MapEditor))
-                .in_set(MapeditorSystems::GameBoardMouseHandlers),
-        );
+        app.init_resource::<DrawingStroke>()
+            .add_systems(OnExit(AppState::MapEditor), discard_drawing_stroke)
+            .add_systems(
+                Update,
+                // This ordering of function calls needs to be kept intact, else the player spawn placement will NOT work anymore!!!
+                (mouse_down_handler, mouse_down_handler_playerspawn)
+                    .chain()
+                    .run_if(in_state(AppState::MapEditor))
+                    .in_set(MapeditorSystems::GameBoardMouseHandlers),
+            )
+            .add_systems(
+                Update,
+                undo_redo_keyboard_handler
+                    .run_if(in_state(AppState::MapEditor))
+                    .after(MapeditorSystems::GameBoardMouseHandlers),
+            );
     }
 }
 
+/// Abort the current stroke, e.g., if the map editor is left while a mouse button is held down
+fn discard_drawing_stroke(mut drawing_stroke: ResMut<DrawingStroke>) {
+    drawing_stroke.stroke = None;
+}
+
+/// Query filter for the views of all world tiles
+pub type TileViewFilter = (With<WorldTile>, Without<PlayerSpawnComponent>);
+/// Query filter for the player spawn placeholder
+type PlayerSpawnFilter = (With<PlayerSpawnComponent>, Without<WorldTile>);
+
 /// Delete the first tile with the given position from the view.
 fn view_delete_tile_at(
     pos: &Vec2,
     commands: &mut Commands,
-    tile_entity_query: &Query<(Entity, &mut Transform, &mut Sprite), With<WorldTile>>,
+    tile_entity_query: &Query<(Entity, &Transform), TileViewFilter>,
     map_texture_atlas: &TilesetManager,
 ) {
-    for (entity, transform, _) in tile_entity_query.iter() {
+    for (entity, transform) in tile_entity_query.iter() {
         if transform.translation.x as i32
             == (pos.x as i32 * map_texture_atlas.current_tileset.texture_size() as i32)
             && transform.translation.y as i32
@@ -46,92 +69,118 @@ fn view_delete_tile_at(
     }
 }
 
-/// Update the tile texture of the given tile to match the new tile.
-fn update_texture_at(
+/// Update the view of the tile at the given position to match the world, i.e., delete the
+/// current tile view and spawn a new one if the tile is visible.
+pub fn refresh_view_at(
     pos: &Vec2,
-    tile_entity_query: &mut Query<(Entity, &mut Transform, &mut Sprite), With<WorldTile>>,
-    new_tile: &Tile,
-    map_texture_atlas: &TilesetManager,
+    world: &GameWorld,
+    commands: &mut Commands,
+    tile_entity_query: &Query<(Entity, &Transform), TileViewFilter>,
+    atlas: &TilesetManager,
+    layer: &RenderLayers,
 ) {
-    if let Some(new_atlas_index) = new_tile.atlas_index() {
-        for (_, transform, mut sprite) in tile_entity_query.iter_mut() {
-            if transform.translation.x as i32
-                == (pos.x as i32 * map_texture_atlas.current_tileset.texture_size() as i32)
-                && transform.translation.y as i32
-                    == (pos.y as i32 * map_texture_atlas.current_tileset.texture_size() as i32)
-            {
-                if let Some(ref mut a) = sprite.texture_atlas {
-                    a.index = new_atlas_index;
-                }
-                debug!("Updated tile texture at position {},{}", pos.x, pos.y);
-                return;
-            }
-        }
-    } else {
-        panic!("The tile {} does not have an atlas index", *new_tile);
+    view_delete_tile_at(pos, commands, tile_entity_query, atlas);
+    if let Some(tile) = world.get(pos.x as i32, pos.y as i32)
+        && let Some(atlas_index) = tile.atlas_index()
+    {
+        spawn_tile(commands, atlas, atlas_index, pos, tile, layer);
     }
 }
 
-/// Replace the world tile at the given position. Takes care of handling different tile kinds.
-fn replace_world_tile_at(
-    pos: Vec2,
+/// Move the player spawn placeholder to the player spawn of the given world
+fn refresh_player_spawn(
+    world: &GameWorld,
+    player_spawn_query: &mut Query<&mut Transform, PlayerSpawnFilter>,
+    texture_size: f32,
+) {
+    if let Ok(mut transform) = player_spawn_query.single_mut() {
+        let (x, y) = world.player_spawn();
+        transform.translation.x = x as f32 * texture_size;
+        transform.translation.y = y as f32 * texture_size;
+    }
+}
+
+/// Replace the world tiles at the given positions with the given tile and record the change in
+/// the editor history.
+/// If `merge` is true, the change is merged into the last edit, such that both are undone at once.
+/// All tiles that are changed by the edit are updated in the view.
+/// Return true if any tile has been changed.
+pub fn replace_world_tiles_at(
+    positions: impl IntoIterator<Item = (usize, usize)>,
     new_tile: &Tile,
+    merge: bool,
     commands: &mut Commands,
     map: &mut MapWrapper,
-    tile_entity_query: &mut Query<(Entity, &mut Transform, &mut Sprite), With<WorldTile>>,
+    history: &mut EditorHistory,
+    tile_entity_query: &Query<(Entity, &Transform), TileViewFilter>,
     atlas: &TilesetManager,
-    layer_query: Query<&RenderLayers, With<LayerCamera>>,
-) {
-    if let Some(current_world_tile) = map.world.get(pos.x as i32, pos.y as i32) {
-        debug!(
-            "Replacing world tile {},{} ({}) with {}",
-            pos.x, pos.y, current_world_tile, new_tile
+    layer: &RenderLayers,
+) -> bool {
+    let tiles = positions
+        .into_iter()
+        .map(|position| (position, new_tile.clone()));
+    let changed = if merge {
+        history.history.extend_tiles(&mut map.world, tiles)
+    } else {
+        history.history.set_tiles(&mut map.world, tiles)
+    };
+    for (x, y) in changed.iter() {
+        debug!("Replaced world tile {},{} with {}", x, y, new_tile);
+        refresh_view_at(
+            &Vec2::new(*x as f32, *y as f32),
+            &map.world,
+            commands,
+            tile_entity_query,
+            atlas,
+            layer,
         );
-        match *new_tile {
-            Tile::AIR => {
-                // If a tile is replaced with air, it should just be deleted from the view:
-                view_delete_tile_at(&pos, commands, tile_entity_query, atlas);
-            },
-            Tile::PLAYERSPAWN => {
-                // Delete the tile at the given position. This action does not do anything if the current tile is Air, so we skip that check
-                view_delete_tile_at(&pos, commands, tile_entity_query, atlas);
-            },
-            _ => {
-                if *current_world_tile == Tile::AIR || *current_world_tile == Tile::PLAYERSPAWN {
-                    // The world currently contains air at the given place, i.e. just create a new tile
-                    let layer: &RenderLayers = layer_query.single().unwrap();
-                    spawn_tile(
-                        commands,
-                        atlas,
-                        new_tile.atlas_index().unwrap(),
-                        &pos,
-                        new_tile,
-                        layer,
-                    );
-                } else {
-                    // The world currently contains a different tile than the new one. We need to update the texture:
-                    update_texture_at(&pos, tile_entity_query, new_tile, atlas);
-                }
-            },
-        }
+    }
+    !changed.is_empty()
+}
 
-        // Now, actually replace the tile in the world:
-        match *new_tile {
-            Tile::MESSAGE { .. } => {
-                if matches!(current_world_tile, Tile::MESSAGE { .. }) {
-                    // Do nothing, there already is a message tile. Leave it and do not change any ID
-                } else {
-                    // Create a new Message ID and place the message tile in the world:
-                    map.world
-                        .set_message_tile(pos.x as usize, pos.y as usize, "".to_string());
-                }
-            },
-            _ => {
-                map.world
-                    .set(pos.x as usize, pos.y as usize, new_tile.clone());
-            },
-        }
-        map.world.set_dirty();
+/// Check if the given tile can be drawn with the drawing tools.
+/// Tiles that are unique within a map or that need to be edited in a dialog are always placed
+/// with a single click.
+fn is_drawable_with_tools(tile: &Tile) -> bool {
+    !matches!(
+        tile,
+        Tile::PLAYERSPAWN | Tile::TELEPORTEXIT { .. } | Tile::MESSAGE { .. }
+    )
+}
+
+/// A mouse drag that is currently drawn with a drawing tool
+struct Stroke {
+    /// The position where the drag has started
+    start: (usize, usize),
+    /// The position of the cursor in the last frame
+    last: (usize, usize),
+    /// The mouse button that is held down
+    button: MouseButton,
+    /// The tile that is drawn
+    tile: Tile,
+    /// True if the stroke has already been recorded in the editor history
+    recorded: bool,
+}
+
+#[derive(Resource, Default)]
+struct DrawingStroke {
+    stroke: Option<Stroke>,
+}
+
+/// Get the positions that are changed by the given tool for a drag from `start` to `end`
+fn tool_positions(
+    tool: &DrawingTool,
+    start: (usize, usize),
+    end: (usize, usize),
+    world: &GameWorld,
+) -> Vec<(usize, usize)> {
+    match tool {
+        DrawingTool::Pencil => vec![end],
+        DrawingTool::Line => line(start, end),
+        DrawingTool::Rectangle => rectangle(start, end, false),
+        DrawingTool::FilledRectangle => rectangle(start, end, true),
+        DrawingTool::Fill => flood_fill(world, start.0, start.1),
+        DrawingTool::Select | DrawingTool::Stamp | DrawingTool::Link => vec![],
     }
 }
 
@@ -141,73 +190,127 @@ fn mouse_down_handler(
     q_layer_camera: Query<(&Camera, &GlobalTransform), With<LayerCamera>>,
     q_main_camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
     mut map: ResMut<MapWrapper>,
+    mut history: ResMut<EditorHistory>,
     buttons: Res<ButtonInput<MouseButton>>,
     current_tile: Res<SelectedTile>,
-    mut tile_entity_query: Query<(Entity, &mut Transform, &mut Sprite), With<WorldTile>>,
+    tool: Res<DrawingTool>,
+    mut drawing_stroke: ResMut<DrawingStroke>,
+    mut shape_preview: ResMut<ShapePreview>,
+    tile_entity_query: Query<(Entity, &Transform), TileViewFilter>,
     atlas: Res<TilesetManager>,
     layer_query: Query<&RenderLayers, With<LayerCamera>>,
     config: Res<GameConfig>,
     mut state: ResMut<NextState<AppState>>,
 ) {
+    if !tool.places_selected_tile() {
+        // Selections and stamps are handled by the clipboard, links by the link tool
+        return;
+    }
     let (layer_camera, layer_camera_transform) = q_layer_camera
         .single()
         .expect("There were multiple layer cameras spawned");
     let (main_camera, main_camera_transform) = q_main_camera
         .single()
         .expect("There were multiple main cameras spawned");
-    if buttons.just_released(MouseButton::Left) {
-        if let Some((world_x, world_y)) = compute_cursor_position_in_world(
-            &wnds,
-            main_camera,
-            main_camera_transform,
-            layer_camera,
-            layer_camera_transform,
-            config.texture_size(),
-        ) {
-            replace_world_tile_at(
-                Vec2::new(world_x as f32, world_y as f32),
-                &current_tile.tile,
+    let layer: &RenderLayers = layer_query.single().unwrap();
+    let cursor = compute_cursor_position_in_world(
+        &wnds,
+        main_camera,
+        main_camera_transform,
+        layer_camera,
+        layer_camera_transform,
+        config.texture_size(),
+    )
+    .filter(|(x, y)| map.world.get(*x, *y).is_some())
+    .map(|(x, y)| (x as usize, y as usize));
+    // Start a new stroke. The right mouse button draws air, i.e., deletes tiles.
+    if drawing_stroke.stroke.is_none()
+        && let Some(position) = cursor
+    {
+        let drawn = if buttons.just_pressed(MouseButton::Left)
+            && is_drawable_with_tools(&current_tile.tile)
+        {
+            Some((MouseButton::Left, current_tile.tile.clone()))
+        } else if buttons.just_pressed(MouseButton::Right) {
+            Some((MouseButton::Right, Tile::AIR))
+        } else {
+            None
+        };
+        if let Some((button, tile)) = drawn {
+            drawing_stroke.stroke = Some(Stroke {
+                start: position,
+                last: position,
+                button,
+                tile,
+                recorded: false,
+            });
+        }
+    }
+    if let Some(stroke) = drawing_stroke.stroke.as_mut() {
+        let position = cursor.unwrap_or(stroke.last);
+        let released = !buttons.pressed(stroke.button);
+        if *tool == DrawingTool::Pencil {
+            // Paint a line to the last cursor position, such that fast mouse movements do not leave gaps
+            stroke.recorded |= replace_world_tiles_at(
+                line(stroke.last, position),
+                &stroke.tile,
+                stroke.recorded,
                 &mut commands,
                 &mut map,
-                &mut tile_entity_query,
+                &mut history,
+                &tile_entity_query,
                 &atlas,
-                layer_query,
+                layer,
             );
-            if let Some(Tile::MESSAGE { message_id }) = map.world.get(world_x, world_y) {
-                // The new tile is a message with the given ID. Open the Editor and allow the user to set or edit the message. Setting an appropriate ID has been taken care of by the replace_world_tile_at function.
-                commands.insert_resource(DialogResource {
-                    ui_dialog: Box::new(EditMessageDialog::new(
-                        *message_id,
-                        map.world.get_message(*message_id).unwrap_or("").to_string(),
-                    )),
-                });
-                state.set(AppState::MapEditorDialog);
-            }
+        } else if released {
+            replace_world_tiles_at(
+                tool_positions(&tool, stroke.start, position, &map.world),
+                &stroke.tile,
+                false,
+                &mut commands,
+                &mut map,
+                &mut history,
+                &tile_entity_query,
+                &atlas,
+                layer,
+            );
+        } else {
+            shape_preview.set_if_neq(ShapePreview {
+                tiles: tool_positions(&tool, stroke.start, position, &map.world)
+                    .into_iter()
+                    .map(|position| (position, stroke.tile.clone()))
+                    .collect(),
+            });
         }
-    } else if buttons.just_released(MouseButton::Right) {
-        // On Right Click, replace the current tile with air
-        if let Some((world_x, world_y)) = compute_cursor_position_in_world(
-            &wnds,
-            main_camera,
-            main_camera_transform,
-            layer_camera,
-            layer_camera_transform,
-            config.texture_size(),
-        ) {
-            if let Some(current_world_tile) = map.world.get(world_x, world_y) {
-                if *current_world_tile != Tile::AIR {
-                    replace_world_tile_at(
-                        Vec2::new(world_x as f32, world_y as f32),
-                        &Tile::AIR,
-                        &mut commands,
-                        &mut map,
-                        &mut tile_entity_query,
-                        &atlas,
-                        layer_query,
-                    );
-                    map.world.set_dirty();
-                }
-            }
+        stroke.last = position;
+        if released {
+            drawing_stroke.stroke = None;
+            shape_preview.set_if_neq(ShapePreview::default());
+        }
+    } else if buttons.just_released(MouseButton::Left)
+        && let Some((world_x, world_y)) = cursor
+    {
+        // Tiles that cannot be drawn with tools are placed with a single click
+        replace_world_tiles_at(
+            [(world_x, world_y)],
+            &current_tile.tile,
+            false,
+            &mut commands,
+            &mut map,
+            &mut history,
+            &tile_entity_query,
+            &atlas,
+            layer,
+        );
+        if let Some(Tile::MESSAGE { message_id }) = map.world.get(world_x as i32, world_y as i32) {
+            // The new tile is a message with the given ID. Open the Editor and allow the user to set or edit the message. Setting an appropriate ID has been taken care of by the history.
+            commands.insert_resource(DialogResource {
+                ui_dialog: Box::new(EditMessageDialog::new(
+                    *message_id,
+                    map.world.get_message(*message_id).unwrap_or("").to_string(),
+                )),
+            });
+            state.set(AppState::MapEditorDialog);
         }
     }
 }
@@ -219,10 +322,14 @@ fn mouse_down_handler_playerspawn(
     _map: ResMut<MapWrapper>,
     buttons: Res<ButtonInput<MouseButton>>,
     current_tile: Res<SelectedTile>,
+    tool: Res<DrawingTool>,
     mut player_spawn_query: Query<&mut Transform, With<PlayerSpawnComponent>>,
     config: Res<GameConfig>,
 ) {
-    if current_tile.tile == Tile::PLAYERSPAWN && buttons.just_pressed(MouseButton::Left) {
+    if current_tile.tile == Tile::PLAYERSPAWN
+        && tool.places_selected_tile()
+        && buttons.just_pressed(MouseButton::Left)
+    {
         let (layer_camera, layer_camera_transform) = q_layer_camera.single().unwrap();
         let (main_camera, main_camera_transform) = q_main_camera.single().unwrap();
         if let Some((world_x, world_y)) = compute_cursor_position_in_world(
@@ -246,3 +353,46 @@ fn mouse_down_handler_playerspawn(
         }
     }
 }
+
+/// Undo the last edit on Ctrl+Z and redo the last undone edit on Ctrl+Y or Ctrl+Shift+Z
+fn undo_redo_keyboard_handler(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut map: ResMut<MapWrapper>,
+    mut history: ResMut<EditorHistory>,
+    tile_entity_query: Query<(Entity, &Transform), TileViewFilter>,
+    mut player_spawn_query: Query<&mut Transform, PlayerSpawnFilter>,
+    atlas: Res<TilesetManager>,
+    layer_query: Query<&RenderLayers, With<LayerCamera>>,
+    config: Res<GameConfig>,
+) {
+    if !keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        return;
+    }
+    let shift = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let changed = if keyboard_input.just_pressed(KeyCode::KeyZ) && !shift {
+        history.history.undo(&mut map.world)
+    } else if keyboard_input.just_pressed(KeyCode::KeyY)
+        || (keyboard_input.just_pressed(KeyCode::KeyZ) && shift)
+    {
+        history.history.redo(&mut map.world)
+    } else {
+        return;
+    };
+    let Some(changed) = changed else {
+        debug!("There is nothing to undo or redo");
+        return;
+    };
+    let layer: &RenderLayers = layer_query.single().unwrap();
+    for (x, y) in changed {
+        refresh_view_at(
+            &Vec2::new(x as f32, y as f32),
+            &map.world,
+            &mut commands,
+            &tile_entity_query,
+            &atlas,
+            layer,
+        );
+    }
+    refresh_player_spawn(&map.world, &mut player_spawn_query, config.texture_size());
+}
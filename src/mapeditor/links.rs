@@ -0,0 +1,131 @@
+use crate::game::camera::{LayerCamera, MainCamera};
+use crate::game::tilewrapper::MapWrapper;
+use crate::mapeditor::preview_tile::ShapePreview;
+use crate::mapeditor::{
+    DrawingTool, EditorHistory, MapeditorSystems, compute_cursor_position_in_world,
+};
+use crate::{AppState, GameConfig};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use libexodus::tiles::{Tile, TileKind};
+
+pub struct LinkPlugin;
+
+impl Plugin for LinkPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LinkingSwitch>()
+            .add_systems(
+                OnEnter(AppState::MapSelectionScreen),
+                discard_linking_switch,
+            )
+            .add_systems(
+                Update,
+                (
+                    discard_linking_switch.run_if(resource_changed::<DrawingTool>),
+                    link_mouse_handler,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::MapEditor))
+                    .in_set(MapeditorSystems::GameBoardMouseHandlers),
+            );
+    }
+}
+
+/// The switch whose links are currently edited with the link tool
+#[derive(Resource, Default)]
+pub struct LinkingSwitch {
+    pub switch: Option<(usize, usize)>,
+}
+
+fn discard_linking_switch(
+    mut linking_switch: ResMut<LinkingSwitch>,
+    mut shape_preview: ResMut<ShapePreview>,
+) {
+    *linking_switch = LinkingSwitch::default();
+    shape_preview.set_if_neq(ShapePreview::default());
+}
+
+/// Select a switch with the link tool, then link or unlink the clicked tiles to the selected switch.
+/// All linked tiles are previewed in the state they will have after the switch has been toggled.
+fn link_mouse_handler(
+    wnds: Query<&Window, With<PrimaryWindow>>,
+    q_layer_camera: Query<(&Camera, &GlobalTransform), With<LayerCamera>>,
+    q_main_camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut map: ResMut<MapWrapper>,
+    mut history: ResMut<EditorHistory>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    tool: Res<DrawingTool>,
+    mut linking_switch: ResMut<LinkingSwitch>,
+    mut shape_preview: ResMut<ShapePreview>,
+    config: Res<GameConfig>,
+) {
+    if *tool != DrawingTool::Link {
+        return;
+    }
+    let (layer_camera, layer_camera_transform) = q_layer_camera.single().unwrap();
+    let (main_camera, main_camera_transform) = q_main_camera.single().unwrap();
+    let cursor = compute_cursor_position_in_world(
+        &wnds,
+        main_camera,
+        main_camera_transform,
+        layer_camera,
+        layer_camera_transform,
+        config.texture_size(),
+    )
+    .filter(|(x, y)| map.world.get(*x, *y).is_some())
+    .map(|(x, y)| (x as usize, y as usize));
+    let is_switch = |position: (usize, usize)| {
+        map.world
+            .get(position.0 as i32, position.1 as i32)
+            .is_some_and(|tile| tile.kind() == TileKind::SWITCH)
+    };
+    // The selected switch might have been removed, e.g., by undoing an edit
+    if linking_switch
+        .switch
+        .is_some_and(|switch| !is_switch(switch))
+    {
+        linking_switch.switch = None;
+    }
+    if buttons.just_pressed(MouseButton::Right) {
+        linking_switch.switch = None;
+    } else if buttons.just_pressed(MouseButton::Left)
+        && let Some(position) = cursor
+    {
+        match linking_switch.switch {
+            Some(switch) if switch != position => {
+                let linked = history
+                    .history
+                    .toggle_link(&mut map.world, switch, position);
+                debug!(
+                    "{} the tile at {},{} to the switch at {},{}",
+                    if linked { "Linked" } else { "Unlinked" },
+                    position.0,
+                    position.1,
+                    switch.0,
+                    switch.1
+                );
+            },
+            Some(_) => linking_switch.switch = None,
+            None if is_switch(position) => linking_switch.switch = Some(position),
+            None => {},
+        }
+    }
+    // Mark the selected switch and show all linked tiles in their toggled state
+    shape_preview.set_if_neq(ShapePreview {
+        tiles: linking_switch
+            .switch
+            .map(|switch| {
+                std::iter::once((switch, Tile::AIR))
+                    .chain(map.world.linked_blocks(switch).into_iter().map(|block| {
+                        let toggled = map
+                            .world
+                            .get(block.0 as i32, block.1 as i32)
+                            .and_then(|tile| tile.toggled())
+                            .unwrap_or(Tile::AIR);
+                        (block, toggled)
+                    }))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    });
+}
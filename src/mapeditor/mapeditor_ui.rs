@@ -1,22 +1,26 @@
 use crate::dialogs::DialogResource;
+use crate::dialogs::resize_map_dialog::ResizeMapDialog;
 use crate::dialogs::save_file_dialog::SaveFileDialog;
 use crate::dialogs::unsaved_changes_dialog::UnsavedChangesDialog;
 use crate::game::constants::MAPEDITOR_BUTTON_SIZE;
 use crate::game::player::ReturnTo;
 use crate::game::tilewrapper::MapWrapper;
+use crate::mapeditor::clipboard::{ClipboardAction, EditorClipboard, Selection};
 use crate::mapeditor::player_spawn::{
     PlayerSpawnComponent, destroy_player_spawn, init_player_spawn,
 };
-use crate::mapeditor::{MapeditorSystems, SelectedTile};
+use crate::mapeditor::{DrawingTool, EditorHistory, MapeditorSystems, SelectedTile};
 use crate::textures::egui_textures::{EguiButtonTextures, atlas_to_egui_textures};
 use crate::ui::uicontrols::WindowUiOverlayInfo;
 use crate::ui::{UiSizeChangedEvent, check_ui_size_changed, image_button};
 use crate::{AppLabels, AppState, GameDirectoriesWrapper};
 use bevy::prelude::*;
+use bevy::tasks::AsyncComputeTaskPool;
 use bevy_egui::egui::load::SizedTexture;
 use bevy_egui::egui::{Align, Layout, TextBuffer, Ui, UiKind};
 use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
 use libexodus::tiles::{TeleportId, Tile, UITiles};
+use libexodus::world::validation::{validate, validate_structure};
 use std::borrow::Borrow;
 use strum::IntoEnumIterator;
 pub struct MapEditorUiPlugin;
@@ -87,6 +91,7 @@ fn mapeditor_ui(
     mut commands: Commands,
     mut egui_ctx: EguiContexts,
     mut selected_tile: ResMut<SelectedTile>,
+    mut selected_tool: ResMut<DrawingTool>,
     egui_textures: Res<EguiButtonTextures>,
     player: Query<&PlayerSpawnComponent>,
     mut state: ResMut<NextState<AppState>>,
@@ -95,6 +100,9 @@ fn mapeditor_ui(
     mut window_size_event_writer: EventWriter<UiSizeChangedEvent>,
     directories: Res<GameDirectoriesWrapper>,
     return_to: Res<ReturnTo>,
+    selection: Res<Selection>,
+    clipboard: Res<EditorClipboard>,
+    mut clipboard_actions: EventWriter<ClipboardAction>,
 ) {
     let player_it = player
         .iter()
@@ -146,6 +154,11 @@ fn mapeditor_ui(
                                     );
                                     if sbutton.clicked() {
                                         worldwrapper.world.recompute_hash();
+                                        // Checking if the exit can be reached might take a while
+                                        // on large maps, hence it runs in the background
+                                        let world = worldwrapper.world.clone();
+                                        let validation_task = AsyncComputeTaskPool::get()
+                                            .spawn(async move { validate(&world) });
                                         commands.insert_resource(DialogResource {
                                             ui_dialog: Box::new(SaveFileDialog::new(
                                                 worldwrapper.world.get_filename(),
@@ -154,6 +167,10 @@ fn mapeditor_ui(
                                                 &worldwrapper.world.hash_str().as_str()[..16],
                                                 &directories.game_directories,
                                                 worldwrapper.world.forced_tileset(),
+                                                worldwrapper.world.metadata(),
+                                                worldwrapper.world.lives(),
+                                                validate_structure(&worldwrapper.world),
+                                                validation_task,
                                             )),
                                         });
                                         state.set(AppState::MapEditorDialog);
@@ -286,6 +303,13 @@ fn mapeditor_ui(
                                 &mut selected_tile,
                                 player_it,
                             );
+                            tile_kind_selector_button_for(
+                                ui,
+                                egui_textures.borrow(),
+                                &Tile::CHECKPOINT,
+                                &mut selected_tile,
+                                player_it,
+                            );
                             tile_kind_selector_button_for(
                                 ui,
                                 egui_textures.borrow(),
@@ -300,6 +324,7 @@ fn mapeditor_ui(
                                 &mut selected_tile,
                                 player_it,
                             );
+                            // Crates are held back until their sprite is drawn into every tileset
                             ui.separator();
                             tile_kind_selector_button_for(
                                 ui,
@@ -487,6 +512,72 @@ fn mapeditor_ui(
                             );
                         })
                     });
+                    ui.scope(|ui| {
+                        ui.horizontal(|ui| {
+                            // Buttons for the different drawing tools
+                            for tool in DrawingTool::ALL {
+                                ui.selectable_value(
+                                    selected_tool.as_mut(),
+                                    tool,
+                                    t!(format!("map_editor.tools.{}", tool.str_id())),
+                                )
+                                .on_hover_text(t!(format!(
+                                    "map_editor.tools.{}_tooltip",
+                                    tool.str_id()
+                                )));
+                            }
+                            ui.separator();
+                            // Buttons for the selection and the clipboard
+                            for (action, str_id, enabled) in [
+                                (ClipboardAction::Copy, "copy", selection.corners.is_some()),
+                                (ClipboardAction::Cut, "cut", selection.corners.is_some()),
+                                (ClipboardAction::Paste, "paste", clipboard.region.is_some()),
+                                (
+                                    ClipboardAction::MirrorHorizontally,
+                                    "mirror_horizontally",
+                                    clipboard.region.is_some(),
+                                ),
+                                (
+                                    ClipboardAction::MirrorVertically,
+                                    "mirror_vertically",
+                                    clipboard.region.is_some(),
+                                ),
+                                (
+                                    ClipboardAction::RotateClockwise,
+                                    "rotate",
+                                    clipboard.region.is_some(),
+                                ),
+                            ] {
+                                let button =
+                                    ui.add_enabled(
+                                        enabled,
+                                        egui::Button::new(t!(format!(
+                                            "map_editor.clipboard.{}",
+                                            str_id
+                                        ))),
+                                    )
+                                    .on_hover_text(t!(
+                                        format!("map_editor.clipboard.{}_tooltip", str_id)
+                                    ));
+                                if button.clicked() {
+                                    clipboard_actions.write(action);
+                                }
+                            }
+                            ui.separator();
+                            let resize_button = ui
+                                .button(t!("map_editor.dialog.resize_button"))
+                                .on_hover_text(t!("map_editor.dialog.resize_tooltip"));
+                            if resize_button.clicked() {
+                                commands.insert_resource(DialogResource {
+                                    ui_dialog: Box::new(ResizeMapDialog::new(
+                                        worldwrapper.world.width(),
+                                        worldwrapper.world.height(),
+                                    )),
+                                });
+                                state.set(AppState::MapEditorDialog);
+                            }
+                        })
+                    });
                 });
             });
     let top = panel.response.rect.height();
@@ -511,6 +602,8 @@ fn mapeditor_ui(
                         player_it,
                     );
                 }
+                // Colored keys and doors, levers, toggle blocks, crumbling platforms and falling blocks
+                // are held back until their sprites are drawn into every tileset
             });
         });
     let left = left_panel.response.rect.width();
@@ -531,6 +624,7 @@ fn mapeditor_dialog(
     mut dialog: ResMut<DialogResource>,
     mut state: ResMut<NextState<AppState>>,
     mut worldwrapper: ResMut<MapWrapper>,
+    mut history: ResMut<EditorHistory>,
     directories: Res<GameDirectoriesWrapper>,
     return_to: Res<ReturnTo>,
     mut commands: Commands,
@@ -556,7 +650,12 @@ fn mapeditor_dialog(
             worldwrapper
                 .world
                 .set_forced_tileset(save_dialog.get_forced_tileset());
+            worldwrapper.world.set_metadata(save_dialog.get_metadata());
+            // The number of lives is part of the map hash, hence the hash needs to be updated
+            worldwrapper.world.set_lives(save_dialog.get_lives());
+            worldwrapper.world.recompute_hash();
             if worldwrapper.world.get_filename().is_some() {
+                worldwrapper.world.update_timestamps_with_current_time();
                 let result = worldwrapper
                     .world
                     .save_to_file(worldwrapper.world.get_filename().unwrap());
@@ -564,6 +663,7 @@ fn mapeditor_dialog(
                     Ok(_) => {
                         // Mark map as clean, i.e. there are no unsaved changes
                         worldwrapper.world.set_clean();
+                        history.history.mark_clean();
                     },
                     Err(v) => {
                         error!(
@@ -584,10 +684,29 @@ fn mapeditor_dialog(
             state.set(AppState::MapEditor);
         } else if dialog.ui_dialog.as_unsaved_changes_dialog().is_some() {
             state.set(return_to.0);
+        } else if let Some(resize_dialog) = dialog.ui_dialog.as_resize_map_dialog() {
+            let (width, height) = (resize_dialog.get_width(), resize_dialog.get_height());
+            if (width, height) != (worldwrapper.world.width(), worldwrapper.world.height()) {
+                match worldwrapper
+                    .world
+                    .resize(width, height, resize_dialog.get_anchor())
+                {
+                    Ok(_) => {
+                        debug!("Resized map to {}x{}", width, height);
+                        // Previous edits refer to the old tile positions and cannot be undone anymore
+                        history.history.clear();
+                    },
+                    Err(e) => {
+                        error!("Could not resize map to {}x{}: {}", width, height, e);
+                    },
+                }
+            }
+            state.set(AppState::MapEditor);
         } else if let Some(edit_dialog) = dialog.ui_dialog.as_edit_message_dialog() {
-            worldwrapper
-                .world
+            history
+                .history
                 .set_message(
+                    &mut worldwrapper.world,
                     edit_dialog.get_message_id(),
                     edit_dialog.get_message().to_string(),
                 )
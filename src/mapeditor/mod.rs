@@ -1,13 +1,19 @@
+use crate::AppState;
 use crate::game::camera::compute_viewport_to_world;
 use crate::game::tilewrapper::MapWrapper;
+use crate::mapeditor::clipboard::ClipboardPlugin;
 use crate::mapeditor::edit_world::EditWorldPlugin;
+use crate::mapeditor::links::LinkPlugin;
 use crate::mapeditor::mapeditor_ui::MapEditorUiPlugin;
 use crate::mapeditor::preview_tile::MapEditorPreviewTilePlugin;
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 use libexodus::tiles::Tile;
+use libexodus::world::history::EditHistory;
 
+mod clipboard;
 mod edit_world;
+mod links;
 mod mapeditor_ui;
 mod player_spawn;
 mod preview_tile;
@@ -32,15 +38,87 @@ impl FromWorld for SelectedTile {
     }
 }
 
+/// The tool that is used to place the selected tile in the map editor.
+/// The right mouse button uses the same tool to delete tiles, i.e., to place air.
+#[derive(Resource, Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum DrawingTool {
+    /// Place tiles under the cursor while the mouse button is held down
+    #[default]
+    Pencil,
+    /// Place tiles on a straight line from where the mouse button was pressed to where it was released
+    Line,
+    /// Place the border of a rectangle spanned by the mouse drag
+    Rectangle,
+    /// Place a filled rectangle spanned by the mouse drag
+    FilledRectangle,
+    /// Replace all connected tiles that are equal to the clicked tile
+    Fill,
+    /// Select a rectangular region that can be copied or cut
+    Select,
+    /// Paste the copied region wherever the map is clicked
+    Stamp,
+    /// Link blocks to the clicked switch, such that they are toggled by the switch
+    Link,
+}
+
+impl DrawingTool {
+    pub const ALL: [DrawingTool; 8] = [
+        DrawingTool::Pencil,
+        DrawingTool::Line,
+        DrawingTool::Rectangle,
+        DrawingTool::FilledRectangle,
+        DrawingTool::Fill,
+        DrawingTool::Select,
+        DrawingTool::Stamp,
+        DrawingTool::Link,
+    ];
+    pub fn str_id(&self) -> &'static str {
+        match self {
+            DrawingTool::Pencil => "pencil",
+            DrawingTool::Line => "line",
+            DrawingTool::Rectangle => "rectangle",
+            DrawingTool::FilledRectangle => "filled_rectangle",
+            DrawingTool::Fill => "fill",
+            DrawingTool::Select => "select",
+            DrawingTool::Stamp => "stamp",
+            DrawingTool::Link => "link",
+        }
+    }
+    /// Check if this tool places the currently selected tile
+    pub fn places_selected_tile(&self) -> bool {
+        !matches!(
+            self,
+            DrawingTool::Select | DrawingTool::Stamp | DrawingTool::Link
+        )
+    }
+}
+
+/// The undo and redo history of the map that is currently being edited
+#[derive(Resource, Default)]
+pub struct EditorHistory {
+    pub history: EditHistory,
+}
+
+/// Discard the edit history. The map editor is always entered from the map selection screen,
+/// hence the history is cleared there before another map can be edited.
+fn clear_editor_history(mut history: ResMut<EditorHistory>) {
+    history.history.clear();
+}
+
 pub struct MapEditorPlugin;
 
 impl Plugin for MapEditorPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<MapWrapper>()
+            .init_resource::<EditorHistory>()
+            .init_resource::<DrawingTool>()
+            .add_systems(OnEnter(AppState::MapSelectionScreen), clear_editor_history)
             // The world plugin is already added here. Adding it twice causes an error
             .add_plugins(MapEditorUiPlugin)
             .add_plugins(MapEditorPreviewTilePlugin)
-            .add_plugins(EditWorldPlugin);
+            .add_plugins(EditWorldPlugin)
+            .add_plugins(ClipboardPlugin)
+            .add_plugins(LinkPlugin);
     }
 }
 
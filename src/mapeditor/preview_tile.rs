@@ -3,7 +3,7 @@ use crate::game::constants::{
     MAPEDITOR_PREVIEWTILE_AIR_ATLAS_INDEX, MAPEDITOR_PREVIEWTILE_ALPHA, MAPEDITOR_PREVIEWTILE_Z,
 };
 use crate::game::tilewrapper::MapWrapper;
-use crate::mapeditor::{SelectedTile, compute_cursor_position_in_world};
+use crate::mapeditor::{DrawingTool, SelectedTile, compute_cursor_position_in_world};
 use crate::{App, AppState, GameConfig, LAYER_ID, TilesetManager};
 use bevy::prelude::*;
 use bevy::render::view::RenderLayers;
@@ -16,15 +16,34 @@ pub struct PreviewTile {
     current_tile: Tile,
 }
 
+/// A transparent preview of a tile that will be placed by the current drawing tool
+#[derive(Component)]
+struct ShapePreviewTile;
+
+/// The tiles that will be placed by the current drawing tool once the mouse button is released,
+/// together with their positions
+#[derive(Resource, Default, PartialEq)]
+pub struct ShapePreview {
+    pub tiles: Vec<((usize, usize), Tile)>,
+}
+
 pub struct MapEditorPreviewTilePlugin;
 
 impl Plugin for MapEditorPreviewTilePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::MapEditor), setup_preview_tile)
-            .add_systems(OnExit(AppState::MapEditor), destroy_preview_tile)
+        app.init_resource::<ShapePreview>()
+            .add_systems(OnEnter(AppState::MapEditor), setup_preview_tile)
+            .add_systems(
+                OnExit(AppState::MapEditor),
+                (destroy_preview_tile, destroy_shape_preview),
+            )
             .add_systems(
                 Update,
-                update_preview_tile.run_if(in_state(AppState::MapEditor)),
+                (
+                    update_preview_tile,
+                    update_shape_preview.run_if(resource_changed::<ShapePreview>),
+                )
+                    .run_if(in_state(AppState::MapEditor)),
             );
     }
 }
@@ -34,17 +53,34 @@ fn destroy_preview_tile(mut commands: Commands, preview_tile_q: Query<Entity, Wi
     commands.entity(ent).despawn();
 }
 
-/// Spawn a WALL PreviewTile at an invisible position
-pub fn setup_preview_tile(mut commands: Commands, current_texture_atlas: Res<TilesetManager>) {
-    let previewtile: PreviewTile = PreviewTile {
-        current_tile: Tile::WALL,
-    };
-    let layer = RenderLayers::layer(LAYER_ID);
+fn destroy_shape_preview(
+    mut commands: Commands,
+    shape_preview_q: Query<Entity, With<ShapePreviewTile>>,
+    mut shape_preview: ResMut<ShapePreview>,
+) {
+    for ent in shape_preview_q.iter() {
+        commands.entity(ent).despawn();
+    }
+    *shape_preview = ShapePreview::default();
+}
+
+/// Get the atlas index that is used to preview the given tile
+fn preview_atlas_index(tile: &Tile) -> usize {
+    match *tile {
+        Tile::PLAYERSPAWN => Player::new().atlas_index(),
+        _ => tile
+            .atlas_index()
+            .unwrap_or(MAPEDITOR_PREVIEWTILE_AIR_ATLAS_INDEX),
+    }
+}
+
+/// Create a transparent sprite that previews the given tile
+fn preview_sprite(tile: &Tile, current_texture_atlas: &TilesetManager) -> Sprite {
     let mut sprite = Sprite::from_atlas_image(
         current_texture_atlas.current_texture_handle(),
         TextureAtlas {
             layout: current_texture_atlas.current_atlas_handle(),
-            index: previewtile.current_tile.atlas_index().unwrap(),
+            index: preview_atlas_index(tile),
         },
     );
     sprite.color = Color::Srgba(Srgba {
@@ -53,6 +89,16 @@ pub fn setup_preview_tile(mut commands: Commands, current_texture_atlas: Res<Til
         blue: 1.0,
         alpha: MAPEDITOR_PREVIEWTILE_ALPHA,
     });
+    sprite
+}
+
+/// Spawn a WALL PreviewTile at an invisible position
+pub fn setup_preview_tile(mut commands: Commands, current_texture_atlas: Res<TilesetManager>) {
+    let previewtile: PreviewTile = PreviewTile {
+        current_tile: Tile::WALL,
+    };
+    let layer = RenderLayers::layer(LAYER_ID);
+    let sprite = preview_sprite(&previewtile.current_tile, &current_texture_atlas);
     commands.spawn((
         sprite,
         Transform::from_translation(Vec3::new(-1f32, -1f32, MAPEDITOR_PREVIEWTILE_Z)),
@@ -68,21 +114,8 @@ fn set_preview_tile_texture(
     current_texture_atlas: &TilesetManager,
 ) {
     if let Some(ref mut a) = sprite.texture_atlas {
-        match *new_tile {
-            Tile::PLAYERSPAWN => {
-                a.layout = current_texture_atlas.current_atlas_handle();
-                a.index = Player::new().atlas_index();
-            },
-            _ => {
-                if let Some(atlas_index) = new_tile.atlas_index() {
-                    a.layout = current_texture_atlas.current_atlas_handle();
-                    a.index = atlas_index;
-                } else {
-                    a.layout = current_texture_atlas.current_atlas_handle();
-                    a.index = MAPEDITOR_PREVIEWTILE_AIR_ATLAS_INDEX;
-                }
-            },
-        }
+        a.layout = current_texture_atlas.current_atlas_handle();
+        a.index = preview_atlas_index(new_tile);
     }
     sprite.image = current_texture_atlas.current_texture_handle();
     preview_tile.current_tile = new_tile.clone();
@@ -95,6 +128,7 @@ fn update_preview_tile(
     q_main_camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
     map: Res<MapWrapper>,
     current_tile: Res<SelectedTile>,
+    tool: Res<DrawingTool>,
     mut preview_tile_q: Query<(&mut PreviewTile, &mut Sprite, &mut Transform)>,
     current_texture_atlas: Res<TilesetManager>,
     config: Res<GameConfig>,
@@ -119,7 +153,8 @@ fn update_preview_tile(
         config.texture_size(),
     ) {
         // The cursor is inside the window
-        if world_x_coord >= 0
+        if tool.places_selected_tile()
+            && world_x_coord >= 0
             && world_y_coord >= 0
             && world_x_coord < map.world.width() as i32
             && world_y_coord < map.world.height() as i32
@@ -137,3 +172,29 @@ fn update_preview_tile(
         transform.translation.y = -10000.0;
     }
 }
+
+/// System to show transparent preview tiles for all tiles that will be placed by the current drawing tool
+fn update_shape_preview(
+    mut commands: Commands,
+    shape_preview: Res<ShapePreview>,
+    shape_preview_q: Query<Entity, With<ShapePreviewTile>>,
+    current_texture_atlas: Res<TilesetManager>,
+    config: Res<GameConfig>,
+) {
+    for ent in shape_preview_q.iter() {
+        commands.entity(ent).despawn();
+    }
+    let layer = RenderLayers::layer(LAYER_ID);
+    for ((x, y), tile) in shape_preview.tiles.iter() {
+        commands.spawn((
+            preview_sprite(tile, &current_texture_atlas),
+            Transform::from_translation(Vec3::new(
+                *x as f32 * config.texture_size(),
+                *y as f32 * config.texture_size(),
+                MAPEDITOR_PREVIEWTILE_Z,
+            )),
+            ShapePreviewTile,
+            layer.clone(),
+        ));
+    }
+}
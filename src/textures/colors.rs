@@ -20,6 +20,8 @@ impl Plugin for ColorsPlugin {
         app.add_systems(OnEnter(AppState::MainMenu), set_menu_colors)
             .add_systems(OnEnter(AppState::MapSelectionScreen), set_menu_colors)
             .add_systems(OnEnter(AppState::CreditsScreen), set_menu_colors)
-            .add_systems(OnEnter(AppState::ConfigScreen), set_menu_colors);
+            .add_systems(OnEnter(AppState::ConfigScreen), set_menu_colors)
+            .add_systems(OnEnter(AppState::CampaignSelectionScreen), set_menu_colors)
+            .add_systems(OnEnter(AppState::CampaignTrailEditor), set_menu_colors);
     }
 }
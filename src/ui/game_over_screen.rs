@@ -1,14 +1,17 @@
 use crate::game::HighscoresDatabaseWrapper;
 use crate::game::player::ReturnTo;
+use crate::game::replay::{RecordedReplay, ReplayMode, save_replay};
 use crate::game::scoreboard::GameOverState;
 use crate::game::tilewrapper::MapWrapper;
 use crate::textures::egui_textures::EguiButtonTextures;
 use crate::ui::{UIBIGMARGIN, UIMARGIN, UIPANELWIDTH, image_button};
-use crate::{AppState, GameConfig};
+use crate::{AppState, GameConfig, GameDirectoriesWrapper};
 use bevy::prelude::*;
 use bevy_egui::egui::Frame;
 use bevy_egui::{EguiContexts, EguiPreUpdateSet, EguiPrimaryContextPass, egui};
 use libexodus::highscores::highscore::Highscore;
+use libexodus::replay::Replay;
+use libexodus::simulation::SimulationState;
 use libexodus::tiles::UITiles;
 
 pub struct GameOverScreen;
@@ -22,6 +25,8 @@ fn game_over_screen_ui(
     return_to: Res<ReturnTo>,
     config: Res<GameConfig>,
     mut save_state: ResMut<SaveHighscoreState>,
+    recorded: Res<RecordedReplay>,
+    mut replay_mode: ResMut<ReplayMode>,
 ) {
     egui::CentralPanel::default()
         .frame(Frame::NONE)
@@ -60,6 +65,12 @@ fn game_over_screen_ui(
                                         "Coins: {} Moves: {}",
                                         score.coins, score.moves
                                     ));
+                                    if score.deaths > 0 {
+                                        ui.label(t!(
+                                            "game_over_screen.deaths_fmt",
+                                            deaths = &score.deaths.to_string()
+                                        ));
+                                    }
                                     //TODO Table with previous best/Map Name/Coins,Moves/Player Name
                                 },
                             });
@@ -72,6 +83,9 @@ fn game_over_screen_ui(
                                     GameOverState::Lost => {
                                         t!("game_over_screen.highscore_info.lost")
                                     },
+                                    GameOverState::Won { .. } if recorded.is_playback => {
+                                        t!("game_over_screen.highscore_info.playback")
+                                    },
                                     GameOverState::Won { .. } => match &*save_state {
                                         SaveHighscoreState::Save => t!(
                                             "game_over_screen.highscore_info.won",
@@ -132,6 +146,18 @@ fn game_over_screen_ui(
                 if replay_button.clicked() {
                     state.set(AppState::Playing);
                 }
+                if !recorded.replay.is_empty() {
+                    let watch_replay_button = image_button(
+                        ui,
+                        &egui_textures,
+                        &UITiles::PLAYBUTTON,
+                        "game_over_screen.watch_replay_button_tooltip",
+                    );
+                    if watch_replay_button.clicked() {
+                        *replay_mode = ReplayMode::playback(recorded.replay.clone());
+                        state.set(AppState::Playing);
+                    }
+                }
             });
         });
 }
@@ -143,6 +169,8 @@ fn save_highscores(
     map: Res<MapWrapper>,
     mut highscore_database: ResMut<HighscoresDatabaseWrapper>,
     save_state: Res<SaveHighscoreState>,
+    recorded: Res<RecordedReplay>,
+    directories: Res<GameDirectoriesWrapper>,
 ) {
     match &*game_status {
         GameOverState::Lost => {
@@ -150,16 +178,22 @@ fn save_highscores(
         },
         GameOverState::Won { score } => match &*save_state {
             SaveHighscoreState::Save => {
-                highscore_database.highscores.put_with_current_time(
-                    *map.world.hash(),
-                    config.config.player_id.clone(),
-                    Highscore::new(score.moves as u32, score.coins as u32),
-                );
+                // Store the replay with the highscore, such that the highscore can be verified later
+                highscore_database
+                    .highscores
+                    .put_with_current_time_and_replay(
+                        *map.world.hash(),
+                        config.config.player_id.clone(),
+                        Highscore::new(score.moves as u32, score.coins as u32)
+                            .with_deaths(score.deaths as u32),
+                        recorded.replay.clone(),
+                    );
                 info!(
-                    "Added Highscore for player {} with {} moves and {} coins in map with hash {} to the highscores database.",
+                    "Added Highscore for player {} with {} moves, {} coins and {} deaths in map with hash {} to the highscores database.",
                     config.config.player_id,
                     score.moves,
                     score.coins,
+                    score.deaths,
                     map.world.hash_str()
                 );
                 match highscore_database
@@ -188,6 +222,7 @@ fn save_highscores(
                         );
                     },
                 }
+                save_best_replay(&recorded, &map, (score.coins, score.moves), &directories);
             },
             SaveHighscoreState::NoSave => {},
         },
@@ -196,14 +231,41 @@ fn save_highscores(
     commands.remove_resource::<GameOverState>();
 }
 
+/// Save the recorded replay as the replay of the given map, if it is better than the replay that
+/// has been saved for the map before.
+fn save_best_replay(
+    recorded: &RecordedReplay,
+    map: &MapWrapper,
+    (coins, moves): (i32, usize),
+    directories: &GameDirectoriesWrapper,
+) {
+    let replay_file = directories.game_directories.replay_file(map.world.hash());
+    let previous_best = Replay::load_from_file(replay_file.as_path())
+        .ok()
+        .and_then(|replay| replay.simulate(&map.world))
+        .filter(|simulation| simulation.state() == SimulationState::Won);
+    if let Some(previous_best) = previous_best {
+        if previous_best.coins() as i32 - previous_best.moves() as i32 >= coins - moves as i32 {
+            debug!(
+                "Kept the previous replay of map {}, because it has a better score",
+                map.world.hash_str()
+            );
+            return;
+        }
+    }
+    save_replay(&recorded.replay, replay_file.as_path());
+}
+
 /// Init Function for the Game Over Screen
 fn init_game_over_screen_ui(
     mut commands: Commands,
     config: Res<GameConfig>,
     game_status: Res<GameOverState>,
+    recorded: Res<RecordedReplay>,
 ) {
     commands.insert_resource(match *game_status {
         GameOverState::Lost => SaveHighscoreState::NoSave,
+        GameOverState::Won { .. } if recorded.is_playback => SaveHighscoreState::NoSave,
         GameOverState::Won { .. } => {
             if config.config.player_id.trim().is_empty() {
                 SaveHighscoreState::NoSave
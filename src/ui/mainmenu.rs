@@ -23,7 +23,7 @@ fn mainmenu_buttons(
                 ui.centered_and_justified(|ui| {
                     let campaign_btn = ui.button(t!("main_menu.campaign_screen"));
                     if campaign_btn.clicked() {
-                        state.set(AppState::CampaignTrailScreen);
+                        state.set(AppState::CampaignSelectionScreen);
                     }
                 });
             });
@@ -3,9 +3,11 @@ use crate::dialogs::create_new_map_dialog::{
     CreateMapBackgroundWorkerThread, CreateNewMapDialog, bevy_job_handler,
 };
 use crate::dialogs::delete_map_dialog::DeleteMapDialog;
+use crate::dialogs::save_file_dialog::{difficulty_text, validation_report_labels};
 use crate::game::HighscoresDatabaseWrapper;
 use crate::game::camera::{destroy_camera, handle_ui_resize, setup_camera};
 use crate::game::player::ReturnTo;
+use crate::game::replay::ReplayMode;
 use crate::game::scoreboard::{Scoreboard, egui_highscore_label};
 use crate::game::tilewrapper::MapWrapper;
 use crate::game::world::destroy_world;
@@ -18,30 +20,136 @@ use bevy_egui::egui::load::SizedTexture;
 use bevy_egui::egui::{Align, Layout, RichText, Ui};
 use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
 use libexodus::highscores::highscores_database::HighscoresDatabase;
+use libexodus::replay::Replay;
 use libexodus::tiles::UITiles;
+use libexodus::world::metadata::Difficulty;
+use libexodus::world::validation::{ValidationReport, validate_structure};
 use libexodus::world::{GameWorld, presets};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use strum::IntoEnumIterator;
 
 #[derive(Resource)]
 struct Maps {
     maps: Vec<MapWrapper>,
+    /// The hashes of all maps that have a saved replay
+    replays: HashSet<[u8; 32]>,
+    /// The issues that have been found in each map, by map hash.
+    /// Only the structure of the maps is validated, since solving all maps would take too long.
+    validation: HashMap<[u8; 32], ValidationReport>,
 }
 
 impl FromWorld for Maps {
     fn from_world(world: &mut World) -> Self {
         Maps {
             maps: vec![MapWrapper::from_world(world)],
+            replays: HashSet::new(),
+            validation: HashMap::new(),
         }
     }
 }
 
+/// The order in which the maps are listed in the map selection screen
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+enum MapSortOrder {
+    #[default]
+    Name,
+    Author,
+    Difficulty,
+    ParMoves,
+    Created,
+    Modified,
+}
+
+impl MapSortOrder {
+    const ALL: [MapSortOrder; 6] = [
+        MapSortOrder::Name,
+        MapSortOrder::Author,
+        MapSortOrder::Difficulty,
+        MapSortOrder::ParMoves,
+        MapSortOrder::Created,
+        MapSortOrder::Modified,
+    ];
+    fn text(&self) -> String {
+        match self {
+            MapSortOrder::Name => t!("map_selection_screen.sort_by_name"),
+            MapSortOrder::Author => t!("map_selection_screen.sort_by_author"),
+            MapSortOrder::Difficulty => t!("map_selection_screen.sort_by_difficulty"),
+            MapSortOrder::ParMoves => t!("map_selection_screen.sort_by_par_moves"),
+            MapSortOrder::Created => t!("map_selection_screen.sort_by_created"),
+            MapSortOrder::Modified => t!("map_selection_screen.sort_by_modified"),
+        }
+        .to_string()
+    }
+    /// Compare the given maps. Maps without the respective metadata are always sorted last.
+    fn compare(&self, a: &GameWorld, b: &GameWorld) -> Ordering {
+        /// Compare the given optional values, sorting None last
+        fn some_first<T: Ord>(a: Option<T>, b: Option<T>) -> Ordering {
+            match (a, b) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (a, b) => b.is_some().cmp(&a.is_some()),
+            }
+        }
+        let (ma, mb) = (a.metadata(), b.metadata());
+        match self {
+            MapSortOrder::Name => a
+                .get_name()
+                .to_lowercase()
+                .cmp(&b.get_name().to_lowercase()),
+            MapSortOrder::Author => a
+                .get_author()
+                .to_lowercase()
+                .cmp(&b.get_author().to_lowercase()),
+            MapSortOrder::Difficulty => some_first(ma.difficulty, mb.difficulty),
+            MapSortOrder::ParMoves => some_first(ma.par_moves, mb.par_moves),
+            // The newest maps are listed first
+            MapSortOrder::Created => some_first(mb.created, ma.created).reverse(),
+            MapSortOrder::Modified => some_first(mb.modified, ma.modified).reverse(),
+        }
+    }
+}
+
+/// The filter and sort order that is applied to the list of maps
+#[derive(Resource, Default)]
+struct MapFilter {
+    /// Only maps that match this search query are shown
+    query: String,
+    /// If set, only maps with this difficulty are shown
+    difficulty: Option<Difficulty>,
+    sort_order: MapSortOrder,
+}
+
+impl MapFilter {
+    fn matches(&self, map: &GameWorld) -> bool {
+        map.matches_search(&self.query)
+            && self
+                .difficulty
+                .is_none_or(|difficulty| map.metadata().difficulty == Some(difficulty))
+    }
+}
+
+/// Sort the given maps with the given sort order
+fn sort_maps(maps: &mut [MapWrapper], sort_order: MapSortOrder) {
+    maps.sort_by(|a, b| {
+        sort_order
+            .compare(&a.world, &b.world)
+            .then_with(|| MapSortOrder::Name.compare(&a.world, &b.world))
+    });
+}
+
+/// Get the best highscore of the given player in the given map, and whether it has been verified
 fn get_highscore(
     highscores: &HighscoresDatabase,
     map: &GameWorld,
     player_name: &String,
-) -> Option<Scoreboard> {
-    highscores
+) -> (Option<Scoreboard>, bool) {
+    let highscore = highscores
         .get_best(map.hash(), player_name)
-        .map(|highscore| highscore.1.into())
+        .map(|highscore| highscore.1.into());
+    let verified = highscores
+        .verify_best(map, player_name)
+        .is_some_and(|verification| verification.is_verified());
+    (highscore, verified)
 }
 
 /// Load all maps from the Map Directory. This might take a while, depending on how many maps there are in the maps folder
@@ -50,9 +158,12 @@ fn load_maps(
     directories: Res<GameDirectoriesWrapper>,
     highscores: Res<HighscoresDatabaseWrapper>,
     config: Res<GameConfig>,
+    filter: Res<MapFilter>,
 ) {
     // Delete all maps
     maps.maps = Vec::new();
+    maps.replays = HashSet::new();
+    maps.validation = HashMap::new();
 
     // Load all maps from the game's map directory and all subdirectories
     directories
@@ -77,21 +188,23 @@ fn load_maps(
                     map
                 })
             {
-                let highscore =
+                let (highscore, verified) =
                     get_highscore(&highscores.highscores, &map, &config.config.player_id);
                 maps.maps.push(MapWrapper {
                     world: map,
                     previous_best: highscore,
+                    previous_best_verified: verified,
                 })
             }
         });
+    sort_maps(&mut maps.maps, filter.sort_order);
 
     //If we are in debug mode, insert the debug maps
     if cfg!(debug_assertions) {
         let mut example_map = GameWorld::exampleworld();
         example_map.set_name(t!("debug.map_presets.example_world").as_ref());
         example_map.recompute_hash();
-        let example_map_highscore = get_highscore(
+        let (example_map_highscore, example_map_verified) = get_highscore(
             &highscores.highscores,
             &example_map,
             &config.config.player_id,
@@ -99,11 +212,12 @@ fn load_maps(
         maps.maps.push(MapWrapper {
             world: example_map,
             previous_best: example_map_highscore,
+            previous_best_verified: example_map_verified,
         });
         let mut showcasemap = GameWorld::showcaseworld();
         showcasemap.set_name(t!("debug.map_presets.showcase").as_ref());
         showcasemap.recompute_hash();
-        let showcasemap_highscore = get_highscore(
+        let (showcasemap_highscore, showcasemap_verified) = get_highscore(
             &highscores.highscores,
             &showcasemap,
             &config.config.player_id,
@@ -111,6 +225,7 @@ fn load_maps(
         maps.maps.push(MapWrapper {
             world: showcasemap,
             previous_best: showcasemap_highscore,
+            previous_best_verified: showcasemap_verified,
         });
         let mut psion_sized_map = presets::map_with_border(35, 15);
         psion_sized_map.set_name(t!("debug.map_presets.empty5mx").as_ref());
@@ -118,6 +233,7 @@ fn load_maps(
         maps.maps.push(MapWrapper {
             world: psion_sized_map,
             previous_best: None,
+            previous_best_verified: false,
         });
         // Fill the list to test scrolling
         for i in 1..=20 {
@@ -134,9 +250,28 @@ fn load_maps(
             maps.maps.push(MapWrapper {
                 world: map,
                 previous_best: None,
+                previous_best_verified: false,
             })
         }
     }
+
+    // Find all maps that have a saved replay
+    let replays = maps
+        .maps
+        .iter()
+        .map(|map| *map.world.hash())
+        .filter(|hash| directories.game_directories.replay_file(hash).exists())
+        .collect();
+    maps.replays = replays;
+
+    // Validate all maps
+    let validation = maps
+        .maps
+        .iter()
+        .map(|map| (*map.world.hash(), validate_structure(&map.world)))
+        .filter(|(_, report)| !report.is_empty())
+        .collect();
+    maps.validation = validation;
 }
 
 #[derive(Resource)]
@@ -144,6 +279,7 @@ enum MapSelectionScreenAction {
     Play { map_index: usize },
     Delete { map_index: usize },
     Edit { map_index: usize },
+    WatchReplay { map_index: usize },
     None,
 }
 
@@ -158,6 +294,8 @@ fn map_selection_screen_execute_event_queue(
     action: Res<MapSelectionScreenAction>,
     mut maps: ResMut<Maps>,
     mut state: ResMut<NextState<AppState>>,
+    directories: Res<GameDirectoriesWrapper>,
+    mut replay_mode: ResMut<ReplayMode>,
 ) {
     match *action {
         MapSelectionScreenAction::Play { map_index } => {
@@ -181,6 +319,26 @@ fn map_selection_screen_execute_event_queue(
             state.set(AppState::MapEditor);
             commands.insert_resource(MapSelectionScreenAction::None)
         },
+        MapSelectionScreenAction::WatchReplay { map_index } => {
+            let replay_file = directories
+                .game_directories
+                .replay_file(maps.maps[map_index].world.hash());
+            match Replay::load_from_file(replay_file.as_path()) {
+                Ok(replay) => {
+                    let mapwrapper = maps.maps.remove(map_index);
+                    commands.insert_resource(mapwrapper);
+                    commands.insert_resource(ReturnTo(AppState::MapSelectionScreen));
+                    *replay_mode = ReplayMode::playback(replay);
+                    state.set(AppState::Playing);
+                },
+                Err(e) => error!(
+                    "Could not load replay file at {}! Error: {}",
+                    replay_file.to_str().unwrap_or("<Invalid Path>"),
+                    e
+                ),
+            }
+            commands.insert_resource(MapSelectionScreenAction::None)
+        },
         MapSelectionScreenAction::None => {},
     }
 }
@@ -191,7 +349,8 @@ fn map_selection_screen_ui(
     mut egui_ctx: EguiContexts,
     mut state: ResMut<NextState<AppState>>,
     egui_textures: Res<EguiButtonTextures>,
-    maps: Res<Maps>,
+    mut maps: ResMut<Maps>,
+    mut filter: ResMut<MapFilter>,
 ) {
     add_navbar_with_extra_buttons(
         egui_ctx.ctx_mut().unwrap(),
@@ -219,6 +378,10 @@ fn map_selection_screen_ui(
         egui_ctx.ctx_mut().unwrap().style().spacing.item_spacing.y,
     );
     egui::CentralPanel::default().show(egui_ctx.ctx_mut().unwrap(), |ui| {
+        if filter_bar(ui, &mut filter) {
+            sort_maps(&mut maps.maps, filter.sort_order);
+        }
+        ui.separator();
         egui::ScrollArea::new([false, true])
             .auto_shrink([false; 2])
             .max_width(ui.available_width())
@@ -228,13 +391,25 @@ fn map_selection_screen_ui(
                         .striped(true)
                         .num_columns(1)
                         .show(ui, |ui| {
-                            for (i, map) in maps.maps.iter().enumerate() {
+                            for (i, map) in maps
+                                .maps
+                                .iter()
+                                .enumerate()
+                                .filter(|(_, map)| filter.matches(&map.world))
+                            {
                                 ui.scope(|ui| {
                                     ui.set_width(ui.available_width());
                                     ui.set_height(BUTTON_HEIGHT * 1.8);
                                     ui.with_layout(Layout::right_to_left(Align::TOP), |ui| {
                                         ui.add_space(ui.spacing().item_spacing.x);
-                                        buttons(spacing, ui, &egui_textures, &mut commands, i);
+                                        buttons(
+                                            spacing,
+                                            ui,
+                                            &egui_textures,
+                                            &mut commands,
+                                            i,
+                                            maps.replays.contains(map.world.hash()),
+                                        );
                                         ui.with_layout(Layout::top_down(Align::LEFT), |ui| {
                                             ui.add_space(UIMARGIN);
                                             labels_name_author(ui, &map.world);
@@ -249,6 +424,7 @@ fn map_selection_screen_ui(
                                                     let h = egui_highscore_label(
                                                         ui,
                                                         &map.previous_best,
+                                                        map.previous_best_verified,
                                                         &egui_textures,
                                                     )
                                                     .rect
@@ -278,6 +454,12 @@ fn map_selection_screen_ui(
                                                                 ),
                                                         );
                                                     }
+                                                    labels_metadata(ui, &map.world);
+                                                    if let Some(report) =
+                                                        maps.validation.get(map.world.hash())
+                                                    {
+                                                        label_validation(ui, report);
+                                                    }
                                                 },
                                             );
                                             ui.add_space(UIMARGIN);
@@ -291,12 +473,103 @@ fn map_selection_screen_ui(
             });
     });
 }
+/// Show the search field, the difficulty filter and the sort order selection.
+/// Returns true if the sort order has been changed.
+fn filter_bar(ui: &mut Ui, filter: &mut MapFilter) -> bool {
+    let mut sort_order_changed = false;
+    ui.horizontal(|ui| {
+        ui.label(format!("{}:", t!("map_selection_screen.search")));
+        ui.text_edit_singleline(&mut filter.query)
+            .on_hover_text(t!("map_selection_screen.search_tooltip"));
+        ui.add_space(UIMARGIN);
+        ui.label(format!("{}:", t!("map_selection_screen.filter_difficulty")));
+        egui::ComboBox::from_id_salt("filter_difficulty")
+            .selected_text(match &filter.difficulty {
+                Some(_) => difficulty_text(&filter.difficulty),
+                None => t!("map_selection_screen.filter_difficulty_any").to_string(),
+            })
+            .show_ui(ui, |ui| {
+                ui.selectable_value(
+                    &mut filter.difficulty,
+                    None,
+                    t!("map_selection_screen.filter_difficulty_any"),
+                );
+                for difficulty in Difficulty::iter() {
+                    ui.selectable_value(
+                        &mut filter.difficulty,
+                        Some(difficulty),
+                        difficulty_text(&Some(difficulty)),
+                    );
+                }
+            });
+        ui.add_space(UIMARGIN);
+        ui.label(format!("{}:", t!("map_selection_screen.sort_by")));
+        egui::ComboBox::from_id_salt("sort_order")
+            .selected_text(filter.sort_order.text())
+            .show_ui(ui, |ui| {
+                for sort_order in MapSortOrder::ALL {
+                    sort_order_changed |= ui
+                        .selectable_value(&mut filter.sort_order, sort_order, sort_order.text())
+                        .changed();
+                }
+            });
+    });
+    sort_order_changed
+}
+
+/// Show the difficulty, par moves and tags of the given map
+fn labels_metadata(ui: &mut Ui, world: &GameWorld) {
+    let metadata = world.metadata();
+    let mut info: Vec<String> = vec![];
+    if metadata.difficulty.is_some() {
+        info.push(difficulty_text(&metadata.difficulty));
+    }
+    if let Some(par_moves) = metadata.par_moves {
+        info.push(
+            t!(
+                "map_selection_screen.par_moves_fmt",
+                moves = par_moves.to_string().as_str()
+            )
+            .to_string(),
+        );
+    }
+    info.extend(metadata.tags.iter().map(|tag| format!("#{}", tag)));
+    if !info.is_empty() {
+        ui.add_space(8. * UIMARGIN);
+        ui.label(
+            RichText::new(info.join("  "))
+                .text_style(egui::TextStyle::Name("MapTexturepackName".into()))
+                .color(ui.visuals().weak_text_color()),
+        );
+    }
+}
+
+/// Show a warning sign that lists all issues of the map when hovered
+fn label_validation(ui: &mut Ui, report: &ValidationReport) {
+    let (text, color) = if report.has_errors() {
+        (t!("validation.map_has_errors"), ui.visuals().error_fg_color)
+    } else {
+        (
+            t!("validation.map_has_warnings"),
+            ui.visuals().warn_fg_color,
+        )
+    };
+    ui.add_space(8. * UIMARGIN);
+    ui.label(
+        RichText::new(format!("⚠ {}", text))
+            .text_style(egui::TextStyle::Name("MapTexturepackName".into()))
+            .color(color),
+    )
+    .on_hover_ui(|ui| validation_report_labels(ui, report));
+}
+
 fn buttons(
     _spacing: (f32, f32),
     ui: &mut Ui,
     egui_textures: &EguiButtonTextures,
     commands: &mut Commands,
     map_index: usize,
+    has_replay: bool,
 ) {
     ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
         ui.set_height(ui.available_height());
@@ -328,6 +601,17 @@ fn buttons(
         if delete_btn.clicked() {
             commands.insert_resource(MapSelectionScreenAction::Delete { map_index });
         }
+        if has_replay {
+            let replay_btn = image_button(
+                ui,
+                egui_textures,
+                &UITiles::REPLAYBUTTON,
+                "map_selection_screen.watch_replay",
+            );
+            if replay_btn.clicked() {
+                commands.insert_resource(MapSelectionScreenAction::WatchReplay { map_index });
+            }
+        }
     });
 }
 
@@ -345,6 +629,22 @@ pub fn labels_name_author(ui: &mut Ui, world: &GameWorld) {
                     .text_style(egui::TextStyle::Name("MapAuthor".into())),
             );
         });
+        let description = &world.metadata().description;
+        if !description.is_empty() {
+            ui.add_space(UIMARGIN);
+            ui.vertical(|ui| {
+                ui.add_space(4.);
+                ui.add(
+                    egui::Label::new(
+                        egui::RichText::new(description.lines().next().unwrap_or_default())
+                            .text_style(egui::TextStyle::Name("MapAuthor".into()))
+                            .color(ui.visuals().weak_text_color()),
+                    )
+                    .truncate(),
+                )
+                .on_hover_text(description.as_str());
+            });
+        }
     });
 }
 /// Handle all possible kinds of dialogs that can occur in the Map Selection Screen
@@ -385,6 +685,7 @@ fn map_selection_screen_dialog(
                 commands.insert_resource(MapWrapper {
                     world,
                     previous_best: None,
+                    previous_best_verified: false,
                 });
                 commands.insert_resource(ReturnTo(AppState::MapSelectionScreen));
                 state.set(AppState::MapEditor);
@@ -420,6 +721,7 @@ pub struct MapSelectionScreenPlugin;
 impl Plugin for MapSelectionScreenPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<Maps>()
+            .init_resource::<MapFilter>()
             .init_resource::<MapSelectionScreenAction>()
             .add_systems(
                 OnEnter(AppState::MapSelectionScreen),